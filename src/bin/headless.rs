@@ -0,0 +1,682 @@
+// src/bin/headless.rs - Windowless mission runner for regression tests and balance sweeps
+//
+//   cargo run --bin headless -- --scene mission1 --seconds 180
+//   cargo run --bin headless -- --city <city_id> --script orders.json --out run.json
+//   cargo run --bin headless -- --scene mission1 --seed 42 --check-determinism
+//
+// Without `--script` the squad runs on a simple autopilot that works through the bound
+// objectives and heads for extraction. The JSON summary goes to stdout (and `--out`).
+// Every update advances exactly one fixed simulation step.
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use subversive::core::*;
+use subversive::systems::*;
+use subversive::systems::combat::get_weapon_range;
+use subversive::systems::objectives::{CarryingObjectiveItem, ObjectiveItem, ObjectivePredicate, ObjectiveState, ObjectiveTarget};
+use subversive::systems::police::PoliceEscalation;
+use subversive::systems::profiling::PerformanceMetrics;
+use subversive::systems::scenes::*;
+use subversive::systems::selection::AgentIndex;
+
+const SQUAD_SLOTS: usize = 3;
+const DEFAULT_SCENE: &str = "mission1";
+const DEFAULT_SECONDS: f32 = 180.0;
+const DEFAULT_HZ: f32 = SIMULATION_HZ as f32;
+/// Autopilot trigger discipline; the player can't click faster than this either
+const ORDER_INTERVAL: f32 = 0.5;
+const ARRIVE_RADIUS: f32 = 20.0;
+
+const USAGE: &str = "usage: headless [--scene NAME] [--city ID] [--seconds N] [--hz N] [--seed N] [--script FILE] [--out FILE] [--check-determinism]";
+
+// === ARGUMENTS ===
+struct RunnerArgs {
+    scene: Option<String>,
+    city: Option<String>,
+    seconds: f32,
+    hz: f32,
+    seed: Option<u64>,
+    script: Option<String>,
+    out: Option<String>,
+    /// Run the mission twice and compare the world every step
+    check_determinism: bool,
+}
+
+impl RunnerArgs {
+    fn parse() -> Result<Self, String> {
+        let mut args = Self {
+            scene: None,
+            city: None,
+            seconds: DEFAULT_SECONDS,
+            hz: DEFAULT_HZ,
+            seed: None,
+            script: None,
+            out: None,
+            check_determinism: false,
+        };
+
+        let mut iter = std::env::args().skip(1);
+        while let Some(flag) = iter.next() {
+            let mut value = || iter.next().ok_or_else(|| format!("{} needs a value", flag));
+            match flag.as_str() {
+                "--scene" => args.scene = Some(value()?),
+                "--city" => args.city = Some(value()?),
+                "--seconds" => args.seconds = parse_positive(&flag, &value()?)?,
+                "--hz" => args.hz = parse_positive(&flag, &value()?)?,
+                "--seed" => {
+                    let raw = value()?;
+                    args.seed = Some(raw.parse().map_err(|_| format!("--seed expects an integer, got '{}'", raw))?);
+                }
+                "--check-determinism" => args.check_determinism = true,
+                "--script" => args.script = Some(value()?),
+                "--out" => args.out = Some(value()?),
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                other => return Err(format!("unknown argument '{}'\n{}", other, USAGE)),
+            }
+        }
+
+        // A city on its own gets its briefing's generated map, like the campaign does
+        if args.scene.is_none() && args.city.is_none() {
+            args.scene = Some(DEFAULT_SCENE.to_string());
+        }
+        Ok(args)
+    }
+}
+
+fn parse_positive(flag: &str, value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(n) if n > 0.0 => Ok(n),
+        _ => Err(format!("{} expects a positive number, got '{}'", flag, value)),
+    }
+}
+
+// === SCRIPTED CONTROL ===
+/// One line of a control script: at `at` seconds, squad slot `agent` gets `command`
+#[derive(Debug, Clone, Deserialize)]
+struct ScriptedOrder {
+    at: f32,
+    agent: usize,
+    #[serde(flatten)]
+    command: ScriptedCommand,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ScriptedCommand {
+    MoveTo { position: [f32; 2] },
+    AttackNearest,
+    Interact,
+    Hold,
+    /// Hands the agent back to the autopilot
+    Autopilot,
+}
+
+fn load_script(path: &str) -> Result<Vec<ScriptedOrder>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("can't read script {}: {}", path, e))?;
+    let mut orders: Vec<ScriptedOrder> = serde_json::from_str(&content)
+        .map_err(|e| format!("bad script {}: {}", path, e))?;
+    orders.sort_by(|a, b| a.at.total_cmp(&b.at));
+    Ok(orders)
+}
+
+// === RESOURCES ===
+#[derive(Resource)]
+struct HeadlessMission {
+    scene: Option<String>,
+    city: Option<String>,
+    /// What actually got loaded, generated maps included
+    loaded_scene: String,
+}
+
+#[derive(Resource, Default)]
+struct SquadControl {
+    orders: Vec<ScriptedOrder>,
+    next_order: usize,
+    elapsed: f32,
+    /// Slots taking orders from the script instead of the autopilot
+    scripted: [bool; SQUAD_SLOTS],
+    cooldown: [f32; SQUAD_SLOTS],
+}
+
+#[derive(Resource, Default)]
+struct HeadlessStats {
+    alert_events: u32,
+}
+
+// === SUMMARY ===
+#[derive(Serialize)]
+struct RunSummary {
+    scene: String,
+    city: Option<String>,
+    control: &'static str,
+    seed: u64,
+    mission_seed: u64,
+    simulated_secs: f32,
+    steps: u32,
+    wall_clock_ms: u128,
+    outcome: &'static str,
+    kills: u32,
+    terminals_accessed: u32,
+    alerts: AlertSummary,
+    objectives: Vec<ObjectiveSummary>,
+    agents: Vec<AgentSummary>,
+    grade: Option<GradeSummary>,
+    metrics: PerformanceMetrics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    determinism: Option<DeterminismReport>,
+}
+
+#[derive(Serialize)]
+struct DeterminismReport {
+    steps_compared: u32,
+    /// First step where the two runs' worlds differed; `None` means identical
+    first_divergent_step: Option<u32>,
+    final_checksum: String,
+}
+
+#[derive(Serialize)]
+struct AlertSummary {
+    alert_events: u32,
+    detected: bool,
+    peak: AlertLevel,
+    police_escalation: String,
+    civilian_casualties: u32,
+}
+
+#[derive(Serialize)]
+struct ObjectiveSummary {
+    name: String,
+    required: bool,
+    state: ObjectiveState,
+    method: Option<CompletionMethod>,
+    completed_at: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct AgentSummary {
+    slot: usize,
+    status: &'static str,
+    health: f32,
+    kills: u32,
+    hacks: u32,
+    damage_dealt: f32,
+    damage_taken: f32,
+}
+
+#[derive(Serialize)]
+struct GradeSummary {
+    grade: String,
+    score: u32,
+    stealth_rating: f32,
+    efficiency_rating: f32,
+    par_time: f32,
+    agent_xp: [u32; SQUAD_SLOTS],
+}
+
+fn main() {
+    let args = match RunnerArgs::parse() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+
+    let orders = match args.script.as_deref().map(load_script).transpose() {
+        Ok(orders) => orders,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+    let control_label = if orders.is_some() { "script" } else { "autopilot" };
+
+    // Both runs of a determinism check need the same campaign, so pick it once up front
+    let seed = args.seed.or_else(env_seed).unwrap_or_else(rand::random);
+
+    let mut run = run_mission(&args, orders.clone(), seed);
+    let determinism = args.check_determinism.then(|| {
+        let rerun = run_mission(&args, orders, seed);
+        compare_runs(&run.checksums, &rerun.checksums)
+    });
+
+    let mut summary = summarize(&mut run.app, &args, control_label, run.steps, run.wall_clock_ms);
+    let diverged = determinism.as_ref().is_some_and(|report| report.first_divergent_step.is_some());
+    summary.determinism = determinism;
+
+    let json = serde_json::to_string_pretty(&summary).expect("summary serializes");
+    if let Some(path) = &args.out {
+        if let Err(e) = std::fs::write(path, &json) {
+            eprintln!("failed to write {}: {}", path, e);
+        }
+    }
+    println!("{}", json);
+
+    if diverged {
+        eprintln!("determinism check failed: runs diverged with seed {}", seed);
+        std::process::exit(1);
+    }
+}
+
+struct MissionRun {
+    app: App,
+    steps: u32,
+    wall_clock_ms: u128,
+    /// World checksum after every step, only kept for determinism checks
+    checksums: Vec<u64>,
+}
+
+fn run_mission(args: &RunnerArgs, orders: Option<Vec<ScriptedOrder>>, seed: u64) -> MissionRun {
+    let mut app = build_app(args, orders, seed);
+    app.finish();
+    app.cleanup();
+
+    let steps = (args.seconds * args.hz).ceil() as u32;
+    let started = Instant::now();
+    let mut steps_run = 0;
+    let mut checksums = Vec::new();
+    for _ in 0..steps {
+        app.update();
+        steps_run += 1;
+        if args.check_determinism {
+            checksums.push(world_checksum(app.world_mut()));
+        }
+        if *app.world().resource::<State<GameState>>().get() != GameState::Mission {
+            break;
+        }
+    }
+
+    MissionRun { app, steps: steps_run, wall_clock_ms: started.elapsed().as_millis(), checksums }
+}
+
+fn build_app(args: &RunnerArgs, orders: Option<Vec<ScriptedOrder>>, seed: u64) -> App {
+    // The same duration for the clock and the fixed timestep, so each update is one step
+    let step = Duration::from_secs_f64(1.0 / args.hz as f64);
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, SimulationPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(step))
+        .insert_resource(Time::<Fixed>::from_duration(step))
+        .insert_state(GameState::Mission)
+
+        .insert_resource(HeadlessMission {
+            scene: args.scene.clone(),
+            city: args.city.clone(),
+            loaded_scene: String::new(),
+        })
+        .insert_resource(SquadControl {
+            scripted: [orders.is_some(); SQUAD_SLOTS],
+            orders: orders.unwrap_or_default(),
+            ..default()
+        })
+        .init_resource::<HeadlessStats>()
+
+        .insert_resource(GlobalData { campaign_seed: seed, ..default() })
+        .insert_resource(GameRng::new(seed))
+        .init_resource::<GameSprites>()
+        .insert_resource(WeaponDatabase::load())
+        .insert_resource(LoreDatabase::load())
+        .insert_resource(CitiesDatabase::load())
+
+        .add_systems(OnEnter(GameState::Mission), setup_headless_mission)
+
+        // Squad control
+        .add_systems(FixedUpdate, (
+            run_scripted_orders,
+            squad_autopilot,
+        ).chain().run_if(in_state(GameState::Mission)))
+
+        .add_systems(FixedUpdate, count_alert_events.run_if(in_state(GameState::Mission)))
+        .add_systems(Update, collect_headless_metrics.run_if(in_state(GameState::Mission)));
+
+    app
+}
+
+// === SETUP ===
+/// Trimmed-down `setup_isometric_mission_scene`: same scene choice, no rendering
+fn setup_headless_mission(
+    mut commands: Commands,
+    mut run: ResMut<HeadlessMission>,
+    global_data: Res<GlobalData>,
+    cities_db: Res<CitiesDatabase>,
+    sprites: Res<GameSprites>,
+    tilemap_settings: Option<Res<IsometricSettings>>,
+    power_grid: ResMut<PowerGrid>,
+    mut rng: ResMut<GameRng>,
+) {
+    let city = run.city.as_deref().and_then(|id| cities_db.get_city(id));
+    if run.city.is_some() && city.is_none() {
+        eprintln!("unknown city '{}', running without a briefing", run.city.as_deref().unwrap_or_default());
+    }
+
+    let briefing = city.map(|city| generate_mission_briefing_for_city(
+        &global_data, &cities_db, &global_data.cities_progress, &city.id));
+
+    let (scene_name, scene) = match (&run.scene, city, &briefing) {
+        (Some(name), _, _) => (name.clone(), load_scene(name)),
+        (None, Some(city), Some(briefing)) => {
+            let seed = mapgen::mission_map_seed(global_data.campaign_seed, &city.id, global_data.current_day);
+            let default_settings = IsometricSettings::default();
+            let settings = tilemap_settings.as_deref().unwrap_or(&default_settings);
+            let generated = mapgen::generate_mission_map(briefing, city, seed, settings);
+            (generated.scene_name(), Some(generated.scene))
+        }
+        _ => (DEFAULT_SCENE.to_string(), load_scene(DEFAULT_SCENE)),
+    };
+
+    let objectives = match &briefing {
+        Some(briefing) => MissionObjectives::from_briefing(&briefing.objectives),
+        None => MissionObjectives::from_briefing(&objectives::fallback_objectives()),
+    };
+    commands.insert_resource(MissionPerformanceTracker::new(&objectives));
+    commands.insert_resource(objectives);
+
+    let Some(scene) = scene else {
+        eprintln!("failed to load scene '{}'", scene_name);
+        std::process::exit(1);
+    };
+    rng.reseed(mission_seed(global_data.campaign_seed, &scene_name, run.city.as_deref(), global_data.current_day));
+    run.loaded_scene = scene_name;
+
+    spawn_from_scene_isometric(&mut commands, &scene, &global_data, &sprites, &tilemap_settings,
+        briefing.as_ref().map(|b| &b.resistance), rng.stream(RngStream::Spawning));
+    let mut power_grid = Some(power_grid);
+    spawn_scene_infrastructure(&mut commands, &scene, &mut power_grid, &tilemap_settings);
+
+    commands.insert_resource(MissionScript::from_scene(&scene.triggers, briefing.as_ref().map(|b| b.resistance.clone())));
+    commands.insert_resource(scene);
+}
+
+// === SQUAD CONTROL ===
+fn nearest<T>(from: Vec2, candidates: impl Iterator<Item = (T, Vec2)>) -> Option<(T, Vec2)> {
+    candidates.min_by(|a, b| a.1.distance_squared(from).total_cmp(&b.1.distance_squared(from)))
+}
+
+fn run_scripted_orders(
+    time: Res<Time>,
+    mut control: ResMut<SquadControl>,
+    agents: Query<(Entity, &Transform, &AgentIndex), (With<Agent>, Without<Dead>)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Dead>)>,
+    terminals: Query<(Entity, &Transform), With<Terminal>>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    control.elapsed += time.delta_secs();
+
+    while let Some(order) = control.orders.get(control.next_order).cloned() {
+        if order.at > control.elapsed {
+            break;
+        }
+        control.next_order += 1;
+
+        let Some((entity, transform, _)) = agents.iter().find(|(_, _, index)| index.0 == order.agent) else {
+            continue;
+        };
+        let pos = transform.translation.truncate();
+        if order.agent < SQUAD_SLOTS {
+            control.scripted[order.agent] = !matches!(order.command, ScriptedCommand::Autopilot);
+        }
+
+        let action = match order.command {
+            ScriptedCommand::MoveTo { position } => Some(Action::MoveTo(Vec2::from(position))),
+            ScriptedCommand::Hold => Some(Action::MoveTo(pos)),
+            ScriptedCommand::AttackNearest => nearest(pos, enemies.iter().map(|(e, t)| (e, t.translation.truncate())))
+                .map(|(target, _)| Action::Attack(target)),
+            ScriptedCommand::Interact => nearest(pos, terminals.iter().map(|(e, t)| (e, t.translation.truncate())))
+                .map(|(target, _)| Action::InteractWith(target)),
+            ScriptedCommand::Autopilot => None,
+        };
+        if let Some(action) = action {
+            action_events.write(ActionEvent { entity, action });
+        }
+    }
+}
+
+/// Works the objectives in briefing order, shooting whatever is in range on the way
+fn squad_autopilot(
+    time: Res<Time>,
+    mut control: ResMut<SquadControl>,
+    objectives: Res<MissionObjectives>,
+    extraction: Res<ExtractionState>,
+    agents: Query<(Entity, &Transform, &AgentIndex, &Inventory, Option<&WeaponState>,
+        Option<&MoveTarget>, Option<&CarryingObjectiveItem>), (With<Agent>, Without<Dead>)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Dead>)>,
+    objective_targets: Query<(Entity, &Transform, &ObjectiveTarget), Without<Dead>>,
+    items: Query<(&Transform, &ObjectiveItem)>,
+    terminals: Query<(&Transform, &Terminal)>,
+    zones: Query<&Transform, With<ExtractionZone>>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, transform, index, inventory, weapon, move_target, carrying) in agents.iter() {
+        let slot = index.0;
+        if slot >= SQUAD_SLOTS || control.scripted[slot] {
+            continue;
+        }
+        control.cooldown[slot] -= dt;
+        let pos = transform.translation.truncate();
+        let nearest_zone = nearest(pos, zones.iter().map(|t| ((), t.translation.truncate()))).map(|(_, p)| p);
+
+        // Objective targets first, then anyone else in range
+        let range = get_weapon_range(inventory, weapon);
+        let target = objective_targets.iter()
+            .map(|(e, t, _)| (e, t.translation.truncate()))
+            .chain(enemies.iter().map(|(e, t)| (e, t.translation.truncate())))
+            .find(|(_, p)| p.distance(pos) <= range);
+
+        let mut interact = None;
+        let goal = if extraction.phase == MissionPhase::Extraction || carrying.is_some() {
+            nearest_zone
+        } else {
+            objectives.objectives.iter().enumerate()
+                .filter(|(_, o)| o.state == ObjectiveState::Active)
+                .find_map(|(idx, o)| match o.predicate {
+                    ObjectivePredicate::TerminalAccessed(terminal) => terminals.get(terminal).ok().map(|(t, term)| {
+                        let terminal_pos = t.translation.truncate();
+                        if terminal_pos.distance(pos) <= term.range {
+                            interact = Some(terminal);
+                        }
+                        terminal_pos
+                    }),
+                    ObjectivePredicate::ZoneReached { center, .. } => Some(center),
+                    ObjectivePredicate::ItemExtracted => items.iter()
+                        .find(|(_, item)| item.0 == idx)
+                        .map(|(t, _)| t.translation.truncate()),
+                    ObjectivePredicate::TargetKilled => objective_targets.iter()
+                        .find(|(_, _, target)| target.0 == idx)
+                        .map(|(_, t, _)| t.translation.truncate()),
+                    ObjectivePredicate::TimerSurvived { .. } | ObjectivePredicate::Unbound => None,
+                })
+        };
+
+        if control.cooldown[slot] <= 0.0 {
+            if let Some((target, _)) = target {
+                action_events.write(ActionEvent { entity, action: Action::Attack(target) });
+                control.cooldown[slot] = ORDER_INTERVAL;
+            } else if let Some(terminal) = interact {
+                action_events.write(ActionEvent { entity, action: Action::InteractWith(terminal) });
+                control.cooldown[slot] = ORDER_INTERVAL;
+            }
+        }
+
+        if let Some(goal) = goal {
+            let heading_there = move_target.is_some_and(|m| m.position.distance(goal) <= ARRIVE_RADIUS);
+            if !heading_there && pos.distance(goal) > ARRIVE_RADIUS {
+                action_events.write(ActionEvent { entity, action: Action::MoveTo(goal) });
+            }
+        }
+    }
+}
+
+// === DETERMINISM ===
+/// Hash of every unit's position and health, in entity order
+fn world_checksum(world: &mut World) -> u64 {
+    let mut query = world.query_filtered::<(Entity, &Transform, Option<&Health>),
+        Or<(With<Agent>, With<Enemy>, With<Civilian>, With<Police>)>>();
+    let mut units: Vec<(Entity, Vec3, Option<f32>)> = query.iter(world)
+        .map(|(entity, transform, health)| (entity, transform.translation, health.map(|h| h.0)))
+        .collect();
+    units.sort_by_key(|(entity, _, _)| *entity);
+
+    let mut hasher = DefaultHasher::new();
+    for (entity, position, health) in units {
+        entity.to_bits().hash(&mut hasher);
+        position.x.to_bits().hash(&mut hasher);
+        position.y.to_bits().hash(&mut hasher);
+        health.map(f32::to_bits).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn compare_runs(first: &[u64], second: &[u64]) -> DeterminismReport {
+    let first_divergent_step = first.iter().zip(second)
+        .position(|(a, b)| a != b)
+        .or_else(|| (first.len() != second.len()).then_some(first.len().min(second.len())))
+        .map(|step| step as u32 + 1);
+
+    DeterminismReport {
+        steps_compared: first.len().min(second.len()) as u32,
+        first_divergent_step,
+        final_checksum: format!("{:016x}", first.last().copied().unwrap_or_default()),
+    }
+}
+
+// === STATS ===
+fn count_alert_events(mut alert_events: EventReader<AlertEvent>, mut stats: ResMut<HeadlessStats>) {
+    stats.alert_events += alert_events.read().count() as u32;
+}
+
+/// Wall-clock stand-in for `ProfilingPlugin`, which needs the frame diagnostics
+fn collect_headless_metrics(
+    mut metrics: ResMut<PerformanceMetrics>,
+    mut last_frame: Local<Option<Instant>>,
+    entities: Query<()>,
+    agents: Query<(), With<Agent>>,
+    enemies: Query<(), With<Enemy>>,
+    civilians: Query<(), With<Civilian>>,
+) {
+    let now = Instant::now();
+    if let Some(last) = *last_frame {
+        let frame_ms = now.duration_since(last).as_secs_f32() * 1000.0;
+        metrics.frame_time_ms = frame_ms;
+        metrics.fps = if frame_ms > 0.0 { 1000.0 / frame_ms } else { 0.0 };
+        metrics.frame_time_history.push_back(frame_ms);
+        if metrics.frame_time_history.len() > 60 {
+            metrics.frame_time_history.pop_front();
+        }
+    }
+    *last_frame = Some(now);
+
+    metrics.total_entities = entities.iter().count();
+    metrics.agent_count = agents.iter().count();
+    metrics.enemy_count = enemies.iter().count();
+    metrics.civilian_count = civilians.iter().count();
+}
+
+fn summarize(app: &mut App, args: &RunnerArgs, control: &'static str, steps: u32, wall_clock_ms: u128) -> RunSummary {
+    let world = app.world_mut();
+
+    let finished = *world.resource::<State<GameState>>().get() != GameState::Mission;
+    let mut agents_query = world.query_filtered::<(&AgentIndex, &Health, Has<Dead>), With<Agent>>();
+    let agent_health: Vec<(usize, f32, bool)> = agents_query.iter(world)
+        .map(|(index, health, dead)| (index.0, health.0, dead))
+        .collect();
+
+    let mission_data = world.resource::<MissionData>();
+    let extraction = world.resource::<ExtractionState>();
+    let tracker = world.resource::<MissionPerformanceTracker>();
+    let objectives = world.resource::<MissionObjectives>();
+    let post_mission = world.resource::<PostMissionResults>();
+    let run = world.resource::<HeadlessMission>();
+    let seed = world.resource::<GlobalData>().campaign_seed;
+    let mission_seed = world.resource::<GameRng>().seed();
+
+    let agents = (0..SQUAD_SLOTS)
+        .filter(|&slot| tracker.agents[slot].deployed)
+        .map(|slot| {
+            let health = agent_health.iter().find(|(i, _, _)| *i == slot);
+            let status = if extraction.extracted.contains(&slot) {
+                "extracted"
+            } else if mission_data.agents_lost.contains(&slot) || health.is_some_and(|h| h.2) {
+                "killed"
+            } else if mission_data.agents_missing.contains(&slot) {
+                "missing"
+            } else {
+                "in_field"
+            };
+            let stats = &tracker.agents[slot];
+            AgentSummary {
+                slot,
+                status,
+                health: health.map_or(0.0, |h| h.1),
+                kills: stats.kills,
+                hacks: stats.hacks,
+                damage_dealt: stats.damage_dealt,
+                damage_taken: stats.damage_taken,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let grade = finished.then(|| {
+        let returned: Vec<usize> = agents.iter()
+            .filter(|a| a.status == "extracted" || a.status == "in_field")
+            .map(|a| a.slot)
+            .collect();
+        let performance = grade_mission(post_mission, tracker, objectives, &returned);
+        GradeSummary {
+            grade: format!("{:?}", performance.grade),
+            score: performance.score,
+            stealth_rating: performance.stealth_rating,
+            efficiency_rating: performance.efficiency_rating,
+            par_time: performance.par_time,
+            agent_xp: performance.agent_xp,
+        }
+    });
+
+    let outcome = match (finished, post_mission.outcome) {
+        (false, _) => "in_progress",
+        (true, MissionOutcome::Success) => "success",
+        (true, MissionOutcome::PartialSuccess) => "partial_success",
+        (true, MissionOutcome::Failed) => "failed",
+    };
+
+    RunSummary {
+        scene: run.loaded_scene.clone(),
+        city: args.city.clone(),
+        control,
+        seed,
+        mission_seed,
+        simulated_secs: mission_data.timer,
+        steps,
+        wall_clock_ms,
+        outcome,
+        kills: mission_data.enemies_killed,
+        terminals_accessed: mission_data.terminals_accessed,
+        alerts: AlertSummary {
+            alert_events: world.resource::<HeadlessStats>().alert_events,
+            detected: tracker.detected,
+            peak: tracker.alert_peak,
+            police_escalation: format!("{:?}", world.resource::<PoliceEscalation>().level),
+            civilian_casualties: tracker.civilian_casualties,
+        },
+        objectives: objectives.objectives.iter().map(|o| ObjectiveSummary {
+            name: o.objective.name.clone(),
+            required: o.objective.required,
+            state: o.state,
+            method: o.status.method,
+            completed_at: (o.state == ObjectiveState::Completed).then_some(o.status.time_taken),
+        }).collect(),
+        agents,
+        grade,
+        metrics: world.resource::<PerformanceMetrics>().clone(),
+        determinism: None,
+    }
+}
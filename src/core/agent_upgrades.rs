@@ -199,7 +199,7 @@ pub fn check_veteran_bonuses(performance: &mut AgentPerformance) {
     
     for config in VETERAN_BONUS_CONFIGS {
         let current_stat = (config.stat_getter)(performance);
-        let has_bonus = performance.veteran_bonuses.iter().any(|b| b.name.contains(config.id));
+        let has_bonus = performance.veteran_bonuses.iter().any(|b| b.name == config.name);
         
         if current_stat >= config.threshold && !has_bonus {
             new_bonuses.push(VeteranBonus {
@@ -212,7 +212,7 @@ pub fn check_veteran_bonuses(performance: &mut AgentPerformance) {
     }
     
     // Special case for speed bonus (uses f32 comparison)
-    let has_speed_bonus = performance.veteran_bonuses.iter().any(|b| b.name.contains("speed_demon"));
+    let has_speed_bonus = performance.veteran_bonuses.iter().any(|b| b.name == "Speed Demon");
     if performance.fastest_mission_time > 0.0 && performance.fastest_mission_time < 120.0 && !has_speed_bonus {
         new_bonuses.push(VeteranBonus {
             name: "Speed Demon".into(),
@@ -256,10 +256,23 @@ pub fn assign_random_trait_from_db(traits_db: &TraitsDatabase) -> Option<AgentTr
     }
 }
 
+/// Up to two distinct traits for a fresh recruit
+pub fn roll_recruit_traits(traits_db: &TraitsDatabase) -> Vec<AgentTrait> {
+    let mut traits: Vec<AgentTrait> = Vec::new();
+    for candidate in (0..2).filter_map(|_| assign_random_trait_from_db(traits_db)) {
+        if !traits.iter().any(|t| t.id == candidate.id) {
+            traits.push(candidate);
+        }
+    }
+    traits
+}
+
 // === PERFORMANCE TRACKING ===
+/// Folds one mission into an agent's career record; `survived` is false for agents lost or left behind
 pub fn update_agent_performance(
-    agent_upgrades: &mut AgentUpgrades,
+    perf: &mut AgentPerformance,
     mission_success: bool,
+    survived: bool,
     enemies_killed: u32,
     terminals_accessed: u32,
     mission_time: f32,
@@ -267,15 +280,13 @@ pub fn update_agent_performance(
     damage_taken: f32,
     stealth_mission: bool,
 ) {
-    let perf = &mut agent_upgrades.performance;
-    
     perf.missions_completed += 1;
     perf.enemies_killed += enemies_killed;
     perf.terminals_hacked += terminals_accessed;
     perf.total_damage_dealt += damage_dealt;
     perf.total_damage_taken += damage_taken;
     
-    if mission_success {
+    if survived {
         perf.missions_survived += 1;
         perf.current_survival_streak += 1;
         perf.longest_survival_streak = perf.longest_survival_streak.max(perf.current_survival_streak);
@@ -283,11 +294,11 @@ pub fn update_agent_performance(
         perf.current_survival_streak = 0;
     }
     
-    if stealth_mission {
+    if mission_success && stealth_mission {
         perf.stealth_missions += 1;
     }
     
-    if perf.fastest_mission_time == 0.0 || mission_time < perf.fastest_mission_time {
+    if mission_success && (perf.fastest_mission_time == 0.0 || mission_time < perf.fastest_mission_time) {
         perf.fastest_mission_time = mission_time;
     }
     
@@ -155,7 +155,7 @@ pub enum OriginalInventoryItem {
     Keycard { access_level: u8, facility_id: String },
 }
 
-#[derive(Component, Default)]
+#[derive(Component, Default, Clone, Serialize, Deserialize)]
 pub struct Inventory {
     pub weapons: Vec<WeaponConfig>,
     pub tools: Vec<crate::core::ToolType>,
@@ -197,6 +197,16 @@ impl Inventory {
         self.tools.push(tool);
     }
 
+    /// Uses up one of `tool`; false if there wasn't one to use
+    pub fn consume_tool(&mut self, tool: &crate::core::ToolType) -> bool {
+        let Some(pos) = self.tools.iter().position(|t| t == tool) else { return false; };
+        self.tools.remove(pos);
+        if !self.tools.contains(tool) {
+            self.equipped_tools.retain(|t| t != tool);
+        }
+        true
+    }
+
     pub fn add_cybernetic(&mut self, cybernetic: crate::core::CyberneticType) {
         self.cybernetics.push(cybernetic);
     }
@@ -0,0 +1,124 @@
+// src/core/enemy_archetypes.rs - Per-archetype enemy stats, loadouts and GOAP profiles
+use bevy::prelude::*;
+use rand::Rng;
+use crate::core::*;
+use crate::core::factions::Faction;
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EnemyArchetype(pub EnemyType);
+
+/// Flat fraction of incoming damage absorbed (0.0-0.9)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Armor(pub f32);
+
+impl Armor {
+    pub fn mitigate(armor: Option<&Armor>, damage: f32) -> f32 {
+        armor.map_or(damage, |a| damage * (1.0 - a.0.clamp(0.0, 0.9)))
+    }
+
+    /// Takes `damage` off `health` after armor and returns what actually landed.
+    /// Every damage source goes through here so armor counts the same against all of them.
+    pub fn apply_damage(health: &mut Health, armor: Option<&Armor>, damage: f32) -> f32 {
+        let damage = Self::mitigate(armor, damage);
+        health.0 = (health.0 - damage).max(0.0);
+        damage
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchetypeStats {
+    pub health: f32,
+    pub armor: f32,
+    pub speed: f32,
+    pub vision_range: f32,
+    pub vision_fov: f32,
+    pub morale: f32,
+    pub panic_threshold: f32,
+    /// Empty means "use the faction's default weapon"
+    pub weapons: &'static [WeaponType],
+    /// GOAP profile key; `None` falls back to the faction profile
+    pub goap_profile: Option<&'static str>,
+    pub tint: Color,
+    pub scale: f32,
+}
+
+impl EnemyType {
+    pub fn stats(&self) -> ArchetypeStats {
+        match self {
+            EnemyType::Guard => ArchetypeStats {
+                health: 100.0, armor: 0.0, speed: 100.0,
+                vision_range: 120.0, vision_fov: 60.0,
+                morale: 100.0, panic_threshold: 25.0,
+                weapons: &[],
+                goap_profile: None,
+                tint: Color::WHITE, scale: 1.0,
+            },
+            EnemyType::Patrol => ArchetypeStats {
+                health: 90.0, armor: 0.0, speed: 110.0,
+                vision_range: 130.0, vision_fov: 70.0,
+                morale: 90.0, panic_threshold: 30.0,
+                weapons: &[],
+                goap_profile: None,
+                tint: Color::WHITE, scale: 1.0,
+            },
+            EnemyType::Elite => ArchetypeStats {
+                health: 150.0, armor: 0.25, speed: 115.0,
+                vision_range: 160.0, vision_fov: 80.0,
+                morale: 160.0, panic_threshold: 15.0,
+                weapons: &[WeaponType::Rifle, WeaponType::Shotgun, WeaponType::LaserRifle],
+                goap_profile: Some("elite"),
+                tint: Color::srgb(1.0, 0.75, 0.3), scale: 1.1,
+            },
+            EnemyType::Cyborg => ArchetypeStats {
+                health: 250.0, armor: 0.45, speed: 75.0,
+                vision_range: 200.0, vision_fov: 100.0,
+                morale: 300.0, panic_threshold: 0.0,
+                weapons: &[WeaponType::Minigun, WeaponType::PlasmaGun, WeaponType::Flamethrower],
+                goap_profile: Some("cyborg"),
+                tint: Color::srgb(0.5, 0.9, 1.0), scale: 1.3,
+            },
+        }
+    }
+
+    pub fn is_heavy(&self) -> bool {
+        matches!(self, EnemyType::Elite | EnemyType::Cyborg)
+    }
+
+    pub fn pick_weapon(&self, faction: &Faction, rng: &mut impl Rng) -> WeaponType {
+        let weapons = self.stats().weapons;
+        if weapons.is_empty() {
+            crate::systems::scenes::select_weapon_for_faction(faction, rng)
+        } else {
+            weapons[rng.gen_range(0..weapons.len())].clone()
+        }
+    }
+}
+
+/// Opposition mix for a briefing; tougher archetypes unlock with security and corruption
+pub fn enemy_types_for_security(security_level: u8, corruption_level: u8) -> Vec<EnemyType> {
+    let mut types = vec![EnemyType::Guard, EnemyType::Patrol];
+    if security_level >= 3 || corruption_level >= 6 {
+        types.push(EnemyType::Elite);
+    }
+    if security_level >= 4 && corruption_level >= 8 {
+        types.push(EnemyType::Cyborg);
+    }
+    types
+}
+
+/// Picks an archetype for one spawn; heavy units make up ~10% per security level of the roster
+pub fn pick_enemy_archetype(resistance: Option<&ResistanceProfile>, rng: &mut impl Rng) -> EnemyType {
+    let Some(resistance) = resistance else { return EnemyType::Guard };
+
+    let (heavy, standard): (Vec<&EnemyType>, Vec<&EnemyType>) = resistance.enemy_types.iter()
+        .partition(|t| t.is_heavy());
+
+    let heavy_chance = resistance.security_level as f32 * 0.1;
+    let pool = if !heavy.is_empty() && (standard.is_empty() || rng.gen::<f32>() < heavy_chance) {
+        heavy
+    } else {
+        standard
+    };
+
+    pool.get(rng.gen_range(0..pool.len().max(1))).map(|t| (*t).clone()).unwrap_or(EnemyType::Guard)
+}
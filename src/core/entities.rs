@@ -3,7 +3,7 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 // === MORALE SYSTEM ===
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Morale {
     pub current: f32,
     pub max: f32,
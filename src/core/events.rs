@@ -14,6 +14,12 @@ pub enum Action {
     TakeDamage(f32),
     NeurovectorControl { target: Entity },
     InteractWith(Entity),
+    /// Hack a device through the network rather than standing next to it
+    RemoteHack(Entity),
+    /// Enter cyberspace through the nearest terminal or security system
+    JackIn,
+    /// Aim a hijacked turret or route a hijacked drone; `queue` adds to a drone's route instead of replacing it
+    CommandDevice { target: Vec2, queue: bool },
     Reload,
     // NEW: Advanced actions
     UseMedKit,
@@ -36,6 +42,7 @@ pub enum Action {
     UsePrototype(String), 
 }
 
+/// A hit that has already landed on the target's Health; readers react to it, they never apply it again
 #[derive(Event)]
 pub struct CombatEvent {
     pub attacker: Entity,
@@ -71,6 +78,8 @@ pub enum AlertType {
     TrafficIncident,
     VehicularAssault,
     ConvoyAttacked,    
+    /// A network trace has pinned down a hacker; guards come from further out than for a call for help
+    IntrusionTraced,
 }
 
 // NEW: Specific events for advanced actions
@@ -1,8 +1,9 @@
 // src/core/factions.rs - Simple faction system for enemy-vs-enemy combat
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::core::*;
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Faction {
     Player,      // Agents
     Corporate,   // Standard security
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use crate::core::{ResearchProgress};
 use crate::core::cities::*;
 use crate::core::research::{Scientist};
+use crate::core::agent_upgrades::{AgentPerformance, AgentTrait, TraitsDatabase, roll_recruit_traits};
+use crate::core::rng::env_seed;
 use std::collections::HashSet;
 
 // === GAME STATES ===
@@ -11,6 +13,7 @@ use std::collections::HashSet;
 pub enum GameState {
     Loading,
     MainMenu,
+    LoadGame,
     NewGame,
     Settings,
     Credits,
@@ -18,7 +21,7 @@ pub enum GameState {
     Mission,
     PostMission,
     Cyberspace,
-
+    SceneEditor,
 }
 
 impl Default for GameState {
@@ -26,8 +29,9 @@ impl Default for GameState {
 }
 
 // === ALERT LEVELS ===
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
 pub enum AlertLevel {
+    #[default]
     Green,
     Yellow,
     Orange,
@@ -53,6 +57,14 @@ pub struct GlobalData {
     pub recruited_scientists: Vec<Scientist>,
     pub research_facilities_discovered: HashSet<String>,
     pub alert_level: u8,
+
+    /// Ironman campaigns keep one autosave and lose agents for good
+    pub ironman: bool,
+    pub agent_traits: [Vec<AgentTrait>; MAX_SQUAD_SIZE],
+    /// Career record per squad slot; veteran bonuses unlock from it
+    pub agent_performance: [AgentPerformance; MAX_SQUAD_SIZE],
+    /// Root of every briefing, weather and mission seed in this campaign
+    pub campaign_seed: u64,
 }
 
 impl GlobalData {
@@ -66,6 +78,24 @@ impl GlobalData {
             info!("Saved loadout for Agent {}", agent_idx + 1);
         }
     }
+
+    /// Permadeath: the fallen agent's record is gone and a level 1 recruit takes the slot
+    pub fn recruit_replacement_agent(&mut self, agent_idx: usize, traits_db: &TraitsDatabase) {
+        if agent_idx >= MAX_SQUAD_SIZE {
+            return;
+        }
+
+        self.agent_levels[agent_idx] = 1;
+        self.agent_experience[agent_idx] = 0;
+        self.agent_recovery[agent_idx] = self.current_day;
+        self.agent_loadouts[agent_idx] = crate::core::AgentLoadout::default();
+        self.agent_traits[agent_idx] = roll_recruit_traits(traits_db);
+        self.agent_performance[agent_idx] = AgentPerformance::default();
+
+        let names: Vec<&str> = self.agent_traits[agent_idx].iter().map(|t| t.name.as_str()).collect();
+        info!("Agent {} recruited as a replacement (traits: {})", agent_idx + 1,
+            if names.is_empty() { "none".to_string() } else { names.join(", ") });
+    }
 }
 
 impl Default for GlobalData {
@@ -104,6 +134,11 @@ impl Default for GlobalData {
             recruited_scientists: Vec::new(),
             research_facilities_discovered: HashSet::new(),
             alert_level: 1,
+
+            ironman: false,
+            agent_traits: Default::default(),
+            agent_performance: Default::default(),
+            campaign_seed: env_seed().unwrap_or_else(rand::random),
         }
     }
 }
@@ -1,8 +1,11 @@
 // src/core/goap.rs
 use bevy::prelude::*;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use crate::systems::ai::AIMode;
 use crate::core::factions::Faction;
+use crate::core::rng::{GameRng, RngStream};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 macro_rules! world_state {
     ( $( $key:expr => $value:expr ),* $(,)? ) => {{
@@ -13,7 +16,7 @@ macro_rules! world_state {
 }
 
 // === CORE TYPES ===
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum WorldKey {
     // Position & Movement
     AtPatrolPoint, AtLastKnownPosition, AtTarget,
@@ -41,7 +44,7 @@ pub type WorldState = HashMap<WorldKey, bool>;
 
 #[derive(Debug, Clone)]
 pub struct GoapAction {
-    pub name: &'static str,
+    pub name: String,
     pub cost: f32,
     pub preconditions: WorldState,
     pub effects: WorldState,
@@ -61,12 +64,90 @@ pub enum ActionType {
 
 #[derive(Debug, Clone)]
 pub struct Goal {
-    pub name: &'static str,
+    pub name: String,
     pub priority: f32,
     pub desired_state: WorldState,
 }
 
 // === PLANNER ===
+#[derive(Debug, Clone, Copy)]
+pub struct PlannerLimits {
+    pub max_depth: usize,
+    pub max_nodes: usize,
+}
+
+impl Default for PlannerLimits {
+    fn default() -> Self {
+        Self { max_depth: 8, max_nodes: 256 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanFailure {
+    NoUnsatisfiedGoal,
+    NoActionForCondition { key: WorldKey, value: bool },
+    DepthLimitReached { depth: usize },
+    NodeLimitReached { nodes: usize },
+    Unreachable { nodes_expanded: usize },
+    GoalFailed { goal: String, reason: Box<PlanFailure> },
+}
+
+impl std::fmt::Display for PlanFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanFailure::NoUnsatisfiedGoal => write!(f, "all goals already satisfied"),
+            PlanFailure::NoActionForCondition { key, value } => write!(f, "no action produces {:?} = {}", key, value),
+            PlanFailure::DepthLimitReached { depth } => write!(f, "no plan within {} actions", depth),
+            PlanFailure::NodeLimitReached { nodes } => write!(f, "search exceeded {} nodes", nodes),
+            PlanFailure::Unreachable { nodes_expanded } => write!(f, "goal unreachable ({} nodes expanded)", nodes_expanded),
+            PlanFailure::GoalFailed { goal, reason } => write!(f, "goal '{}': {}", goal, reason),
+        }
+    }
+}
+
+pub const PLAN_HISTORY_LEN: usize = 12;
+
+/// One planning attempt, kept for the GOAP debugger
+#[derive(Debug, Clone)]
+pub struct PlanRecord {
+    pub time: f32,
+    pub goal: Option<String>,
+    pub actions: Vec<String>,
+    pub cost: f32,
+    pub failure: Option<PlanFailure>,
+}
+
+struct SearchNode {
+    conditions: Vec<(WorldKey, bool)>,
+    parent: Option<usize>,
+    action: Option<usize>,
+    g: f32,
+    depth: usize,
+}
+
+struct OpenEntry {
+    f: f32,
+    node: usize,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == std::cmp::Ordering::Equal }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for OpenEntry {
+    // Reversed so BinaryHeap pops the lowest f; ties prefer older nodes for stable plans
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.f.partial_cmp(&self.f).unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
 #[derive(Component)]
 pub struct GoapAgent {
     pub current_plan: VecDeque<GoapAction>,
@@ -75,6 +156,17 @@ pub struct GoapAgent {
     pub available_actions: Vec<GoapAction>,
     pub goals: Vec<Goal>,
     pub planning_cooldown: f32,
+    /// Multipliers applied to `GoapAction::cost` by action name (e.g. injured agents avoid flanking)
+    pub cost_modifiers: HashMap<String, f32>,
+    pub planner_limits: PlannerLimits,
+    pub last_plan_failure: Option<PlanFailure>,
+    /// Profile key in `data/ai/goap_config.json`; `None` resolves from the entity's `Faction`
+    pub profile: Option<String>,
+    /// Role-specific goal pushed by the squad blackboard, planned alongside the agent's own goals
+    pub squad_goal: Option<Goal>,
+    pub plan_history: VecDeque<PlanRecord>,
+    /// Name and world position of the last executed action's resolved target
+    pub action_target: Option<(String, Vec2)>,
 }
 
 impl Default for GoapAgent {
@@ -83,9 +175,17 @@ impl Default for GoapAgent {
             current_plan: VecDeque::new(),
             current_goal: None,
             world_state: WorldState::new(),
-            available_actions: create_action_library(),
-            goals: create_goal_library(),
+            // Filled from GoapConfigCache when the agent is spawned
+            available_actions: Vec::new(),
+            goals: Vec::new(),
             planning_cooldown: 0.0,
+            cost_modifiers: HashMap::new(),
+            planner_limits: PlannerLimits::default(),
+            last_plan_failure: None,
+            profile: None,
+            squad_goal: None,
+            plan_history: VecDeque::with_capacity(PLAN_HISTORY_LEN),
+            action_target: None,
         };
         agent.setup_initial_world_state();
         agent
@@ -124,38 +224,68 @@ impl GoapAgent {
         }
     }
 
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Replaces the action and goal libraries with those built for `profile_key`
+    pub fn apply_profile(&mut self, config: &GoapConfig, profile_key: &str) {
+        let (actions, goals) = config.build_profile(profile_key);
+        self.available_actions = actions;
+        self.goals = goals;
+        self.abort_plan();
+    }
+
+    pub fn set_cost_modifier(&mut self, action_name: &str, multiplier: f32) {
+        if (multiplier - 1.0).abs() < f32::EPSILON {
+            self.cost_modifiers.remove(action_name);
+        } else {
+            self.cost_modifiers.insert(action_name.to_string(), multiplier);
+        }
+    }
+
+    pub fn action_cost(&self, action: &GoapAction) -> f32 {
+        action.cost * self.cost_modifiers.get(action.name.as_str()).copied().unwrap_or(1.0)
+    }
+
     pub fn plan(&mut self) -> bool {
-        let goal = self.goals.iter()
+        let mut candidates: Vec<Goal> = self.goals.iter()
+            .chain(self.squad_goal.iter())
             .filter(|g| !self.is_goal_satisfied(&g.desired_state))
-            .max_by(|a, b| a.priority.partial_cmp(&b.priority).unwrap_or(std::cmp::Ordering::Equal));
-
-        if let Some(goal) = goal {
-            // println!("GOAP Planning: Selected goal '{}' (priority: {:.1})", goal.name, goal.priority);
-
-            // Debug world state that triggered this goal
-            /* println!("GOAP Planning: Current world state - HasTarget: {:?}, Outnumbered: {:?}, IsInjured: {:?}, IsRetreating: {:?}",
-                     self.world_state.get(&WorldKey::HasTarget),
-                     self.world_state.get(&WorldKey::Outnumbered),
-                     self.world_state.get(&WorldKey::IsInjured),
-                     self.world_state.get(&WorldKey::IsRetreating));
-            */
-            self.current_goal = Some(goal.clone());
-            self.current_plan = self.find_plan(&goal.desired_state);
-
-            if !self.current_plan.is_empty() {
-                // println!("GOAP Planning: Generated plan with {} actions", self.current_plan.len());
-                for (i, action) in self.current_plan.iter().enumerate() {
-                    // println!("  {}: {}", i, action.name);
+            .cloned()
+            .collect();
+        candidates.sort_by(|a, b| b.priority.partial_cmp(&a.priority).unwrap_or(std::cmp::Ordering::Equal));
+
+        if candidates.is_empty() {
+            self.current_goal = None;
+            self.current_plan.clear();
+            self.last_plan_failure = Some(PlanFailure::NoUnsatisfiedGoal);
+            return false;
+        }
+
+        // Fall through to lower priority goals when the preferred one can't be planned
+        let mut first_failure = None;
+        for goal in candidates {
+            match self.find_plan(&goal.desired_state) {
+                Ok(plan) => {
+                    self.current_goal = Some(goal);
+                    self.current_plan = plan;
+                    self.last_plan_failure = first_failure;
+                    return true;
+                }
+                Err(failure) => {
+                    if first_failure.is_none() {
+                        first_failure = Some(PlanFailure::GoalFailed { goal: goal.name.clone(), reason: Box::new(failure) });
+                    }
                 }
-            } else {
-                // println!("GOAP Planning: Failed to generate plan for goal '{}'", goal.name);
             }
-
-            !self.current_plan.is_empty()
-        } else {
-            // println!("GOAP Planning: No unsatisfied goals found");
-            false
         }
+
+        self.current_goal = None;
+        self.current_plan.clear();
+        self.last_plan_failure = first_failure;
+        false
     }
 
     fn is_goal_satisfied(&self, desired_state: &WorldState) -> bool {
@@ -164,104 +294,154 @@ impl GoapAgent {
         })
     }
 
-    fn find_plan(&self, goal_state: &WorldState) -> VecDeque<GoapAction> {
-        // println!("GOAP Debug: Finding plan for goal state: {:?}", goal_state);
+    /// Regressive A*: searches backwards from the goal over the set of conditions
+    /// still unsatisfied by the current world state, minimising total action cost.
+    pub fn find_plan(&self, goal_state: &WorldState) -> Result<VecDeque<GoapAction>, PlanFailure> {
+        let start = self.unsatisfied(goal_state.iter().map(|(&k, &v)| (k, v)));
+        if start.is_empty() {
+            return Ok(VecDeque::new());
+        }
+
+        let (min_cost, max_effects) = self.available_actions.iter().fold((f32::MAX, 1usize), |(c, e), a| {
+            (c.min(self.action_cost(a)), e.max(a.effects.len()))
+        });
+        let heuristic = |conditions: &[(WorldKey, bool)]| {
+            if min_cost == f32::MAX { 0.0 } else { conditions.len() as f32 * min_cost.max(0.0) / max_effects as f32 }
+        };
 
-        let mut plan = VecDeque::new();
-        let mut current_state = self.world_state.clone();
-        let mut remaining_goals = goal_state.clone();
-
-        /* println!("GOAP Debug: Current world state: WeaponLoaded={:?}, IsReloading={:?}, HasWeapon={:?}",
-                 current_state.get(&WorldKey::WeaponLoaded),
-                 current_state.get(&WorldKey::IsReloading),
-                 current_state.get(&WorldKey::HasWeapon));
-        */
-        for iteration in 0..10 {
-            if remaining_goals.is_empty() {
-                // println!("GOAP Debug: All goals satisfied after {} iterations", iteration);
-                break;
+        let mut nodes = vec![SearchNode { conditions: start.clone(), parent: None, action: None, g: 0.0, depth: 0 }];
+        let mut open = BinaryHeap::new();
+        let mut best_cost: HashMap<Vec<(WorldKey, bool)>, f32> = HashMap::new();
+        best_cost.insert(start.clone(), 0.0);
+        open.push(OpenEntry { f: heuristic(&start), node: 0 });
+
+        let mut expanded = 0;
+        let mut depth_limited = false;
+        let mut unsupported: Option<(WorldKey, bool)> = None;
+
+        while let Some(OpenEntry { node: index, .. }) = open.pop() {
+            let (conditions, g, depth) = {
+                let node = &nodes[index];
+                (node.conditions.clone(), node.g, node.depth)
+            };
+
+            if conditions.is_empty() {
+                return Ok(self.reconstruct_plan(&nodes, index));
             }
 
-            if let Some(action) = self.find_satisfying_action(&remaining_goals) {
-                // println!("GOAP Debug: Found action '{}' for remaining goals: {:?}", action.name, remaining_goals);
-                // println!("GOAP Debug: Action preconditions: {:?}", action.preconditions);
+            // Skip stale heap entries
+            if best_cost.get(&conditions).map_or(false, |&best| g > best) {
+                continue;
+            }
 
-                if self.can_execute_action(&action, &current_state) {
-                    // println!("GOAP Debug: Action '{}' can be executed", action.name);
-                    self.apply_effects(&action.effects, &mut current_state);
+            expanded += 1;
+            if expanded > self.planner_limits.max_nodes {
+                return Err(PlanFailure::NodeLimitReached { nodes: self.planner_limits.max_nodes });
+            }
 
-                    for (key, &value) in &action.effects {
-                        if remaining_goals.get(key) == Some(&value) {
-                            remaining_goals.remove(key);
-                        }
-                    }
+            if depth >= self.planner_limits.max_depth {
+                depth_limited = true;
+                continue;
+            }
 
-                    plan.push_front(action);
-                } else {
-                    // println!("GOAP Debug: Action '{}' cannot be executed, adding preconditions to goals", action.name);
-                    for (&key, &value) in &action.preconditions {
-                        if current_state.get(&key) != Some(&value) {
-                            // println!("GOAP Debug: Adding precondition {:?} => {:?} to goals", key, value);
-                            remaining_goals.insert(key, value);
-                        }
-                    }
-                }
-            } else {
-                // println!("GOAP Debug: No satisfying action found for goals: {:?}", remaining_goals);
-                // println!("GOAP Debug: Available actions:");
-                for action in &self.available_actions {
-                    // println!("  - {} (effects: {:?})", action.name, action.effects);
+            let mut any_relevant = false;
+            for (action_index, action) in self.available_actions.iter().enumerate() {
+                let Some(next) = self.regress(&conditions, action) else { continue };
+                any_relevant = true;
+
+                let next_g = g + self.action_cost(action);
+                if best_cost.get(&next).map_or(false, |&best| best <= next_g) {
+                    continue;
                 }
-                break;
+                best_cost.insert(next.clone(), next_g);
+
+                let f = next_g + heuristic(&next);
+                nodes.push(SearchNode { conditions: next, parent: Some(index), action: Some(action_index), g: next_g, depth: depth + 1 });
+                open.push(OpenEntry { f, node: nodes.len() - 1 });
             }
-        }
 
-        if remaining_goals.is_empty() {
-            // println!("GOAP Debug: Plan generated successfully with {} actions", plan.len());
-            plan
-        } else {
-            // println!("GOAP Debug: Failed to generate plan, remaining goals: {:?}", remaining_goals);
-            VecDeque::new()
+            if !any_relevant && unsupported.is_none() {
+                unsupported = conditions.iter().copied()
+                    .find(|&(key, value)| !self.available_actions.iter().any(|a| a.effects.get(&key) == Some(&value)));
+            }
         }
+
+        Err(match (unsupported, depth_limited) {
+            (Some((key, value)), _) => PlanFailure::NoActionForCondition { key, value },
+            (None, true) => PlanFailure::DepthLimitReached { depth: self.planner_limits.max_depth },
+            (None, false) => PlanFailure::Unreachable { nodes_expanded: expanded },
+        })
     }
 
-    fn find_satisfying_action(&self, goals: &WorldState) -> Option<GoapAction> {
-        self.available_actions.iter()
-            .find(|action| action.effects.iter().any(|(key, &value)| goals.get(key) == Some(&value)))
-            .cloned()
+    /// Conditions from `state` not already true in the agent's current world state, in a canonical order.
+    fn unsatisfied(&self, state: impl Iterator<Item = (WorldKey, bool)>) -> Vec<(WorldKey, bool)> {
+        let mut conditions: Vec<(WorldKey, bool)> = state
+            .filter(|(key, value)| self.world_state.get(key).unwrap_or(&false) != value)
+            .collect();
+        conditions.sort();
+        conditions.dedup();
+        conditions
     }
 
-    fn can_execute_action(&self, action: &GoapAction, current_state: &WorldState) -> bool {
-        action.preconditions.iter().all(|(key, &required_value)| {
-            current_state.get(key).unwrap_or(&false) == &required_value
-        })
+    /// Regresses `conditions` through `action`, or `None` if the action doesn't help or contradicts them.
+    fn regress(&self, conditions: &[(WorldKey, bool)], action: &GoapAction) -> Option<Vec<(WorldKey, bool)>> {
+        let mut satisfies_any = false;
+        for &(key, value) in conditions {
+            match action.effects.get(&key) {
+                Some(&effect) if effect == value => satisfies_any = true,
+                Some(_) => return None,
+                None => {}
+            }
+        }
+        if !satisfies_any { return None; }
+
+        let remaining = conditions.iter().copied().filter(|(key, _)| !action.effects.contains_key(key));
+        let mut next: Vec<(WorldKey, bool)> = Vec::with_capacity(conditions.len() + action.preconditions.len());
+        for (key, value) in remaining.chain(action.preconditions.iter().map(|(&k, &v)| (k, v))) {
+            if next.iter().any(|&(k, v)| k == key && v != value) {
+                return None;
+            }
+            next.push((key, value));
+        }
+
+        Some(self.unsatisfied(next.into_iter()))
     }
 
-    fn apply_effects(&self, effects: &WorldState, current_state: &mut WorldState) {
-        for (&key, &value) in effects {
-            current_state.insert(key, value);
+    fn reconstruct_plan(&self, nodes: &[SearchNode], mut index: usize) -> VecDeque<GoapAction> {
+        // Walking back from the solved node yields actions in execution order
+        let mut plan = VecDeque::new();
+        while let (Some(parent), Some(action_index)) = (nodes[index].parent, nodes[index].action) {
+            plan.push_back(self.available_actions[action_index].clone());
+            index = parent;
         }
+        plan
     }
 
     pub fn get_next_action(&mut self) -> Option<GoapAction> {
         self.current_plan.pop_front()
     }
 
+    pub fn record_plan(&mut self, time: f32) {
+        let record = PlanRecord {
+            time,
+            goal: self.current_goal.as_ref().map(|g| g.name.clone()),
+            actions: self.current_plan.iter().map(|a| a.name.clone()).collect(),
+            cost: self.current_plan.iter().map(|a| self.action_cost(a)).sum(),
+            failure: self.last_plan_failure.clone(),
+        };
+
+        if self.plan_history.len() >= PLAN_HISTORY_LEN {
+            self.plan_history.pop_front();
+        }
+        self.plan_history.push_back(record);
+    }
+
     pub fn abort_plan(&mut self) {
         self.current_plan.clear();
         self.current_goal = None;
     }
 }
 
-// === EXTERNAL DATA ===
-fn create_action_library() -> Vec<GoapAction> {
-    include!("../data/goap_actions.rs")
-}
-
-fn create_goal_library() -> Vec<Goal> {
-    include!("../data/goap_goals.rs")
-}
-
 // === INTEGRATION COMPONENTS ===
 #[derive(Component)]
 pub struct CoverPoint {
@@ -282,23 +462,27 @@ pub struct Equipment { pub medkits: u8, pub grenades: u8, pub tools: Vec<String>
 // === MAIN AI SYSTEM ===
 use crate::core::*;
 use crate::systems::ai::AIState;
+use crate::systems::squads::{SquadBlackboard, SquadBlackboards, SquadMember, SquadRole};
+use crate::systems::perception::{PerceptionMemory, SuspicionLevel};
 
 pub fn goap_ai_system(
     mut commands: Commands,
     mut enemy_query: Query<(Entity, &Transform, &mut AIState, &mut GoapAgent, &mut Vision,
-        &Patrol, &Health, &Faction, Option<&WeaponState>), (With<Enemy>, Without<Dead>)>,
+        &Patrol, &Health, &Faction, Option<&WeaponState>, Option<&Inventory>, Option<&SquadMember>, Option<&mut PerceptionMemory>), (With<Enemy>, Without<Dead>)>,
     agent_query: Query<(Entity, &Transform), With<Agent>>,
     all_enemy_query: Query<(Entity, &Transform, &Faction), (With<Enemy>, Without<Dead>)>,
     cover_query: Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>,
     mut action_events: EventWriter<ActionEvent>,
     mut audio_events: EventWriter<AudioEvent>,
     mut alert_events: EventWriter<AlertEvent>,
+    mut squads: ResMut<SquadBlackboards>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
-    for (enemy_entity, enemy_transform, mut ai_state, mut goap_agent, mut vision, patrol, health, faction, weapon_state) in enemy_query.iter_mut() {
+    for (enemy_entity, enemy_transform, mut ai_state, mut goap_agent, mut vision, patrol, health, faction, weapon_state, inventory, squad_member, mut memory) in enemy_query.iter_mut() {
         goap_agent.planning_cooldown -= time.delta_secs();
         ai_state.target_last_seen += time.delta_secs(); // Increment target age
 
@@ -317,14 +501,29 @@ pub fn goap_ai_system(
             }
         }
 
-        update_world_state_from_perception(&mut goap_agent, enemy_transform, &mut vision, faction,
-            enemy_entity, &agent_query, &all_enemy_query, &mut ai_state, patrol, &cover_query, health, weapon_state);
+        let mut board = squad_member.and_then(|m| squads.get_mut(m.squad_id));
+        let reserved_cover: Vec<Entity> = board.as_ref().map(|b| b.cover_reservations.iter()
+            .filter(|(_, holder)| **holder != enemy_entity)
+            .map(|(cover, _)| *cover)
+            .collect()).unwrap_or_default();
+
+        let visible_hostile = update_world_state_from_perception(&mut goap_agent, enemy_transform, &mut vision, faction,
+            enemy_entity, &agent_query, &all_enemy_query, &mut ai_state, patrol, &cover_query, &reserved_cover, health, weapon_state,
+            memory.as_deref_mut(), time.delta_secs(), time.elapsed_secs(), rng.stream(RngStream::Ai));
+        goap_agent.update_world_state(WorldKey::HasMedKit, inventory.is_some_and(|inv| inv.tools.contains(&ToolType::MedKit)));
+
+        if let (Some(member), Some(board)) = (squad_member, board.as_deref_mut()) {
+            apply_squad_knowledge(&mut goap_agent, &mut ai_state, board, member, enemy_entity, visible_hostile,
+                &agent_query, &all_enemy_query, time.elapsed_secs());
+        }
+        update_cost_modifiers(&mut goap_agent, health, squad_member.map(|m| m.role));
 
         let should_replan = goap_agent.current_plan.is_empty() || goap_agent.planning_cooldown <= 0.0 ||
                           plan_invalidated(&goap_agent, &ai_state, health);
 
         if should_replan {
             goap_agent.plan();
+            goap_agent.record_plan(time.elapsed_secs());
             goap_agent.planning_cooldown = match (health.0 < 30.0, *goap_agent.world_state.get(&WorldKey::HasTarget).unwrap_or(&false)) {
                 (true, _) => 0.3,
                 (_, true) => 0.5,
@@ -333,9 +532,15 @@ pub fn goap_ai_system(
         }
 
         if let Some(action) = goap_agent.get_next_action() {
-            execute_goap_action(&action, enemy_entity, enemy_transform, &mut ai_state,
+            let outcome = execute_goap_action(&action, enemy_entity, enemy_transform, &mut ai_state,
                 &mut action_events, &mut audio_events, &mut alert_events, patrol,
-                &agent_query, &all_enemy_query, &vision, &cover_query, &mut commands);
+                &agent_query, &all_enemy_query, &vision, &cover_query, &reserved_cover,
+                memory.as_deref().map(|m| m.search_radius()), &mut commands);
+
+            goap_agent.action_target = outcome.target.map(|pos| (action.name.clone(), pos));
+            if let (Some(cover), Some(board)) = (outcome.claimed_cover, board) {
+                board.reserve_cover(cover, enemy_entity);
+            }
         }
     }
 }
@@ -344,13 +549,26 @@ fn update_world_state_from_perception(
     current_entity: Entity, agent_query: &Query<(Entity, &Transform), With<Agent>>,
     enemy_query: &Query<(Entity, &Transform, &Faction), (With<Enemy>, Without<Dead>)>,
     ai_state: &mut AIState, patrol: &Patrol, cover_query: &Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>,
-    health: &Health, weapon_state: Option<&WeaponState>,
-) {
+    reserved_cover: &[Entity], health: &Health, weapon_state: Option<&WeaponState>,
+    mut memory: Option<&mut PerceptionMemory>, delta: f32, now: f32, rng: &mut impl Rng,
+) -> Option<Entity> {
     let enemy_pos = enemy_transform.translation.truncate();
 
     update_vision_direction(goap_agent, ai_state, patrol, vision, enemy_pos, current_entity, agent_query, enemy_query);
 
-    let visible_hostile = check_line_of_sight_goap(enemy_transform, vision, faction, current_entity, agent_query, enemy_query);
+    let seen_hostile = check_line_of_sight_goap(enemy_transform, vision, faction, current_entity, agent_query, enemy_query);
+
+    // A sighting only becomes a confirmed target once suspicion has built up
+    let visible_hostile = match (seen_hostile, memory.as_deref_mut()) {
+        (Some(target), Some(memory)) => {
+            if let Some(pos) = get_entity_position(target, agent_query, enemy_query) {
+                let distance_ratio = enemy_pos.distance(pos) / vision.range.max(1.0);
+                memory.observe(target, pos, distance_ratio, delta, now);
+            }
+            memory.is_alerted().then_some(target)
+        }
+        (seen, _) => seen,
+    };
     let has_target = visible_hostile.is_some();
 
     if let Some(target_entity) = visible_hostile {
@@ -363,27 +581,108 @@ fn update_world_state_from_perception(
         .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
         .collect();
 
-    let tactical_state = assess_tactical_situation(enemy_pos, patrol, cover_query, &enemy_positions,
-        current_entity, health, agent_query, visible_hostile);
+    let tactical_state = assess_tactical_situation(enemy_pos, patrol, cover_query, reserved_cover, &enemy_positions,
+        current_entity, health, agent_query, visible_hostile, rng);
 
     update_weapon_state(goap_agent, weapon_state);
     update_world_states(goap_agent, &tactical_state, has_target, visible_hostile);
     update_ai_mode(goap_agent, ai_state, has_target, visible_hostile);
+
+    if let Some(memory) = memory.as_deref() {
+        apply_perception_memory(goap_agent, ai_state, memory, enemy_pos, has_target);
+    }
+
+    visible_hostile
+}
+
+/// Drives investigation from remembered stimuli instead of only what is visible this frame
+fn apply_perception_memory(goap_agent: &mut GoapAgent, ai_state: &mut AIState, memory: &PerceptionMemory,
+    enemy_pos: Vec2, has_target: bool) {
+
+    let Some(stimulus) = memory.strongest() else {
+        goap_agent.update_world_state(WorldKey::AtLastKnownPosition, false);
+        // Nothing left to chase: stand down once suspicion has faded
+        if memory.level() == SuspicionLevel::Calm && matches!(ai_state.mode, AIMode::Investigate { .. } | AIMode::Search { .. }) {
+            ai_state.mode = AIMode::Patrol;
+            ai_state.last_known_target = None;
+            goap_agent.abort_plan();
+        }
+        return;
+    };
+
+    goap_agent.update_world_state(WorldKey::AtLastKnownPosition, enemy_pos.distance(stimulus.position) < 25.0);
+    if has_target {
+        return;
+    }
+
+    ai_state.last_known_target = Some(stimulus.position);
+    if memory.level() != SuspicionLevel::Calm && matches!(ai_state.mode, AIMode::Patrol) {
+        ai_state.mode = AIMode::Investigate { location: stimulus.position };
+        goap_agent.update_world_state(WorldKey::HeardSound, true);
+        goap_agent.abort_plan();
+    }
+}
+
+/// Shares this member's sighting with the squad and folds squad knowledge back into its world state
+fn apply_squad_knowledge(
+    goap_agent: &mut GoapAgent, ai_state: &mut AIState, board: &mut SquadBlackboard, member: &SquadMember,
+    current_entity: Entity, visible_hostile: Option<Entity>,
+    agent_query: &Query<(Entity, &Transform), With<Agent>>,
+    enemy_query: &Query<(Entity, &Transform, &Faction), (With<Enemy>, Without<Dead>)>, now: f32,
+) {
+    if let Some(target) = visible_hostile {
+        if let Some(pos) = get_entity_position(target, agent_query, enemy_query) {
+            board.report_sighting(target, pos, current_entity, now);
+        }
+    }
+
+    let squadmates = board.members.len().saturating_sub(1);
+    let engaged_members = board.engaged.len().max(1);
+    let allies_nearby = *goap_agent.world_state.get(&WorldKey::NearbyAlliesAvailable).unwrap_or(&false);
+    goap_agent.update_multiple([
+        (WorldKey::NearbyAlliesAvailable, allies_nearby || squadmates > 0),
+        (WorldKey::AlliesAdvancing, member.role != SquadRole::Flanker && board.role_engaged(SquadRole::Flanker, now)),
+        (WorldKey::Outnumbered, board.fresh_target_count(now) >= engaged_members + 2),
+    ]);
+
+    // A squadmate has eyes on something we don't: move in on the reported position
+    if visible_hostile.is_none() && matches!(ai_state.mode, AIMode::Patrol) {
+        if let Some(known) = board.freshest_target(now) {
+            ai_state.last_known_target = Some(known.position);
+            ai_state.mode = AIMode::Investigate { location: known.position };
+            goap_agent.update_world_state(WorldKey::HeardSound, true);
+        }
+    }
+}
+
+fn update_cost_modifiers(goap_agent: &mut GoapAgent, health: &Health, role: Option<SquadRole>) {
+    let injured = *goap_agent.world_state.get(&WorldKey::IsInjured).unwrap_or(&false);
+    let wounded = health.0 < 60.0;
+    let role_mult = |name: &str| role.map_or(1.0, |r| r.cost_multiplier(name));
+
+    // Hurt enemies pay more for exposed manoeuvres and less for staying safe
+    goap_agent.set_cost_modifier("flank_target", role_mult("flank_target") * if injured { 3.0 } else if wounded { 1.5 } else { 1.0 });
+    goap_agent.set_cost_modifier("move_to_target", role_mult("move_to_target") * if injured { 2.0 } else { 1.0 });
+    goap_agent.set_cost_modifier("take_cover", role_mult("take_cover") * if wounded { 0.5 } else { 1.0 });
+
+    for name in ["suppressing_fire", "minigun_suppression", "use_medkit"] {
+        goap_agent.set_cost_modifier(name, role_mult(name));
+    }
 }
 
 struct TacticalState {
     at_patrol_point: bool, cover_available: bool, nearby_allies: bool, is_injured: bool,
     outnumbered: bool, at_safe_distance: bool, target_grouped: bool, safe_throw_distance: bool,
-    has_medkit: bool, has_grenade: bool, under_fire: bool, better_cover_available: bool,
+    has_grenade: bool, under_fire: bool, better_cover_available: bool,
     allies_advancing: bool, retreat_path_clear: bool,
 }
 
 fn assess_tactical_situation(enemy_pos: Vec2, patrol: &Patrol, cover_query: &Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>,
-    enemy_positions: &[(Entity, Vec2)], current_enemy: Entity, health: &Health,
-    agent_query: &Query<(Entity, &Transform), With<Agent>>, visible_hostile: Option<Entity>) -> TacticalState {
+    reserved_cover: &[Entity], enemy_positions: &[(Entity, Vec2)], current_enemy: Entity, health: &Health,
+    agent_query: &Query<(Entity, &Transform), With<Agent>>, visible_hostile: Option<Entity>, rng: &mut impl Rng) -> TacticalState {
 
     let at_patrol_point = patrol.current_target().map(|t| enemy_pos.distance(t) < 20.0).unwrap_or(true);
-    let cover_available = find_cover(enemy_pos, cover_query, None, false).is_some();
+    let cover_available = find_cover(enemy_pos, cover_query, reserved_cover, None, false).is_some();
     let nearby_allies = enemy_positions.iter().filter(|(e, p)| *e != current_enemy && enemy_pos.distance(*p) <= 200.0).count() > 0;
 
     // More conservative injury threshold - only consider injured if health is very low
@@ -407,8 +706,7 @@ fn assess_tactical_situation(enemy_pos: Vec2, patrol: &Patrol, cover_query: &Que
         at_safe_distance,
         target_grouped,
         safe_throw_distance,
-        has_medkit: is_injured && rand::random::<f32>() < 0.3,
-        has_grenade: target_grouped && rand::random::<f32>() < 0.2,
+        has_grenade: target_grouped && rng.gen::<f32>() < 0.2,
         under_fire: agent_count > 0 && agent_query.iter().any(|(_, t)| enemy_pos.distance(t.translation.truncate()) <= 80.0), // Reduced from 120.0
         better_cover_available: cover_available && cover_query.iter().count() > 1,
         allies_advancing: enemy_count > 0 && agent_count > 0,
@@ -465,7 +763,7 @@ fn update_world_states(goap_agent: &mut GoapAgent, tactical_state: &TacticalStat
         (WorldKey::TargetGrouped, tactical_state.target_grouped), (WorldKey::SafeThrowDistance, tactical_state.safe_throw_distance),
         (WorldKey::UnderFire, tactical_state.under_fire), (WorldKey::BetterCoverAvailable, tactical_state.better_cover_available),
         (WorldKey::AlliesAdvancing, tactical_state.allies_advancing), (WorldKey::RetreatPathClear, tactical_state.retreat_path_clear),
-        (WorldKey::HasMedKit, tactical_state.has_medkit), (WorldKey::HasGrenade, tactical_state.has_grenade),
+        (WorldKey::HasGrenade, tactical_state.has_grenade),
     ]);
 
     if !has_target {
@@ -530,7 +828,7 @@ fn plan_invalidated(goap_agent: &GoapAgent, ai_state: &AIState, health: &Health)
     let severely_outnumbered = *goap_agent.world_state.get(&WorldKey::Outnumbered).unwrap_or(&false)
                               && health.0 < 40.0; // Only replan for outnumbered if also injured
     let has_tactical_goal = goap_agent.current_goal.as_ref()
-        .map(|g| ["tactical_advantage", "survival", "panic_survival"].contains(&g.name)).unwrap_or(false);
+        .map(|g| ["tactical_advantage", "survival", "panic_survival"].contains(&g.name.as_str())).unwrap_or(false);
 
     if severely_outnumbered && !has_tactical_goal { return true; }
 
@@ -605,20 +903,31 @@ fn get_entity_position(entity: Entity, agent_query: &Query<(Entity, &Transform),
     }
 }
 
+#[derive(Default)]
+struct ActionOutcome {
+    claimed_cover: Option<Entity>,
+    target: Option<Vec2>,
+}
+
 fn execute_goap_action(action: &GoapAction, enemy_entity: Entity, enemy_transform: &Transform, ai_state: &mut AIState,
     action_events: &mut EventWriter<ActionEvent>, audio_events: &mut EventWriter<AudioEvent>, alert_events: &mut EventWriter<AlertEvent>,
     patrol: &Patrol, agent_query: &Query<(Entity, &Transform), With<Agent>>, all_enemy_query: &Query<(Entity, &Transform, &Faction), (With<Enemy>, Without<Dead>)>,
-    vision: &Vision, cover_query: &Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>, commands: &mut Commands) {
+    vision: &Vision, cover_query: &Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>, reserved_cover: &[Entity],
+    search_radius: Option<f32>, commands: &mut Commands) -> ActionOutcome {
+
+    let mut outcome = ActionOutcome::default();
 
     match &action.action_type {
         ActionType::Patrol => {
             if let Some(target) = patrol.current_target() {
                 ai_state.mode = AIMode::Patrol;
                 action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(target) });
+                outcome.target = Some(target);
             }
         },
         ActionType::MoveTo { target } => {
             action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(*target) });
+            outcome.target = Some(*target);
         },
 ActionType::Attack { .. } => {
     // Check if we have a valid cached target first
@@ -678,7 +987,7 @@ ActionType::Attack { .. } => {
                     action: Action::MoveTo(pos)
                 });
             }
-            return;
+            return ActionOutcome { claimed_cover: None, target: Some(pos) };
         }
     }
 
@@ -689,6 +998,7 @@ ActionType::Attack { .. } => {
             action: Action::MoveTo(last_pos)
         });
         ai_state.mode = AIMode::Investigate { location: last_pos };
+        outcome.target = Some(last_pos);
         // println!("GOAP: Enemy {:?} investigating last known position", enemy_entity);
     } else {
         // println!("GOAP: Enemy {:?} has no targets or last known position", enemy_entity);
@@ -708,6 +1018,7 @@ ActionType::Attack { .. } => {
                         action: Action::MoveTo(flank_position)
                     });
                     ai_state.mode = AIMode::Combat { target: agent_entity };
+                    outcome.target = Some(flank_position);
                 }
             }
         },
@@ -719,24 +1030,27 @@ ActionType::Attack { .. } => {
             let investigation_target = ai_state.last_known_target.unwrap_or(Vec2::ZERO);
             ai_state.mode = AIMode::Investigate { location: investigation_target };
             action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(investigation_target) });
+            outcome.target = Some(investigation_target);
         },
         ActionType::SearchArea { center, radius } => {
             let search_center = ai_state.last_known_target.unwrap_or(*center);
             let enemy_pos = enemy_transform.translation.truncate();
             let angle = (enemy_pos.x + enemy_pos.y) * 0.1;
-            let search_offset = Vec2::new(angle.cos(), angle.sin()) * radius;
+            let search_offset = Vec2::new(angle.cos(), angle.sin()) * search_radius.unwrap_or(*radius);
             let search_point = search_center + search_offset;
 
             action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(search_point) });
             ai_state.mode = AIMode::Search { area: search_center };
+            outcome.target = Some(search_point);
         },
         ActionType::Search { area } => {
             ai_state.mode = AIMode::Search { area: *area };
         },
         ActionType::TakeCover => {
-            if let Some((cover_entity, cover_pos)) = find_cover(enemy_transform.translation.truncate(), cover_query, None, false) {
+            if let Some((cover_entity, cover_pos)) = find_cover(enemy_transform.translation.truncate(), cover_query, reserved_cover, None, false) {
                 action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(cover_pos) });
                 commands.entity(enemy_entity).insert(InCover { cover_entity });
+                outcome = ActionOutcome { claimed_cover: Some(cover_entity), target: Some(cover_pos) };
             }
         },
         ActionType::Retreat { .. } => {
@@ -752,6 +1066,7 @@ ActionType::Attack { .. } => {
             let retreat_point = enemy_pos + retreat_direction * 120.0;
             action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(retreat_point) });
             ai_state.mode = AIMode::Patrol;
+            outcome.target = Some(retreat_point);
         },
         ActionType::CallForHelp => {
             audio_events.write(AudioEvent { sound: AudioType::Alert, volume: 1.0 });
@@ -775,7 +1090,7 @@ ActionType::Attack { .. } => {
             });
         },
         ActionType::UseMedKit => {
-            audio_events.write(AudioEvent { sound: AudioType::Alert, volume: 0.3 });
+            action_events.write(ActionEvent { entity: enemy_entity, action: Action::UseMedKit });
         },
         ActionType::ThrowGrenade { .. } => {
             let throw_target = if let Some(agent_entity) = find_closest_agent(enemy_transform, agent_query) {
@@ -785,6 +1100,7 @@ ActionType::Attack { .. } => {
             } else { enemy_transform.translation.truncate() + Vec2::new(50.0, 0.0) };
 
             audio_events.write(AudioEvent { sound: AudioType::Alert, volume: 1.0 });
+            outcome.target = Some(throw_target);
         },
         ActionType::ActivateAlarm { .. } => {
             audio_events.write(AudioEvent { sound: AudioType::Alert, volume: 1.0 });
@@ -794,15 +1110,17 @@ ActionType::Attack { .. } => {
             });
         },
         ActionType::FindBetterCover { .. } => {
-            if let Some((cover_entity, cover_pos)) = find_cover(enemy_transform.translation.truncate(), cover_query, Some(&agent_query), true) {
+            if let Some((cover_entity, cover_pos)) = find_cover(enemy_transform.translation.truncate(), cover_query, reserved_cover, Some(&agent_query), true) {
                 action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(cover_pos) });
                 commands.entity(enemy_entity).insert(InCover { cover_entity });
+                outcome = ActionOutcome { claimed_cover: Some(cover_entity), target: Some(cover_pos) };
             }
         },
         ActionType::SuppressingFire { .. } => {
             if let Some(agent_entity) = find_closest_agent(enemy_transform, agent_query) {
                 action_events.write(ActionEvent { entity: enemy_entity, action: Action::Attack(agent_entity) });
                 audio_events.write(AudioEvent { sound: AudioType::Gunshot, volume: 0.8 });
+                outcome.target = get_entity_position(agent_entity, agent_query, all_enemy_query);
             }
         },
         ActionType::MaintainDistance => {
@@ -814,6 +1132,7 @@ ActionType::Attack { .. } => {
                     let retreat_pos = enemy_pos + away_direction * 80.0;
 
                     action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(retreat_pos) });
+                    outcome.target = Some(retreat_pos);
                 }
             }
         },
@@ -832,6 +1151,7 @@ ActionType::Attack { .. } => {
             };
 
             action_events.write(ActionEvent { entity: enemy_entity, action: Action::MoveTo(retreat_target) });
+            outcome.target = Some(retreat_target);
 
             if let Some(agent_entity) = find_closest_agent(enemy_transform, agent_query) {
                 action_events.write(ActionEvent { entity: enemy_entity, action: Action::Attack(agent_entity) });
@@ -840,14 +1160,16 @@ ActionType::Attack { .. } => {
             ai_state.mode = AIMode::Patrol;
         },
     }
+
+    outcome
 }
 
-fn find_cover(enemy_pos: Vec2, cover_q: &Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>,
+fn find_cover(enemy_pos: Vec2, cover_q: &Query<(Entity, &Transform, &CoverPoint), Without<Enemy>>, reserved: &[Entity],
     agent_q: Option<&Query<(Entity, &Transform), With<Agent>>>, use_score: bool) -> Option<(Entity, Vec2)> {
 
     let (mut best, mut val) = (None, if use_score { f32::MIN } else { f32::MAX });
     for (e, t, c) in cover_q.iter() {
-        if c.current_users >= c.capacity { continue; }
+        if c.current_users >= c.capacity || reserved.contains(&e) { continue; }
         let p = t.translation.truncate();
         let d = enemy_pos.distance(p);
         if use_score {
@@ -0,0 +1,240 @@
+// src/core/goap_config.rs - Data-driven GOAP actions, goals and per-faction profiles
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::SystemTime;
+use crate::core::factions::Faction;
+use crate::core::goap::{ActionType, GoapAction, GoapAgent, Goal, WorldState};
+
+pub const GOAP_CONFIG_PATH: &str = "data/ai/goap_config.json";
+pub const DEFAULT_GOAP_PROFILE: &str = "default";
+
+// === SCHEMA ===
+/// Serializable form of `ActionType`; runtime targets are resolved when the action executes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ActionTemplate {
+    Patrol, MoveTo, Attack, Investigate, Search, Reload, TacticalReload,
+    CallForHelp, TakeCover, FlankTarget,
+    SearchArea { #[serde(default = "default_search_radius")] radius: f32 },
+    Retreat, UseMedKit, ThrowGrenade, ActivateAlarm, FindBetterCover,
+    SuppressingFire, FightingWithdrawal, MaintainDistance, Wait,
+}
+
+fn default_search_radius() -> f32 { 50.0 }
+
+impl ActionTemplate {
+    pub fn instantiate(&self) -> ActionType {
+        match self {
+            Self::Patrol => ActionType::Patrol,
+            Self::MoveTo => ActionType::MoveTo { target: Vec2::ZERO },
+            Self::Attack => ActionType::Attack { target: Entity::PLACEHOLDER },
+            Self::Investigate => ActionType::Investigate { location: Vec2::ZERO },
+            Self::Search => ActionType::Search { area: Vec2::ZERO },
+            Self::Reload => ActionType::Reload,
+            Self::TacticalReload => ActionType::TacticalReload,
+            Self::CallForHelp => ActionType::CallForHelp,
+            Self::TakeCover => ActionType::TakeCover,
+            Self::FlankTarget => ActionType::FlankTarget { target_pos: Vec2::ZERO, flank_pos: Vec2::ZERO },
+            Self::SearchArea { radius } => ActionType::SearchArea { center: Vec2::ZERO, radius: *radius },
+            Self::Retreat => ActionType::Retreat { retreat_point: Vec2::ZERO },
+            Self::UseMedKit => ActionType::UseMedKit,
+            Self::ThrowGrenade => ActionType::ThrowGrenade { target_pos: Vec2::ZERO },
+            Self::ActivateAlarm => ActionType::ActivateAlarm { panel_pos: Vec2::ZERO },
+            Self::FindBetterCover => ActionType::FindBetterCover { new_cover_pos: Vec2::ZERO },
+            Self::SuppressingFire => ActionType::SuppressingFire { target_area: Vec2::ZERO },
+            Self::FightingWithdrawal => ActionType::FightingWithdrawal { retreat_path: Vec2::ZERO },
+            Self::MaintainDistance => ActionType::MaintainDistance,
+            Self::Wait => ActionType::Wait,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionDefinition {
+    pub name: String,
+    pub cost: f32,
+    #[serde(default)]
+    pub preconditions: WorldState,
+    #[serde(default)]
+    pub effects: WorldState,
+    pub action: ActionTemplate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalDefinition {
+    pub name: String,
+    pub priority: f32,
+    pub desired_state: WorldState,
+}
+
+/// Selects and tunes a subset of the shared library for one faction or enemy archetype
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoapProfile {
+    /// Action names to include; `None` means every action in the library
+    pub actions: Option<Vec<String>>,
+    pub exclude_actions: Vec<String>,
+    /// Goal names to include; `None` means every goal in the library
+    pub goals: Option<Vec<String>>,
+    pub goal_priorities: HashMap<String, f32>,
+    pub cost_overrides: HashMap<String, f32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GoapConfig {
+    pub actions: Vec<ActionDefinition>,
+    pub goals: Vec<GoalDefinition>,
+    #[serde(default)]
+    pub profiles: HashMap<String, GoapProfile>,
+}
+
+impl GoapConfig {
+    pub fn load_from_file(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn profile(&self, key: &str) -> Option<&GoapProfile> {
+        self.profiles.get(key)
+    }
+
+    /// Builds the action and goal libraries for a profile, falling back to `default`
+    pub fn build_profile(&self, key: &str) -> (Vec<GoapAction>, Vec<Goal>) {
+        let empty = GoapProfile::default();
+        let profile = self.profiles.get(key)
+            .or_else(|| self.profiles.get(DEFAULT_GOAP_PROFILE))
+            .unwrap_or(&empty);
+
+        let actions = self.actions.iter()
+            .filter(|a| profile.actions.as_ref().map_or(true, |names| names.contains(&a.name)))
+            .filter(|a| !profile.exclude_actions.contains(&a.name))
+            .map(|a| GoapAction {
+                name: a.name.clone(),
+                cost: profile.cost_overrides.get(&a.name).copied().unwrap_or(a.cost),
+                preconditions: a.preconditions.clone(),
+                effects: a.effects.clone(),
+                action_type: a.action.instantiate(),
+            })
+            .collect();
+
+        let goals = self.goals.iter()
+            .filter(|g| profile.goals.as_ref().map_or(true, |names| names.contains(&g.name)))
+            .map(|g| Goal {
+                name: g.name.clone(),
+                priority: profile.goal_priorities.get(&g.name).copied().unwrap_or(g.priority),
+                desired_state: g.desired_state.clone(),
+            })
+            .collect();
+
+        (actions, goals)
+    }
+}
+
+pub fn faction_profile_key(faction: &Faction) -> &'static str {
+    match faction {
+        Faction::Player => "player",
+        Faction::Corporate => "corporate",
+        Faction::Syndicate => "syndicate",
+        Faction::Police => "police",
+        Faction::Civilian => "civilian",
+        Faction::Military => "military",
+        Faction::Underground => "underground",
+    }
+}
+
+fn resolve_profile_key(goap_agent: &GoapAgent, faction: Option<&Faction>) -> String {
+    goap_agent.profile.clone()
+        .or_else(|| faction.map(|f| faction_profile_key(f).to_string()))
+        .unwrap_or_else(|| DEFAULT_GOAP_PROFILE.to_string())
+}
+
+// === CACHE ===
+#[derive(Resource)]
+pub struct GoapConfigCache {
+    config: GoapConfig,
+    last_modified: SystemTime,
+    reload_count: u32,
+}
+
+impl Default for GoapConfigCache {
+    fn default() -> Self {
+        let mut cache = Self {
+            config: GoapConfig::default(),
+            last_modified: SystemTime::UNIX_EPOCH,
+            reload_count: 0,
+        };
+        cache.reload();
+        cache
+    }
+}
+
+impl GoapConfigCache {
+    pub fn config(&self) -> &GoapConfig {
+        &self.config
+    }
+
+    /// Reloads if the file changed on disk; returns true when new data was applied
+    pub fn check_for_changes(&mut self) -> bool {
+        let file_modified = match std::fs::metadata(GOAP_CONFIG_PATH) {
+            Ok(metadata) => metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            Err(_) => return false,
+        };
+
+        file_modified > self.last_modified && self.reload()
+    }
+
+    /// Force reload (keeps the previous config if the new file fails to parse)
+    pub fn reload(&mut self) -> bool {
+        let file_modified = std::fs::metadata(GOAP_CONFIG_PATH)
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        self.last_modified = file_modified;
+
+        match GoapConfig::load_from_file(GOAP_CONFIG_PATH) {
+            Ok(config) => {
+                self.config = config;
+                self.reload_count += 1;
+                info!("GOAP config loaded: {} actions, {} goals, {} profiles (reloads: {})",
+                      self.config.actions.len(), self.config.goals.len(),
+                      self.config.profiles.len(), self.reload_count);
+                true
+            }
+            Err(e) => {
+                error!("Failed to load GOAP config {}", e);
+                false
+            }
+        }
+    }
+}
+
+// === SYSTEMS ===
+/// Gives newly spawned GOAP agents the library for their profile or faction
+pub fn apply_goap_profiles_system(
+    cache: Res<GoapConfigCache>,
+    mut new_agents: Query<(&mut GoapAgent, Option<&Faction>), Added<GoapAgent>>,
+) {
+    for (mut goap_agent, faction) in new_agents.iter_mut() {
+        let key = resolve_profile_key(&goap_agent, faction);
+        goap_agent.apply_profile(cache.config(), &key);
+    }
+}
+
+/// Polls the config file and rebuilds every live agent's library when it changes
+pub fn goap_config_hot_reload_system(
+    mut cache: ResMut<GoapConfigCache>,
+    mut agents: Query<(&mut GoapAgent, Option<&Faction>)>,
+) {
+    if !cache.check_for_changes() {
+        return;
+    }
+
+    let mut updated = 0;
+    for (mut goap_agent, faction) in agents.iter_mut() {
+        let key = resolve_profile_key(&goap_agent, faction);
+        goap_agent.apply_profile(cache.config(), &key);
+        updated += 1;
+    }
+
+    info!("GOAP config hot-reloaded, updated {} agents", updated);
+}
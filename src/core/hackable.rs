@@ -31,7 +31,8 @@ pub struct Hackable {
     pub requires_tool: Option<HackTool>,
     pub is_hacked: bool,
     pub network_id: Option<String>,
-    pub disabled_duration: f32,
+    /// How long a hack keeps the device down; `None` keeps it down for the rest of the mission
+    pub disabled_duration: Option<f32>,
     pub hack_effects: Vec<HackEffect>,
 }
 
@@ -78,18 +79,32 @@ impl Hackable {
             },
             is_hacked: false,
             network_id: None,
-            disabled_duration: 30.0,
+            disabled_duration: Some(30.0),
             hack_effects: effects.to_vec(),
         }
     }
     
+    /// Effects a finished hack applies. Taking a device over supersedes switching it off,
+    /// and overloading needs a hacker strong enough to push it past a plain power cut.
+    pub fn landed_effects(&self, can_overload: bool) -> Vec<HackEffect> {
+        let takes_control = self.hack_effects.iter().any(|effect| matches!(effect, HackEffect::TakeControl));
+        self.hack_effects.iter()
+            .filter(|effect| match effect {
+                HackEffect::Disable => !takes_control,
+                HackEffect::Overload => can_overload,
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn with_network(mut self, network_id: String) -> Self {
         self.network_id = Some(network_id);
         self
     }
     
     pub fn permanent_hack(mut self) -> Self {
-        self.disabled_duration = -1.0;
+        self.disabled_duration = None;
         self
     }
     
@@ -139,15 +154,40 @@ fn get_device_function(device_type: DeviceType) -> DeviceFunction {
 }
 
 // === POWER GRID ===
+/// Device networks are the grid's districts. Stations feed substations, substations feed districts
+/// and each other over tie lines, and every link carries a limited amount of power.
 #[derive(Resource, Default)]
 pub struct PowerGrid {
     pub networks: HashMap<String, PowerNetwork>,
+    pub topology: GridTopology,
+    /// Set when networks gain devices or stations, so the topology is rebuilt around them
+    pub topology_dirty: bool,
+}
+
+impl PowerGrid {
+    /// Network `id`, created if it's new
+    pub fn network_mut(&mut self, id: &str) -> &mut PowerNetwork {
+        self.topology_dirty = true;
+        self.networks.entry(id.to_string()).or_insert_with(|| PowerNetwork::new(id.to_string()))
+    }
+
+    pub fn is_hardened(&self, network_id: Option<&String>) -> bool {
+        network_id.and_then(|id| self.networks.get(id)).is_some_and(|network| network.hardened)
+    }
+
+    /// Devices off the grid run on their own supply
+    pub fn supply_for(&self, network_id: Option<&String>) -> SupplyState {
+        network_id.and_then(|id| self.networks.get(id)).map_or(SupplyState::Powered, |network| network.supply)
+    }
 }
 
 #[derive(Clone)]
 pub struct PowerNetwork {
     pub id: String,
     pub powered: bool,
+    pub supply: SupplyState,
+    /// Locked down after a completed trace; hacks on it take longer for the rest of the mission
+    pub hardened: bool,
     pub connected_devices: HashSet<Entity>,
     pub power_sources: HashSet<Entity>,
 }
@@ -157,18 +197,192 @@ impl PowerNetwork {
         Self {
             id,
             powered: true,
+            supply: SupplyState::Powered,
+            hardened: false,
             connected_devices: HashSet::new(),
             power_sources: HashSet::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SupplyState {
+    Powered,
+    /// Getting some of what it draws, not all of it
+    Brownout,
+    /// Cut off from the grid and running on its own generator until the fuel's gone
+    Backup,
+    Blackout,
+}
+
+impl SupplyState {
+    pub fn is_powered(self) -> bool {
+        self != SupplyState::Blackout
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridTier {
+    Station,
+    Substation,
+    District,
+}
+
+#[derive(Debug, Clone)]
+pub struct GridNode {
+    pub id: String,
+    pub tier: GridTier,
+    /// The power station behind a station node
+    pub entity: Option<Entity>,
+    pub position: Vec2,
+    /// Generation for stations, throughput for substations, draw for districts
+    pub capacity: f32,
+    /// What went through the node on the last solve; for districts, what was delivered
+    pub flow: f32,
+    /// False while a station is tripped
+    pub online: bool,
+    /// Seconds a station has run flat out with districts still short; it trips when this builds up
+    pub strain: f32,
+    /// Seconds until a tripped station comes back
+    pub trip_timer: f32,
+    /// Districts with a backup generator, in seconds of fuel left
+    pub backup_fuel: Option<f32>,
+    pub supply: SupplyState,
+}
+
+impl GridNode {
+    pub fn new(id: String, tier: GridTier, position: Vec2, capacity: f32) -> Self {
+        Self {
+            id,
+            tier,
+            entity: None,
+            position,
+            capacity,
+            flow: 0.0,
+            online: true,
+            strain: 0.0,
+            trip_timer: 0.0,
+            backup_fuel: None,
+            supply: SupplyState::Powered,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GridLink {
+    pub from: usize,
+    pub to: usize,
+    pub capacity: f32,
+    pub flow: f32,
+}
+
+#[derive(Default, Clone)]
+pub struct GridTopology {
+    pub nodes: Vec<GridNode>,
+    pub links: Vec<GridLink>,
+}
+
+impl GridTopology {
+    pub fn add_node(&mut self, node: GridNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// One-way link; tie lines between substations carry power either way, so callers add both directions
+    pub fn link(&mut self, from: usize, to: usize, capacity: f32) {
+        self.links.push(GridLink { from, to, capacity, flow: 0.0 });
+    }
+
+    pub fn district(&self, network_id: &str) -> Option<&GridNode> {
+        self.nodes.iter().find(|node| node.tier == GridTier::District && node.id == network_id)
+    }
+
+    /// Routes as much power as the stations can push to the districts (max flow), so a district
+    /// that loses its usual feed draws through whatever path is left. `available(i)` is how much
+    /// station or substation `i` can pass right now.
+    pub fn solve(&mut self, available: impl Fn(usize, &GridNode) -> f32) {
+        // Every node is split into an in and an out vertex so its own capacity caps what passes through
+        let n = self.nodes.len() * 2 + 2;
+        let (source, sink) = (n - 2, n - 1);
+        let mut capacity = vec![vec![0.0f32; n]; n];
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let through = match node.tier {
+                GridTier::District => node.capacity,
+                _ => available(i, node),
+            };
+            capacity[i * 2][i * 2 + 1] = through.max(0.0);
+            match node.tier {
+                GridTier::Station => capacity[source][i * 2] = f32::MAX,
+                GridTier::District => capacity[i * 2 + 1][sink] = f32::MAX,
+                GridTier::Substation => {}
+            }
+        }
+        for link in &self.links {
+            capacity[link.from * 2 + 1][link.to * 2] += link.capacity;
+        }
+
+        let residual_start = capacity.clone();
+        let mut residual = capacity;
+        loop {
+            // Shortest augmenting path first (Edmonds-Karp)
+            let mut previous = vec![usize::MAX; n];
+            previous[source] = source;
+            let mut queue = std::collections::VecDeque::from([source]);
+            while let Some(at) = queue.pop_front() {
+                if at == sink {
+                    break;
+                }
+                for (next, &room) in residual[at].iter().enumerate() {
+                    if previous[next] == usize::MAX && room > 1e-4 {
+                        previous[next] = at;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            if previous[sink] == usize::MAX {
+                break;
+            }
+
+            let mut push = f32::MAX;
+            let mut at = sink;
+            while at != source {
+                push = push.min(residual[previous[at]][at]);
+                at = previous[at];
+            }
+            let mut at = sink;
+            while at != source {
+                residual[previous[at]][at] -= push;
+                residual[at][previous[at]] += push;
+                at = previous[at];
+            }
+        }
+
+        let used = |from: usize, to: usize| (residual_start[from][to] - residual[from][to]).max(0.0);
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.flow = used(i * 2, i * 2 + 1);
+        }
+        // Parallel links share a vertex pair, so each gets its share of what went across
+        let mut pair_capacity: HashMap<(usize, usize), f32> = HashMap::new();
+        for link in &self.links {
+            *pair_capacity.entry((link.from, link.to)).or_default() += link.capacity;
+        }
+        for link in &mut self.links {
+            let total = pair_capacity[&(link.from, link.to)];
+            let across = used(link.from * 2 + 1, link.to * 2);
+            link.flow = if total > 0.0 { across * link.capacity / total } else { 0.0 };
+        }
+    }
+}
+
 // === EVENTS ===
 #[derive(Event)]
 pub struct HackAttemptEvent {
     pub agent: Entity,
     pub target: Entity,
     pub tool_used: HackTool,
+    /// Network hops between the agent's way in and the target; 0 when hacking on site
+    pub hops: u32,
 }
 
 #[derive(Event)]
@@ -179,21 +393,22 @@ pub struct HackCompletedEvent {
     pub effects: Vec<HackEffect>,
 }
 
+/// Sent for each district whose supply changed
 #[derive(Event)]
 pub struct PowerGridEvent {
     pub network_id: String,
     pub powered: bool,
-    pub source: Entity,
+    pub supply: SupplyState,
 }
 
 
 pub fn hack_recovery_system(
-    mut hackable_query: Query<(Entity, &mut Hackable, &mut DeviceState)>,
-    mut power_events: EventWriter<PowerGridEvent>,
+    mut hackable_query: Query<(&mut Hackable, &mut DeviceState)>,
+    power_grid: Res<PowerGrid>,
     time: Res<Time>,
 ) {
-    for (entity, mut hackable, mut device_state) in hackable_query.iter_mut() {
-        if !hackable.is_hacked || hackable.disabled_duration < 0.0 {
+    for (mut hackable, mut device_state) in hackable_query.iter_mut() {
+        if !hackable.is_hacked || hackable.disabled_duration.is_none() {
             continue;
         }
         
@@ -201,20 +416,12 @@ pub fn hack_recovery_system(
         
         if device_state.hack_timer <= 0.0 {
             hackable.is_hacked = false;
-            device_state.operational = true;
-            device_state.powered = true;
+            // Back to whatever its district is getting; a blacked-out device stays dark
+            let powered = power_grid.supply_for(hackable.network_id.as_ref()).is_powered();
+            device_state.powered = powered;
+            device_state.operational = powered;
             device_state.current_function = device_state.original_function;
-            
-            // Restore power grid if needed
-            if hackable.device_type == DeviceType::PowerStation {
-                if let Some(network_id) = &hackable.network_id {
-                    power_events.write(PowerGridEvent {
-                        network_id: network_id.clone(),
-                        powered: true,
-                        source: entity,
-                    });
-                }
-            }
+            // A recovered power station is picked up by the next grid solve
         }
     }
 }
@@ -230,15 +437,15 @@ pub fn power_grid_system(
         };
         
         network.powered = event.powered;
+        network.supply = event.supply;
         
         // Update all connected devices
         for &device_entity in &network.connected_devices.clone() {
             if let Ok((hackable, mut device_state)) = device_query.get_mut(device_entity) {
                 if hackable.network_id.as_ref() == Some(&event.network_id) {
                     device_state.powered = event.powered;
-                    if !event.powered {
-                        device_state.operational = false;
-                    }
+                    // Power coming back doesn't undo a hack
+                    device_state.operational = event.powered && !hackable.is_hacked;
                 }
             }
         }
@@ -261,9 +468,7 @@ pub fn make_hackable_networked(
 ) {
     let hackable = Hackable::new(device_type).with_network(network_id.clone());
     
-    power_grid.networks.entry(network_id.clone())
-        .or_insert_with(|| PowerNetwork::new(network_id))
-        .connected_devices.insert(entity);
+    power_grid.network_mut(&network_id).connected_devices.insert(entity);
     
     commands.entity(entity)
         .insert(hackable)
@@ -1,10 +1,12 @@
 // src/core/input.rs - Input definitions and targeting
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // === INPUT ACTIONS ===
-#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
 #[reflect(Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub enum PlayerAction {
     Pause,
     Select,
@@ -24,6 +26,7 @@ pub enum TargetingMode {
     Neurovector { agent: Entity },
     Combat { agent: Entity },
     Scanning,
+    DeviceControl { device: Entity },
 }
 
 // === SELECTION ===
@@ -1,6 +1,8 @@
 // src/core/missions.rs - Mission briefing and evaluation systems
 use bevy::prelude::*;
 use bevy_egui::egui;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use crate::core::*;
 
 // === MISSION BRIEFING ===
@@ -23,7 +25,7 @@ pub struct MissionObjective {
     pub difficulty: u8, // 1-5
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ObjectiveType {
     Eliminate,
     Extract,
@@ -41,7 +43,8 @@ pub struct ResistanceProfile {
     pub alert_sensitivity: f32,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum EnemyType {
     Guard,
     Patrol,
@@ -133,14 +136,14 @@ pub struct MissionTracker {
     pub efficiency_rating: f32, // Based on time and method
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ObjectiveStatus {
     pub completed: bool,
     pub method: Option<CompletionMethod>,
     pub time_taken: f32,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum CompletionMethod {
     Stealth,
     Combat,
@@ -158,17 +161,83 @@ pub struct MissionModifiers {
 }
 
 // === MISSION PERFORMANCE ===
-#[derive(Default)]
+/// Scorecard for a finished mission, see `systems::grading`
+#[derive(Clone, Default)]
 pub struct MissionPerformance {
     pub success: bool,
+    /// 1.0 = never detected, falling with the alert peak once spotted
     pub stealth_rating: f32,
+    /// 1.0 at or under par time, 0.0 at twice par
     pub efficiency_rating: f32,
     pub objective_completion: f32,
+    /// Score points for staying hidden
     pub stealth_bonus: u32,
+    /// Score points for beating par
     pub speed_bonus: u32,
+    /// Score points lost to civilian casualties
     pub civilian_penalty: f32,
     pub research_progress: Option<String>,
     pub final_credits: u32,
+
+    pub score: u32,
+    pub grade: MissionGrade,
+    pub detected: bool,
+    pub alert_peak: AlertLevel,
+    pub civilian_casualties: u32,
+    pub optional_completed: u32,
+    pub optional_total: u32,
+    pub par_time: f32,
+    pub damage_taken: f32,
+    pub neurovector_uses: u32,
+    /// Experience each squad slot earned, split by contribution
+    pub agent_xp: [u32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MissionGrade {
+    S,
+    A,
+    B,
+    C,
+    D,
+    #[default]
+    F,
+}
+
+impl MissionGrade {
+    pub fn from_score(score: u32) -> Self {
+        match score {
+            90.. => MissionGrade::S,
+            80..=89 => MissionGrade::A,
+            65..=79 => MissionGrade::B,
+            50..=64 => MissionGrade::C,
+            35..=49 => MissionGrade::D,
+            _ => MissionGrade::F,
+        }
+    }
+
+    /// Scales the squad's experience pool
+    pub fn xp_multiplier(&self) -> f32 {
+        match self {
+            MissionGrade::S => 1.5,
+            MissionGrade::A => 1.25,
+            MissionGrade::B => 1.0,
+            MissionGrade::C => 0.9,
+            MissionGrade::D => 0.75,
+            MissionGrade::F => 0.5,
+        }
+    }
+
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            MissionGrade::S => egui::Color32::from_rgb(252, 255, 82),
+            MissionGrade::A => egui::Color32::GREEN,
+            MissionGrade::B => egui::Color32::LIGHT_GREEN,
+            MissionGrade::C => egui::Color32::YELLOW,
+            MissionGrade::D => egui::Color32::ORANGE,
+            MissionGrade::F => egui::Color32::RED,
+        }
+    }
 }
 
 // === EQUIPMENT RECOMMENDATIONS ===
@@ -247,10 +316,9 @@ pub fn generate_mission_briefing_for_city(
     
     let city_state = cities_progress.get_city_state(city_id);
     
-    // Use a simple hash-based random generation for consistency
-    let seed = city_id.len() + global_data.current_day as usize + city.corruption_level as usize;
-    let pseudo_random = (seed * 1103515245 + 12345) % (1 << 31);
-    let random_f32 = (pseudo_random as f32) / (1u32 << 31) as f32;
+    // Same campaign, city and day always brief the same mission
+    let mut rng = StdRng::seed_from_u64(briefing_seed(global_data, city_id));
+    let random_f32 = rng.gen::<f32>();
     
     // Convert city data to mission parameters
     let base_difficulty = city.corruption_level as f32 / 10.0; // 0.1 to 1.0
@@ -264,7 +332,7 @@ pub fn generate_mission_briefing_for_city(
     let effective_difficulty = (base_difficulty * alert_modifier).clamp(0.1, 2.0);
     
     // Generate objectives based on city traits and corporation
-    let objectives = generate_city_objectives(&city, effective_difficulty, seed);
+    let objectives = generate_city_objectives(&city, effective_difficulty, &mut rng);
     
     // Enemy resistance based on city properties
     let enemy_count = (8.0 + (city.population as f32 * 0.5) + (effective_difficulty * 10.0)) as u32;
@@ -275,7 +343,7 @@ pub fn generate_mission_briefing_for_city(
         security_level: security_level.clamp(1, 5),
         alert_sensitivity: 0.3 + (effective_difficulty * 0.4),
         patrol_density: 0.2 + (effective_difficulty * 0.3),
-        enemy_types: vec![EnemyType::Guard, EnemyType::Patrol], // Use existing enemy types only
+        enemy_types: enemy_types_for_security(security_level.clamp(1, 5), city.corruption_level),
     };
     
     // Environment based on city traits  
@@ -284,7 +352,7 @@ pub fn generate_mission_briefing_for_city(
         cover_density: 0.4 + random_f32 * 0.4,
         visibility: 0.6 + random_f32 * 0.3,
         civilian_presence: (city.population / 3).clamp(0, 5) as u8, // Civilians are separate from enemies
-        time_of_day: match pseudo_random % 4 {
+        time_of_day: match rng.gen_range(0..4) {
             0 => TimeOfDay::Dawn,
             1 => TimeOfDay::Day,
             2 => TimeOfDay::Dusk,
@@ -332,10 +400,14 @@ pub fn generate_mission_briefing_for_city(
         environment,
         risks,
         rewards,
-        region_id: city_id.len() + seed, // Use city_id as region_id for now
+        region_id: global_data.selected_region,
     }
 }
 
+pub fn briefing_seed(global_data: &GlobalData, city_id: &str) -> u64 {
+    derive_seed(global_data.campaign_seed, &[b"briefing", city_id.as_bytes(), &global_data.current_day.to_le_bytes()])
+}
+
 /*
     Eliminate,
     Extract,
@@ -343,7 +415,7 @@ pub fn generate_mission_briefing_for_city(
     Infiltrate,
     Survive,
     */
-fn generate_city_objectives(city: &City, difficulty: f32, seed: usize) -> Vec<MissionObjective> {
+fn generate_city_objectives(city: &City, difficulty: f32, rng: &mut impl Rng) -> Vec<MissionObjective> {
     let mut objectives = vec![
         MissionObjective {
             name: "Infiltrate Target Building".to_string(),
@@ -354,8 +426,7 @@ fn generate_city_objectives(city: &City, difficulty: f32, seed: usize) -> Vec<Mi
         }
     ];
     
-    // Use seed for deterministic randomness
-    let random_chance = ((seed * 31) % 100) as f32 / 100.0;
+    let random_chance = rng.gen::<f32>();
     
     if random_chance > 0.3 {
         objectives.push(MissionObjective {
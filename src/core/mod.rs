@@ -6,6 +6,7 @@ pub mod events;
 pub mod audio;
 pub mod sprites;
 pub mod goap;
+pub mod goap_config;
 pub mod research;
 pub mod attachments;
 pub mod agent_upgrades;
@@ -19,6 +20,7 @@ pub mod components;
 pub mod resources;
 pub mod entities;
 pub mod missions;
+pub mod enemy_archetypes;
 pub mod weapons;
 pub mod config;
 pub mod scene_cache;
@@ -28,6 +30,7 @@ pub mod hackable;
 pub mod cities;
 pub mod despawn;
 pub mod spawn_damage_text;
+pub mod rng;
 
 // 0.2.17
 pub mod territory;
@@ -43,6 +46,7 @@ pub use events::*;
 pub use audio::*;
 pub use sprites::*;
 pub use goap::*;
+pub use goap_config::*;
 pub use research::*;
 pub use attachments::*;
 pub use agent_upgrades::*;
@@ -55,6 +59,7 @@ pub use components::*;
 pub use resources::*;
 pub use entities::*;
 pub use missions::*;
+pub use enemy_archetypes::*;
 pub use weapons::*;
 pub use config::*;
 pub use scene_cache::*;
@@ -62,6 +67,7 @@ pub use lore::*;
 pub use hackable::*;
 pub use cities::*;
 pub use spawn_damage_text::*;
+pub use rng::*;
 
 
 // === MISSING TYPES ===
@@ -510,7 +510,7 @@ pub fn spawn_research_facility(
             hack_time: 5.0 + security_level as f32 * 2.0, // Harder facilities take longer
             security_level: security_level as u8,
             is_hacked: false,
-            disabled_duration: 0.0,
+            disabled_duration: Some(0.0),
             hack_effects: Vec::new(),
             network_id: None,
             requires_tool: None,
@@ -1,6 +1,6 @@
 // src/core/resources.rs - Game resources and state
 use bevy::prelude::*;
-use crate::core::{TargetingMode, AlertLevel, AttachmentSlot};
+use crate::core::{TargetingMode, AlertLevel, AttachmentSlot, MissionPerformance};
 
 #[derive(Resource)]
 pub struct MissionLaunchData {
@@ -8,6 +8,13 @@ pub struct MissionLaunchData {
     pub region_id: usize,
 }
 
+/// Scene file actually spawned for the running mission, used to key quicksaves
+#[derive(Resource, Clone)]
+pub struct ActiveMissionScene {
+    pub scene_name: String,
+    pub city_id: Option<String>,
+}
+
 // === GAME MODE ===
 #[derive(Resource)]
 pub struct GameMode {
@@ -44,6 +51,10 @@ pub struct MissionData {
     pub enemies_killed: u32,
     pub terminals_accessed: u32,
     pub time_limit: f32,
+    /// Squad slots (`AgentIndex`) of agents killed this mission
+    pub agents_lost: Vec<usize>,
+    /// Squad slots still in the field when the extraction window closed
+    pub agents_missing: Vec<usize>,
 }
 
 impl Default for MissionData {
@@ -56,30 +67,51 @@ impl Default for MissionData {
             enemies_killed: 0,
             terminals_accessed: 0,
             time_limit: 300.0, // 5 minutes
+            agents_lost: Vec::new(),
+            agents_missing: Vec::new(),
         }
     }
 }
 
 // === POST MISSION ===
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MissionOutcome {
+    Success,
+    /// Objectives done, but not every agent made it out
+    PartialSuccess,
+    #[default]
+    Failed,
+}
+
 #[derive(Resource)]
 pub struct PostMissionResults {
+    /// True for both full and partial success
     pub success: bool,
+    pub outcome: MissionOutcome,
     pub time_taken: f32,
     pub enemies_killed: u32,
     pub terminals_accessed: u32,
     pub credits_earned: u32,
     pub alert_level: AlertLevel,
+    pub agents_extracted: u32,
+    pub agents_missing: u32,
+    /// Filled in by `process_mission_results`
+    pub performance: MissionPerformance,
 }
 
 impl Default for PostMissionResults {
     fn default() -> Self {
         Self {
             success: false,
+            outcome: MissionOutcome::Failed,
             time_taken: 0.0,
             enemies_killed: 0,
             terminals_accessed: 0,
             credits_earned: 0,
             alert_level: AlertLevel::Green,
+            agents_extracted: 0,
+            agents_missing: 0,
+            performance: MissionPerformance::default(),
         }
     }
 }
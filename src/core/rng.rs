@@ -0,0 +1,109 @@
+// src/core/rng.rs - Seeded gameplay randomness, split into independent per-subsystem streams
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Overrides the campaign and mission seeds, for reproducing a reported run
+pub const SIM_SEED_ENV: &str = "SUBVERSIVE_SEED";
+
+/// Rate of the `FixedUpdate` gameplay simulation
+pub const SIMULATION_HZ: f64 = 60.0;
+
+/// Each subsystem draws from its own stream, so adding a roll to one
+/// doesn't shift every other subsystem's sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    Combat,
+    Ai,
+    Spawning,
+    Civilians,
+    Traffic,
+    Environment,
+    Hacking,
+}
+
+impl RngStream {
+    pub const ALL: [RngStream; 7] = [
+        RngStream::Combat,
+        RngStream::Ai,
+        RngStream::Spawning,
+        RngStream::Civilians,
+        RngStream::Traffic,
+        RngStream::Environment,
+        RngStream::Hacking,
+    ];
+}
+
+/// All mission randomness goes through here; reseeded at the start of every mission.
+/// Purely cosmetic jitter (decals, camera shake, damage numbers) may still use `rand::random`.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: Vec<StdRng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: RngStream::ALL.iter()
+                .map(|&stream| StdRng::seed_from_u64(splitmix64(seed ^ (stream as u64 + 1).wrapping_mul(GOLDEN_GAMMA))))
+                .collect(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(env_seed().unwrap_or_else(rand::random))
+    }
+}
+
+pub fn env_seed() -> Option<u64> {
+    std::env::var(SIM_SEED_ENV).ok().and_then(|value| value.trim().parse().ok())
+}
+
+/// Seed for one mission's `GameRng`; replaying the same campaign, scene and day plays out the same
+pub fn mission_seed(campaign_seed: u64, scene_name: &str, city_id: Option<&str>, current_day: u32) -> u64 {
+    env_seed().unwrap_or_else(|| derive_seed(campaign_seed, &[
+        b"mission",
+        scene_name.as_bytes(),
+        city_id.unwrap_or_default().as_bytes(),
+        &current_day.to_le_bytes(),
+    ]))
+}
+
+/// Stable seed from a campaign seed plus identifying bytes (city id, day, ...)
+pub fn derive_seed(base: u64, parts: &[&[u8]]) -> u64 {
+    // FNV-1a, finished with splitmix so nearby inputs still diverge. Each part goes in behind its
+    // length, so ["ab", "c"] and ["a", "bc"] don't hash the same.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325 ^ base;
+    for part in parts {
+        for &byte in (part.len() as u64).to_le_bytes().iter().chain(part.iter()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    splitmix64(hash)
+}
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(GOLDEN_GAMMA);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct GameSprites {
     pub agent: Handle<Image>,
     pub civilian: Handle<Image>,
@@ -171,7 +171,7 @@ impl WeaponDatabase {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct WeaponState {
     pub current_ammo: u32,
     pub max_ammo: u32,
@@ -0,0 +1,3 @@
+// src/lib.rs - The game itself; the windowed game and the headless runner are both thin binaries over it
+pub mod core;
+pub mod systems;
@@ -19,54 +19,50 @@ use bevy::time::common_conditions::on_timer;
 use leafwing_input_manager::prelude::*;
 use std::sync::{Arc, Mutex};
 
-use systems::interactive_decals::*;
-use systems::explosion_decal_integration::*;
-// use systems::police::{load_police_config, PoliceResponse, PoliceEscalation};
+use subversive::systems::interactive_decals::*;
+use subversive::systems::explosion_decal_integration::*;
 
-use systems::ui::enhanced_inventory::*;
-use systems::ui::inventory_integration::*;
-use systems::ui::inventory_compatibility::*;
-use systems::ui::hub::singapore_map::{SingaporeMapState,SingaporeVectorMap};
-use systems::scenes::{spawn_fallback_isometric_mission};
+use subversive::systems::ui::enhanced_inventory::*;
+use subversive::systems::ui::inventory_integration::*;
+use subversive::systems::ui::inventory_compatibility::*;
+use subversive::systems::ui::hub::singapore_map::{SingaporeMapState,SingaporeVectorMap};
+use subversive::systems::scenes::{spawn_fallback_isometric_mission};
 
-use systems::input::*;
+use subversive::systems::input::*;
 
 // 0.2.16
 // use systems::light_2d_setup::*;
 
 // 0.2.17
-use crate::core::territory::*;
-use crate::systems::territory_events::{TerritoryControlEvent};
-use crate::systems::profiling::PerformanceMetrics;
+use subversive::core::territory::*;
+use subversive::systems::territory_events::{TerritoryControlEvent};
+use subversive::systems::profiling::profile_system;
 
 // XAML UI
-use crate::systems::ui::layout::{UILayoutCache, setup_ui_layout_system};
-mod core;
-mod systems;
-
-use core::*;
-use core::factions;
-use systems::*;
-use pool::*;
-use systems::scenes::*;
-use systems::explosions::*;
-use systems::projectiles::*;
-use systems::ui::{loading_system};
-use systems::world_scan::{WorldScanState, WorldScanEvent, EntityScannedEvent};
-use systems::ui::hub::research::{research_navigation_system,ResearchUIState};
-use systems::ui::settings::{GameSettings};
+use subversive::systems::ui::layout::{UILayoutCache, setup_ui_layout_system};
+
+use subversive::core::*;
+use subversive::core::factions;
+use subversive::systems::*;
+use subversive::systems::pool::*;
+use subversive::systems::scenes::*;
+use subversive::systems::explosions::*;
+use subversive::systems::projectiles::*;
+use subversive::systems::ui::{loading_system};
+use subversive::systems::world_scan::{WorldScanState, WorldScanEvent, EntityScannedEvent};
+use subversive::systems::ui::hub::research::{research_navigation_system,ResearchUIState};
+use subversive::systems::ui::settings::{GameSettings};
 // USER INTERFACE
-use systems::ui::hub::{CyberneticsDatabase, HubState, HubDatabases};
-use systems::ui::hub::agents::AgentManagementState;
-use systems::ui::{main_menu, settings, credits};
-use systems::ui::{MainMenuState};
-use systems::ui::screens::InventoryUIState;
-use systems::ui::post_mission::{PostMissionUIState};
-use systems::enhanced_pathfinding::{EnhancedPathfindingGrid};
+use subversive::systems::ui::hub::{CyberneticsDatabase, HubState, HubDatabases};
+use subversive::systems::ui::hub::agents::AgentManagementState;
+use subversive::systems::ui::{main_menu, settings, credits, load_game, new_game};
+use subversive::systems::ui::{MainMenuState};
+use subversive::systems::ui::screens::InventoryUIState;
+use subversive::systems::ui::post_mission::{PostMissionUIState};
 
 fn main() {
 
-    let (global_data, research_progress, territory_manager, progression_tracker) = load_global_data_or_default();
+    let (global_data, research_progress, territory_manager, progression_tracker, save_session) = load_global_data_or_default();
     ensure_data_directories();
 
     App::new()
@@ -81,6 +77,11 @@ fn main() {
             ..default()
         }))
         .add_plugins(ProfilingPlugin)
+        .add_plugins(GoapDebugPlugin)
+        .add_plugins(SceneEditorPlugin)
+        .add_plugins(ReplayPlugin)
+        .add_plugins(CyberspacePlugin)
+        .add_plugins(SimulationPlugin)
 
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
         .add_plugins(InputManagerPlugin::<PlayerAction>::default())
@@ -94,32 +95,24 @@ fn main() {
         .init_state::<GameState>()
 
         .init_resource::<GameSettings>()
-        .init_resource::<GameMode>()
         .init_resource::<FontsLoaded>()
-        .init_resource::<SelectionState>()
         .init_resource::<SceneData>()
-        .init_resource::<MissionData>()
+        .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
         .init_resource::<InventoryState>()
         .init_resource::<InventoryUIState>()
-        .init_resource::<PostMissionResults>()
         .init_resource::<MissionState>()
-        .init_resource::<DayNightCycle>()
 
-        .init_resource::<UrbanSecurity>()
-        .init_resource::<UrbanSecurity>()
-
-        .init_resource::<CombatTextSettings>()
         .init_resource::<AgentManagementState>()
         .init_resource::<CitiesDatabase>()
         .init_resource::<CitiesProgress>()
-        .init_resource::<MessageLog>()
         .init_resource::<ScannerState>()
         .init_resource::<MainMenuState>()
+        .insert_resource(save_session)
+        .init_resource::<save::SaveSlotCache>()
+        .init_resource::<load_game::LoadGameState>()
+        .init_resource::<new_game::NewGameState>()
         .init_resource::<ProjectilePool>()
         .init_resource::<ContinuousAttackState>()
-        .init_resource::<DecalSettings>()
-        .init_resource::<InteractiveDecalSettings>()
-        .init_resource::<PathfindingGrid>() // 0.2.5.3
 
         .init_resource::<PostMissionUIState>() // 0.2.5.4
 
@@ -146,20 +139,14 @@ fn main() {
         .init_resource::<HubState>()
         .init_resource::<UnlockedAttachments>()
         .init_resource::<ManufactureState>()
-        .init_resource::<PoliceResponse>()
         .init_resource::<FormationState>()
         // .init_resource::<CivilianSpawner>()
-        .init_resource::<PowerGrid>()
         
         .insert_resource(AgentManagementState::default())
 
         // 0.2.9
-        .init_resource::<TrafficSystem>()
         .init_resource::<RoadGrid>()
 
-        // 0.2.10
-        .init_resource::<BankingNetwork>()
-
         .insert_resource(HubState::default())
         .insert_resource(HubDatabases::default())
         // .insert_resource(HubProgress::default())
@@ -182,10 +169,7 @@ fn main() {
         .init_resource::<InventoryCache>()
 
         // 0.2.16
-        .init_resource::<IsometricSettings>()
         .init_resource::<CameraZoomLevels>()
-        // phase 2
-        .init_resource::<EnhancedPathfindingGrid>()
         //.init_resource::<TileLightingGrid>()
 
         // 0.2.17
@@ -199,32 +183,11 @@ fn main() {
         // XAML UI
         .init_resource::<UILayoutCache>()
 
-        // older
-        .add_event::<ActionEvent>()
-        .add_event::<CombatEvent>()
-        .add_event::<AudioEvent>()
-        .add_event::<AlertEvent>()
-        .add_event::<GrenadeEvent>()
-        .add_event::<BarkEvent>()
-        .add_event::<LoreAccessEvent>()
-        .add_event::<HackAttemptEvent>()
-        .add_event::<HackCompletedEvent>()
-        .add_event::<PowerGridEvent>()
-        .add_event::<DamageTextEvent>()
-
-        // 0.2.10
-        .add_event::<AccessEvent>()
-        .add_event::<GateStateChange>()
-        .add_event::<DoorStateChange>()
-
         // 0.2.12
         .add_event::<ScientistRecruitmentEvent>()
         .add_event::<ResearchCompletedEvent>()
         .add_event::<ResearchSabotageEvent>()
 
-        // 0.2.16
-        .add_event::<TileDamageEvent>()
-
         // 0.2.17
         .add_event::<TerritoryControlEvent>()
 
@@ -241,18 +204,12 @@ fn main() {
             research_gameplay::apply_loaded_research_benefits,
             fonts::check_fonts_loaded,
             
-            setup_urban_security_system,
-            
             sprites::load_sprites,
-            pathfinding::setup_pathfinding_grid, // 0.2.5.3
-            enhanced_pathfinding::setup_enhanced_pathfinding_grid, // 0.2.16 P2
         ))
         .add_systems(Startup, (
 //            setup_weather_tile_system,
 
             setup_cyberpunk2077_theme, // 0.2.5.4
-            setup_traffic_system, // 0.2.9
-            hacking_financial::setup_banking_network, // 0.2.10
             setup_enhanced_inventory,   // 0.2.15
 
             // Cursor and interaction systems
@@ -281,8 +238,11 @@ fn main() {
             ui::fps_system,
 
             pool::cleanup_inactive_entities,
-            save::auto_save_system,
+            // A replay runs on a borrowed campaign that must never reach the save slots
+            save::auto_save_system.run_if(not(is_replaying)),
             save::save_input_system,
+            save::playtime_system.run_if(not(is_replaying)),
+            save::ironman_autosave_system.run_if(not(is_replaying)),
             audio::audio_system,
             scene_cache_debug_system,
         ))
@@ -304,13 +264,27 @@ fn main() {
         .add_systems(Update, (
             // Cleanup systems - run less frequently for performance
             interaction_prompts::cleanup_orphaned_prompts,
+            goap_config::goap_config_hot_reload_system,
         ).run_if(on_timer(std::time::Duration::from_secs_f32(1.0)))) // Only run every second
 
         // MAIN MENU
+        .add_systems(OnEnter(GameState::MainMenu), main_menu::setup_main_menu_egui)
         .add_systems(Update, (
             main_menu::main_menu_system_egui,
         ).run_if(in_state(GameState::MainMenu)))
 
+        // LOAD GAME
+        .add_systems(OnEnter(GameState::LoadGame), load_game::setup_load_game_screen)
+        .add_systems(Update, (
+            load_game::load_game_system_egui,
+        ).run_if(in_state(GameState::LoadGame)))
+
+        // NEW GAME
+        .add_systems(OnEnter(GameState::NewGame), new_game::setup_new_game_screen)
+        .add_systems(Update, (
+            new_game::new_game_system_egui,
+        ).run_if(in_state(GameState::NewGame)))
+
         // SETTINGS
         .add_systems(OnEnter(GameState::Settings), (
             settings::setup_settings_ui
@@ -377,7 +351,8 @@ fn main() {
 
                 ).after(setup_isometric_mission_scene),
             ).after(setup_mission_tilemap),
-        ))
+        // Coming back from cyberspace resumes the mission rather than rebuilding it
+        ).run_if(not(is_jacked_in)))
 
         
         .add_systems(OnEnter(GameState::Mission), (
@@ -385,7 +360,7 @@ fn main() {
             // 0.2.13
             weather::setup_weather_system,
             weather::spawn_weather_overlay,
-        ))
+        ).run_if(not(is_jacked_in)))
         
         // 0.2.12
         .add_systems(Update, (
@@ -414,83 +389,31 @@ fn main() {
 
         // 0.2.16
         .add_systems(Update, (
-             
-            movement::system.pipe(profile_system("movement")),
-            
             // REPLACE: camera::movement,
-            isometric_camera_movement, // USE THIS INSTEAD
-            camera_edge_scrolling,
-            camera_follow_selected_agent,
+            isometric_camera_movement.run_if(not(is_replaying)), // USE THIS INSTEAD
+            camera_edge_scrolling.run_if(not(is_replaying)),
+            camera_follow_selected_agent.run_if(not(is_replaying)),
             camera_shake_system,
             camera_zoom_presets,
             update_camera_bounds,
 
-            selection::system,
-            handle_input,
+            // Replays feed recorded commands instead of live input
+            selection::system.run_if(not(is_replaying)),
+            handle_input.run_if(not(is_replaying)),
 
             tilemap_props::isometric_depth_sorting.pipe(profile_system("depth_sorting")),
 
         ).run_if(in_state(GameState::Mission)))
 
-        // Core AI Systems
         .add_systems(Update, (
-            goap::goap_ai_system.pipe(profile_system("goap_ai")),
-
-            ai::goap_sound_detection_system,
-            ai::alert_system,
-            ai::legacy_enemy_ai_system.pipe(profile_system("legacy_ai")),
-            ai::sound_detection_system,
-
-            morale::morale_system,
-            morale::civilian_morale_system,
-            morale::flee_system,
-        ).run_if(in_state(GameState::Mission)))
-
-        // Combat and interaction systems
-        .add_systems(Update, (
-            weapon_swap::weapon_drop_system,
-            weapon_swap::weapon_pickup_system,
-            weapon_swap::weapon_behavior_system,
-
-            interaction::system,
-            collision_feedback_system,
-
-            combat::process_attack_events,
-            combat::enemy_combat_system,
-
-            combat::system,
-
-            death::death_system,
-            death::explodable_death_system,
-            combat::auto_reload_system,
-            combat::cleanup_miss_targets,
+            combat::system.run_if(not(is_replaying)),
 
             damage_text_event_system,
 
             ui::world::system,
 
-            ui::pause_system,
-
-        ).run_if(in_state(GameState::Mission)))
-
-        .add_systems(Update, (
-            add_enhanced_pathfinding_to_agents,
+            ui::pause_system.run_if(not(is_replaying)),
 
-            // === CORE TILE SYSTEMS ===
-            assign_tile_properties_system,
-            tile_destruction_system,
-            // tile_interaction_system, // DAMN THING CRASHES
-            tile_cover_system,
- 
-            // === ENHANCED PATHFINDING ===
-            update_enhanced_pathfinding_system,
- 
-            enhanced_pathfinding::enhanced_movement_system.pipe(profile_system("pathfinding")),
- 
-            // === VISION AND COVER ===
-            enhanced_vision_system.pipe(profile_system("vision")),
-            enhanced_cover_system,
- 
         ).run_if(in_state(GameState::Mission)))
 
         // 0.2.15
@@ -509,9 +432,6 @@ fn main() {
             decals::decal_cleanup_system,
             death::corpse_cleanup_system,
 
-            // Add decals for projectile impacts
-            // projectile_impact_decals,
-            enhanced_projectile_impact_decals,
             explosion_scorch_decals,
 
             // Minimap systems
@@ -521,85 +441,37 @@ fn main() {
         ).run_if(in_state(GameState::Mission)))
 
         .add_systems(Update, (
-
-            projectiles::unified_projectile_system,
             projectiles::impact_effect_system,
-
         ).run_if(in_state(GameState::Mission)))
 
-        // Mission management systems
         .add_systems(Update, (
-
-            cover::cover_management_system,
-            cover::cover_exit_system,
-
             quicksave::quicksave_system,
+            quicksave::quickload_system,
+        ).run_if(in_state(GameState::Mission)).run_if(not(is_replaying)))
 
-            reload::reload_system,
-
-        ).run_if(in_state(GameState::Mission)))
-
-        // Area control and formations
         .add_systems(Update, (
-
-            // CRASH RISK
-            area_control::weapon_area_control_system,
-
-            area_control::area_effect_system,
-            area_control::suppression_movement_system,
-
-            formations::formation_input_system,
-            formations::formation_movement_system,
+            formations::formation_input_system.run_if(not(is_replaying)),
             formations::formation_visual_system,
 
-            enhanced_neurovector::enhanced_neurovector_system,
-            enhanced_neurovector::controlled_civilian_behavior_system,
+            enhanced_neurovector::enhanced_neurovector_system.run_if(not(is_replaying)),
             enhanced_neurovector::controlled_civilian_visual_system,
         ).run_if(in_state(GameState::Mission)))
 
         // 0.2.9
         .add_systems(Update, (
-            // Traffic core systems
-            traffic::traffic_spawn_system,
-            traffic::traffic_movement_system,
             traffic::traffic_visual_effects_system,
-            traffic::traffic_collision_system,
-            traffic::traffic_cleanup_system,
-
-            // Civilian Handling
-            traffic::civilian_traffic_interaction_system,
-            traffic::traffic_light_vehicle_system,
-
-            // Emergency and military systems
-            emergency_response_system,
-            military_convoy_system,
-
         ).run_if(in_state(GameState::Mission)))
 
-        // 0.2.10
         .add_systems(Update, (
-            // Access control systems
-            access_control::motion_sensor_system,
-            access_control::access_control_system,
             access_control::access_control_prompts,
             access_control::gate_door_visual_system,
             access_control::gate_door_audio_system,
 
-            hacking_financial::atm_hacking_system,
-            hacking_financial::billboard_influence_system,
-            hacking_financial::terminal_account_data_system,
             hacking_financial::financial_interaction_prompts,
 
         ).run_if(in_state(GameState::Mission)))
 
-        // Environmental systems
         .add_systems(Update, (
-            vehicles::vehicle_explosion_system,
-            //vehicles::explosion_damage_system,
-            vehicles::vehicle_cover_system,
-            vehicles::vehicle_spawn_system,
-
-            day_night::day_night_system,
             day_night::lighting_system,
             day_night::time_ui_system,
 
@@ -641,75 +513,24 @@ fn main() {
 
         ).run_if(in_state(GameState::Mission)))
 
-        // Police escalation
         .add_systems(Update, (
-            // CORE
-            urban_security::unified_urban_security_system,
-
-            weapons::enemy_weapon_update_system,
-            
-        ).run_if(in_state(GameState::Mission)))
-
-        .add_systems(Update, (
-            // explosions::explosion_damage_system,
-            enhanced_explosion_damage_system,
-            explosions::time_bomb_system,
-            explosions::pending_explosion_system,
-            explosions::status_effect_system,
             explosions::floating_text_system,
-            // explosions::handle_grenade_events,
-            enhanced_handle_grenade_events,
-            // explosions::handle_vehicle_explosions,
-            enhanced_handle_vehicle_explosions,
-
             scanner::scanner_ui_system,
             scanner::scanner_cleanup_system,
         ).run_if(in_state(GameState::Mission)))
 
-        .add_systems(Update, (
-            // Movement and interaction systems
-            interactive_decal_movement_system,
-            electrical_hazard_system,
-            stuck_entities_system,
-
-            // Fire systems
-            fire_ignition_system,
-            fire_burn_system,
-
-            // 0.2.12
-            spawners::spawn_scientists_in_mission,
-        ).run_if(in_state(GameState::Mission)))
-
         
         .add_systems(Update, (
             lore::lore_interaction_system,
             lore::lore_notification_system,
-            // Power grid updates
-            power_grid_system,
-            power_grid_management_system,
-            
-            // Calculate lighting
-            // colored_lighting::calculate_colored_lighting,
-            
-            // Apply lighting to visuals
-            //colored_lighting::update_tile_visuals_from_lighting,
-            //colored_lighting::update_entity_colored_lighting,
-            
-            // Light behaviors
-            //colored_lighting::colored_light_behavior_system,
-            //tile_lighting::street_light_behavior_system,            
-        ).chain().run_if(in_state(GameState::Mission)))
+        ).run_if(in_state(GameState::Mission)))
+
+        .add_systems(Update, (
+            device_takeover::device_control_input_system.run_if(not(is_replaying)),
+            device_takeover::hijacked_device_gizmos,
+        ).run_if(in_state(GameState::Mission)))
 
-        // Hacking and infrastructure
         .add_systems(Update, (
-            hacking_feedback::enhanced_hacking_system,
-            hack_recovery_system,
-
-            street_light_system,
-            traffic_light_system,
-            security_camera_system,
-            automated_turret_system,
-            security_door_system,
             power_grid_debug_system,
         ).run_if(in_state(GameState::Mission)))
 
@@ -723,14 +544,13 @@ fn main() {
             hacking_feedback::hack_progress_visualization,
             hacking_feedback::hack_status_indicator_system,
             hacking_feedback::device_visual_feedback_system,
-            hacking_feedback::hack_interruption_system,
             hacking_feedback::hack_notification_system,
+            hack_trace::hack_trace_gizmos,
+            remote_hacking::remote_access_gizmos,
 
-            mission::timer_system,
-            mission::check_completion,
-
-            // ALWAYS LAST
-            
+            extraction::extraction_zone_gizmos,
+            objectives::objective_gizmos_system,
+            objectives::objectives_hud_system,
         ).run_if(in_state(GameState::Mission)))
 
         // 0.2.14
@@ -763,7 +583,11 @@ fn main() {
             //weather::cleanup_weather_system,
             // 0.2.14
             world_scan::cleanup_scan_overlays,
-        ))
+            squads::cleanup_squads,
+            police::reset_police_state,
+            extraction::cleanup_extraction_zones,
+            power_grid::reset_power_grid,
+        ).run_if(not(is_jacked_in)))
 
         // POST MISSION
         .add_systems(OnEnter(GameState::PostMission), (
@@ -780,26 +604,6 @@ fn main() {
         .run();
 }
 
-// Helper function to profile systems
-fn profile_system<T>(name: &'static str) -> impl Fn(In<T>, ResMut<PerformanceMetrics>) -> T {
-    move |input: In<T>, mut metrics: ResMut<PerformanceMetrics>| {
-        let start = std::time::Instant::now();
-        let result = input.0;
-        
-        let elapsed = start.elapsed().as_secs_f32() * 1000.0;
-        match name {
-            "movement" => metrics.physics_ms = elapsed,
-            "depth_sorting" => metrics.depth_sort_ms = elapsed,
-            "goap_ai" | "legacy_ai" => metrics.ai_ms += elapsed,
-            "pathfinding" => metrics.pathfinding_ms = elapsed,
-            _ => {}
-        }
-        
-        result
-    }
-}
-
-
 // === TESTING SCENARIOS ===
 
 /*
@@ -835,11 +639,13 @@ pub fn setup_isometric_mission_scene(
     cities_db: Res<CitiesDatabase>,
     cities_progress: Res<CitiesProgress>,
     mut scene_cache: ResMut<SceneCache>,
-    mut banking_network: ResMut<BankingNetwork>,
     agents: Query<Entity, With<Agent>>,
     tilemap_settings: Option<Res<IsometricSettings>>,
-    mut power_grid: ResMut<crate::core::PowerGrid>,
+    mut power_grid: ResMut<subversive::core::PowerGrid>,
     mut scene: ResMut<SceneData>,
+    playtest: Option<Res<EditorPlaytest>>,
+    mut rng: ResMut<GameRng>,
+    replay: Option<Res<ReplayPlayback>>,
 ) {
     info!("setup_isometric_mission_scene");
 
@@ -848,31 +654,82 @@ pub fn setup_isometric_mission_scene(
         commands.entity(entity).insert(MarkedForDespawn);
     }
 
-    // Determine scene
+    // Determine scene; an editor playtest ignores whatever the campaign last launched
+    let launch_data = launch_data.filter(|_| playtest.is_none());
+    let launched_city_id = launch_data.as_ref().map(|data| data.city_id.clone());
     let selected_city = if let Some(launch_data) = launch_data {
         cities_db.get_city(&launch_data.city_id)
     } else {
         None
     };
 
-    let scene_name = if let Some(city) = selected_city {
-        match city.traits.first() {
-            Some(CityTrait::FinancialHub) => "mission_corporate",
-            Some(CityTrait::DrugCartels) => "mission_syndicate",
-            Some(CityTrait::Underground) => "mission_underground",
-            _ => "mission1",
-        }
-    } else {
-        match global_data.selected_region {
-            0 => "mission1",
+    // Opposition mix comes from the same briefing the player saw in the hub
+    let briefing = selected_city.map(|city| generate_mission_briefing_for_city(
+        &global_data, &cities_db, &global_data.cities_progress, &city.id));
+
+    let authored_scene = match (&playtest, selected_city) {
+        (Some(playtest), _) => Some(playtest.scene_name.as_str()),
+        (None, Some(city)) => match city.traits.first() {
+            Some(CityTrait::FinancialHub) => Some("mission_corporate"),
+            Some(CityTrait::DrugCartels) => Some("mission_syndicate"),
+            Some(CityTrait::Underground) => Some("mission_underground"),
+            _ => None,
+        },
+        (None, None) => Some(match global_data.selected_region {
             1 => "mission2",
             2 => "mission3",
             _ => "mission1",
+        }),
+    };
+
+    // Cities without a hand-made scene get a map built from their briefing
+    let generated = match (authored_scene, selected_city, &briefing) {
+        (None, Some(city), Some(briefing)) => {
+            let seed = mapgen::mission_map_seed(global_data.campaign_seed, &city.id, global_data.current_day);
+            let default_settings = IsometricSettings::default();
+            let settings = tilemap_settings.as_deref().unwrap_or(&default_settings);
+            Some(mapgen::generate_mission_map(briefing, city, seed, settings))
         }
+        _ => None,
     };
 
+    let scene_name = match &generated {
+        Some(generated) => generated.scene_name(),
+        None => authored_scene.unwrap_or("mission1").to_string(),
+    };
+
+    // A replay brings its own seed, in case it was recorded under a seed override
+    let seed = match &replay {
+        Some(replay) => replay.file.mission_seed,
+        None => mission_seed(global_data.campaign_seed, &scene_name, launched_city_id.as_deref(), global_data.current_day),
+    };
+    rng.reseed(seed);
+    info!("Mission seed: {}", seed);
+
+    commands.insert_resource(ActiveMissionScene {
+        scene_name: scene_name.clone(),
+        city_id: launched_city_id,
+    });
+
+    let objectives = match &briefing {
+        Some(briefing) => MissionObjectives::from_briefing(&briefing.objectives),
+        None => MissionObjectives::from_briefing(&fallback_objectives()),
+    };
+    commands.insert_resource(MissionPerformanceTracker::new(&objectives));
+    commands.insert_resource(objectives);
+    commands.insert_resource(ExtractionState::default());
+
     // Load and apply scene
-    match load_scene_cached(&mut scene_cache, scene_name) {
+    let loaded_scene = match &generated {
+        Some(generated) => Some(generated.scene.clone()),
+        None => load_scene_cached(&mut scene_cache, &scene_name),
+    };
+    match generated {
+        Some(generated) => commands.insert_resource(generated),
+        None => commands.remove_resource::<mapgen::GeneratedMission>(),
+    }
+
+    match &loaded_scene {
         Some(scene) => {
             // Generate tilemap from scene data
             if tilemap_settings.is_some() {
@@ -881,44 +738,40 @@ pub fn setup_isometric_mission_scene(
             }
 
             // Spawn entities with isometric positioning
-            spawn_from_scene_isometric(&mut commands, &scene, &*global_data, &sprites, &tilemap_settings);
+            spawn_from_scene_isometric(&mut commands, scene, &*global_data, &sprites, &tilemap_settings,
+                briefing.as_ref().map(|b| &b.resistance), rng.stream(RngStream::Spawning));
             info!("Loaded isometric scene: {} for city: {}",
                   scene_name, selected_city.map_or("None", |c| &c.name));
             spawn_hackable_test_objects(&mut commands, &sprites, &mut power_grid);
         },
         None => {
             error!("Failed to load scene: {}. Creating fallback.", scene_name);
-            spawn_fallback_isometric_mission(&mut commands, &*global_data, &sprites, &tilemap_settings);
+            spawn_fallback_isometric_mission(&mut commands, &*global_data, &sprites, &tilemap_settings, rng.stream(RngStream::Spawning));
         }
     }
 
+    commands.insert_resource(MissionScript::from_scene(
+        loaded_scene.as_ref().map_or(&[][..], |scene| &scene.triggers),
+        briefing.as_ref().map(|b| b.resistance.clone()),
+    ));
+
     // Add environmental hazards (same as before)
     spawn_oil_spill(&mut commands, Vec2::new(100.0, 100.0), 50.0);
     spawn_gasoline_spill(&mut commands, Vec2::new(200.0, 100.0), 40.0);
-    spawn_explodable(&mut commands, Vec2::new(250.0, 100.0), ExplodableType::FuelBarrel);
-
-    let network_id = "district_main".to_string();
-    let some_id = Some(network_id.clone());
-
-    spawners::spawn_street_light(
-        &mut commands, 
-        Vec2::new(100.0, 100.0), 
-        network_id, 
-        &mut power_grid
-    );
 
     colored_lighting::spawn_colored_light(
         &mut commands, 
         Vec2::new(75.0,150.0), 
         LightType::NeonSign, 
-        some_id, 
+        Some("district_main".to_string()), 
         Some(&mut power_grid)
     );
 
-
-    hacking_financial::setup_financial_district(&mut commands, power_grid, banking_network, Vec2::new(0.0, 0.0));
-
-    // setup_district_power_grid(&mut commands, power_grid, Vec2::new(300,300));
+    // Power networks, security devices, access points and props all come from the scene
+    let mut power_grid = Some(power_grid);
+    if let Some(scene) = &loaded_scene {
+        spawn_scene_infrastructure(&mut commands, scene, &mut power_grid, &tilemap_settings);
+    }
 
     // spawn_enhanced_colored_scene_lighting(&mut commands, &mut scene, power_grid);
 }
@@ -954,16 +807,26 @@ pub fn setup_mission_tilemap (
     // setup_isometric_tilemap(commands, asset_server);
 }
 
-fn load_global_data_or_default() -> (GlobalData, ResearchProgress, TerritoryManager, CampaignProgressionTracker) {
-    if let Some((loaded_global_data, territory_manager, progression_tracker)) = crate::systems::save::load_game() {
+fn load_global_data_or_default() -> (GlobalData, ResearchProgress, TerritoryManager, CampaignProgressionTracker, save::SaveSession) {
+    let slots = save::list_save_slots();
+    let latest = save::latest_save_slot(&slots)
+        .and_then(|slot| save::load_save_slot(slot).map(|save_data| (slot, save_data)));
+
+    if let Some((slot, save_data)) = latest {
+        let session = save::SaveSession {
+            active_slot: slot,
+            playtime_secs: save_data.metadata.playtime_secs,
+        };
+        let (loaded_global_data, territory_manager, progression_tracker) = save_data.into_campaign();
         let research_progress = loaded_global_data.research_progress.clone();
-        (loaded_global_data, research_progress, territory_manager, progression_tracker)
+        (loaded_global_data, research_progress, territory_manager, progression_tracker, session)
     } else {
         let global_data = GlobalData::default();
         let research_progress = global_data.research_progress.clone();
         let territory_manager = TerritoryManager::default();
         let progression_tracker = CampaignProgressionTracker::default();
-        (global_data, research_progress, territory_manager, progression_tracker)
+        let session = save::SaveSession { active_slot: save::slot_for_new_game(&slots), playtime_secs: 0.0 };
+        (global_data, research_progress, territory_manager, progression_tracker, session)
     }
 }
 
@@ -1003,40 +866,6 @@ pub fn preload_common_scenes(mut scene_cache: ResMut<SceneCache>) {
 }
 
 
-pub fn collision_feedback_system(
-    mut collision_events: EventReader<CollisionEvent>,
-    mut units: Query<&mut Velocity, Or<(With<Agent>, With<Civilian>, With<Enemy>)>>,
-) {
-    for collision_event in collision_events.read() {
-        if let CollisionEvent::Started(e1, e2, _) = collision_event {
-            // Check if both entities are units before proceeding
-            if units.get(*e1).is_ok() && units.get(*e2).is_ok() {
-                let separation_force = Vec2::new(fastrand::f32() - 0.5, fastrand::f32() - 0.5) * 50.0;
-
-                // Apply opposite forces to separate the entities
-                if let Ok(mut vel1) = units.get_mut(*e1) {
-                    vel1.linvel += separation_force;
-                }
-                if let Ok(mut vel2) = units.get_mut(*e2) {
-                    vel2.linvel -= separation_force; // Opposite direction
-                }
-            }
-        }
-    }
-}
-
-/*
-fn setup_police_system(mut commands: Commands) {
-    // Load configuration from file
-    let config = load_police_config();
-
-    // Insert as resources
-    commands.insert_resource(config);
-    commands.insert_resource(PoliceResponse::default());
-    commands.insert_resource(PoliceEscalation::default());
-}
- */
-
 fn setup_cyberpunk2077_theme(mut contexts: EguiContexts) {
     if let Ok(ctx) = contexts.ctx_mut() {
         let mut style = (*ctx.style()).clone();
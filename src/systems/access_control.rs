@@ -315,6 +315,24 @@ pub fn motion_sensor_system(
     }
 }
 
+/// Secured gates and doors fail open once hacked or cut off from their power network
+pub fn access_point_power_system(
+    mut gates: Query<(&mut Gate, &DeviceState), (Changed<DeviceState>, Without<Door>)>,
+    mut doors: Query<(&mut Door, &DeviceState), (Changed<DeviceState>, Without<Gate>)>,
+) {
+    for (mut gate, device_state) in gates.iter_mut() {
+        if gate.access_level.is_some() && (!device_state.operational || !device_state.powered) {
+            gate.access_level = None;
+        }
+    }
+
+    for (mut door, device_state) in doors.iter_mut() {
+        if door.access_level.is_some() && (!device_state.operational || !device_state.powered) {
+            door.access_level = None;
+        }
+    }
+}
+
 // === ACCESS CONTROL SYSTEM ===
 pub fn access_control_system(
     mut action_events: EventReader<ActionEvent>,
@@ -2,6 +2,7 @@
 use bevy::prelude::*;
 use crate::core::*;
 use crate::systems::death::*;
+use crate::systems::perception::{PerceptionMemory, StimulusKind};
 
 #[derive(Component)]
 pub struct AIState {
@@ -41,10 +42,11 @@ impl Default for AIState {
 // Alert coordination system for CallForHelp
 pub fn alert_system(
     mut alert_events: EventReader<AlertEvent>,
-    mut enemy_query: Query<(Entity, &Transform, &mut AIState, &mut GoapAgent), (With<Enemy>, Without<Dead>)>,
+    mut enemy_query: Query<(Entity, &Transform, &mut AIState, &mut GoapAgent, Option<&mut PerceptionMemory>), (With<Enemy>, Without<Dead>)>,
+    time: Res<Time>,
 ) {
     for alert_event in alert_events.read() {
-        for (enemy_entity, enemy_transform, mut ai_state, mut goap_agent) in enemy_query.iter_mut() {
+        for (enemy_entity, enemy_transform, mut ai_state, mut goap_agent, memory) in enemy_query.iter_mut() {
             // Skip the alerter itself
             if enemy_entity == alert_event.alerter {
                 continue;
@@ -57,12 +59,13 @@ pub fn alert_system(
                 AlertType::CallForHelp => 200.0,
                 AlertType::GunshotHeard => 150.0,
                 AlertType::EnemySpotted => 250.0,
+                AlertType::IntrusionTraced => 450.0,
                 _ => 200.0,
             };
             
             if distance <= alert_range {
                 match alert_event.alert_type {
-                    AlertType::CallForHelp => {
+                    AlertType::CallForHelp | AlertType::IntrusionTraced => {
                         // Set last known target to the alert position
                         ai_state.last_known_target = Some(alert_event.position);
                         ai_state.mode = crate::systems::ai::AIMode::Investigate { 
@@ -73,6 +76,11 @@ pub fn alert_system(
                         goap_agent.update_world_state(WorldKey::HeardSound, true);
                         goap_agent.update_world_state(WorldKey::IsAlert, true);
                         goap_agent.abort_plan(); // Force immediate replanning
+
+                        if let Some(mut memory) = memory {
+                            let confidence = 1.0 - 0.5 * distance / alert_range;
+                            memory.record(StimulusKind::Alert, alert_event.position, Some(alert_event.alerter), confidence, time.elapsed_secs());
+                        }
                         
                         info!("Enemy {} responding to call for help from {} (distance: {:.1})", enemy_entity.index(), alert_event.alerter.index(), distance);
                     },
@@ -307,9 +315,10 @@ pub fn sound_detection_system(
 
 // Update GOAP sound detection system
 pub fn goap_sound_detection_system(
-    mut enemy_query: Query<(Entity, &Transform, &mut GoapAgent), (With<Enemy>, Without<Dead>)>,
+    mut enemy_query: Query<(Entity, &Transform, &mut GoapAgent, Option<&mut PerceptionMemory>), (With<Enemy>, Without<Dead>)>,
     mut combat_events: EventReader<CombatEvent>,
     combat_transforms: Query<(&Transform, &Inventory), With<Agent>>,
+    time: Res<Time>,
 ) {
     // React to gunshots by updating GOAP world state with attachment consideration
     for combat_event in combat_events.read() {
@@ -328,12 +337,18 @@ pub fn goap_sound_detection_system(
             let base_range = 200.0;
             let detection_range = (base_range * noise_modifier).max(50.0);
             
-            for (_, enemy_transform, mut goap_agent) in enemy_query.iter_mut() {
+            for (_, enemy_transform, mut goap_agent, memory) in enemy_query.iter_mut() {
                 let distance = enemy_transform.translation.truncate().distance(gunshot_pos);
                 
                 if distance <= detection_range {
                     goap_agent.update_world_state(WorldKey::HeardSound, true);
                     goap_agent.abort_plan(); // Force replanning
+
+                    // Distant shots are harder to place, so they widen the later search
+                    if let Some(mut memory) = memory {
+                        let confidence = 1.0 - 0.7 * distance / detection_range;
+                        memory.record(StimulusKind::Sound, gunshot_pos, None, confidence, time.elapsed_secs());
+                    }
                 }
             }
         }
@@ -138,7 +138,7 @@ fn handle_minigun_suppression(
 pub fn area_effect_system(
     mut area_query: Query<(Entity, &mut AreaDenial, &Transform), Without<MarkedForDespawn>>,
     mut suppression_query: Query<(Entity, &mut SuppressionZone, &Transform), Without<MarkedForDespawn>>,
-    mut agent_query: Query<(&Transform, &mut Health), (With<Agent>, Without<MarkedForDespawn>)>,
+    mut agent_query: Query<(&Transform, &mut Health, Option<&Armor>), (With<Agent>, Without<MarkedForDespawn>)>,
     mut commands: Commands,
     time: Res<Time>,
     game_mode: Res<GameMode>,
@@ -159,11 +159,11 @@ pub fn area_effect_system(
 
         let area_pos = area_transform.translation.truncate();
         
-        for (agent_transform, mut health) in agent_query.iter_mut() {
+        for (agent_transform, mut health, armor) in agent_query.iter_mut() {
             let distance = area_pos.distance(agent_transform.translation.truncate());
             
             if distance <= area_denial.control_radius {
-                health.0 -= area_denial.damage_per_second * time.delta_secs();
+                Armor::apply_damage(&mut health, armor, area_denial.damage_per_second * time.delta_secs());
             }
         }
     }
@@ -348,12 +348,7 @@ pub fn spawn_colored_light(
 
         // Add to power network
         if let Some(power_grid) = power_grid {
-            power_grid.networks.entry(network_id.clone())
-                .or_insert_with(|| crate::core::PowerNetwork::new(network_id.clone()));
-            
-            if let Some(network) = power_grid.networks.get_mut(&network_id) {
-                network.connected_devices.insert(entity_commands.id());
-            }
+            power_grid.network_mut(&network_id).connected_devices.insert(entity_commands.id());
         }
     } else if health > 0.0 && !matches!(light_type, LightType::FireLight | LightType::LaserSight) {
         // Non-networked but still hackable (battery powered)
@@ -1,8 +1,15 @@
 // src/systems/combat.rs - Updated with projectile system
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::projectiles::*;
+use crate::systems::device_takeover::Spotted;
+
+/// Extra hit chance against an enemy a hijacked drone is spotting
+const SPOTTED_ACCURACY_BONUS: f32 = 0.15;
+/// Health one medkit gives back
+const MEDKIT_HEAL: f32 = 50.0;
 
 // Separate system to process attack events
 pub fn process_attack_events(
@@ -12,26 +19,25 @@ pub fn process_attack_events(
     agent_query: Query<(&Transform, &Inventory), With<Agent>>,
     mut agent_weapon_query: Query<&mut WeaponState, With<Agent>>,
     target_query: Query<(Entity, &Transform, &Health), Or<(With<Enemy>, With<Vehicle>, With<Civilian>)>>,
+    spotted_query: Query<(), With<Spotted>>,
     weapon_db: Res<WeaponDatabase>,
+    mut rng: ResMut<GameRng>,
 ) {
     for event in action_events.read() {
         if let Action::Attack(target) = event.action {
             execute_attack(event.entity, target, &mut commands, &agent_query, &mut agent_weapon_query,
-                         &target_query, &mut audio_events, &weapon_db);
+                         &target_query, &spotted_query, &mut audio_events, &weapon_db, rng.stream(RngStream::Combat));
         }
     }
 }
 
-// Main combat system - only handles direct input, no ActionEvent reading
+// Main combat system - turns direct input into ActionEvents for the fixed-step simulation
 pub fn system(
-    mut commands: Commands,
     input: Query<&ActionState<PlayerAction>>,
-    mut audio_events: EventWriter<AudioEvent>,
     agent_query: Query<(&Transform, &Inventory), With<Agent>>,
-    mut agent_weapon_query: Query<&mut WeaponState, With<Agent>>,
+    agent_weapon_query: Query<&WeaponState, With<Agent>>,
     target_query: Query<(Entity, &Transform, &Health), Or<(With<Enemy>, With<Vehicle>, With<Civilian>)>>,
     game_mode: Res<GameMode>,
-    weapon_db: Res<WeaponDatabase>,
     windows: Query<&Window>,
     cameras: Query<(&Camera, &GlobalTransform)>,
     selection: Res<SelectionState>,
@@ -39,6 +45,8 @@ pub fn system(
     mut action_events: EventWriter<ActionEvent>,
 ) {
     if game_mode.paused { return; }
+    // Right-clicks belong to the hijacked device while it's being driven
+    if matches!(game_mode.targeting, Some(TargetingMode::DeviceControl { .. })) { return; }
 
     let Ok(action_state) = input.single() else { return; };
 
@@ -65,7 +73,10 @@ pub fn system(
                         isometric_settings.as_deref()
                     ) {
                         // info!("Combat: Agent {:?} attacking target {:?}", agent, target);
-                        execute_attack(agent, target, &mut commands, &agent_query, &mut agent_weapon_query, &target_query, &mut audio_events, &weapon_db);
+                        action_events.write(ActionEvent {
+                            entity: agent,
+                            action: Action::Attack(target),
+                        });
                         target_found = true;
                         break; // Found a target, stop checking other agents
                     }
@@ -94,8 +105,10 @@ fn execute_attack(
     agent_query: &Query<(&Transform, &Inventory), With<Agent>>,
     agent_weapon_query: &mut Query<&mut WeaponState, With<Agent>>,
     target_query: &Query<(Entity, &Transform, &Health), Or<(With<Enemy>, With<Vehicle>, With<Civilian>)>>,
+    spotted_query: &Query<(), With<Spotted>>,
     audio_events: &mut EventWriter<AudioEvent>,
     weapon_db: &WeaponDatabase,
+    rng: &mut impl Rng,
 ) {
     // Get positions first
     let Ok((attacker_transform, inventory)) = agent_query.get(attacker) else { return; };
@@ -131,7 +144,13 @@ fn execute_attack(
         weapon_db
     );
 
-    let hit = rand::random::<f32>() < accuracy;
+    let accuracy = if spotted_query.contains(target) {
+        (accuracy + SPOTTED_ACCURACY_BONUS).min(0.98)
+    } else {
+        accuracy
+    };
+
+    let hit = rng.gen::<f32>() < accuracy;
 
     if hit {
         spawn_projectile(
@@ -151,8 +170,8 @@ fn execute_attack(
     } else {
         // Miss logic
         let miss_offset = Vec2::new(
-            (rand::random::<f32>() - 0.5) * 100.0,
-            (rand::random::<f32>() - 0.5) * 100.0,
+            (rng.gen::<f32>() - 0.5) * 100.0,
+            (rng.gen::<f32>() - 0.5) * 100.0,
         );
         let miss_target_pos = target_pos + miss_offset;
 
@@ -197,7 +216,7 @@ pub fn cleanup_miss_targets(
     }
 }
 
-fn get_weapon_range(inventory: &Inventory, weapon_state: Option<&WeaponState>) -> f32 {
+pub fn get_weapon_range(inventory: &Inventory, weapon_state: Option<&WeaponState>) -> f32 {
     let base_range = 150.0;
     if let Some(weapon_config) = &inventory.equipped_weapon {
         let stats = weapon_config.stats();
@@ -270,10 +289,12 @@ pub fn enemy_combat_system(
     mut commands: Commands,
     mut action_events: EventReader<ActionEvent>,
     mut audio_events: EventWriter<AudioEvent>,
-    mut enemy_query: Query<(&Transform, &Inventory, &mut WeaponState), With<Enemy>>,
+    mut enemy_query: Query<(&Transform, &mut Inventory, &mut WeaponState), With<Enemy>>,
+    mut enemy_health: Query<&mut Health, (With<Enemy>, Without<Agent>)>,
     agent_query: Query<(Entity, &Transform, &Health), With<Agent>>,
     weapon_db: Res<WeaponDatabase>,
     game_mode: Res<GameMode>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
@@ -289,11 +310,12 @@ pub fn enemy_combat_system(
                             target,
                             &mut commands,
                             enemy_transform,
-                            inventory,
+                            &inventory,
                             &mut weapon_state,
                             &agent_query,
                             &mut audio_events,
                             &weapon_db,
+                            rng.stream(RngStream::Combat),
                         );
                     } else {
                         // println!("Enemy {:?} target {:?} is not a valid agent - skipping", event.entity, target);
@@ -313,7 +335,12 @@ pub fn enemy_combat_system(
                 }
             },
             Action::UseMedKit => {
-                // info!("Using MedKit!");
+                // The plan only gets here with a kit in hand, but it may have been used since
+                let (Ok((_, mut inventory, _)), Ok(mut health)) = (enemy_query.get_mut(event.entity), enemy_health.get_mut(event.entity)) else { continue; };
+                if inventory.consume_tool(&ToolType::MedKit) {
+                    health.0 += MEDKIT_HEAL;
+                    audio_events.write(AudioEvent { sound: AudioType::Alert, volume: 0.3 });
+                }
             },
             _ => {} // Ignore other actions
         }
@@ -330,6 +357,7 @@ fn execute_enemy_attack(
     target_query: &Query<(Entity, &Transform, &Health), With<Agent>>,
     audio_events: &mut EventWriter<AudioEvent>,
     weapon_db: &WeaponDatabase,
+    rng: &mut impl Rng,
 ) {
     // Debug output
     // println!("Enemy {:?} executing attack on agent {:?}. Ammo: {}/{}", attacker, target, weapon_state.current_ammo, weapon_state.max_ammo);
@@ -365,7 +393,7 @@ fn execute_enemy_attack(
     let (damage, accuracy, noise) = get_enemy_attack_stats(inventory, weapon_state, weapon_db);
 
     // Check if shot hits (accuracy check)
-    let hit = rand::random::<f32>() < accuracy;
+    let hit = rng.gen::<f32>() < accuracy;
 
     // println!("Enemy attack: damage={:.1}, accuracy={:.2}, hit={}", damage, accuracy, hit);
 
@@ -391,8 +419,8 @@ fn execute_enemy_attack(
     } else {
         // Miss - spawn projectile that goes past target
         let miss_offset = Vec2::new(
-            (rand::random::<f32>() - 0.5) * 80.0,
-            (rand::random::<f32>() - 0.5) * 80.0,
+            (rng.gen::<f32>() - 0.5) * 80.0,
+            (rng.gen::<f32>() - 0.5) * 80.0,
         );
         let miss_target_pos = target_pos + miss_offset;
 
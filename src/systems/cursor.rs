@@ -124,6 +124,7 @@ pub fn determine_cursor_type(
     match &game_mode.targeting {
         Some(TargetingMode::Neurovector { .. }) => return CursorType::Examine,
         Some(TargetingMode::Scanning { .. }) => return CursorType::Examine,
+        Some(TargetingMode::DeviceControl { .. }) => return CursorType::Crosshair,
         _ => {}
     }
 
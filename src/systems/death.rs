@@ -1,10 +1,12 @@
 // src/systems/death.rs - Enhanced death handling and decal system
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::core::*;
 use crate::systems::*;
 use crate::systems::explosions::*;
+use crate::systems::selection::AgentIndex;
 
 // === DEATH COMPONENTS ===
 
@@ -14,7 +16,7 @@ pub struct Corpse {
     pub decay_timer: Option<f32>, // None = permanent
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum CorpseType {
     Agent,
     Enemy, 
@@ -36,11 +38,12 @@ pub fn death_system(
         Option<&Civilian>,
         Option<&Police>,
         Option<&Vehicle>,
-    ), (Or<(With<Enemy>, With<Vehicle>, With<Civilian>, With<Police>)>, Without<Dead>)>,
+        Option<&AgentIndex>,
+    ), (Or<(With<Agent>, With<Enemy>, With<Vehicle>, With<Civilian>, With<Police>)>, Without<Dead>)>,
     mut mission_data: ResMut<MissionData>,
     decal_settings: Res<DecalSettings>,
 ) {
-    for (entity, mut health, mut sprite, transform, agent, enemy, civilian, police, vehicle) in target_query.iter_mut() {
+    for (entity, mut health, mut sprite, transform, agent, enemy, civilian, police, vehicle, agent_index) in target_query.iter_mut() {
         if health.0 <= 0.0 {
             // === IMMEDIATE AI SHUTDOWN ===
             // Stop all movement and physics immediately
@@ -61,6 +64,12 @@ pub fn death_system(
 
             // === DETERMINE ENTITY TYPE ===
             let corpse_type = if agent.is_some() {
+                commands.entity(entity)
+                    .remove::<Controllable>()
+                    .remove::<Selectable>();
+                if let Some(AgentIndex(idx)) = agent_index {
+                    mission_data.agents_lost.push(*idx);
+                }
                 CorpseType::Agent
             } else if enemy.is_some() {
                 mission_data.enemies_killed += 1;
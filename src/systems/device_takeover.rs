@@ -0,0 +1,320 @@
+// src/systems/device_takeover.rs - What TakeControl, PlantVirus and Overload do once a hack lands
+//
+// Hijacked turrets and drones work for the squad until the hack wears off, a virus planted in a
+// security system works its way through the rest of its network, and an overloaded power station
+// blows and drops out of the grid, leaving the rest of it to carry its load or black out.
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use std::collections::VecDeque;
+use crate::core::*;
+use crate::systems::explosions::{spawn_explosion, ExplosionType};
+use crate::systems::power_grid::{AutomatedTurret, Drone, PowerStation};
+
+/// How close to an enemy an aiming click has to land
+const AIM_PICK_RADIUS: f32 = 40.0;
+const VIRUS_SPREAD_SECS: f32 = 4.0;
+const OVERLOAD_RADIUS: f32 = 90.0;
+const OVERLOAD_DAMAGE: f32 = 120.0;
+/// How long an enemy stays marked after a hijacked drone loses sight of it
+pub const SPOTTED_SECS: f32 = 3.0;
+
+// === COMPONENTS ===
+/// A turret or drone working for the squad until the hack wears off
+#[derive(Component)]
+pub struct Hijacked {
+    pub controller: Entity,
+    /// Drones only: the route the player has drawn, flown once and then held at its end
+    pub route: VecDeque<Vec2>,
+}
+
+/// Enemy a hijacked drone has eyes on; agents land more of their shots on it
+#[derive(Component)]
+pub struct Spotted {
+    pub remaining: f32,
+}
+
+/// Lives on the security system it was planted in and takes one more device on its network
+/// every few seconds
+#[derive(Component)]
+pub struct NetworkVirus {
+    pub planted_by: Entity,
+    pub network_id: String,
+    pub spread_timer: f32,
+}
+
+/// A power station about to blow
+#[derive(Component)]
+pub struct PowerSurge {
+    pub timer: f32,
+}
+
+/// Blown for the rest of the mission; the grid is laid out without it
+#[derive(Component)]
+pub struct Overloaded;
+
+// === HACK RESULTS ===
+pub fn hack_takeover_system(
+    mut commands: Commands,
+    mut hack_completed: EventReader<HackCompletedEvent>,
+    devices: Query<(&Hackable, Has<AutomatedTurret>, Has<Drone>, Has<PowerStation>)>,
+) {
+    for event in hack_completed.read() {
+        let Ok((hackable, is_turret, is_drone, is_station)) = devices.get(event.target) else { continue; };
+
+        for effect in &event.effects {
+            match effect {
+                HackEffect::TakeControl if is_turret || is_drone => {
+                    commands.entity(event.target).insert(Hijacked {
+                        controller: event.agent,
+                        route: VecDeque::new(),
+                    });
+                    info!("{:?} is now under squad control", event.device_type);
+                }
+                HackEffect::PlantVirus if event.device_type == DeviceType::SecuritySystem => {
+                    let Some(network_id) = hackable.network_id.clone() else {
+                        info!("Virus planted, but the security system isn't on any network");
+                        continue;
+                    };
+                    info!("Virus planted in network {}", network_id);
+                    commands.entity(event.target).insert(NetworkVirus {
+                        planted_by: event.agent,
+                        network_id,
+                        spread_timer: VIRUS_SPREAD_SECS,
+                    });
+                }
+                HackEffect::Overload if is_station => {
+                    commands.entity(event.target).insert(PowerSurge { timer: 0.0 });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Hands a device back once its hack wears off
+pub fn release_hijacked_devices(
+    mut commands: Commands,
+    devices: Query<(Entity, &Hackable), With<Hijacked>>,
+) {
+    for (entity, hackable) in devices.iter() {
+        if !hackable.is_hacked {
+            commands.entity(entity).remove::<Hijacked>();
+        }
+    }
+}
+
+pub fn device_command_system(
+    mut action_events: EventReader<ActionEvent>,
+    mut devices: Query<(&Transform, &mut Hijacked, Option<&mut AutomatedTurret>)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Dead>)>,
+) {
+    for event in action_events.read() {
+        let Action::CommandDevice { target, queue } = event.action else { continue; };
+        let Ok((transform, mut hijacked, turret)) = devices.get_mut(event.entity) else { continue; };
+
+        match turret {
+            Some(mut turret) => {
+                let turret_pos = transform.translation.truncate();
+                let aimed = enemies.iter()
+                    .map(|(entity, enemy_transform)| (entity, enemy_transform.translation.truncate()))
+                    .filter(|(_, pos)| pos.distance(target) <= AIM_PICK_RADIUS && pos.distance(turret_pos) <= turret.range)
+                    .min_by(|(_, a), (_, b)| a.distance(target).total_cmp(&b.distance(target)))
+                    .map(|(entity, _)| entity);
+                if aimed.is_some() {
+                    turret.target = aimed;
+                }
+            }
+            None => {
+                if !queue {
+                    hijacked.route.clear();
+                }
+                hijacked.route.push_back(target);
+            }
+        }
+    }
+}
+
+// === VIRUS ===
+pub fn network_virus_system(
+    mut commands: Commands,
+    mut viruses: Query<(Entity, &Transform, &mut NetworkVirus)>,
+    mut devices: Query<(Entity, &Transform, &mut Hackable, &mut DeviceState), Without<NetworkVirus>>,
+    mut hack_completed: EventWriter<HackCompletedEvent>,
+    time: Res<Time>,
+) {
+    for (virus_entity, virus_transform, mut virus) in viruses.iter_mut() {
+        virus.spread_timer -= time.delta_secs();
+        if virus.spread_timer > 0.0 {
+            continue;
+        }
+        virus.spread_timer = VIRUS_SPREAD_SECS;
+
+        // Closest device first, so the infection visibly creeps outwards
+        let origin = virus_transform.translation.truncate();
+        let next = devices.iter()
+            .filter(|(_, _, hackable, _)| !hackable.is_hacked && hackable.network_id.as_ref() == Some(&virus.network_id))
+            .map(|(entity, transform, ..)| (entity, transform.translation.truncate().distance(origin)))
+            .min_by(|(entity_a, a), (entity_b, b)| a.total_cmp(b).then(entity_a.cmp(entity_b)))
+            .map(|(entity, _)| entity);
+
+        let Some(next) = next else {
+            info!("Virus has run through network {}", virus.network_id);
+            commands.entity(virus_entity).remove::<NetworkVirus>();
+            continue;
+        };
+        let Ok((_, _, mut hackable, mut device_state)) = devices.get_mut(next) else { continue; };
+
+        hackable.is_hacked = true;
+        device_state.operational = false;
+        device_state.hack_timer = hackable.disabled_duration.unwrap_or_default();
+
+        hack_completed.write(HackCompletedEvent {
+            agent: virus.planted_by,
+            target: next,
+            device_type: hackable.device_type,
+            effects: vec![HackEffect::Disable],
+        });
+    }
+}
+
+// === OVERLOAD ===
+pub fn power_surge_system(
+    mut commands: Commands,
+    mut surges: Query<(Entity, &mut PowerSurge)>,
+    mut stations: Query<(&Transform, &mut Hackable, &mut DeviceState), (With<PowerStation>, Without<Overloaded>)>,
+    mut power_grid: ResMut<PowerGrid>,
+    mut audio_events: EventWriter<AudioEvent>,
+    time: Res<Time>,
+) {
+    let mut blown = Vec::new();
+    for (entity, mut surge) in surges.iter_mut() {
+        surge.timer -= time.delta_secs();
+        if surge.timer <= 0.0 {
+            blown.push(entity);
+        }
+    }
+
+    for entity in blown {
+        commands.entity(entity).remove::<PowerSurge>().insert(Overloaded);
+        let Ok((transform, mut hackable, mut device_state)) = stations.get_mut(entity) else { continue; };
+
+        // A blown station stays dark; the recovery timer never brings it back
+        hackable.is_hacked = true;
+        hackable.disabled_duration = None;
+        device_state.powered = false;
+        device_state.operational = false;
+
+        let position = transform.translation.truncate();
+        spawn_explosion(&mut commands, position, OVERLOAD_RADIUS, OVERLOAD_DAMAGE, ExplosionType::Cascading);
+        audio_events.write(AudioEvent {
+            sound: AudioType::PowerDown,
+            volume: 0.8,
+        });
+        info!("Power station overloaded");
+
+        // Re-laid without it: the next solve re-routes its load over the tie lines, where the
+        // stations left may trip in turn, or blacks out the districts it alone was feeding
+        power_grid.topology_dirty = true;
+    }
+}
+
+// === SPOTTING ===
+pub fn spotted_decay_system(
+    mut commands: Commands,
+    mut spotted: Query<(Entity, &mut Spotted)>,
+    time: Res<Time>,
+) {
+    for (entity, mut mark) in spotted.iter_mut() {
+        mark.remaining -= time.delta_secs();
+        if mark.remaining <= 0.0 {
+            commands.entity(entity).remove::<Spotted>();
+        }
+    }
+}
+
+// === INPUT ===
+/// V steps through hijacked devices and back out; right-click aims a turret or draws a drone's
+/// route, with Shift adding to the route rather than replacing it
+pub fn device_control_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Query<&ActionState<PlayerAction>>,
+    mut game_mode: ResMut<GameMode>,
+    hijacked: Query<Entity, With<Hijacked>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    let controlled = match game_mode.targeting {
+        Some(TargetingMode::DeviceControl { device }) => Some(device),
+        _ => None,
+    };
+
+    if let Some(device) = controlled.filter(|&device| !hijacked.contains(device)) {
+        info!("Lost control of {:?}", device);
+        game_mode.targeting = None;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyV) {
+        let mut devices: Vec<Entity> = hijacked.iter().collect();
+        devices.sort();
+        let next = match controlled {
+            Some(device) => devices.iter().skip_while(|&&other| other != device).nth(1).copied(),
+            None => devices.first().copied(),
+        };
+        game_mode.targeting = next.map(|device| TargetingMode::DeviceControl { device });
+        return;
+    }
+
+    let Some(device) = controlled else { return; };
+    let Ok(action_state) = input.single() else { return; };
+    if !action_state.just_pressed(&PlayerAction::Move) {
+        return;
+    }
+    let Some(target) = get_world_mouse_position(&windows, &cameras) else { return; };
+
+    action_events.write(ActionEvent {
+        entity: device,
+        action: Action::CommandDevice {
+            target,
+            queue: keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight),
+        },
+    });
+}
+
+// === VISUALS ===
+pub fn hijacked_device_gizmos(
+    mut gizmos: Gizmos,
+    game_mode: Res<GameMode>,
+    devices: Query<(Entity, &Transform, &Hijacked, Option<&AutomatedTurret>)>,
+    targets: Query<&Transform, Without<Hijacked>>,
+    spotted: Query<&Transform, With<Spotted>>,
+) {
+    let controlled = match game_mode.targeting {
+        Some(TargetingMode::DeviceControl { device }) => Some(device),
+        _ => None,
+    };
+
+    for (entity, transform, hijacked, turret) in devices.iter() {
+        let pos = transform.translation.truncate();
+        let color = if controlled == Some(entity) { Color::srgb(0.2, 1.0, 1.0) } else { Color::srgb(0.2, 0.6, 0.8) };
+        gizmos.circle_2d(pos, 18.0, color);
+
+        if let Some(turret) = turret {
+            if let Some(target_pos) = turret.target.and_then(|target| targets.get(target).ok()) {
+                gizmos.line_2d(pos, target_pos.translation.truncate(), Color::srgba(1.0, 0.3, 0.2, 0.6));
+            }
+        }
+
+        let mut from = pos;
+        for &waypoint in &hijacked.route {
+            gizmos.line_2d(from, waypoint, color);
+            gizmos.circle_2d(waypoint, 4.0, color);
+            from = waypoint;
+        }
+    }
+
+    for transform in spotted.iter() {
+        gizmos.circle_2d(transform.translation.truncate(), 14.0, Color::srgb(1.0, 0.6, 0.1));
+    }
+}
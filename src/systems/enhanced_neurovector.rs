@@ -21,38 +21,62 @@ impl Default for ControlledCivilian {
     }
 }
 
+/// Turns a neurovector click into control orders; applied by `neurovector_control_system`
 pub fn enhanced_neurovector_system(
-    mut commands: Commands,
     input: Query<&ActionState<PlayerAction>>,
-    mut action_events: EventReader<ActionEvent>,
-    mut audio_events: EventWriter<AudioEvent>,
-    mut neurovector_query: Query<(&Transform, &mut NeurovectorCapability), With<Agent>>,
-    billboard_query: Query<(&Transform, &Billboard, &DeviceState)>,    
-    target_query: Query<(Entity, &Transform, &mut Sprite), (With<NeurovectorTarget>, Without<ControlledCivilian>)>,
+    mut action_events: EventWriter<ActionEvent>,
+    neurovector_query: Query<(&Transform, &NeurovectorCapability), With<Agent>>,
+    target_query: Query<(Entity, &Transform), (With<NeurovectorTarget>, Without<ControlledCivilian>)>,
     game_mode: Res<GameMode>,
     windows: Query<&Window>,
     cameras: Query<(&Camera, &GlobalTransform)>,
-    time: Res<Time>,
 ) {
-    for (_, mut neurovector) in neurovector_query.iter_mut() {
-        if neurovector.current_cooldown > 0.0 {
-            neurovector.current_cooldown -= time.delta_secs();
-        }
-    }
-
     if let Some(TargetingMode::Neurovector { agent }) = &game_mode.targeting {
         let Ok(action_state) = input.single() else { return; };
         
         if action_state.just_pressed(&PlayerAction::Move) {
             if let Some(targets) = find_neurovector_targets(*agent, &neurovector_query, &target_query, &windows, &cameras) {
-                execute_mass_neurovector_control(&mut commands, *agent, targets, &mut neurovector_query, &mut audio_events);
+                for target in targets {
+                    action_events.write(ActionEvent {
+                        entity: *agent,
+                        action: Action::NeurovectorControl { target },
+                    });
+                }
             }
         }
     }
+}
+
+pub fn neurovector_control_system(
+    mut commands: Commands,
+    mut action_events: EventReader<ActionEvent>,
+    mut audio_events: EventWriter<AudioEvent>,
+    mut neurovector_query: Query<&mut NeurovectorCapability, With<Agent>>,
+    billboard_query: Query<(&Transform, &Billboard, &DeviceState)>,
+    time: Res<Time>,
+) {
+    for mut neurovector in neurovector_query.iter_mut() {
+        if neurovector.current_cooldown > 0.0 {
+            neurovector.current_cooldown -= time.delta_secs();
+        }
+    }
 
+    // Several targets for one agent in the same step came from a single sweep
+    let mut orders: Vec<(Entity, Vec<Entity>)> = Vec::new();
     for event in action_events.read() {
         if let Action::NeurovectorControl { target } = event.action {
-            execute_single_neurovector_control(&mut commands, event.entity, target, &mut neurovector_query, &mut audio_events);
+            match orders.iter_mut().find(|(agent, _)| *agent == event.entity) {
+                Some((_, targets)) => targets.push(target),
+                None => orders.push((event.entity, vec![target])),
+            }
+        }
+    }
+
+    for (agent, targets) in orders {
+        if targets.len() > 1 {
+            execute_mass_neurovector_control(&mut commands, agent, targets, &mut neurovector_query, &mut audio_events);
+        } else {
+            execute_single_neurovector_control(&mut commands, agent, targets[0], &mut neurovector_query, &mut audio_events);
         }
     }
 
@@ -80,8 +104,8 @@ pub fn enhanced_neurovector_system(
 
 fn find_neurovector_targets(
     agent: Entity,
-    neurovector_query: &Query<(&Transform, &mut NeurovectorCapability), With<Agent>>,
-    target_query: &Query<(Entity, &Transform, &mut Sprite), (With<NeurovectorTarget>, Without<ControlledCivilian>)>,
+    neurovector_query: &Query<(&Transform, &NeurovectorCapability), With<Agent>>,
+    target_query: &Query<(Entity, &Transform), (With<NeurovectorTarget>, Without<ControlledCivilian>)>,
     windows: &Query<&Window>,
     cameras: &Query<(&Camera, &GlobalTransform)>,
 ) -> Option<Vec<Entity>> {
@@ -93,7 +117,7 @@ fn find_neurovector_targets(
     let agent_pos = agent_transform.translation.truncate();
     let mut targets = Vec::new();
 
-    for (entity, transform, _) in target_query.iter() {
+    for (entity, transform) in target_query.iter() {
         let target_pos = transform.translation.truncate();
         let agent_distance = agent_pos.distance(target_pos);
         let mouse_distance = mouse_pos.distance(target_pos);
@@ -113,10 +137,10 @@ fn execute_mass_neurovector_control(
     commands: &mut Commands,
     agent: Entity,
     targets: Vec<Entity>,
-    neurovector_query: &mut Query<(&Transform, &mut NeurovectorCapability), With<Agent>>,
+    neurovector_query: &mut Query<&mut NeurovectorCapability, With<Agent>>,
     audio_events: &mut EventWriter<AudioEvent>,
 ) {
-    let Ok((_, mut neurovector)) = neurovector_query.get_mut(agent) else { return; };
+    let Ok(mut neurovector) = neurovector_query.get_mut(agent) else { return; };
 
     for target in targets {
         commands.entity(target).insert(ControlledCivilian {
@@ -139,10 +163,10 @@ fn execute_single_neurovector_control(
     commands: &mut Commands,
     agent: Entity,
     target: Entity,
-    neurovector_query: &mut Query<(&Transform, &mut NeurovectorCapability), With<Agent>>,
+    neurovector_query: &mut Query<&mut NeurovectorCapability, With<Agent>>,
     audio_events: &mut EventWriter<AudioEvent>,
 ) {
-    let Ok((_, mut neurovector)) = neurovector_query.get_mut(agent) else { return; };
+    let Ok(mut neurovector) = neurovector_query.get_mut(agent) else { return; };
 
     if neurovector.controlled.len() < neurovector.max_targets as usize {
         commands.entity(target).insert(ControlledCivilian {
@@ -538,38 +538,6 @@ pub fn enhanced_vision_system(
     }
 }
 
-// System to handle cover mechanics
-pub fn enhanced_cover_system(
-    mut agents: Query<(Entity, &Transform, &mut Health), With<Agent>>,
-    enhanced_grid: Res<EnhancedPathfindingGrid>,
-    mut damage_events: EventReader<CombatEvent>,
-) {
-    for damage_event in damage_events.read() {
-        if let Ok((entity, transform, mut health)) = agents.get_mut(damage_event.target) {
-            let target_pos = transform.translation.truncate();
-            
-            if let Some(tile_pos) = enhanced_grid.world_to_tile(target_pos) {
-                let cover_value = enhanced_grid.get_cover_value(tile_pos.x as usize, tile_pos.y as usize);
-                
-                // Reduce damage based on cover
-                let damage_reduction = cover_value * 0.8; // Up to 80% damage reduction
-                let final_damage = damage_event.damage * (1.0 - damage_reduction);
-                
-                // Apply the reduced damage
-                health.0 -= final_damage;
-                
-                if cover_value > 0.0 {
-                    info!("Cover reduced damage from {:.1} to {:.1} ({:.0}% reduction)", 
-                          damage_event.damage, final_damage, damage_reduction * 100.0);
-                }
-            } else {
-                // No cover, apply full damage
-                health.0 -= damage_event.damage;
-            }
-        }
-    }
-}
-
 // === UTILITY FUNCTIONS ===
 
 // Line of sight check between two world positions
@@ -1,5 +1,6 @@
 // src/systems/explosion_decal_integration.rs - Connect explosions with interactive decals
 use bevy::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::decals::*;
 use crate::systems::explosions::*;
@@ -9,20 +10,23 @@ use crate::systems::interactive_decals::*;
 
 /// Enhanced version of explosion_damage_system that creates interactive decals
 pub fn enhanced_explosion_damage_system(
-    mut explosion_query: Query<(Entity, &mut Explosion, &Transform), Without<MarkedForDespawn>>,
-    mut damageable_query: Query<(Entity, &Transform, &mut Health), (Without<Explosion>, Without<Dead>)>,
+    mut explosion_query: Query<(Entity, &mut Explosion, &Transform, Option<&DamageSource>), Without<MarkedForDespawn>>,
+    mut damageable_query: Query<(Entity, &Transform, &mut Health, Option<&Armor>), (Without<Explosion>, Without<Dead>)>,
     explodable_query: Query<(Entity, &Transform, &Explodable), Without<PendingExplosion>>,
     vehicle_query: Query<(Entity, &Transform, &Vehicle), With<Vehicle>>,
     mut commands: Commands,
     mut audio_events: EventWriter<AudioEvent>,
+    mut combat_events: EventWriter<CombatEvent>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
     combat_text_settings: Res<CombatTextSettings>,
     decal_settings: Res<DecalSettings>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
-    for (explosion_entity, mut explosion, explosion_transform) in explosion_query.iter_mut() {
+    let rng = rng.stream(RngStream::Environment);
+    for (explosion_entity, mut explosion, explosion_transform, source) in explosion_query.iter_mut() {
         let is_new = explosion.duration == match explosion.explosion_type {
             ExplosionType::Grenade => 2.0,
             ExplosionType::Vehicle => 3.0,
@@ -36,25 +40,28 @@ pub fn enhanced_explosion_damage_system(
             let explosion_pos = explosion_transform.translation.truncate();
             
             // === ORIGINAL DAMAGE LOGIC ===
-            for (entity, target_transform, mut health) in damageable_query.iter_mut() {
+            for (entity, target_transform, mut health, armor) in damageable_query.iter_mut() {
                 let target_pos = target_transform.translation.truncate();
                 let distance = explosion_pos.distance(target_pos);
                 
                 if distance <= explosion.radius {
                     let damage_factor = (1.0 - (distance / explosion.radius)).max(0.1);
-                    let damage = explosion.damage * damage_factor;
-                    
-                    health.0 = (health.0 - damage).max(0.0);
+                    let raw_damage = explosion.damage * damage_factor;
+                    let damage = Armor::apply_damage(&mut health, armor, raw_damage);
+                    if let Some(source) = source {
+                        combat_events.write(CombatEvent { attacker: source.0, target: entity, damage, hit: true });
+                    }
                     
                     // Apply fire effect for some explosions
                     if matches!(explosion.explosion_type, ExplosionType::Vehicle | ExplosionType::TimeBomb) 
-                       && damage > 20.0 && rand::random::<f32>() < 0.3 {
+                       && raw_damage > 20.0 && rng.gen::<f32>() < 0.3 {
                         commands.entity(entity).insert(StatusEffect {
                             effect_type: StatusType::Fire,
                             duration: 5.0,
-                            intensity: damage * 0.1,
+                            intensity: raw_damage * 0.1,
                             tick_timer: 0.0,
                             tick_rate: 1.0,
+                            source: source.map(|s| s.0),
                         });
                     }
                     
@@ -76,6 +83,9 @@ pub fn enhanced_explosion_damage_system(
                         radius: explodable.radius,
                         explosion_type: ExplosionType::Cascading,
                     });
+                    if let Some(source) = source {
+                        commands.entity(explodable_entity).insert(*source);
+                    }
                 }
             }
             
@@ -102,6 +112,7 @@ pub fn enhanced_explosion_damage_system(
                                 vehicle_pos,
                                 vehicle,
                                 explosion.damage,
+                                rng,
                             );
                         }
                     }
@@ -127,7 +138,7 @@ pub fn enhanced_explosion_damage_system(
                     );
                     
                     // 30% chance to create an oil spill from ruptured pipes/containers
-                    if rand::random::<f32>() < 0.3 {
+                    if rng.gen::<f32>() < 0.3 {
                         spawn_oil_spill(&mut commands, explosion_pos, explosion.radius * 0.6);
                     }
                 },
@@ -162,6 +173,7 @@ fn create_vehicle_spill_from_explosion(
     vehicle_pos: Vec2,
     vehicle: &Vehicle,
     explosion_damage: f32,
+    rng: &mut impl Rng,
 ) {
     // Determine vehicle type based on vehicle properties (you'll need to adapt this)
     let vehicle_type = determine_vehicle_type(vehicle);
@@ -181,8 +193,8 @@ fn create_vehicle_spill_from_explosion(
             // Trucks might also spill some debris
             for i in 0..3 {
                 let offset = Vec2::new(
-                    (rand::random::<f32>() - 0.5) * 80.0,
-                    (rand::random::<f32>() - 0.5) * 80.0,
+                    (rng.gen::<f32>() - 0.5) * 80.0,
+                    (rng.gen::<f32>() - 0.5) * 80.0,
                 );
                 spawn_oil_spill(commands, vehicle_pos + offset, 20.0);
             }
@@ -228,16 +240,19 @@ pub fn enhanced_handle_grenade_events(
     mut grenade_events: EventReader<GrenadeEvent>,
     mut commands: Commands,
     decal_settings: Res<DecalSettings>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream(RngStream::Environment);
     for event in grenade_events.read() {
         // Create the explosion
-        spawn_explosion(
+        let explosion = spawn_explosion(
             &mut commands,
             event.target_pos,
             event.explosion_radius,
             event.damage,
             ExplosionType::Grenade,
         );
+        commands.entity(explosion).insert(DamageSource(event.thrower));
         
         // Create scorch decal
         spawn_decal(
@@ -249,10 +264,10 @@ pub fn enhanced_handle_grenade_events(
         );
         
         // Small chance for grenade to rupture nearby containers
-        if rand::random::<f32>() < 0.15 {
+        if rng.gen::<f32>() < 0.15 {
             let offset = Vec2::new(
-                (rand::random::<f32>() - 0.5) * 40.0,
-                (rand::random::<f32>() - 0.5) * 40.0,
+                (rng.gen::<f32>() - 0.5) * 40.0,
+                (rng.gen::<f32>() - 0.5) * 40.0,
             );
             spawn_oil_spill(&mut commands, event.target_pos + offset, 25.0);
         }
@@ -266,6 +281,7 @@ pub fn enhanced_handle_vehicle_explosions(
     mut commands: Commands,
     mut vehicle_query: Query<(Entity, &Transform, &Vehicle), (With<Vehicle>, Added<Dead>, Without<MarkedForDespawn>)>,
     decal_settings: Res<DecalSettings>,
+    mut rng: ResMut<GameRng>,
 ) {
     for (entity, transform, vehicle) in vehicle_query.iter_mut() {
         let vehicle_pos = transform.translation.truncate();
@@ -294,6 +310,7 @@ pub fn enhanced_handle_vehicle_explosions(
             vehicle_pos,
             vehicle,
             vehicle.explosion_damage(),
+            rng.stream(RngStream::Environment),
         );
         
         commands.entity(entity).insert(MarkedForDespawn);
@@ -308,7 +325,9 @@ pub fn enhanced_projectile_impact_decals(
     impact_query: Query<&Transform, (With<ProjectileImpact>, Added<ProjectileImpact>)>,
     mut flammable_decals: Query<(Entity, &Transform, &Flammable, &mut InteractiveDecal), Without<OnFire>>,
     settings: Res<DecalSettings>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream(RngStream::Environment);
     for impact_transform in impact_query.iter() {
         let impact_pos = impact_transform.translation.truncate();
         
@@ -323,13 +342,13 @@ pub fn enhanced_projectile_impact_decals(
         
         // Check if projectile hit near flammable decals (tracer rounds, incendiary, etc.)
         // Small chance for special ammo to ignite spills
-        if rand::random::<f32>() < 0.05 { // 5% chance for regular bullets
+        if rng.gen::<f32>() < 0.05 { // 5% chance for regular bullets
             for (entity, decal_transform, flammable, mut decal) in flammable_decals.iter_mut() {
                 let decal_pos = decal_transform.translation.truncate();
                 let distance = impact_pos.distance(decal_pos);
                 
                 if distance <= 15.0 { // Very close hit
-                    ignite_decal_from_impact(&mut commands, entity, &mut decal, flammable);
+                    ignite_decal_from_impact(&mut commands, entity, &mut decal, flammable, rng);
                 }
             }
         }
@@ -341,11 +360,12 @@ fn ignite_decal_from_impact(
     entity: Entity,
     decal: &mut InteractiveDecal,
     flammable: &Flammable,
+    rng: &mut impl Rng,
 ) {
     // Only ignite gasoline easily, oil needs more heat
     let can_ignite = match decal.decal_type {
         InteractiveDecalType::GasolineSpill => true,
-        InteractiveDecalType::OilSpill => rand::random::<f32>() < 0.3, // Oil harder to ignite
+        InteractiveDecalType::OilSpill => rng.gen::<f32>() < 0.3, // Oil harder to ignite
         _ => false,
     };
     
@@ -359,3 +379,52 @@ fn ignite_decal_from_impact(
         info!("Bullet ignited {:?} decal!", decal.decal_type);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::simulation::SimulationPlugin;
+
+    /// Every fixed step the game runs, one step per update
+    fn mission_app() -> App {
+        let step = Duration::from_secs_f64(1.0 / SIMULATION_HZ as f64);
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, SimulationPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(step))
+            .insert_resource(Time::<Fixed>::from_duration(step))
+            .insert_state(GameState::Mission)
+            .init_resource::<GlobalData>()
+            .init_resource::<GameSprites>()
+            .insert_resource(WeaponDatabase::load())
+            .insert_resource(LoreDatabase::load());
+        app
+    }
+
+    #[test]
+    fn sourced_explosion_hits_an_agent_once() {
+        let mut app = mission_app();
+        let thrower = app.world_mut().spawn_empty().id();
+        let agent = app.world_mut().spawn((
+            Agent::default(),
+            Health(100.0),
+            Armor(0.5),
+            Transform::default(),
+        )).id();
+        app.world_mut().spawn((
+            Explosion { radius: 80.0, damage: 40.0, duration: 2.0, explosion_type: ExplosionType::Grenade },
+            DamageSource(thrower),
+            Transform::default(),
+        ));
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // Point blank, so the full 40 before armor and 20 after it
+        let health = app.world().get::<Health>(agent).unwrap().0;
+        assert_eq!(health, 80.0);
+    }
+}
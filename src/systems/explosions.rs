@@ -27,6 +27,7 @@
 
 use bevy::prelude::*;
 use bevy_light_2d::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::*;
 
@@ -64,6 +65,10 @@ pub struct Explodable {
     pub delay: f32,
 }
 
+/// Who is credited with the damage an explosion deals. Carried onto chained explosions and burns.
+#[derive(Component, Clone, Copy)]
+pub struct DamageSource(pub Entity);
+
 #[derive(Component)]
 pub struct PendingExplosion {
     pub timer: f32,
@@ -80,6 +85,7 @@ pub struct StatusEffect {
     pub intensity: f32,
     pub tick_timer: f32,
     pub tick_rate: f32,
+    pub source: Option<Entity>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -127,7 +133,7 @@ pub fn spawn_explosion(
     radius: f32,
     damage: f32,
     explosion_type: ExplosionType,
-) {
+) -> Entity {
     let (color, duration) = match explosion_type {
         ExplosionType::Grenade => (Color::srgba(1.0, 0.8, 0.0, 0.25), 2.0),
         ExplosionType::Vehicle => (Color::srgba(1.0, 0.5, 0.0, 0.25), 3.0),
@@ -154,7 +160,7 @@ pub fn spawn_explosion(
             intensity: 5.0,
             ..default()
         },        
-    ));
+    )).id()
 }
 
 /// Spawn an object that can explode when damaged or triggered by nearby explosions
@@ -213,6 +219,8 @@ pub fn spawn_explodable(
     )).id()
 }
 
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ExplodableType {
     FuelBarrel,
     GasCanister,
@@ -223,18 +231,20 @@ pub enum ExplodableType {
 
 /// Main explosion damage system - applies damage and triggers chain reactions
 pub fn explosion_damage_system(
-    mut explosion_query: Query<(Entity, &mut Explosion, &Transform), Without<MarkedForDespawn>>,
-    mut damageable_query: Query<(Entity, &Transform, &mut Health), (Without<Explosion>, Without<Dead>)>,
+    mut explosion_query: Query<(Entity, &mut Explosion, &Transform, Option<&DamageSource>), Without<MarkedForDespawn>>,
+    mut damageable_query: Query<(Entity, &Transform, &mut Health, Option<&Armor>), (Without<Explosion>, Without<Dead>)>,
     explodable_query: Query<(Entity, &Transform, &Explodable), Without<PendingExplosion>>,
     mut commands: Commands,
     mut audio_events: EventWriter<AudioEvent>,
+    mut combat_events: EventWriter<CombatEvent>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
     combat_text_settings: Res<CombatTextSettings>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
-    for (explosion_entity, mut explosion, explosion_transform) in explosion_query.iter_mut() {
+    for (explosion_entity, mut explosion, explosion_transform, source) in explosion_query.iter_mut() {
         let is_new = explosion.duration == match explosion.explosion_type {
             ExplosionType::Grenade => 2.0,
             ExplosionType::Vehicle => 3.0,
@@ -248,25 +258,28 @@ pub fn explosion_damage_system(
             let explosion_pos = explosion_transform.translation.truncate();
 
             // Damage entities
-            for (entity, target_transform, mut health) in damageable_query.iter_mut() {
+            for (entity, target_transform, mut health, armor) in damageable_query.iter_mut() {
                 let target_pos = target_transform.translation.truncate();
                 let distance = explosion_pos.distance(target_pos);
 
                 if distance <= explosion.radius {
                     let damage_factor = (1.0 - (distance / explosion.radius)).max(0.1);
-                    let damage = explosion.damage * damage_factor;
-
-                    health.0 = (health.0 - damage).max(0.0);
+                    let raw_damage = explosion.damage * damage_factor;
+                    let damage = Armor::apply_damage(&mut health, armor, raw_damage);
+                    if let Some(source) = source {
+                        combat_events.write(CombatEvent { attacker: source.0, target: entity, damage, hit: true });
+                    }
 
                     // Apply fire effect for some explosions
                     if matches!(explosion.explosion_type, ExplosionType::Vehicle | ExplosionType::TimeBomb)
-                       && damage > 20.0 && rand::random::<f32>() < 0.3 {
+                       && raw_damage > 20.0 && rng.stream(RngStream::Environment).gen::<f32>() < 0.3 {
                         commands.entity(entity).insert(StatusEffect {
                             effect_type: StatusType::Fire,
                             duration: 5.0,
-                            intensity: damage * 0.1,
+                            intensity: raw_damage * 0.1,
                             tick_timer: 0.0,
                             tick_rate: 1.0,
+                            source: source.map(|s| s.0),
                         });
                     }
 
@@ -291,6 +304,9 @@ pub fn explosion_damage_system(
                         radius: explodable.radius,
                         explosion_type: ExplosionType::Cascading,
                     });
+                    if let Some(source) = source {
+                        commands.entity(explodable_entity).insert(*source);
+                    }
                 }
             }
 
@@ -335,27 +351,30 @@ pub fn time_bomb_system(
 
 /// Process delayed explosions from chain reactions
 pub fn pending_explosion_system(
-    mut pending_query: Query<(Entity, &mut PendingExplosion, &Transform, Option<&Explodable>), Without<MarkedForDespawn>>,
+    mut pending_query: Query<(Entity, &mut PendingExplosion, &Transform, Option<&Explodable>, Option<&DamageSource>), Without<MarkedForDespawn>>,
     mut commands: Commands,
     time: Res<Time>,
     game_mode: Res<GameMode>,
 ) {
     if game_mode.paused { return; }
 
-    for (entity, mut pending, transform, explodable) in pending_query.iter_mut() {
+    for (entity, mut pending, transform, explodable, source) in pending_query.iter_mut() {
         pending.timer -= time.delta_secs();
 
         if pending.timer <= 0.0 {
             let pos = transform.translation.truncate();
 
             // Spawn the actual explosion
-            spawn_explosion(
+            let explosion = spawn_explosion(
                 &mut commands,
                 pos,
                 pending.radius,
                 pending.damage,
                 pending.explosion_type.clone(),
             );
+            if let Some(source) = source {
+                commands.entity(explosion).insert(*source);
+            }
 
             // Clean up the entity
             if explodable.is_some() {
@@ -371,25 +390,29 @@ pub fn pending_explosion_system(
 
 /// Apply damage over time effects
 pub fn status_effect_system(
-    mut affected_query: Query<(Entity, &mut StatusEffect, &mut Health, &Transform)>,
+    mut affected_query: Query<(Entity, &mut StatusEffect, &mut Health, &Transform, Option<&Armor>)>,
     mut commands: Commands,
+    mut combat_events: EventWriter<CombatEvent>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
     combat_text_settings: Res<CombatTextSettings>,
 ) {
     if game_mode.paused { return; }
 
-    for (entity, mut status, mut health, transform) in affected_query.iter_mut() {
+    for (entity, mut status, mut health, transform, armor) in affected_query.iter_mut() {
         status.duration -= time.delta_secs();
         status.tick_timer -= time.delta_secs();
 
         if status.tick_timer <= 0.0 {
             match status.effect_type {
                 StatusType::Fire => {
-                    health.0 = (health.0 - status.intensity).max(0.0);
+                    let damage = Armor::apply_damage(&mut health, armor, status.intensity);
+                    if let Some(attacker) = status.source {
+                        combat_events.write(CombatEvent { attacker, target: entity, damage, hit: true });
+                    }
 
                     if combat_text_settings.enabled {
-                        spawn_fire_text(&mut commands, transform.translation.truncate(), status.intensity);
+                        spawn_fire_text(&mut commands, transform.translation.truncate(), damage);
                     }
                 },
                 StatusType::EMP => {
@@ -435,13 +458,14 @@ pub fn handle_grenade_events(
     mut commands: Commands,
 ) {
     for event in grenade_events.read() {
-        spawn_explosion(
+        let explosion = spawn_explosion(
             &mut commands,
             event.target_pos,
             event.explosion_radius,
             event.damage,
             ExplosionType::Grenade,
         );
+        commands.entity(explosion).insert(DamageSource(event.thrower));
     }
 }
 
@@ -512,10 +536,12 @@ pub struct VehicleExplosion {
 pub fn explosion_road_blocking_system(
     mut explosion_events: EventReader<GrenadeEvent>,
     mut traffic_system: ResMut<TrafficSystem>,
+    mut incident_events: EventWriter<PoliceIncidentEvent>,
 ) {
     for explosion in explosion_events.read() {
         // Block roads near explosions
         mark_road_blocked(&mut traffic_system, explosion.target_pos, explosion.explosion_radius);
+        incident_events.write(PoliceIncidentEvent { position: explosion.target_pos, incident: IncidentType::Explosion });
 
         // Roads should clear after some time (need to add a timer system for this)
     }
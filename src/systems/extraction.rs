@@ -0,0 +1,164 @@
+// src/systems/extraction.rs - Extraction zones and the exit phase once objectives are done
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::core::*;
+use crate::systems::police::PoliceEscalation;
+use crate::systems::selection::AgentIndex;
+
+pub const DEFAULT_EXTRACTION_RADIUS: f32 = 60.0;
+/// Minimum time left on the clock once the squad is told to extract
+pub const MIN_EXTRACTION_WINDOW: f32 = 90.0;
+/// Police heat per second while extracting, growing the longer the squad lingers
+const EXTRACTION_HEAT_BASE: f32 = 1.0;
+const EXTRACTION_HEAT_RAMP: f32 = 0.05;
+
+// === COMPONENTS ===
+#[derive(Component)]
+pub struct ExtractionZone {
+    pub radius: f32,
+}
+
+// === RESOURCES ===
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum MissionPhase {
+    #[default]
+    Objectives,
+    /// Required objectives are done; agents have to reach an extraction zone
+    Extraction,
+}
+
+#[derive(Resource, Default)]
+pub struct ExtractionState {
+    pub phase: MissionPhase,
+    /// Mission timer when the extraction phase began
+    pub started_at: f32,
+    /// Squad slots that made it out
+    pub extracted: Vec<usize>,
+    /// Credits carried out by extracted agents
+    pub credits: u32,
+    /// Set from the pause menu to leave whoever is still in the field behind
+    pub abandon_requested: bool,
+}
+
+impl ExtractionState {
+    pub fn begin(&mut self, mission_data: &mut MissionData) {
+        self.phase = MissionPhase::Extraction;
+        self.started_at = mission_data.timer;
+        mission_data.time_limit = mission_data.time_limit.max(mission_data.timer + MIN_EXTRACTION_WINDOW);
+        info!("Objectives complete - extract ({:.0}s left)", mission_data.time_limit - mission_data.timer);
+    }
+
+    /// Results once nobody is left to extract; agents still in the field are listed in `agents_missing`
+    pub fn results(&self, mission_data: &MissionData) -> PostMissionResults {
+        let outcome = if self.extracted.is_empty() {
+            MissionOutcome::Failed
+        } else if mission_data.agents_missing.is_empty() && mission_data.agents_lost.is_empty() {
+            MissionOutcome::Success
+        } else {
+            MissionOutcome::PartialSuccess
+        };
+
+        PostMissionResults {
+            success: outcome != MissionOutcome::Failed,
+            outcome,
+            time_taken: mission_data.timer,
+            enemies_killed: mission_data.enemies_killed,
+            terminals_accessed: mission_data.terminals_accessed,
+            credits_earned: if outcome == MissionOutcome::Failed { 0 } else { self.credits },
+            alert_level: mission_data.alert_level,
+            agents_extracted: self.extracted.len() as u32,
+            agents_missing: mission_data.agents_missing.len() as u32,
+            ..default()
+        }
+    }
+}
+
+// === SYSTEMS ===
+/// Agents inside a zone during the exit phase leave the map with whatever they carry
+pub fn agent_extraction_system(
+    mut commands: Commands,
+    mut extraction: ResMut<ExtractionState>,
+    zones: Query<(&Transform, &ExtractionZone)>,
+    agents: Query<(Entity, &Transform, &AgentIndex, Option<&Inventory>), (With<Agent>, Without<Dead>, Without<MarkedForDespawn>)>,
+) {
+    if extraction.phase != MissionPhase::Extraction {
+        return;
+    }
+
+    for (entity, transform, agent_index, inventory) in agents.iter() {
+        let pos = transform.translation.truncate();
+        let in_zone = zones.iter().any(|(zone_transform, zone)| {
+            zone_transform.translation.truncate().distance(pos) <= zone.radius
+        });
+        if !in_zone {
+            continue;
+        }
+
+        extraction.extracted.push(agent_index.0);
+        extraction.credits += inventory.map_or(0, |inv| inv.currency);
+        commands.entity(entity)
+            .remove::<Controllable>()
+            .remove::<Selectable>()
+            .insert(MarkedForDespawn);
+        info!("Agent {} extracted", agent_index.0 + 1);
+    }
+}
+
+/// Security keeps ramping up while the squad makes for the exit
+pub fn extraction_pressure_system(
+    extraction: Res<ExtractionState>,
+    mission_data: Res<MissionData>,
+    mut response: ResMut<PoliceResponse>,
+    mut escalation: ResMut<PoliceEscalation>,
+    agents: Query<&Transform, (With<Agent>, Without<Dead>)>,
+    game_mode: Res<GameMode>,
+    time: Res<Time>,
+) {
+    if game_mode.paused || extraction.phase != MissionPhase::Extraction || agents.is_empty() {
+        return;
+    }
+
+    let elapsed = (mission_data.timer - extraction.started_at).max(0.0);
+    response.heat_level += (EXTRACTION_HEAT_BASE + elapsed * EXTRACTION_HEAT_RAMP) * time.delta_secs();
+    escalation.calm_timer = 0.0;
+
+    // Responders converge on the squad rather than the last incident
+    let squad_center = agents.iter().map(|t| t.translation.truncate()).sum::<Vec2>() / agents.iter().len() as f32;
+    response.last_incident_pos = Some(squad_center);
+}
+
+pub fn extraction_zone_gizmos(
+    mut gizmos: Gizmos,
+    extraction: Res<ExtractionState>,
+    zones: Query<(&Transform, &ExtractionZone)>,
+) {
+    let color = match extraction.phase {
+        MissionPhase::Objectives => Color::srgba(0.2, 1.0, 0.4, 0.25),
+        MissionPhase::Extraction => Color::srgba(0.2, 1.0, 0.4, 0.9),
+    };
+
+    for (transform, zone) in zones.iter() {
+        let pos = transform.translation.truncate();
+        gizmos.circle_2d(pos, zone.radius, color);
+        if extraction.phase == MissionPhase::Extraction {
+            gizmos.circle_2d(pos, zone.radius * 0.6, color);
+        }
+    }
+}
+
+pub fn cleanup_extraction_zones(
+    mut commands: Commands,
+    zones: Query<Entity, (With<ExtractionZone>, Without<MarkedForDespawn>)>,
+) {
+    for entity in zones.iter() {
+        commands.entity(entity).insert(MarkedForDespawn);
+    }
+}
+
+// === HELPERS ===
+pub fn spawn_extraction_zone(commands: &mut Commands, pos: Vec2, radius: f32) -> Entity {
+    commands.spawn((
+        Transform::from_translation(pos.extend(0.5)),
+        ExtractionZone { radius },
+    )).id()
+}
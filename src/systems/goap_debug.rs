@@ -0,0 +1,213 @@
+// src/systems/goap_debug.rs - In-game GOAP inspector (F7 to toggle, click an enemy to inspect)
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::core::*;
+use crate::core::factions::Faction;
+use crate::systems::ai::AIState;
+use crate::systems::ice::is_jacked_in;
+use crate::systems::perception::PerceptionMemory;
+use crate::systems::squads::SquadMember;
+
+const PICK_RADIUS: f32 = 30.0;
+
+// === RESOURCES ===
+#[derive(Resource, Default)]
+pub struct GoapDebugState {
+    pub enabled: bool,
+    pub inspected: Option<Entity>,
+    pub show_all_targets: bool,
+}
+
+// === PLUGIN ===
+pub struct GoapDebugPlugin;
+
+impl Plugin for GoapDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GoapDebugState>()
+            .add_systems(Update, (
+                toggle_goap_debugger,
+                pick_inspected_enemy,
+                goap_debug_gizmos,
+                goap_debug_ui,
+            ).run_if(in_state(GameState::Mission)))
+            .add_systems(OnExit(GameState::Mission), reset_goap_debugger.run_if(not(is_jacked_in)));
+    }
+}
+
+// === SYSTEMS ===
+fn toggle_goap_debugger(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<GoapDebugState>,
+) {
+    if keyboard.just_pressed(KeyCode::F7) {
+        state.enabled = !state.enabled;
+        info!("GOAP debugger: {}", if state.enabled { "ON" } else { "OFF" });
+    }
+}
+
+fn pick_inspected_enemy(
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, With<GoapAgent>)>,
+    mut contexts: EguiContexts,
+    mut state: ResMut<GoapDebugState>,
+) {
+    if !state.enabled || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // Clicks on the inspector window itself shouldn't change the selection
+    if contexts.ctx_mut().map_or(false, |ctx| ctx.is_pointer_over_area()) {
+        return;
+    }
+
+    let Some(mouse_pos) = get_world_mouse_position(&windows, &cameras) else { return; };
+
+    let picked = enemies.iter()
+        .map(|(entity, transform)| (entity, transform.translation.truncate().distance(mouse_pos)))
+        .filter(|(_, distance)| *distance <= PICK_RADIUS)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(entity, _)| entity);
+
+    if picked.is_some() {
+        state.inspected = picked;
+    }
+}
+
+fn goap_debug_gizmos(
+    mut gizmos: Gizmos,
+    state: Res<GoapDebugState>,
+    agents: Query<(Entity, &Transform, &GoapAgent, &AIState)>,
+) {
+    if !state.enabled {
+        return;
+    }
+
+    for (entity, transform, goap_agent, ai_state) in agents.iter() {
+        let inspected = state.inspected == Some(entity);
+        if !inspected && !state.show_all_targets {
+            continue;
+        }
+
+        let pos = transform.translation.truncate();
+        if inspected {
+            gizmos.circle_2d(pos, 22.0, Color::srgb(1.0, 0.0, 1.0));
+            if let Some(last_known) = ai_state.last_known_target {
+                draw_cross(&mut gizmos, last_known, Color::srgb(1.0, 1.0, 0.0));
+            }
+        }
+
+        if let Some((action_name, target)) = &goap_agent.action_target {
+            let color = action_color(action_name);
+            gizmos.line_2d(pos, *target, color);
+            gizmos.circle_2d(*target, 8.0, color);
+        }
+    }
+}
+
+fn goap_debug_ui(
+    mut contexts: EguiContexts,
+    mut state: ResMut<GoapDebugState>,
+    agents: Query<(&GoapAgent, &AIState, &Health, Option<&Faction>, Option<&SquadMember>, Option<&PerceptionMemory>)>,
+    time: Res<Time>,
+) {
+    if !state.enabled {
+        return;
+    }
+
+    let Ok(ctx) = contexts.ctx_mut() else { return; };
+
+    egui::Window::new("GOAP Debugger")
+        .default_pos(egui::pos2(10.0, 200.0))
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            ui.checkbox(&mut state.show_all_targets, "Show action targets for all enemies");
+            ui.separator();
+
+            let Some((goap_agent, ai_state, health, faction, squad_member, memory)) = state.inspected.and_then(|e| agents.get(e).ok()) else {
+                ui.label("Click an enemy to inspect it");
+                return;
+            };
+
+            ui.label(format!("Profile: {}", goap_agent.profile.as_deref()
+                .or_else(|| faction.map(faction_profile_key))
+                .unwrap_or(DEFAULT_GOAP_PROFILE)));
+            ui.label(format!("Health: {:.0}  Mode: {:?}", health.0, ai_state.mode));
+            if let Some(member) = squad_member {
+                ui.label(format!("Squad {} as {:?}", member.squad_id, member.role));
+            }
+            if let Some(memory) = memory {
+                ui.label(format!("Suspicion: {:.2} ({:?}), {} stimuli, search radius {:.0}",
+                    memory.suspicion, memory.level(), memory.stimuli.len(), memory.search_radius()));
+            }
+
+            ui.separator();
+            match &goap_agent.current_goal {
+                Some(goal) => ui.label(format!("Goal: {} (priority {:.1})", goal.name, goal.priority)),
+                None => ui.label("Goal: none"),
+            };
+
+            if goap_agent.current_plan.is_empty() {
+                ui.label("Plan: empty");
+            } else {
+                for (i, action) in goap_agent.current_plan.iter().enumerate() {
+                    ui.label(format!("  {}. {} ({:.1})", i + 1, action.name, goap_agent.action_cost(action)));
+                }
+            }
+
+            if let Some(failure) = &goap_agent.last_plan_failure {
+                ui.colored_label(egui::Color32::from_rgb(255, 140, 0), format!("Last failure: {}", failure));
+            }
+
+            egui::CollapsingHeader::new("World state").show(ui, |ui| {
+                let mut keys: Vec<_> = goap_agent.world_state.iter().collect();
+                keys.sort_by_key(|(key, _)| **key);
+                egui::ScrollArea::vertical().id_salt("goap_world_state").max_height(200.0).show(ui, |ui| {
+                    for (key, value) in keys {
+                        let color = if *value { egui::Color32::LIGHT_GREEN } else { egui::Color32::GRAY };
+                        ui.colored_label(color, format!("{:?} = {}", key, value));
+                    }
+                });
+            });
+
+            egui::CollapsingHeader::new("Plan history").default_open(true).show(ui, |ui| {
+                let now = time.elapsed_secs();
+                for record in goap_agent.plan_history.iter().rev() {
+                    let age = format!("-{:.1}s", now - record.time);
+                    match (&record.goal, &record.failure) {
+                        (Some(goal), _) => {
+                            ui.label(format!("{} {} [{:.1}]: {}", age, goal, record.cost, record.actions.join(" > ")));
+                        }
+                        (None, Some(failure)) => {
+                            ui.colored_label(egui::Color32::LIGHT_RED, format!("{} failed: {}", age, failure));
+                        }
+                        (None, None) => {
+                            ui.label(format!("{} no plan", age));
+                        }
+                    }
+                }
+            });
+        });
+}
+
+fn reset_goap_debugger(mut state: ResMut<GoapDebugState>) {
+    state.inspected = None;
+}
+
+// === HELPERS ===
+fn action_color(action_name: &str) -> Color {
+    match action_name {
+        n if n.contains("flank") => Color::srgb(1.0, 0.6, 0.0),
+        n if n.contains("retreat") || n.contains("withdraw") || n.contains("distance") => Color::srgb(0.3, 0.5, 1.0),
+        n if n.contains("grenade") => Color::srgb(1.0, 0.2, 0.2),
+        n if n.contains("cover") => Color::srgb(0.2, 0.9, 0.3),
+        _ => Color::srgb(0.8, 0.8, 0.8),
+    }
+}
+
+fn draw_cross(gizmos: &mut Gizmos, pos: Vec2, color: Color) {
+    gizmos.line_2d(pos + Vec2::new(-6.0, -6.0), pos + Vec2::new(6.0, 6.0), color);
+    gizmos.line_2d(pos + Vec2::new(-6.0, 6.0), pos + Vec2::new(6.0, -6.0), color);
+}
@@ -0,0 +1,288 @@
+// src/systems/grading.rs - Mission scoring, letter grades and per-agent experience
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::core::*;
+use crate::systems::enhanced_neurovector::ControlledCivilian;
+use crate::systems::objectives::MissionObjectives;
+use crate::systems::police::PoliceEscalation;
+use crate::systems::selection::AgentIndex;
+use crate::systems::urban_security::EscalationLevel;
+
+const SQUAD_SLOTS: usize = 3;
+
+/// Par time is a flat minute plus time per point of required objective difficulty
+const PAR_BASE_SECS: f32 = 60.0;
+const PAR_SECS_PER_DIFFICULTY: f32 = 40.0;
+
+// Score budget, out of 100
+const SUCCESS_POINTS: f32 = 40.0;
+const PARTIAL_SUCCESS_POINTS: f32 = 25.0;
+const STEALTH_POINTS: f32 = 20.0;
+const OBJECTIVE_POINTS: f32 = 10.0;
+const SPEED_POINTS: f32 = 15.0;
+const DAMAGE_POINTS: f32 = 10.0;
+const NEUROVECTOR_POINTS_EACH: f32 = 1.0;
+const NEUROVECTOR_POINTS_MAX: f32 = 5.0;
+const CIVILIAN_PENALTY_EACH: f32 = 5.0;
+const CIVILIAN_PENALTY_MAX: f32 = 20.0;
+/// Squad damage taken at which the damage bonus runs out
+const DAMAGE_REFERENCE: f32 = 300.0;
+
+const BASE_XP: u32 = 10;
+const XP_PER_KILL: u32 = 5;
+
+// === TRACKING ===
+#[derive(Clone, Default)]
+pub struct AgentMissionStats {
+    pub deployed: bool,
+    pub kills: u32,
+    pub hacks: u32,
+    pub neurovector_uses: u32,
+    pub damage_dealt: f32,
+    pub damage_taken: f32,
+}
+
+impl AgentMissionStats {
+    /// Weight used to split the squad's experience; everyone who went gets a share
+    pub fn contribution(&self) -> f32 {
+        1.0 + self.kills as f32 * 2.0
+            + self.hacks as f32 * 1.5
+            + self.neurovector_uses as f32
+            + self.damage_dealt / 50.0
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MissionPerformanceTracker {
+    pub detected: bool,
+    pub alert_peak: AlertLevel,
+    pub civilian_casualties: u32,
+    pub par_time: f32,
+    pub agents: [AgentMissionStats; SQUAD_SLOTS],
+    /// Squad slot that last hurt each target, so kills can be credited
+    last_hit_by: HashMap<Entity, usize>,
+}
+
+impl MissionPerformanceTracker {
+    pub fn new(objectives: &MissionObjectives) -> Self {
+        let difficulty: u32 = objectives.objectives.iter()
+            .filter(|o| o.objective.required)
+            .map(|o| o.objective.difficulty as u32)
+            .sum();
+
+        Self {
+            par_time: PAR_BASE_SECS + difficulty as f32 * PAR_SECS_PER_DIFFICULTY,
+            ..default()
+        }
+    }
+}
+
+fn squad_slot(index: Option<&AgentIndex>) -> Option<usize> {
+    index.map(|i| i.0).filter(|&i| i < SQUAD_SLOTS)
+}
+
+fn escalation_alert(level: EscalationLevel) -> AlertLevel {
+    match level {
+        EscalationLevel::None => AlertLevel::Green,
+        EscalationLevel::Patrol => AlertLevel::Yellow,
+        EscalationLevel::Armed => AlertLevel::Orange,
+        _ => AlertLevel::Red,
+    }
+}
+
+pub fn track_combat_performance(
+    mut combat_events: EventReader<CombatEvent>,
+    agents: Query<&AgentIndex, With<Agent>>,
+    mut tracker: ResMut<MissionPerformanceTracker>,
+) {
+    for event in combat_events.read() {
+        if !event.hit {
+            continue;
+        }
+
+        if let Ok(target_index) = agents.get(event.target) {
+            if let Some(slot) = squad_slot(Some(target_index)) {
+                tracker.agents[slot].damage_taken += event.damage;
+            }
+            continue;
+        }
+
+        if let Some(slot) = squad_slot(agents.get(event.attacker).ok()) {
+            tracker.agents[slot].damage_dealt += event.damage;
+            tracker.last_hit_by.insert(event.target, slot);
+        }
+    }
+}
+
+pub fn track_casualties(
+    newly_dead: Query<(Entity, Has<Enemy>, Has<Civilian>), Added<Dead>>,
+    mut tracker: ResMut<MissionPerformanceTracker>,
+) {
+    for (entity, is_enemy, is_civilian) in newly_dead.iter() {
+        let slot = tracker.last_hit_by.remove(&entity);
+
+        // A dead civilian counts against the squad whatever killed them; kills need someone to credit
+        if is_civilian {
+            tracker.civilian_casualties += 1;
+        } else if let (true, Some(slot)) = (is_enemy, slot) {
+            tracker.agents[slot].kills += 1;
+        }
+    }
+}
+
+pub fn track_neurovector_usage(
+    controlled: Query<&ControlledCivilian, Added<ControlledCivilian>>,
+    agents: Query<&AgentIndex, With<Agent>>,
+    mut tracker: ResMut<MissionPerformanceTracker>,
+) {
+    for civilian in controlled.iter() {
+        if let Some(slot) = squad_slot(agents.get(civilian.controller).ok()) {
+            tracker.agents[slot].neurovector_uses += 1;
+        }
+    }
+}
+
+pub fn track_hacks(
+    mut hack_events: EventReader<HackCompletedEvent>,
+    agents: Query<&AgentIndex, With<Agent>>,
+    mut tracker: ResMut<MissionPerformanceTracker>,
+) {
+    for event in hack_events.read() {
+        if let Some(slot) = squad_slot(agents.get(event.agent).ok()) {
+            tracker.agents[slot].hacks += 1;
+        }
+    }
+}
+
+pub fn track_detection(
+    agents: Query<&AgentIndex, With<Agent>>,
+    enemies: Query<&GoapAgent, (With<Enemy>, Without<Dead>)>,
+    mission_data: Res<MissionData>,
+    escalation: Option<Res<PoliceEscalation>>,
+    mut tracker: ResMut<MissionPerformanceTracker>,
+) {
+    for index in agents.iter() {
+        if let Some(slot) = squad_slot(Some(index)) {
+            tracker.agents[slot].deployed = true;
+        }
+    }
+
+    if !tracker.detected {
+        tracker.detected = enemies.iter()
+            .any(|goap| goap.world_state.get(&WorldKey::TargetVisible).copied().unwrap_or(false));
+    }
+
+    let mut alert = mission_data.alert_level;
+    if let Some(escalation) = escalation {
+        let police_alert = escalation_alert(escalation.level);
+        if police_alert as u8 > alert as u8 {
+            alert = police_alert;
+        }
+    }
+    if alert as u8 > tracker.alert_peak as u8 {
+        tracker.alert_peak = alert;
+    }
+}
+
+// === GRADING ===
+/// Scores a finished mission; `returned` lists the squad slots that made it home
+pub fn grade_mission(
+    results: &PostMissionResults,
+    tracker: &MissionPerformanceTracker,
+    objectives: &MissionObjectives,
+    returned: &[usize],
+) -> MissionPerformance {
+    let stealth_rating = if !tracker.detected {
+        1.0
+    } else {
+        match tracker.alert_peak {
+            AlertLevel::Green => 0.6,
+            AlertLevel::Yellow => 0.4,
+            AlertLevel::Orange => 0.2,
+            AlertLevel::Red => 0.0,
+        }
+    };
+    let efficiency_rating = (2.0 - results.time_taken / tracker.par_time.max(1.0)).clamp(0.0, 1.0);
+
+    let optional_total = (objectives.objectives.len() - objectives.required_count()) as u32;
+    let optional_completed = objectives.optional_completed() as u32;
+    let optional_rating = if optional_total == 0 {
+        1.0
+    } else {
+        optional_completed as f32 / optional_total as f32
+    };
+    let objective_completion = if objectives.objectives.is_empty() {
+        0.0
+    } else {
+        (objectives.required_completed() as u32 + optional_completed) as f32 / objectives.objectives.len() as f32
+    };
+
+    let damage_taken: f32 = tracker.agents.iter().map(|a| a.damage_taken).sum();
+    let neurovector_uses: u32 = tracker.agents.iter().map(|a| a.neurovector_uses).sum();
+
+    let stealth_bonus = (stealth_rating * STEALTH_POINTS).round() as u32;
+    let speed_bonus = (efficiency_rating * SPEED_POINTS).round() as u32;
+    let civilian_penalty = (tracker.civilian_casualties as f32 * CIVILIAN_PENALTY_EACH).min(CIVILIAN_PENALTY_MAX);
+
+    let score = match results.outcome {
+        MissionOutcome::Failed => 0,
+        outcome => {
+            let base = if outcome == MissionOutcome::Success { SUCCESS_POINTS } else { PARTIAL_SUCCESS_POINTS };
+            let raw = base
+                + stealth_bonus as f32
+                + speed_bonus as f32
+                + optional_rating * OBJECTIVE_POINTS
+                + (1.0 - damage_taken / DAMAGE_REFERENCE).clamp(0.0, 1.0) * DAMAGE_POINTS
+                + (neurovector_uses as f32 * NEUROVECTOR_POINTS_EACH).min(NEUROVECTOR_POINTS_MAX)
+                - civilian_penalty;
+            raw.round().clamp(0.0, 100.0) as u32
+        }
+    };
+    let grade = if results.success { MissionGrade::from_score(score) } else { MissionGrade::F };
+
+    MissionPerformance {
+        success: results.success,
+        stealth_rating,
+        efficiency_rating,
+        objective_completion,
+        stealth_bonus,
+        speed_bonus,
+        civilian_penalty,
+        research_progress: None,
+        final_credits: results.credits_earned,
+        score,
+        grade,
+        detected: tracker.detected,
+        alert_peak: tracker.alert_peak,
+        civilian_casualties: tracker.civilian_casualties,
+        optional_completed,
+        optional_total,
+        par_time: tracker.par_time,
+        damage_taken,
+        neurovector_uses,
+        agent_xp: split_experience(results, tracker, grade, returned),
+    }
+}
+
+/// The squad's pool scales with the grade and goes to those who came home, by contribution
+fn split_experience(
+    results: &PostMissionResults,
+    tracker: &MissionPerformanceTracker,
+    grade: MissionGrade,
+    returned: &[usize],
+) -> [u32; SQUAD_SLOTS] {
+    let mut xp = [0; SQUAD_SLOTS];
+    let returned: Vec<usize> = returned.iter().copied().filter(|&i| i < SQUAD_SLOTS).collect();
+    if !results.success || returned.is_empty() {
+        return xp;
+    }
+
+    let per_agent = BASE_XP + results.enemies_killed * XP_PER_KILL;
+    let pool = (per_agent * returned.len() as u32) as f32 * grade.xp_multiplier();
+    let total: f32 = returned.iter().map(|&i| tracker.agents[i].contribution()).sum();
+
+    for &i in &returned {
+        xp[i] = (pool * tracker.agents[i].contribution() / total).round() as u32;
+    }
+    xp
+}
@@ -0,0 +1,126 @@
+// src/systems/hack_trace.rs - Network security tracing hackers while they work
+//
+// Hacking anything on a secured network starts a trace back to the hacker. Halfway through, the
+// guards nearby are sent to look; if it runs out before the hack lands, the police hear about it,
+// guards from further out converge on the hacker and the network is hardened for the rest of the
+// mission.
+use bevy::prelude::*;
+use crate::core::*;
+use crate::core::agent_upgrades::*;
+use crate::systems::hacking_feedback::HackingProgress;
+use crate::systems::police::PoliceIncidentEvent;
+use crate::systems::urban_security::IncidentType;
+
+/// Devices below this security level aren't watched closely enough to trace
+const TRACE_MIN_SECURITY: u8 = 2;
+/// Trace time at security level 1; it divides down as the security level rises
+const TRACE_BASE_SECS: f32 = 40.0;
+/// Share of the trace after which local guards are sent to the hacker
+const TRACE_ALERT_AT: f32 = 0.5;
+/// Hacks on a hardened network take this much longer
+pub const HARDENED_HACK_MULTIPLIER: f32 = 1.75;
+
+// === COMPONENTS ===
+/// Rides on the HackingProgress entity, so it stops when the hack lands or is broken off
+#[derive(Component)]
+pub struct HackTrace {
+    pub network_id: String,
+    pub elapsed: f32,
+    pub duration: f32,
+    pub guards_alerted: bool,
+}
+
+/// The trace a hack on `hackable` starts, if its network is secured. Hacking implants buy time.
+pub fn start_trace(hackable: &Hackable, upgrades: Option<&AgentUpgrades>) -> Option<HackTrace> {
+    let network_id = hackable.network_id.clone()?;
+    if hackable.security_level < TRACE_MIN_SECURITY {
+        return None;
+    }
+
+    let hacking_bonus: f32 = upgrades.map_or(0.0, |upgrades| {
+        upgrades.calculate_total_effects().iter()
+            .filter_map(|effect| match effect {
+                CyberneticEffect::HackingBonus(bonus) => Some(*bonus),
+                _ => None,
+            })
+            .sum()
+    });
+
+    Some(HackTrace {
+        network_id,
+        elapsed: 0.0,
+        duration: TRACE_BASE_SECS * (1.0 + hacking_bonus).max(0.25) / hackable.security_level as f32,
+        guards_alerted: false,
+    })
+}
+
+// === SYSTEMS ===
+pub fn hack_trace_system(
+    mut commands: Commands,
+    mut traces: Query<(Entity, &HackingProgress, &mut HackTrace), Without<MarkedForDespawn>>,
+    hackers: Query<&Transform, With<Agent>>,
+    mut power_grid: ResMut<PowerGrid>,
+    mut alert_events: EventWriter<AlertEvent>,
+    mut incident_events: EventWriter<PoliceIncidentEvent>,
+    mut audio_events: EventWriter<AudioEvent>,
+    time: Res<Time>,
+) {
+    for (entity, progress, mut trace) in traces.iter_mut() {
+        trace.elapsed += time.delta_secs();
+        let Ok(hacker_transform) = hackers.get(progress.hacker) else { continue; };
+        let hacker_pos = hacker_transform.translation.truncate();
+
+        if !trace.guards_alerted && trace.elapsed >= trace.duration * TRACE_ALERT_AT {
+            trace.guards_alerted = true;
+            alert_events.write(AlertEvent {
+                alerter: progress.target,
+                position: hacker_pos,
+                alert_level: 1,
+                source: AlertSource::Alarm,
+                alert_type: AlertType::CallForHelp,
+            });
+            info!("Trace on network {} narrowing in", trace.network_id);
+        }
+
+        if trace.elapsed < trace.duration {
+            continue;
+        }
+
+        commands.entity(entity).remove::<HackTrace>();
+        alert_events.write(AlertEvent {
+            alerter: progress.target,
+            position: hacker_pos,
+            alert_level: 2,
+            source: AlertSource::Alarm,
+            alert_type: AlertType::IntrusionTraced,
+        });
+        incident_events.write(PoliceIncidentEvent {
+            position: hacker_pos,
+            incident: IncidentType::NetworkIntrusion,
+        });
+        audio_events.write(AudioEvent {
+            sound: AudioType::Alert,
+            volume: 0.7,
+        });
+
+        if let Some(network) = power_grid.networks.get_mut(&trace.network_id) {
+            network.hardened = true;
+        }
+        warn!("Trace complete: hacker located, network {} hardened", trace.network_id);
+    }
+}
+
+// === VISUALS ===
+/// A red ring closing in on the hacker as the trace runs
+pub fn hack_trace_gizmos(
+    mut gizmos: Gizmos,
+    traces: Query<(&HackingProgress, &HackTrace), Without<MarkedForDespawn>>,
+    hackers: Query<&Transform, With<Agent>>,
+) {
+    for (progress, trace) in traces.iter() {
+        let Ok(transform) = hackers.get(progress.hacker) else { continue; };
+        let remaining = (1.0 - trace.elapsed / trace.duration).clamp(0.0, 1.0);
+        let color = if trace.guards_alerted { Color::srgb(1.0, 0.2, 0.2) } else { Color::srgb(1.0, 0.6, 0.2) };
+        gizmos.circle_2d(transform.translation.truncate(), 20.0 + remaining * 60.0, color);
+    }
+}
@@ -1,6 +1,9 @@
 // src/systems/hacking_feedback.rs - Visual feedback for hacking process
 use bevy::prelude::*;
 use crate::core::*;
+use crate::core::agent_upgrades::AgentUpgrades;
+use crate::systems::hack_trace::{start_trace, HARDENED_HACK_MULTIPLIER};
+use crate::systems::remote_hacking::{can_hack_remotely, RemoteAccess, HOP_HACK_SECS};
 use std::time::{SystemTime};
 
 // === HACKING PROGRESS COMPONENT ===
@@ -11,6 +14,8 @@ pub struct HackingProgress {
     pub progress: f32, // 0.0 to 1.0
     pub total_time: f32,
     pub device_type: DeviceType,
+    /// Network hops for a remote hack; 0 when the hacker is on site
+    pub hops: u32,
 }
 
 // === HACK STATUS INDICATORS ===
@@ -27,15 +32,21 @@ pub fn enhanced_hacking_system(
     mut hackable_query: Query<(Entity, &mut Hackable, &mut DeviceState, &Transform)>,
     mut hacking_progress_query: Query<(Entity, &mut HackingProgress)>,
     agent_inventory: Query<&Inventory, With<Agent>>,
+    agent_upgrades: Query<&AgentUpgrades, With<Agent>>,
+    power_grid: Res<PowerGrid>,
     mut audio_events: EventWriter<AudioEvent>,
     time: Res<Time>,
 ) {
     // Start new hack attempts
     for event in hack_attempts.read() {
         if let Ok((target_entity, hackable, device_state, transform)) = hackable_query.get(event.target) {
-            // Check if agent has required tool
+            // Check if agent has required tool; a remote hack runs through the agent's uplink instead
             if let Ok(inventory) = agent_inventory.get(event.agent) {
-                let has_tool = check_hack_tool_available(inventory, &hackable);
+                let has_tool = if event.hops > 0 {
+                    can_hack_remotely(inventory)
+                } else {
+                    check_hack_tool_available(inventory, &hackable)
+                };
                 
                 if !has_tool {
                     info!("Hack failed: Missing required tool");
@@ -48,14 +59,25 @@ pub fn enhanced_hacking_system(
                 continue;
             }
             
+            let hardened = power_grid.is_hardened(hackable.network_id.as_ref());
+            let base_time = if hardened { hackable.hack_time * HARDENED_HACK_MULTIPLIER } else { hackable.hack_time };
+            let hack_time = base_time + event.hops as f32 * HOP_HACK_SECS;
+
             // Start hacking progress
-            commands.spawn(HackingProgress {
+            let mut progress = commands.spawn(HackingProgress {
                 hacker: event.agent,
                 target: event.target,
                 progress: 0.0,
-                total_time: hackable.hack_time,
+                total_time: hack_time,
                 device_type: hackable.device_type.clone(),
+                hops: event.hops,
             });
+
+            // Secured networks start tracing the hacker straight away
+            if let Some(trace) = start_trace(&hackable, agent_upgrades.get(event.agent).ok()) {
+                info!("Network {} is tracing the intrusion ({:.1}s)", trace.network_id, trace.duration);
+                progress.insert(trace);
+            }
             
             // Spawn visual indicator
             spawn_hack_status_indicator(&mut commands, transform.translation.truncate(), event.target);
@@ -66,8 +88,9 @@ pub fn enhanced_hacking_system(
                 volume: 0.3,
             });
             
-            info!("Started hacking {:?} (Security: {}, Time: {:.1}s)", 
-                  hackable.device_type, hackable.security_level, hackable.hack_time);
+            info!("Started hacking {:?} (Security: {}, Time: {:.1}s{})", 
+                  hackable.device_type, hackable.security_level, hack_time,
+                  if hardened { ", hardened network" } else { "" });
         }
     }
     
@@ -80,12 +103,17 @@ pub fn enhanced_hacking_system(
         if progress.progress >= 1.0 {
             // Hack completed!
             if let Ok((_, mut hackable, mut device_state, _)) = hackable_query.get_mut(progress.target) {
+                let can_overload = agent_inventory.get(progress.hacker)
+                    .is_ok_and(|inventory| inventory.equipped_tools.iter().any(|tool| matches!(tool, ToolType::AdvancedHacker)));
+                let effects = hackable.landed_effects(can_overload);
+
                 hackable.is_hacked = true;
-                device_state.operational = false;
-                device_state.hack_timer = hackable.disabled_duration;
+                // A device that's been taken over keeps running, just not for its owners
+                device_state.operational = effects.iter().any(|effect| matches!(effect, HackEffect::TakeControl));
+                device_state.hack_timer = hackable.disabled_duration.unwrap_or_default();
                 
                 // Apply hack effects
-                for effect in &hackable.hack_effects {
+                for effect in &effects {
                     apply_hack_effect(effect, &mut device_state, &hackable.device_type);
                 }
                 
@@ -94,7 +122,7 @@ pub fn enhanced_hacking_system(
                     agent: progress.hacker,
                     target: progress.target,
                     device_type: progress.device_type.clone(),
-                    effects: hackable.hack_effects.clone(),
+                    effects,
                 });
                 
                 // Play completion sound
@@ -221,8 +249,18 @@ pub fn hack_interruption_system(
     progress_query: Query<(Entity, &HackingProgress), Without<MarkedForDespawn>>,
     agent_query: Query<&Transform, With<Agent>>,
     hackable_query: Query<&Transform, (With<Hackable>, Without<MarkedForDespawn>)>,
+    remote_access: Res<RemoteAccess>,
 ) {
     for (progress_entity, progress) in progress_query.iter() {
+        // A remote hack holds as long as its route through the network does
+        if progress.hops > 0 {
+            if !remote_access.routes.contains_key(&progress.target) {
+                commands.entity(progress_entity).insert(MarkedForDespawn);
+                info!("Hack interrupted: Lost the route through the network");
+            }
+            continue;
+        }
+
         // Check if hacker moved away from target
         if let (Ok(agent_transform), Ok(target_transform)) = (
             agent_query.get(progress.hacker),
@@ -279,7 +317,7 @@ fn apply_hack_effect(effect: &HackEffect, device_state: &mut DeviceState, device
                 _ => device_state.original_function.clone(),
             };
         },
-        HackEffect::PowerCut => {
+        HackEffect::PowerCut | HackEffect::Overload => {
             device_state.powered = false;
         },
         _ => {}
@@ -1,10 +1,10 @@
 // src/systems/financial_hacking.rs - ATMs and Billboards
 use bevy::prelude::*;
 use crate::core::*;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use crate::systems::interaction_prompts::{InteractionPrompt, InteractionSprites, InteractionType};
 use crate::systems::minimap::{MinimapSettings};
-use crate::systems::spawners::{spawn_atm, spawn_billboard};
 
 // === COMPONENTS ===
 #[derive(Component)]
@@ -60,12 +60,13 @@ pub fn atm_hacking_system(
     mut atm_query: Query<&mut ATM>,
     banking_network: Res<BankingNetwork>,
     mut audio_events: EventWriter<AudioEvent>,
+    mut rng: ResMut<GameRng>,
 ) {
     for event in hack_completed.read() {
         // Check if this was an ATM hack
         if let Ok(mut atm) = atm_query.get_mut(event.target) {
             if let Ok(mut inventory) = agent_query.get_mut(event.agent) {
-                let withdrawal_amount = calculate_atm_withdrawal(&atm, &inventory, &banking_network);
+                let withdrawal_amount = calculate_atm_withdrawal(&atm, &inventory, &banking_network, rng.stream(RngStream::Hacking));
 
                 if withdrawal_amount > 0 {
                     inventory.add_currency(withdrawal_amount);
@@ -131,15 +132,17 @@ pub fn terminal_account_data_system(
     mut agent_query: Query<&mut Inventory, With<Agent>>,
     terminal_query: Query<&Terminal>,
     mut banking_network: ResMut<BankingNetwork>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream(RngStream::Hacking);
     for event in hack_completed.read() {
         // Check if this was a terminal hack that might have account data
         if let Ok(terminal) = terminal_query.get(event.target) {
             if matches!(terminal.terminal_type, TerminalType::Intel) {
                 if let Ok(mut inventory) = agent_query.get_mut(event.agent) {
                     // 30% chance to find account data in intel terminals
-                    if rand::random::<f32>() < 0.3 {
-                        let account_data = generate_stolen_account_data();
+                    if rng.gen::<f32>() < 0.3 {
+                        let account_data = generate_stolen_account_data(rng);
                         banking_network.stolen_accounts.push(account_data.clone());
 
                         // Add to inventory as intel
@@ -269,7 +272,7 @@ pub fn update_financial_minimap(
 }
 
 // === HELPER FUNCTIONS ===
-fn calculate_atm_withdrawal(atm: &ATM, inventory: &Inventory, banking_network: &BankingNetwork) -> u32 {
+fn calculate_atm_withdrawal(atm: &ATM, inventory: &Inventory, banking_network: &BankingNetwork, rng: &mut impl Rng) -> u32 {
     if atm.requires_account_data {
         // Find valid account for this bank
         let valid_account = banking_network.stolen_accounts.iter()
@@ -278,10 +281,10 @@ fn calculate_atm_withdrawal(atm: &ATM, inventory: &Inventory, banking_network: &
         if let Some(account) = valid_account {
             let max_withdrawal = atm.max_withdrawal.min(atm.current_balance).min(account.balance);
             // Reduce by 10-20% for "transaction fees" and realism
-            (max_withdrawal as f32 * (0.8 + rand::random::<f32>() * 0.2)) as u32
+            (max_withdrawal as f32 * (0.8 + rng.gen::<f32>() * 0.2)) as u32
         } else {
             // No valid account data - can only get small amount
-            (500 + rand::random::<u32>() % 1000).min(atm.current_balance)
+            rng.gen_range(500..1500).min(atm.current_balance)
         }
     } else {
         // Unsecured ATM - full access
@@ -296,14 +299,14 @@ fn has_valid_account_data(inventory: &Inventory, banking_network: &BankingNetwor
     inventory.intel_documents.iter().any(|doc| doc.contains(bank_id))
 }
 
-fn generate_stolen_account_data() -> StolenAccountData {
+fn generate_stolen_account_data(rng: &mut impl Rng) -> StolenAccountData {
     let banks = ["MegaBank", "CyberCredit", "DataVault Financial", "NeoTokyo Savings"];
-    let bank_id = banks[rand::random::<usize>() % banks.len()].to_string();
+    let bank_id = banks[rng.gen_range(0..banks.len())].to_string();
 
     StolenAccountData {
-        account_number: format!("{:08}", rand::random::<u32>() % 100000000),
+        account_number: format!("{:08}", rng.gen_range(0..100000000u32)),
         bank_id,
-        balance: 1000 + rand::random::<u32>() % 50000,
+        balance: rng.gen_range(1000..51000),
         source: "Corporate Terminal".to_string(),
     }
 }
@@ -374,43 +377,6 @@ fn check_hack_tool_available(inventory: &Inventory, hackable: &Hackable) -> bool
     }
 }
 
-// === SETUP HELPER ===
-pub fn setup_financial_district(
-    commands: &mut Commands,
-    mut power_grid: ResMut<PowerGrid>,
-    mut banking_network: ResMut<BankingNetwork>,
-    center: Vec2,
-) {
-    let network_id = "financial_district".to_string();
-
-    // Set up banking network
-    banking_network.banks.push(Bank {
-        id: "MegaBank".to_string(),
-        name: "MegaBank Corp".to_string(),
-        total_funds: 1000000,
-        security_level: 4,
-    });
-
-    banking_network.banks.push(Bank {
-        id: "CyberCredit".to_string(),
-        name: "CyberCredit Union".to_string(),
-        total_funds: 500000,
-        security_level: 3,
-    });
-
-    // Spawn ATMs
-    let mut power_grid_option = Some(power_grid);
-    spawn_atm(commands, center + Vec2::new(-50.0, 100.0),
-             "MegaBank".to_string(), Some(network_id.clone()), &mut power_grid_option);
-    spawn_atm(commands, center + Vec2::new(150.0, -80.0),
-             "CyberCredit".to_string(), Some(network_id.clone()), &mut power_grid_option);
-
-    // Spawn Billboards
-    spawn_billboard(commands, center + Vec2::new(0.0, 200.0),
-                   Some(network_id.clone()), &mut power_grid_option);
-    spawn_billboard(commands, center + Vec2::new(300.0, 0.0),
-                   Some(network_id), &mut power_grid_option);
-}
 
 
 // ===== MAIN.RS =====
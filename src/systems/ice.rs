@@ -1 +1,871 @@
-// src/systems/ice.rs
+// src/systems/ice.rs - Cyberspace: the mission's networks as a node graph defended by ICE
+//
+// An agent jacks in at a terminal or security system and the physical mission holds still
+// while the player hops between nodes, spending energy to break ICE before the trace completes.
+// Everything done in there reaches the mission as one outcome on the first step after jacking out.
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::HashMap;
+use crate::core::*;
+use crate::systems::hacking_financial::{BankingNetwork, ATM};
+use crate::systems::interaction::grant_terminal_reward;
+use crate::systems::world_scan::NetworkNode;
+
+const JACK_IN_RANGE: f32 = 40.0;
+
+const BASE_ENERGY: f32 = 60.0;
+const ENERGY_PER_LEVEL: f32 = 15.0;
+const HOP_SECS: f32 = 0.75;
+const HOP_ENERGY: f32 = 2.0;
+const ICE_ENERGY_PER_STRENGTH: f32 = 8.0;
+const ICE_SECS_PER_STRENGTH: f32 = 1.5;
+
+/// Trace gained per second just for being connected
+const TRACE_RATE: f32 = 1.5;
+/// Extra trace per second, per point of strength, while a tracer is being broken
+const TRACER_RATE_PER_STRENGTH: f32 = 2.0;
+const TRACE_LIMIT: f32 = 100.0;
+const BLACK_ICE_DAMAGE_PER_STRENGTH: f32 = 6.0;
+/// Share of a bank's funds skimmed when there's no stolen account data for it
+const BANK_SKIM_RATE: f32 = 0.002;
+const LOG_LINES: usize = 8;
+
+// === ICE ===
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IceKind {
+    /// Costs energy and time to break, nothing more
+    Barrier,
+    /// Speeds up the trace while it's being broken
+    Tracer,
+    /// Burns whoever breaks it; the damage lands on the agent when they jack out
+    Black,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ice {
+    pub kind: IceKind,
+    pub strength: u8,
+}
+
+impl Ice {
+    /// Security 1 is open, 2-3 walled off, 4 traced and 5 lethal
+    pub fn for_security(security_level: u8) -> Option<Self> {
+        let kind = match security_level {
+            0..=1 => return None,
+            2..=3 => IceKind::Barrier,
+            4 => IceKind::Tracer,
+            _ => IceKind::Black,
+        };
+        Some(Self { kind, strength: security_level })
+    }
+
+    pub fn energy_cost(&self) -> f32 {
+        self.strength as f32 * ICE_ENERGY_PER_STRENGTH
+    }
+
+    pub fn break_secs(&self) -> f32 {
+        self.strength as f32 * ICE_SECS_PER_STRENGTH
+    }
+}
+
+// === NETWORK GRAPH ===
+#[derive(Debug, Clone)]
+pub enum CyberNodeKind {
+    /// A power network's backbone; its stations are what gets switched off
+    Hub { network_id: String, stations: Vec<Entity> },
+    Device { entity: Entity, device_type: DeviceType },
+    Data { terminal: Entity },
+    Bank { bank_id: String, amount: u32 },
+    Relay,
+}
+
+impl CyberNodeKind {
+    /// What can be done once the node is breached
+    pub fn payload(&self) -> Option<&'static str> {
+        match self {
+            Self::Hub { stations, .. } if !stations.is_empty() => Some("Cut power"),
+            Self::Hub { .. } | Self::Relay => None,
+            Self::Device { device_type, .. } => Some(match device_type {
+                DeviceType::Door => "Unlock",
+                DeviceType::Camera | DeviceType::SecurityCamera => "Loop feed",
+                DeviceType::Turret | DeviceType::Drone => "Shut down",
+                DeviceType::PowerStation => "Cut power",
+                DeviceType::SecuritySystem | DeviceType::AlarmPanel => "Silence",
+                _ => "Disable",
+            }),
+            Self::Data { .. } => Some("Download"),
+            Self::Bank { .. } => Some("Drain accounts"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CyberNode {
+    pub kind: CyberNodeKind,
+    pub label: String,
+    /// Where the node's hardware sits in the mission; the map is laid out from it
+    pub position: Vec2,
+    pub ice: Option<Ice>,
+    pub links: Vec<usize>,
+    /// ICE broken (or never there), so the node can be entered
+    pub breached: bool,
+    /// Payload already used, here or out in the mission
+    pub exploited: bool,
+}
+
+impl CyberNode {
+    fn new(kind: CyberNodeKind, label: String, position: Vec2, ice: Option<Ice>) -> Self {
+        Self { kind, label, position, ice, links: Vec::new(), breached: ice.is_none(), exploited: false }
+    }
+}
+
+fn link(nodes: &mut [CyberNode], a: usize, b: usize) {
+    if a != b && !nodes[a].links.contains(&b) {
+        nodes[a].links.push(b);
+        nodes[b].links.push(a);
+    }
+}
+
+/// Where the agent plugged in
+#[derive(Debug, Clone, Copy)]
+enum JackPoint {
+    Terminal(Entity),
+    Device(Entity),
+}
+
+/// Power networks form a chained backbone with their devices hanging off each hub; unnetworked
+/// devices, terminals and loose relays are local to the jack point, and banks are reached through
+/// their ATMs. None if the jack point didn't make it into the graph.
+fn build_network_graph(
+    jack_point: JackPoint,
+    terminals: &Query<(Entity, &Transform, &Terminal)>,
+    hackables: &Query<(Entity, &Transform, &Hackable, &DeviceState)>,
+    network_nodes: &Query<(Entity, &Transform, &NetworkNode)>,
+    atms: &Query<&ATM>,
+    power_grid: &PowerGrid,
+    banking: Option<&BankingNetwork>,
+) -> Option<(Vec<CyberNode>, usize)> {
+    let mut nodes = Vec::new();
+    let mut hubs: HashMap<String, usize> = HashMap::new();
+    let mut devices: HashMap<Entity, usize> = HashMap::new();
+    let mut local = Vec::new();
+
+    let mut network_ids: Vec<&String> = power_grid.networks.keys().collect();
+    network_ids.sort();
+    for network_id in network_ids {
+        let network = &power_grid.networks[network_id];
+        let members: Vec<(Vec2, u8)> = network.connected_devices.iter()
+            .filter_map(|&entity| hackables.get(entity).ok())
+            .map(|(_, transform, hackable, _)| (transform.translation.truncate(), hackable.security_level))
+            .collect();
+        let declared = network_nodes.iter().find(|(_, _, node)| &node.node_id == network_id);
+
+        let position = match declared {
+            Some((_, transform, _)) => transform.translation.truncate(),
+            None if !members.is_empty() => members.iter().map(|(pos, _)| *pos).sum::<Vec2>() / members.len() as f32,
+            None => Vec2::ZERO,
+        };
+        let security = declared.map(|(_, _, node)| node.security_level)
+            .or_else(|| members.iter().map(|(_, security)| *security).max())
+            .unwrap_or(1);
+
+        let mut stations: Vec<Entity> = network.power_sources.iter().copied().collect();
+        stations.sort();
+
+        hubs.insert(network_id.clone(), nodes.len());
+        nodes.push(CyberNode::new(
+            CyberNodeKind::Hub { network_id: network_id.clone(), stations },
+            format!("Grid {}", network_id),
+            position,
+            Ice::for_security(security),
+        ));
+    }
+
+    let mut hackables_sorted: Vec<_> = hackables.iter().collect();
+    hackables_sorted.sort_by_key(|(entity, ..)| *entity);
+    for (entity, transform, hackable, _) in hackables_sorted {
+        let index = nodes.len();
+        let mut node = CyberNode::new(
+            CyberNodeKind::Device { entity, device_type: hackable.device_type },
+            format!("{:?}", hackable.device_type),
+            transform.translation.truncate(),
+            Ice::for_security(hackable.security_level),
+        );
+        if hackable.is_hacked {
+            node.breached = true;
+            node.exploited = true;
+        }
+        nodes.push(node);
+        devices.insert(entity, index);
+
+        match hackable.network_id.as_ref().and_then(|id| hubs.get(id)) {
+            Some(&hub) => link(&mut nodes, index, hub),
+            None => local.push(index),
+        }
+    }
+
+    let mut terminals_sorted: Vec<_> = terminals.iter()
+        .filter(|(entity, ..)| !devices.contains_key(entity))
+        .collect();
+    terminals_sorted.sort_by_key(|(entity, ..)| *entity);
+    for (entity, transform, terminal) in terminals_sorted {
+        // Gear can't come down a wire; only the data terminals show up
+        let security = match terminal.terminal_type {
+            TerminalType::Objective => 3,
+            TerminalType::Intel => 2,
+            TerminalType::Equipment => continue,
+        };
+        let index = nodes.len();
+        let mut node = CyberNode::new(
+            CyberNodeKind::Data { terminal: entity },
+            format!("{:?} data", terminal.terminal_type),
+            transform.translation.truncate(),
+            Ice::for_security(security),
+        );
+        node.exploited = terminal.accessed;
+        nodes.push(node);
+        devices.insert(entity, index);
+        local.push(index);
+    }
+
+    // Declared relays; the ones named after a power network already are its hub
+    let mut relays: Vec<_> = network_nodes.iter()
+        .filter(|(_, _, node)| node.operational && !hubs.contains_key(&node.node_id))
+        .collect();
+    relays.sort_by(|(_, _, a), (_, _, b)| a.node_id.cmp(&b.node_id));
+    let mut relay_index: HashMap<&str, usize> = HashMap::new();
+    for (_, transform, node) in &relays {
+        relay_index.insert(node.node_id.as_str(), nodes.len());
+        nodes.push(CyberNode::new(
+            CyberNodeKind::Relay,
+            format!("Relay {}", node.node_id),
+            transform.translation.truncate(),
+            Ice::for_security(node.security_level),
+        ));
+    }
+    for (_, _, node) in &relays {
+        let index = relay_index[node.node_id.as_str()];
+        for connection in &node.connections {
+            if let Some(&other) = hubs.get(connection).or_else(|| relay_index.get(connection.as_str())) {
+                link(&mut nodes, index, other);
+            }
+        }
+        if nodes[index].links.is_empty() {
+            local.push(index);
+        }
+    }
+
+    let mut hub_order: Vec<usize> = hubs.values().copied().collect();
+    hub_order.sort();
+    for pair in hub_order.windows(2) {
+        link(&mut nodes, pair[0], pair[1]);
+    }
+
+    let entry = match jack_point {
+        JackPoint::Terminal(entity) | JackPoint::Device(entity) => *devices.get(&entity)?,
+    };
+    nodes[entry].breached = true;
+
+    if !nodes[entry].links.iter().any(|&other| matches!(nodes[other].kind, CyberNodeKind::Hub { .. })) {
+        if let Some(&first_hub) = hub_order.first() {
+            link(&mut nodes, entry, first_hub);
+        }
+    }
+    for index in local {
+        link(&mut nodes, entry, index);
+    }
+
+    if let Some(banking) = banking {
+        let bounds = nodes.iter().fold((f32::MIN, f32::MAX, f32::MIN), |(max_x, min_y, max_y), node| {
+            (max_x.max(node.position.x), min_y.min(node.position.y), max_y.max(node.position.y))
+        });
+        let mut banks: Vec<_> = banking.banks.iter().collect();
+        banks.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for (i, bank) in banks.into_iter().enumerate() {
+            let branches: Vec<usize> = devices.iter()
+                .filter(|(&entity, _)| atms.get(entity).is_ok_and(|atm| atm.bank_id == bank.id))
+                .map(|(_, &index)| index)
+                .collect();
+            if branches.is_empty() {
+                continue;
+            }
+
+            let stolen: u32 = banking.stolen_accounts.iter()
+                .filter(|account| account.bank_id == bank.id)
+                .map(|account| account.balance)
+                .sum();
+            let amount = if stolen > 0 { stolen } else { (bank.total_funds as f32 * BANK_SKIM_RATE) as u32 };
+
+            let index = nodes.len();
+            nodes.push(CyberNode::new(
+                CyberNodeKind::Bank { bank_id: bank.id.clone(), amount },
+                bank.name.clone(),
+                Vec2::new(bounds.0 + 200.0, bounds.1 + (bounds.2 - bounds.1) * (0.2 + 0.3 * i as f32)),
+                Ice::for_security(bank.security_level),
+            ));
+            for branch in branches {
+                link(&mut nodes, index, branch);
+            }
+        }
+    }
+
+    Some((nodes, entry))
+}
+
+// === SESSION ===
+/// What a session changed, applied to the mission on the first step after jacking out
+#[derive(Debug, Clone)]
+pub enum CyberEffect {
+    /// Unlocked door, looped camera, downed turret or power station
+    Disable { device: Entity },
+    Download { terminal: Entity },
+    Drain { bank_id: String, amount: u32 },
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct CyberspaceOutcomeEvent {
+    pub agent: Entity,
+    pub effects: Vec<CyberEffect>,
+    pub feedback_damage: f32,
+    pub traced: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CyberTask {
+    Hop { to: usize, remaining: f32 },
+    Break { node: usize, remaining: f32 },
+}
+
+/// Live while jacked in, and until the mission has resumed so re-entering it isn't a fresh start
+#[derive(Resource)]
+pub struct CyberspaceSession {
+    pub agent: Entity,
+    pub nodes: Vec<CyberNode>,
+    pub current: usize,
+    pub selected: Option<usize>,
+    pub energy: f32,
+    pub max_energy: f32,
+    pub trace: f32,
+    task: Option<CyberTask>,
+    effects: Vec<CyberEffect>,
+    feedback_damage: f32,
+    traced: bool,
+    connected: bool,
+    log: Vec<String>,
+}
+
+impl CyberspaceSession {
+    fn new(agent: Entity, nodes: Vec<CyberNode>, entry: usize, energy: f32) -> Self {
+        Self {
+            agent,
+            nodes,
+            current: entry,
+            selected: None,
+            energy,
+            max_energy: energy,
+            trace: 0.0,
+            task: None,
+            effects: Vec::new(),
+            feedback_damage: 0.0,
+            traced: false,
+            connected: true,
+            log: Vec::new(),
+        }
+    }
+
+    fn note(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    fn is_adjacent(&self, node: usize) -> bool {
+        self.nodes[self.current].links.contains(&node)
+    }
+
+    pub fn can_break(&self, node: usize) -> bool {
+        self.task.is_none() && self.is_adjacent(node) && !self.nodes[node].breached
+            && self.nodes[node].ice.is_some_and(|ice| self.energy >= ice.energy_cost())
+    }
+
+    pub fn can_hop(&self, node: usize) -> bool {
+        self.task.is_none() && self.is_adjacent(node) && self.nodes[node].breached && self.energy >= HOP_ENERGY
+    }
+
+    pub fn can_exploit(&self, node: usize) -> bool {
+        self.task.is_none() && node == self.current && !self.nodes[node].exploited
+            && self.nodes[node].kind.payload().is_some()
+    }
+
+    fn start_break(&mut self, node: usize) {
+        if !self.can_break(node) {
+            return;
+        }
+        let Some(ice) = self.nodes[node].ice else { return; };
+        self.energy -= ice.energy_cost();
+        self.task = Some(CyberTask::Break { node, remaining: ice.break_secs() });
+        let line = format!("Breaking {:?} ICE on {}", ice.kind, self.nodes[node].label);
+        self.note(line);
+    }
+
+    fn start_hop(&mut self, to: usize) {
+        if !self.can_hop(to) {
+            return;
+        }
+        self.energy -= HOP_ENERGY;
+        self.task = Some(CyberTask::Hop { to, remaining: HOP_SECS });
+    }
+
+    fn exploit(&mut self, node: usize) {
+        if !self.can_exploit(node) {
+            return;
+        }
+        match self.nodes[node].kind.clone() {
+            CyberNodeKind::Hub { stations, .. } => {
+                self.effects.extend(stations.into_iter().map(|device| CyberEffect::Disable { device }));
+            }
+            CyberNodeKind::Device { entity, .. } => self.effects.push(CyberEffect::Disable { device: entity }),
+            CyberNodeKind::Data { terminal } => self.effects.push(CyberEffect::Download { terminal }),
+            CyberNodeKind::Bank { bank_id, amount } => self.effects.push(CyberEffect::Drain { bank_id, amount }),
+            CyberNodeKind::Relay => return,
+        }
+        self.nodes[node].exploited = true;
+        let line = format!("{}: {}", self.nodes[node].label, self.nodes[node].kind.payload().unwrap_or_default());
+        self.note(line);
+    }
+
+    fn jack_out(&mut self, next_state: &mut NextState<GameState>) {
+        if self.connected {
+            self.connected = false;
+            self.task = None;
+            next_state.set(GameState::Mission);
+        }
+    }
+}
+
+pub fn is_jacked_in(session: Option<Res<CyberspaceSession>>) -> bool {
+    session.is_some()
+}
+
+fn has_hacker_tool(inventory: &Inventory) -> bool {
+    inventory.equipped_tools.iter().any(|tool| matches!(tool, ToolType::Hacker))
+}
+
+// === PLUGIN ===
+pub struct CyberspacePlugin;
+
+impl Plugin for CyberspacePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<CyberspaceOutcomeEvent>()
+            .add_systems(FixedUpdate, (
+                jack_in_system,
+                apply_cyberspace_outcome,
+            ).run_if(in_state(GameState::Mission)))
+            .add_systems(Update, (
+                cyberspace_tick_system,
+                cyberspace_ui_system,
+            ).chain().run_if(in_state(GameState::Cyberspace)))
+            .add_systems(OnExit(GameState::Cyberspace), send_cyberspace_outcome)
+            .add_systems(Update, end_cyberspace_session
+                .run_if(in_state(GameState::Mission))
+                .run_if(is_jacked_in));
+    }
+}
+
+// === SYSTEMS ===
+pub fn jack_in_system(
+    mut commands: Commands,
+    mut action_events: EventReader<ActionEvent>,
+    agents: Query<(&Transform, &Inventory, &Agent), Without<Dead>>,
+    terminals: Query<(Entity, &Transform, &Terminal)>,
+    hackables: Query<(Entity, &Transform, &Hackable, &DeviceState)>,
+    network_nodes: Query<(Entity, &Transform, &NetworkNode)>,
+    atms: Query<&ATM>,
+    power_grid: Res<PowerGrid>,
+    banking: Option<Res<BankingNetwork>>,
+    session: Option<Res<CyberspaceSession>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if session.is_some() {
+        return;
+    }
+
+    for event in action_events.read() {
+        if !matches!(event.action, Action::JackIn) {
+            continue;
+        }
+        let Ok((agent_transform, inventory, agent)) = agents.get(event.entity) else { continue; };
+        if !has_hacker_tool(inventory) {
+            info!("Jacking in needs a hacker tool equipped");
+            continue;
+        }
+
+        let agent_pos = agent_transform.translation.truncate();
+        // Equipment lockers aren't on any network
+        let terminal_points = terminals.iter()
+            .filter(|(_, _, terminal)| !matches!(terminal.terminal_type, TerminalType::Equipment))
+            .map(|(entity, transform, _)| (JackPoint::Terminal(entity), transform));
+        let device_points = hackables.iter()
+            .filter(|(_, _, hackable, state)| {
+                matches!(hackable.device_type, DeviceType::Terminal | DeviceType::SecuritySystem) && state.powered
+            })
+            .map(|(entity, transform, _, _)| (JackPoint::Device(entity), transform));
+
+        let jack_point = terminal_points.chain(device_points)
+            .map(|(point, transform)| (point, agent_pos.distance(transform.translation.truncate())))
+            .filter(|(_, distance)| *distance <= JACK_IN_RANGE)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(point, _)| point);
+        let Some(jack_point) = jack_point else {
+            info!("Nothing to jack into nearby");
+            continue;
+        };
+
+        let Some((nodes, entry)) = build_network_graph(
+            jack_point, &terminals, &hackables, &network_nodes, &atms, &power_grid, banking.as_deref(),
+        ) else {
+            info!("Nothing answers at that jack point");
+            continue;
+        };
+        info!("Agent jacked in at {} ({} nodes)", nodes[entry].label, nodes.len());
+
+        let energy = BASE_ENERGY + agent.level as f32 * ENERGY_PER_LEVEL;
+        commands.insert_resource(CyberspaceSession::new(event.entity, nodes, entry, energy));
+        next_state.set(GameState::Cyberspace);
+        return;
+    }
+}
+
+pub fn cyberspace_tick_system(
+    mut session: ResMut<CyberspaceSession>,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !session.connected {
+        return;
+    }
+    let delta = time.delta_secs();
+    let mut trace_rate = TRACE_RATE;
+
+    match session.task {
+        Some(CyberTask::Hop { to, remaining }) => {
+            if remaining <= delta {
+                session.current = to;
+                session.task = None;
+            } else {
+                session.task = Some(CyberTask::Hop { to, remaining: remaining - delta });
+            }
+        }
+        Some(CyberTask::Break { node, remaining }) => {
+            let ice = session.nodes[node].ice;
+            if let Some(Ice { kind: IceKind::Tracer, strength }) = ice {
+                trace_rate += strength as f32 * TRACER_RATE_PER_STRENGTH;
+            }
+
+            if remaining <= delta {
+                session.nodes[node].breached = true;
+                session.task = None;
+                if let Some(Ice { kind: IceKind::Black, strength }) = ice {
+                    session.feedback_damage += strength as f32 * BLACK_ICE_DAMAGE_PER_STRENGTH;
+                    let line = format!("Black ICE feedback from {}", session.nodes[node].label);
+                    session.note(line);
+                }
+                let line = format!("{} breached", session.nodes[node].label);
+                session.note(line);
+            } else {
+                session.task = Some(CyberTask::Break { node, remaining: remaining - delta });
+            }
+        }
+        None => {}
+    }
+
+    session.trace = (session.trace + trace_rate * delta).min(TRACE_LIMIT);
+    if session.trace >= TRACE_LIMIT {
+        session.traced = true;
+        session.note("Traced - connection dropped".to_string());
+        warn!("Cyberspace trace completed; security knows where the agent is");
+        session.jack_out(&mut next_state);
+    }
+}
+
+/// Click a linked node to select it; breaking, hopping and payloads are on the side panel
+pub fn cyberspace_ui_system(
+    mut contexts: EguiContexts,
+    mut session: ResMut<CyberspaceSession>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        session.jack_out(&mut next_state);
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else { return; };
+
+    egui::SidePanel::right("cyberspace_status")
+        .resizable(false)
+        .min_width(260.0)
+        .show(ctx, |ui| {
+            ui.heading("Cyberspace");
+            ui.add(egui::ProgressBar::new(session.energy / session.max_energy)
+                .fill(egui::Color32::from_rgb(0, 200, 255))
+                .text(format!("Energy {:.0}/{:.0}", session.energy, session.max_energy)));
+            ui.add(egui::ProgressBar::new(session.trace / TRACE_LIMIT)
+                .fill(egui::Color32::from_rgb(220, 60, 60))
+                .text(format!("Trace {:.0}%", session.trace / TRACE_LIMIT * 100.0)));
+
+            match session.task {
+                Some(CyberTask::Hop { to, .. }) => { ui.weak(format!("Moving to {}...", session.nodes[to].label)); }
+                Some(CyberTask::Break { node, remaining }) => {
+                    ui.weak(format!("Breaking {} ({:.1}s)", session.nodes[node].label, remaining));
+                }
+                None => { ui.weak(format!("At {}", session.nodes[session.current].label)); }
+            }
+            ui.separator();
+
+            let current = session.current;
+            if let Some(payload) = session.nodes[current].kind.payload() {
+                if ui.add_enabled(session.can_exploit(current), egui::Button::new(payload)).clicked() {
+                    session.exploit(current);
+                }
+            }
+
+            if let Some(selected) = session.selected.filter(|&node| node != current) {
+                let node = &session.nodes[selected];
+                ui.strong(&node.label);
+                match node.ice {
+                    Some(ice) if !node.breached => {
+                        ui.label(format!("{:?} ICE, strength {}", ice.kind, ice.strength));
+                        let cost = ice.energy_cost();
+                        if ui.add_enabled(session.can_break(selected), egui::Button::new(format!("Break ICE ({:.0} energy)", cost))).clicked() {
+                            session.start_break(selected);
+                        }
+                    }
+                    _ => {
+                        ui.label(if node.exploited { "Compromised" } else { "Open" });
+                        if ui.add_enabled(session.can_hop(selected), egui::Button::new("Move here")).clicked() {
+                            session.start_hop(selected);
+                        }
+                    }
+                }
+                if !session.is_adjacent(selected) {
+                    ui.weak("Not linked to your node");
+                }
+            }
+
+            ui.separator();
+            for line in &session.log {
+                ui.weak(line);
+            }
+            ui.separator();
+            if ui.button("Jack out (Esc)").clicked() {
+                session.jack_out(&mut next_state);
+            }
+        });
+
+    egui::CentralPanel::default()
+        .frame(egui::Frame::new().fill(egui::Color32::from_rgb(4, 8, 20)))
+        .show(ctx, |ui| {
+            let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::click());
+            let rect = response.rect.shrink(40.0);
+
+            let (min, max) = session.nodes.iter().fold((Vec2::MAX, Vec2::MIN), |(min, max), node| {
+                (min.min(node.position), max.max(node.position))
+            });
+            let span = (max - min).max(Vec2::ONE);
+            // World y points up, the screen's down
+            let to_screen = |position: Vec2| {
+                let t = (position - min) / span;
+                egui::pos2(rect.left() + t.x * rect.width(), rect.bottom() - t.y * rect.height())
+            };
+
+            for (index, node) in session.nodes.iter().enumerate() {
+                for &other in node.links.iter().filter(|&&other| other > index) {
+                    let lit = index == session.current || other == session.current;
+                    let color = if lit { egui::Color32::from_rgb(0, 160, 200) } else { egui::Color32::from_rgb(30, 50, 80) };
+                    painter.line_segment([to_screen(node.position), to_screen(session.nodes[other].position)], egui::Stroke::new(1.5, color));
+                }
+            }
+
+            let mut clicked = None;
+            for (index, node) in session.nodes.iter().enumerate() {
+                let pos = to_screen(node.position);
+                let color = if index == session.current {
+                    egui::Color32::from_rgb(0, 230, 255)
+                } else if node.exploited {
+                    egui::Color32::from_rgb(60, 200, 90)
+                } else if !node.breached {
+                    match node.ice.map(|ice| ice.kind) {
+                        Some(IceKind::Black) => egui::Color32::from_rgb(160, 40, 200),
+                        Some(IceKind::Tracer) => egui::Color32::from_rgb(230, 140, 40),
+                        _ => egui::Color32::from_rgb(200, 60, 60),
+                    }
+                } else {
+                    egui::Color32::from_rgb(140, 160, 190)
+                };
+                let radius = if matches!(node.kind, CyberNodeKind::Hub { .. } | CyberNodeKind::Bank { .. }) { 11.0 } else { 7.0 };
+
+                painter.circle_filled(pos, radius, color);
+                if session.selected == Some(index) {
+                    painter.circle_stroke(pos, radius + 4.0, egui::Stroke::new(1.5, egui::Color32::WHITE));
+                }
+                painter.text(pos + egui::vec2(0.0, radius + 4.0), egui::Align2::CENTER_TOP, &node.label,
+                    egui::FontId::proportional(11.0), egui::Color32::from_rgb(170, 190, 220));
+
+                if response.clicked() && response.interact_pointer_pos().is_some_and(|click| click.distance(pos) <= radius + 4.0) {
+                    clicked = Some(index);
+                }
+            }
+
+            if clicked.is_some() {
+                session.selected = clicked;
+            }
+        });
+}
+
+fn send_cyberspace_outcome(
+    mut session: ResMut<CyberspaceSession>,
+    mut outcomes: EventWriter<CyberspaceOutcomeEvent>,
+) {
+    session.connected = false;
+    outcomes.write(CyberspaceOutcomeEvent {
+        agent: session.agent,
+        effects: std::mem::take(&mut session.effects),
+        feedback_damage: session.feedback_damage,
+        traced: session.traced,
+    });
+}
+
+fn end_cyberspace_session(mut commands: Commands) {
+    commands.remove_resource::<CyberspaceSession>();
+}
+
+pub fn apply_cyberspace_outcome(
+    mut outcomes: EventReader<CyberspaceOutcomeEvent>,
+    mut devices: Query<(&mut Hackable, &mut DeviceState)>,
+    mut terminals: Query<&mut Terminal>,
+    mut agents: Query<(&Transform, &mut Inventory, &mut Health), With<Agent>>,
+    mut banking: Option<ResMut<BankingNetwork>>,
+    mut mission_data: ResMut<MissionData>,
+    mut hack_completed: EventWriter<HackCompletedEvent>,
+    mut alert_events: EventWriter<AlertEvent>,
+) {
+    for outcome in outcomes.read() {
+        let Ok((agent_transform, mut inventory, mut health)) = agents.get_mut(outcome.agent) else { continue; };
+
+        for effect in &outcome.effects {
+            match effect {
+                CyberEffect::Disable { device } => {
+                    let Ok((mut hackable, mut device_state)) = devices.get_mut(*device) else { continue; };
+                    if hackable.is_hacked {
+                        continue;
+                    }
+                    hackable.is_hacked = true;
+                    device_state.operational = false;
+                    device_state.hack_timer = hackable.disabled_duration.unwrap_or_default();
+
+                    hack_completed.write(HackCompletedEvent {
+                        agent: outcome.agent,
+                        target: *device,
+                        device_type: hackable.device_type,
+                        effects: vec![HackEffect::Disable],
+                    });
+                }
+                CyberEffect::Download { terminal } => {
+                    let Ok(mut terminal) = terminals.get_mut(*terminal) else { continue; };
+                    if terminal.accessed {
+                        continue;
+                    }
+                    terminal.accessed = true;
+                    mission_data.terminals_accessed += 1;
+                    grant_terminal_reward(&terminal.terminal_type, &mut inventory);
+                }
+                CyberEffect::Drain { bank_id, amount } => {
+                    if let Some(banking) = banking.as_mut() {
+                        banking.stolen_accounts.retain(|account| &account.bank_id != bank_id);
+                        if let Some(bank) = banking.banks.iter_mut().find(|bank| &bank.id == bank_id) {
+                            bank.total_funds = bank.total_funds.saturating_sub(*amount);
+                        }
+                    }
+                    inventory.add_currency(*amount);
+                    info!("Drained ${} from {}", amount, bank_id);
+                }
+            }
+        }
+
+        health.0 -= outcome.feedback_damage;
+
+        if outcome.traced {
+            alert_events.write(AlertEvent {
+                alerter: outcome.agent,
+                position: agent_transform.translation.truncate(),
+                alert_level: 3,
+                source: AlertSource::Alarm,
+                alert_type: AlertType::CallForHelp,
+            });
+            if (mission_data.alert_level as u8) < AlertLevel::Orange as u8 {
+                mission_data.alert_level = AlertLevel::Orange;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn jack_in_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(GameState::Mission)
+            .init_resource::<PowerGrid>()
+            .add_event::<ActionEvent>()
+            .add_systems(Update, jack_in_system);
+        app
+    }
+
+    fn spawn_hacker(app: &mut App) -> Entity {
+        let mut inventory = Inventory::default();
+        inventory.add_tool(ToolType::Hacker);
+        app.world_mut().spawn((Agent::default(), inventory, Transform::default())).id()
+    }
+
+    fn spawn_terminal(app: &mut App, terminal_type: TerminalType, x: f32) -> Entity {
+        app.world_mut().spawn((
+            Terminal { terminal_type, range: 30.0, accessed: false },
+            Transform::from_xyz(x, 0.0, 0.0),
+        )).id()
+    }
+
+    fn jack_in(app: &mut App, agent: Entity) {
+        app.world_mut().send_event(ActionEvent { entity: agent, action: Action::JackIn });
+        app.update();
+    }
+
+    #[test]
+    fn equipment_terminal_is_not_a_jack_point() {
+        let mut app = jack_in_app();
+        let agent = spawn_hacker(&mut app);
+        spawn_terminal(&mut app, TerminalType::Equipment, 5.0);
+
+        jack_in(&mut app, agent);
+
+        assert!(app.world().get_resource::<CyberspaceSession>().is_none());
+    }
+
+    #[test]
+    fn jacks_in_at_data_terminal_past_equipment() {
+        let mut app = jack_in_app();
+        let agent = spawn_hacker(&mut app);
+        spawn_terminal(&mut app, TerminalType::Equipment, 5.0);
+        let intel = spawn_terminal(&mut app, TerminalType::Intel, 20.0);
+
+        jack_in(&mut app, agent);
+
+        let session = app.world().resource::<CyberspaceSession>();
+        assert!(matches!(session.nodes[session.current].kind, CyberNodeKind::Data { terminal } if terminal == intel));
+    }
+}
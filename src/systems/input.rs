@@ -100,6 +100,15 @@ pub fn handle_input(
         }
     }
 
+    if keyboard.just_pressed(KeyCode::KeyJ) {
+        if let Some(&agent) = selection.selected.first() {
+            action_events.write(ActionEvent {
+                entity: agent,
+                action: Action::JackIn,
+            });
+        }
+    }
+
     if keyboard.just_pressed(KeyCode::KeyR) {
         if let Some(&agent) = selection.selected.first() {
             action_events.write(ActionEvent {
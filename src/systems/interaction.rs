@@ -132,26 +132,7 @@ fn execute_terminal_interaction(
                 terminal.accessed = true;
                 terminal_accessed = true;
                 mission_data.terminals_accessed += 1;
-                
-                match terminal.terminal_type {
-                    TerminalType::Objective => {
-                        inventory.add_currency(500);
-                        mission_data.objectives_completed += 1;
-                        info!("Objective completed! ({}/{})", 
-                              mission_data.objectives_completed, mission_data.total_objectives);
-                    }
-                    TerminalType::Equipment => {
-                        inventory.add_weapon(WeaponType::Rifle);
-                        inventory.add_tool(ToolType::Hacker);
-                        inventory.add_currency(200);
-                        info!("Equipment acquired!");
-                    }
-                    TerminalType::Intel => {
-                        inventory.add_intel("Corporate research logs...".to_string());
-                        inventory.add_currency(50);
-                        info!("Intel acquired!");
-                    }
-                }
+                grant_terminal_reward(&terminal.terminal_type, &mut inventory);
             }
         }
         
@@ -176,6 +157,27 @@ fn execute_terminal_interaction(
     }
 }
 
+/// What accessing a terminal hands over; also used when its data is pulled from cyberspace
+pub fn grant_terminal_reward(terminal_type: &TerminalType, inventory: &mut Inventory) {
+    match terminal_type {
+        TerminalType::Objective => {
+            inventory.add_currency(500);
+            info!("Objective terminal accessed!");
+        }
+        TerminalType::Equipment => {
+            inventory.add_weapon(WeaponType::Rifle);
+            inventory.add_tool(ToolType::Hacker);
+            inventory.add_currency(200);
+            info!("Equipment acquired!");
+        }
+        TerminalType::Intel => {
+            inventory.add_intel("Corporate research logs...".to_string());
+            inventory.add_currency(50);
+            info!("Intel acquired!");
+        }
+    }
+}
+
 fn execute_hack_interaction(
     hackable_entity: Entity,
     agent_entity: Entity,
@@ -198,6 +200,7 @@ fn execute_hack_interaction(
         agent: agent_entity,
         target: hackable_entity,
         tool_used,
+        hops: 0,
     });
     
     info!("Hack attempt initiated on entity {:?}", hackable_entity.index());
@@ -1,6 +1,7 @@
 // src/systems/interactive_decals.rs - Interactive decal system with oil spills and ignition
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::explosions::*;
 
@@ -195,9 +196,11 @@ pub fn interactive_decal_movement_system(
     mut affected_entities: Query<(Entity, &mut Velocity), (Or<(With<Agent>, With<Enemy>, With<Civilian>)>, Without<TemporarilyStuck>)>,
     settings: Res<InteractiveDecalSettings>,
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
 ) {
     if !settings.enable_movement_effects { return; }
 
+    let rng = rng.stream(RngStream::Environment);
     for collision_event in collision_events.read() {
         if let CollisionEvent::Started(entity1, entity2, _) = collision_event {
             // Check which entity is the decal and which is the unit
@@ -218,7 +221,7 @@ pub fn interactive_decal_movement_system(
                         velocity.linvel *= hindrance.slow_factor;
                         
                         // Chance to get stuck
-                        if rand::random::<f32>() < hindrance.stuck_chance * time.delta_secs() {
+                        if rng.gen::<f32>() < hindrance.stuck_chance * time.delta_secs() {
                             commands.entity(unit_entity).insert(TemporarilyStuck {
                                 duration: 1.0,
                             });
@@ -234,6 +237,7 @@ pub fn interactive_decal_movement_system(
                                 intensity: 10.0,
                                 tick_timer: 0.0,
                                 tick_rate: 0.5,
+                                source: None,
                             });
                         }
                     },
@@ -245,7 +249,7 @@ pub fn interactive_decal_movement_system(
                     InteractiveDecalType::TarPit => {
                         // Tar really slows you down
                         velocity.linvel *= 0.1;
-                        if rand::random::<f32>() < 0.3 {
+                        if rng.gen::<f32>() < 0.3 {
                             commands.entity(unit_entity).insert(TemporarilyStuck {
                                 duration: 2.0,
                             });
@@ -264,8 +268,9 @@ pub fn electrical_hazard_system(
     mut collision_events: EventReader<CollisionEvent>,
     mut commands: Commands,
     electrical_decals: Query<(&InteractiveDecal, &ElectricalHazard)>,
-    mut affected_entities: Query<(Entity, &mut Health), Or<(With<Agent>, With<Enemy>, With<Civilian>)>>,
+    mut affected_entities: Query<(Entity, &mut Health, Option<&Armor>), Or<(With<Agent>, With<Enemy>, With<Civilian>)>>,
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
 ) {
     for collision_event in collision_events.read() {
         if let CollisionEvent::Started(entity1, entity2, _) = collision_event {
@@ -277,14 +282,14 @@ pub fn electrical_hazard_system(
                 continue;
             };
 
-            if let (Ok((_, hazard)), Ok((_, mut health))) = 
+            if let (Ok((_, hazard)), Ok((_, mut health, armor))) = 
                 (electrical_decals.get(decal_entity), affected_entities.get_mut(unit_entity)) {
                 
                 // Apply electrical damage
-                health.0 -= hazard.damage_per_second * time.delta_secs();
+                Armor::apply_damage(&mut health, armor, hazard.damage_per_second * time.delta_secs());
                 
                 // Chance to stun
-                if rand::random::<f32>() < hazard.stun_chance * time.delta_secs() {
+                if rng.stream(RngStream::Environment).gen::<f32>() < hazard.stun_chance * time.delta_secs() {
                     commands.entity(unit_entity).insert(TemporarilyStuck {
                         duration: 0.5,
                     });
@@ -402,6 +407,7 @@ pub fn fire_burn_system(
     explodable_query: Query<(Entity, &Transform), With<Explodable>>,
     time: Res<Time>,
     settings: Res<InteractiveDecalSettings>,
+    mut rng: ResMut<GameRng>,
 ) {
     for (entity, mut fire, mut decal, transform, flammable, mut sprite) in burning_decals.iter_mut() {
         fire.burn_timer -= time.delta_secs();
@@ -424,7 +430,7 @@ pub fn fire_burn_system(
             let pos = transform.translation.truncate();
             
             // Chance to explode when fuel is consumed
-            if rand::random::<f32>() < flammable.explosion_chance {
+            if rng.stream(RngStream::Environment).gen::<f32>() < flammable.explosion_chance {
                 match decal.decal_type {
                     InteractiveDecalType::GasolineSpill => {
                         spawn_explosion(
@@ -0,0 +1,629 @@
+// src/systems/mapgen.rs - Seeded mission maps built from the briefing and city traits
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::core::*;
+use crate::systems::access_control::CardType;
+use crate::systems::explosions::ExplodableType;
+use crate::systems::extraction::DEFAULT_EXTRACTION_RADIUS;
+use crate::systems::scenes::*;
+use crate::systems::tile_properties::TileType;
+use crate::systems::tilemap::{get_texture_from_tile_type, IsometricMap, IsometricSettings};
+
+/// Set to a seed from the log to regenerate that exact map
+pub const MAP_SEED_ENV: &str = "SUBVERSIVE_MAP_SEED";
+
+const BASE_MAP_TILES: i32 = 18;
+const SQUAD_SPAWN_COUNT: usize = 3;
+const CIVILIANS_PER_PRESENCE: usize = 3;
+const BLOCKS_PER_POWER_STATION: usize = 6;
+
+// === LAYOUT ===
+#[derive(Clone)]
+pub struct MapLayout {
+    pub width: u32,
+    pub height: u32,
+    pub tiles: Vec<TileType>,
+}
+
+impl MapLayout {
+    fn new(width: u32, height: u32, fill: TileType) -> Self {
+        Self { width, height, tiles: vec![fill; (width * height) as usize] }
+    }
+
+    fn in_bounds(&self, tile: IVec2) -> bool {
+        tile.x >= 0 && tile.y >= 0 && (tile.x as u32) < self.width && (tile.y as u32) < self.height
+    }
+
+    fn clamp(&self, tile: IVec2) -> IVec2 {
+        tile.clamp(IVec2::ZERO, IVec2::new(self.width as i32 - 1, self.height as i32 - 1))
+    }
+
+    pub fn get(&self, tile: IVec2) -> Option<TileType> {
+        self.in_bounds(tile).then(|| self.tiles[(tile.y as u32 * self.width + tile.x as u32) as usize])
+    }
+
+    fn set(&mut self, tile: IVec2, tile_type: TileType) {
+        if self.in_bounds(tile) {
+            self.tiles[(tile.y as u32 * self.width + tile.x as u32) as usize] = tile_type;
+        }
+    }
+
+    pub fn is_walkable(&self, tile: IVec2) -> bool {
+        matches!(self.get(tile), Some(t) if !matches!(t, TileType::Building | TileType::Wall))
+    }
+}
+
+/// The map generated for the running mission; absent when a hand-made scene was loaded
+#[derive(Resource, Clone)]
+pub struct GeneratedMission {
+    pub seed: u64,
+    pub city_id: String,
+    pub scene: SceneData,
+    pub layout: MapLayout,
+}
+
+impl GeneratedMission {
+    pub fn scene_name(&self) -> String {
+        format!("generated_{}", self.seed)
+    }
+}
+
+// === CITY STYLE ===
+#[derive(Clone, Copy, PartialEq)]
+enum District {
+    Residential,
+    Commercial,
+    Industrial,
+    Park,
+}
+
+impl District {
+    const ALL: [District; 4] = [District::Residential, District::Commercial, District::Industrial, District::Park];
+
+    fn ground(self) -> TileType {
+        match self {
+            District::Residential => TileType::Residential,
+            District::Commercial => TileType::Commercial,
+            District::Industrial => TileType::Industrial,
+            District::Park => TileType::Grass,
+        }
+    }
+}
+
+struct CityStyle {
+    block_range: (i32, i32),
+    district_weights: [u32; 4],
+}
+
+impl CityStyle {
+    fn new(city: &City, terrain: &TerrainType) -> Self {
+        // Leading trait sets the street grain, every trait skews the district mix
+        let mut block_range = match city.traits.first() {
+            Some(CityTrait::FinancialHub | CityTrait::CorporateHeadquarters | CityTrait::TechCenter) => (6, 8),
+            Some(CityTrait::Underground | CityTrait::DrugCartels | CityTrait::BlackMarket) => (3, 4),
+            _ => (4, 6),
+        };
+        let mut district_weights = [3, 3, 1, 1];
+
+        for city_trait in &city.traits {
+            match city_trait {
+                CityTrait::FinancialHub | CityTrait::CorporateHeadquarters | CityTrait::TechCenter => district_weights[1] += 3,
+                CityTrait::HeavyIndustry | CityTrait::MilitaryBase => district_weights[2] += 4,
+                CityTrait::CivilianUnrest | CityTrait::PoliceBrutality | CityTrait::DrugCartels => district_weights[0] += 2,
+                _ => {}
+            }
+        }
+
+        match terrain {
+            TerrainType::Corporate => district_weights[1] += 2,
+            TerrainType::Industrial => district_weights[2] += 2,
+            TerrainType::Underground => block_range = (3, 4),
+            TerrainType::Urban => {}
+        }
+
+        Self { block_range, district_weights }
+    }
+}
+
+// === BLOCKS ===
+struct Block {
+    min: IVec2,
+    max: IVec2,
+    district: District,
+}
+
+impl Block {
+    fn size(&self) -> IVec2 {
+        self.max - self.min + IVec2::ONE
+    }
+
+    fn center(&self) -> IVec2 {
+        (self.min + self.max) / 2
+    }
+
+    fn touches_edge(&self, layout: &MapLayout) -> bool {
+        self.min.x == 0 || self.min.y == 0
+            || self.max.x == layout.width as i32 - 1 || self.max.y == layout.height as i32 - 1
+    }
+
+    /// Sidewalk corners around the block in walking order
+    fn sidewalk_loop(&self) -> [IVec2; 4] {
+        [
+            self.min - IVec2::ONE,
+            IVec2::new(self.max.x + 1, self.min.y - 1),
+            self.max + IVec2::ONE,
+            IVec2::new(self.min.x - 1, self.max.y + 1),
+        ]
+    }
+}
+
+/// Perimeter tiles of a rectangle, clockwise from `min`
+fn ring(min: IVec2, max: IVec2) -> Vec<IVec2> {
+    if min.x > max.x || min.y > max.y {
+        return Vec::new();
+    }
+    if min.x == max.x || min.y == max.y {
+        return (min.y..=max.y).flat_map(|y| (min.x..=max.x).map(move |x| IVec2::new(x, y))).collect();
+    }
+
+    let mut tiles = Vec::new();
+    tiles.extend((min.x..max.x).map(|x| IVec2::new(x, min.y)));
+    tiles.extend((min.y..max.y).map(|y| IVec2::new(max.x, y)));
+    tiles.extend((min.x + 1..=max.x).rev().map(|x| IVec2::new(x, max.y)));
+    tiles.extend((min.y + 1..=max.y).rev().map(|y| IVec2::new(min.x, y)));
+    tiles
+}
+
+fn road_lines(rng: &mut StdRng, extent: i32, (min_block, max_block): (i32, i32)) -> Vec<i32> {
+    // Road plus a sidewalk either side between each pair of blocks
+    let mut lines = vec![1];
+    loop {
+        let next = lines[lines.len() - 1] + rng.gen_range(min_block..=max_block) + 3;
+        if next >= extent - 1 {
+            return lines;
+        }
+        lines.push(next);
+    }
+}
+
+fn block_spans(lines: &[i32], extent: i32) -> Vec<(i32, i32)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for &line in lines {
+        if line - 2 >= start {
+            spans.push((start, line - 2));
+        }
+        start = line + 2;
+    }
+    if start <= extent - 1 {
+        spans.push((start, extent - 1));
+    }
+    spans
+}
+
+fn lay_road(layout: &mut MapLayout, tile: IVec2, across: IVec2) {
+    layout.set(tile, TileType::Road);
+    for side in [tile + across, tile - across] {
+        if layout.get(side) != Some(TileType::Road) {
+            layout.set(side, TileType::Sidewalk);
+        }
+    }
+}
+
+fn fill_block(layout: &mut MapLayout, block: &Block, cover_density: f32, rng: &mut StdRng) {
+    let ground = block.district.ground();
+    for y in block.min.y..=block.max.y {
+        for x in block.min.x..=block.max.x {
+            layout.set(IVec2::new(x, y), ground);
+        }
+    }
+
+    if block.district != District::Park && block.size().min_element() >= 3 {
+        for y in block.min.y + 1..block.max.y {
+            for x in block.min.x + 1..block.max.x {
+                layout.set(IVec2::new(x, y), TileType::Building);
+            }
+        }
+    }
+
+    // Bins, barriers and parked junk along the edge of each lot
+    let cover_chance = (cover_density * 0.25).clamp(0.0, 1.0) as f64;
+    for tile in ring(block.min, block.max) {
+        if rng.gen_bool(cover_chance) {
+            layout.set(tile, TileType::LowCover);
+        }
+    }
+}
+
+struct Compound {
+    gate: IVec2,
+    courtyard: Vec<IVec2>,
+}
+
+fn build_compound(layout: &mut MapLayout, block: &Block, entry: IVec2) -> Compound {
+    let outer = ring(block.min, block.max);
+
+    if block.size().min_element() < 5 {
+        // Too small for a wall; the whole lot is open ground around the building
+        for &tile in &outer {
+            layout.set(tile, TileType::Concrete);
+        }
+        for y in block.min.y + 1..block.max.y {
+            for x in block.min.x + 1..block.max.x {
+                layout.set(IVec2::new(x, y), TileType::Building);
+            }
+        }
+        let gate = outer.iter().copied().min_by_key(|t| t.distance_squared(entry)).unwrap_or(block.min);
+        return Compound { gate, courtyard: outer };
+    }
+
+    for &tile in &outer {
+        layout.set(tile, TileType::Wall);
+    }
+    let gate = outer.iter().copied()
+        .filter(|t| !ring_corner(*t, block.min, block.max))
+        .min_by_key(|t| t.distance_squared(entry))
+        .unwrap_or(outer[0]);
+    layout.set(gate, TileType::Door);
+
+    let courtyard = ring(block.min + IVec2::ONE, block.max - IVec2::ONE);
+    for &tile in &courtyard {
+        layout.set(tile, TileType::Concrete);
+    }
+    for y in block.min.y + 2..block.max.y - 1 {
+        for x in block.min.x + 2..block.max.x - 1 {
+            layout.set(IVec2::new(x, y), TileType::Building);
+        }
+    }
+
+    Compound { gate, courtyard }
+}
+
+fn ring_corner(tile: IVec2, min: IVec2, max: IVec2) -> bool {
+    (tile.x == min.x || tile.x == max.x) && (tile.y == min.y || tile.y == max.y)
+}
+
+// === SEEDING ===
+/// Stable per-city, per-day seed so a reported mission can be rebuilt exactly
+pub fn mission_map_seed(campaign_seed: u64, city_id: &str, current_day: u32) -> u64 {
+    if let Some(seed) = std::env::var(MAP_SEED_ENV).ok().and_then(|value| value.trim().parse().ok()) {
+        return seed;
+    }
+    derive_seed(campaign_seed, &[b"map", city_id.as_bytes(), &current_day.to_le_bytes()])
+}
+
+// === GENERATION ===
+pub fn generate_mission_map(
+    briefing: &MissionBriefing,
+    city: &City,
+    seed: u64,
+    settings: &IsometricSettings,
+) -> GeneratedMission {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let resistance = &briefing.resistance;
+    let environment = &briefing.environment;
+    let style = CityStyle::new(city, &environment.terrain);
+
+    // Bigger garrisons get more streets to patrol
+    let width = (BASE_MAP_TILES + resistance.enemy_count as i32).min(settings.map_width as i32);
+    let height = (BASE_MAP_TILES + resistance.enemy_count as i32 * 2 / 3).min(settings.map_height as i32);
+    let mut layout = MapLayout::new(width as u32, height as u32, TileType::Concrete);
+
+    // Streets
+    let columns = road_lines(&mut rng, width, style.block_range);
+    let rows = road_lines(&mut rng, height, style.block_range);
+    for &x in &columns {
+        for y in 0..height {
+            lay_road(&mut layout, IVec2::new(x, y), IVec2::X);
+        }
+    }
+    for &y in &rows {
+        for x in 0..width {
+            lay_road(&mut layout, IVec2::new(x, y), IVec2::Y);
+        }
+    }
+
+    // Blocks between the streets
+    let district_dist = WeightedIndex::new(style.district_weights).expect("district weights are non-zero");
+    let mut blocks = Vec::new();
+    for &(x0, x1) in &block_spans(&columns, width) {
+        for &(y0, y1) in &block_spans(&rows, height) {
+            blocks.push(Block {
+                min: IVec2::new(x0, y0),
+                max: IVec2::new(x1, y1),
+                district: District::ALL[district_dist.sample(&mut rng)],
+            });
+        }
+    }
+
+    // Squad comes in from the west edge, the target sits as far away as the grid allows
+    let entry = IVec2::new(columns[0], rows[rng.gen_range(0..rows.len())]);
+    let compound_idx = (0..blocks.len())
+        .filter(|&i| blocks[i].size().min_element() >= 5)
+        .max_by_key(|&i| blocks[i].center().distance_squared(entry))
+        .or_else(|| (0..blocks.len()).max_by_key(|&i| blocks[i].center().distance_squared(entry)))
+        .unwrap_or(0);
+
+    for (i, block) in blocks.iter().enumerate() {
+        if i != compound_idx {
+            fill_block(&mut layout, block, environment.cover_density, &mut rng);
+        }
+    }
+    let compound = build_compound(&mut layout, &blocks[compound_idx], entry);
+
+    let world = |tile: IVec2| settings.tile_to_world(tile).to_array();
+    let mut scene = SceneData::default();
+
+    // === AGENTS ===
+    scene.agents = [entry, entry + IVec2::Y, entry - IVec2::Y].iter()
+        .take(SQUAD_SPAWN_COUNT)
+        .map(|&tile| AgentSpawn { position: world(layout.clamp(tile)), level: 1 })
+        .collect();
+
+    // === EXTRACTION ===
+    // Back out the way they came, or push through to the east edge
+    let far_exit = IVec2::new(width - 1, rows[rng.gen_range(0..rows.len())]);
+    scene.extraction_zones = [entry, far_exit].iter()
+        .map(|&tile| ExtractionZoneSpawn { position: world(layout.clamp(tile)), radius: DEFAULT_EXTRACTION_RADIUS })
+        .collect();
+
+    // === TERMINALS ===
+    let mut courtyard = compound.courtyard.clone();
+    courtyard.shuffle(&mut rng);
+    let objective_terminals = briefing.objectives.iter()
+        .filter(|o| matches!(o.objective_type, ObjectiveType::Hack | ObjectiveType::Extract | ObjectiveType::Infiltrate))
+        .count()
+        .max(1);
+    for &tile in courtyard.iter().take(objective_terminals) {
+        scene.terminals.push(TerminalSpawn { position: world(tile), terminal_type: "objective".to_string() });
+    }
+
+    let others: Vec<usize> = (0..blocks.len()).filter(|&i| i != compound_idx).collect();
+    if let Some(&nearest) = others.iter().min_by_key(|&&i| blocks[i].center().distance_squared(entry)) {
+        let block = &blocks[nearest];
+        if let Some(tile) = ring(block.min, block.max).into_iter().min_by_key(|t| t.distance_squared(entry)) {
+            scene.terminals.push(TerminalSpawn { position: world(tile), terminal_type: "equipment".to_string() });
+        }
+    }
+    let intel_count = 1 + (resistance.security_level >= 3) as usize;
+    for &i in others.choose_multiple(&mut rng, intel_count) {
+        let block = &blocks[i];
+        if let Some(&tile) = ring(block.min, block.max).choose(&mut rng) {
+            scene.terminals.push(TerminalSpawn { position: world(tile), terminal_type: "intel".to_string() });
+        }
+    }
+
+    // === ENEMIES ===
+    let total = resistance.enemy_count as usize;
+    let patrol_count = ((total as f32 * resistance.patrol_density).round() as usize).max(1).min(total);
+    let guard_count = total - patrol_count;
+
+    for g in 0..guard_count {
+        let post = if g == 0 { compound.gate } else { courtyard[g % courtyard.len()] };
+        let partner = courtyard[(g + courtyard.len() / 2) % courtyard.len()];
+        scene.enemies.push(EnemySpawn {
+            position: world(post),
+            patrol_points: vec![world(post), world(partner)],
+            archetype: None,
+        });
+    }
+
+    // Patrols favour the streets around the target as security tightens
+    let mut patrol_blocks: Vec<usize> = others.iter().copied()
+        .filter(|&i| !blocks[i].touches_edge(&layout))
+        .collect();
+    if patrol_blocks.is_empty() {
+        patrol_blocks = if others.is_empty() { vec![compound_idx] } else { others.clone() };
+    }
+    let compound_center = blocks[compound_idx].center();
+    patrol_blocks.sort_by_key(|&i| blocks[i].center().distance_squared(compound_center));
+    let near_target_chance = (0.3 + resistance.security_level as f64 * 0.1).min(0.9);
+
+    for _ in 0..patrol_count {
+        let i = if rng.gen_bool(near_target_chance) {
+            patrol_blocks[rng.gen_range(0..patrol_blocks.len().min(3))]
+        } else {
+            patrol_blocks[rng.gen_range(0..patrol_blocks.len())]
+        };
+        let mut route: Vec<IVec2> = blocks[i].sidewalk_loop().iter().map(|&t| layout.clamp(t)).collect();
+        route.rotate_left(rng.gen_range(0..route.len()));
+        if rng.gen_bool(0.5) {
+            route.reverse();
+        }
+        scene.enemies.push(EnemySpawn {
+            position: world(route[0]),
+            patrol_points: route.into_iter().map(world).collect(),
+            archetype: None,
+        });
+    }
+
+    // === CIVILIANS AND TRAFFIC ===
+    let tiles_of = |kind: TileType| -> Vec<IVec2> {
+        (0..height).flat_map(|y| (0..width).map(move |x| IVec2::new(x, y)))
+            .filter(|&t| layout.get(t) == Some(kind))
+            .collect()
+    };
+
+    let sidewalks = tiles_of(TileType::Sidewalk);
+    let civilian_count = environment.civilian_presence as usize * CIVILIANS_PER_PRESENCE;
+    scene.civilians = sidewalks.choose_multiple(&mut rng, civilian_count)
+        .map(|&tile| CivilianSpawn { position: world(tile) })
+        .collect();
+
+    let parking: Vec<IVec2> = tiles_of(TileType::Road).into_iter()
+        .filter(|t| !(columns.contains(&t.x) && rows.contains(&t.y)) && t.distance_squared(entry) > 9)
+        .collect();
+    let vehicle_count = (environment.cover_density * 6.0).round() as usize;
+    scene.vehicles = parking.choose_multiple(&mut rng, vehicle_count)
+        .map(|&tile| VehicleSpawn { position: world(tile), vehicle_type: "civilian_car".to_string() })
+        .collect();
+
+    // === URBAN AREAS ===
+    let foot_traffic_density = (0.2 + environment.civilian_presence as f32 * 0.15).min(1.0);
+    let mut urban = UrbanAreasData {
+        work_zones: Vec::new(),
+        shopping_zones: Vec::new(),
+        residential_zones: Vec::new(),
+        transit_routes: Vec::new(),
+    };
+
+    for (i, block) in blocks.iter().enumerate() {
+        let size = block.size();
+        let zone = UrbanZoneData {
+            center: world(block.center()),
+            radius: size.max_element() as f32 * settings.tile_height,
+            capacity: ((size.x * size.y) as usize / 2).max(2),
+        };
+        match block.district {
+            _ if i == compound_idx => urban.work_zones.push(zone),
+            District::Industrial => urban.work_zones.push(zone),
+            District::Commercial => urban.shopping_zones.push(zone),
+            District::Residential => urban.residential_zones.push(zone),
+            District::Park => {}
+        }
+    }
+
+    for &y in &rows {
+        let mut points = vec![world(IVec2::new(0, y + 1))];
+        points.extend(columns.iter().map(|&x| world(IVec2::new(x, y + 1))));
+        points.push(world(IVec2::new(width - 1, y + 1)));
+        urban.transit_routes.push(TransitRouteData { points, foot_traffic_density });
+    }
+    for &x in &columns {
+        let mut points = vec![world(IVec2::new(x + 1, 0))];
+        points.extend(rows.iter().map(|&y| world(IVec2::new(x + 1, y))));
+        points.push(world(IVec2::new(x + 1, height - 1)));
+        urban.transit_routes.push(TransitRouteData { points, foot_traffic_density });
+    }
+    scene.urban_areas = Some(urban);
+
+    // === INFRASTRUCTURE ===
+    // The generator only lays out devices; they're spawned by the scene loader like any authored scene's
+    let mut station_blocks: Vec<usize> = others.iter().copied()
+        .filter(|&i| blocks[i].district == District::Industrial)
+        .collect();
+    station_blocks.shuffle(&mut rng);
+    let mut spare: Vec<usize> = others.iter().copied().filter(|i| !station_blocks.contains(i)).collect();
+    spare.shuffle(&mut rng);
+    station_blocks.extend(spare);
+    if station_blocks.is_empty() {
+        station_blocks.push(compound_idx);
+    }
+    station_blocks.truncate((blocks.len() / BLOCKS_PER_POWER_STATION).max(1));
+
+    let stations: Vec<(IVec2, String)> = station_blocks.iter().enumerate()
+        .map(|(n, &i)| (blocks[i].center(), format!("{}_grid_{}", city.id, n + 1)))
+        .collect();
+    // There is always at least one station, so every tile has a nearest network
+    let network_for = |tile: IVec2| -> String {
+        stations.iter().min_by_key(|(pos, _)| pos.distance_squared(tile)).map(|(_, id)| id.clone()).unwrap_or_default()
+    };
+
+    let mut infrastructure: Vec<InfrastructureSpawn> = stations.iter()
+        .map(|(tile, network_id)| InfrastructureSpawn::PowerStation { position: world(*tile), network_id: network_id.clone() })
+        .collect();
+
+    // Darker missions get denser lighting to hide from
+    let light_spacing = match environment.time_of_day {
+        TimeOfDay::Night | TimeOfDay::Dusk => 3,
+        TimeOfDay::Dawn | TimeOfDay::Day => 5,
+    };
+    for &x in &columns {
+        for y in (0..height).step_by(light_spacing) {
+            let tile = IVec2::new(x + 1, y);
+            if layout.get(tile) == Some(TileType::Sidewalk) {
+                infrastructure.push(InfrastructureSpawn::StreetLight { position: world(tile), network_id: network_for(tile) });
+            }
+        }
+    }
+    for &y in &rows {
+        for x in (0..width).step_by(light_spacing) {
+            let tile = IVec2::new(x, y + 1);
+            if layout.get(tile) == Some(TileType::Sidewalk) {
+                infrastructure.push(InfrastructureSpawn::StreetLight { position: world(tile), network_id: network_for(tile) });
+            }
+        }
+    }
+
+    for &x in &columns {
+        for &y in &rows {
+            if rng.gen_bool(0.35) {
+                let tile = IVec2::new(x + 1, y + 1);
+                infrastructure.push(InfrastructureSpawn::TrafficLight { position: world(tile), network_id: network_for(tile) });
+            }
+        }
+    }
+
+    let courtyard_corners: Vec<IVec2> = compound.courtyard.iter().copied()
+        .filter(|&t| ring_corner(t, blocks[compound_idx].min + IVec2::ONE, blocks[compound_idx].max - IVec2::ONE))
+        .collect();
+    for &tile in courtyard_corners.iter().take(resistance.security_level.min(4) as usize) {
+        infrastructure.push(InfrastructureSpawn::SecurityCamera { position: world(tile), network_id: Some(network_for(tile)) });
+    }
+    for &tile in courtyard.iter().rev().take(resistance.security_level.saturating_sub(3) as usize) {
+        infrastructure.push(InfrastructureSpawn::AutomatedTurret { position: world(tile), network_id: Some(network_for(tile)) });
+    }
+
+    // Tighter sites lock the compound gate; a matching card is lying around somewhere in town
+    let gate_level = (resistance.security_level >= 3).then(|| (resistance.security_level / 2).clamp(1, 3));
+    infrastructure.push(InfrastructureSpawn::Door {
+        position: world(compound.gate),
+        requires_person: true,
+        access_level: gate_level,
+        network_id: Some(network_for(compound.gate)),
+    });
+    if let (Some(level), Some(&i)) = (gate_level, others.choose(&mut rng)) {
+        if let Some(&tile) = ring(blocks[i].min, blocks[i].max).choose(&mut rng) {
+            let card_type = match level {
+                1 => CardType::Basic,
+                2 => CardType::Security,
+                _ => CardType::Executive,
+            };
+            infrastructure.push(InfrastructureSpawn::AccessCard { position: world(tile), level, card_type });
+        }
+    }
+
+    for &i in others.iter().filter(|&&i| blocks[i].district == District::Industrial) {
+        if let Some(&tile) = ring(blocks[i].min, blocks[i].max).choose(&mut rng) {
+            infrastructure.push(InfrastructureSpawn::Explodable { position: world(tile), object: ExplodableType::FuelBarrel });
+        }
+    }
+    scene.infrastructure = infrastructure;
+
+    info!("Generated mission map for {} with seed {} ({}x{} tiles, {} blocks, {} enemies) - set {} to reproduce",
+          city.id, seed, width, height, blocks.len(), scene.enemies.len(), MAP_SEED_ENV);
+
+    GeneratedMission {
+        seed,
+        city_id: city.id.clone(),
+        scene,
+        layout,
+    }
+}
+
+// === TILEMAP ===
+pub fn apply_generated_layout(
+    mut commands: Commands,
+    generated: Option<Res<GeneratedMission>>,
+    tilemap_query: Query<Ref<TileStorage>, With<IsometricMap>>,
+) {
+    let Some(generated) = generated else { return; };
+    let Ok(tile_storage) = tilemap_query.single() else { return; };
+    if !generated.is_changed() && !tile_storage.is_added() {
+        return;
+    }
+
+    let layout = &generated.layout;
+    for y in 0..layout.height {
+        for x in 0..layout.width {
+            let Some(tile_entity) = tile_storage.get(&TilePos { x, y }) else { continue; };
+            let Some(tile_type) = layout.get(IVec2::new(x as i32, y as i32)) else { continue; };
+            commands.entity(tile_entity).insert(TileTextureIndex(get_texture_from_tile_type(tile_type)));
+        }
+    }
+}
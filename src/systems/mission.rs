@@ -2,6 +2,17 @@
 use bevy::prelude::*;
 use crate::core::*;
 use crate::core::research::{calculate_research_xp_bonus, calculate_research_credit_bonus};
+use crate::systems::extraction::{ExtractionState, MissionPhase};
+use crate::systems::grading::{grade_mission, MissionPerformanceTracker};
+use crate::systems::objectives::MissionObjectives;
+use crate::systems::replay::ReplayPlayback;
+use crate::systems::scene_editor::EditorPlaytest;
+use crate::systems::selection::AgentIndex;
+
+/// Standard campaigns patch up fallen agents instead of losing them
+const FALLEN_AGENT_RECOVERY_DAYS: u32 = 5;
+/// Standard campaigns get captured agents back, stripped of their gear, after a while
+const MISSING_AGENT_RECOVERY_DAYS: u32 = 10;
 
 pub fn timer_system(
     mut mission_data: ResMut<MissionData>,
@@ -9,19 +20,20 @@ pub fn timer_system(
     mut post_mission: ResMut<PostMissionResults>,
     game_mode: Res<GameMode>,
     time: Res<Time>,
+    extraction: Res<ExtractionState>,
 ) {
     if game_mode.paused { return; }
 
     mission_data.timer += time.delta_secs();
 
-    if mission_data.timer >= mission_data.time_limit {
+    // Running out the clock while extracting is resolved by check_completion
+    if mission_data.timer >= mission_data.time_limit && extraction.phase == MissionPhase::Objectives {
         *post_mission = PostMissionResults {
-            success: false,
             time_taken: mission_data.timer,
             enemies_killed: mission_data.enemies_killed,
             terminals_accessed: mission_data.terminals_accessed,
-            credits_earned: 0,
             alert_level: mission_data.alert_level,
+            ..default()
         };
         info!("Time Limit Exceeded - Mission Failed");
         next_state.set(GameState::PostMission);
@@ -30,33 +42,50 @@ pub fn timer_system(
 
 pub fn check_completion(
     mut next_state: ResMut<NextState<GameState>>,
-    mission_data: Res<MissionData>,
+    mut mission_data: ResMut<MissionData>,
+    objectives: Res<MissionObjectives>,
+    mut extraction: ResMut<ExtractionState>,
     mut post_mission: ResMut<PostMissionResults>,
-    agent_query: Query<&Inventory, With<Agent>>,
+    agent_query: Query<&AgentIndex, (With<Agent>, Without<Dead>, Without<MarkedForDespawn>)>,
 ) {
-    if agent_query.is_empty() && mission_data.timer < 1.0 {
+    if agent_query.is_empty() && extraction.extracted.is_empty() && mission_data.timer < 1.0 {
         return; // Skip check if agents haven't spawned yet
     }
 
-    let objectives_complete = mission_data.objectives_completed >= mission_data.total_objectives;
-    let agents_alive = !agent_query.is_empty();
+    match extraction.phase {
+        MissionPhase::Objectives => {
+            if objectives.any_required_failed() {
+                info!("Required Objective Failed - Mission Failed");
+                *post_mission = PostMissionResults {
+                    time_taken: mission_data.timer,
+                    enemies_killed: mission_data.enemies_killed,
+                    terminals_accessed: mission_data.terminals_accessed,
+                    alert_level: mission_data.alert_level,
+                    ..default()
+                };
+                next_state.set(GameState::PostMission);
+            } else if agent_query.is_empty() {
+                info!("Agents Deceased - Mission Failed");
+                *post_mission = PostMissionResults::default();
+                next_state.set(GameState::PostMission);
+            } else if objectives.all_required_complete() {
+                extraction.begin(&mut mission_data);
+            }
+        }
+        MissionPhase::Extraction => {
+            let out_of_time = mission_data.timer >= mission_data.time_limit;
+            if !agent_query.is_empty() && !out_of_time && !extraction.abandon_requested {
+                return;
+            }
 
-    if objectives_complete {
-        let credits_earned = agent_query.iter().map(|inv| inv.currency).sum();
-        *post_mission = PostMissionResults {
-            success: true,
-            time_taken: mission_data.timer,
-            enemies_killed: mission_data.enemies_killed,
-            terminals_accessed: mission_data.terminals_accessed,
-            credits_earned,
-            alert_level: mission_data.alert_level,
-        };
-        info!("Objectives Completed - Mission Success");
-        next_state.set(GameState::PostMission);
-    } else if !agents_alive {
-        info!("Agents Deceased - Mission Failed");
-        *post_mission = PostMissionResults::default();
-        next_state.set(GameState::PostMission);
+            // Whoever is still in the field is captured or missing
+            mission_data.agents_missing = agent_query.iter().map(|index| index.0).collect();
+            *post_mission = extraction.results(&mission_data);
+            info!("Extraction over - {:?} ({} out, {} missing, {} lost)",
+                  post_mission.outcome, extraction.extracted.len(),
+                  mission_data.agents_missing.len(), mission_data.agents_lost.len());
+            next_state.set(GameState::PostMission);
+        }
     }
 }
 
@@ -104,8 +133,7 @@ pub fn restart_system_optimized(
 pub fn process_mission_results(
     mut global_data: ResMut<GlobalData>,
     mut processed: ResMut<PostMissionProcessed>,
-    post_mission: Res<PostMissionResults>,
-    agent_query: Query<&Agent>,
+    mut post_mission: ResMut<PostMissionResults>,
     cities_db: Res<CitiesDatabase>,
     launch_data: Option<Res<MissionLaunchData>>,
 
@@ -113,26 +141,75 @@ pub fn process_mission_results(
     mut territory_manager: ResMut<TerritoryManager>,
     mut progression_tracker: ResMut<CampaignProgressionTracker>,
     campaign_db: Option<Res<NeoSingaporeCampaignDatabase>>, // Optional for now
+    mut mission_data: ResMut<MissionData>,
+    traits_db: Res<TraitsDatabase>,
+    playtest: Option<Res<EditorPlaytest>>,
+    replay: Option<Res<ReplayPlayback>>,
+    objectives: Res<MissionObjectives>,
+    tracker: Res<MissionPerformanceTracker>,
 ) {
     if processed.0 { return; }
 
+    let mut agents_lost = std::mem::take(&mut mission_data.agents_lost);
+    agents_lost.sort_unstable();
+    agents_lost.dedup();
+    let mut agents_missing = std::mem::take(&mut mission_data.agents_missing);
+    agents_missing.sort_unstable();
+    agents_missing.dedup();
+
+    // Only agents who came home learn anything from it
+    let returned: Vec<usize> = (0..3)
+        .filter(|i| tracker.agents[*i].deployed && !agents_lost.contains(i) && !agents_missing.contains(i))
+        .collect();
+
+    // Graded even in playtests so the editor shows what a run would score
+    post_mission.performance = grade_mission(&post_mission, &tracker, &objectives, &returned);
+    info!("mission graded {:?} ({} points)", post_mission.performance.grade, post_mission.performance.score);
+
+    // Editor playtests and replays don't touch the campaign
+    if playtest.is_some() || replay.is_some() {
+        processed.0 = true;
+        return;
+    }
+
     info!("process_mission_results");
 
     let region_idx = global_data.selected_region;
     global_data.current_day += 1;
     let current_day = global_data.current_day;
 
+    // Career records for everyone who deployed, whatever the outcome
+    let stealth_mission = !post_mission.performance.detected;
+    for i in (0..3).filter(|&i| tracker.agents[i].deployed) {
+        let stats = &tracker.agents[i];
+        let perf = &mut global_data.agent_performance[i];
+        let bonuses_before = perf.veteran_bonuses.len();
+        update_agent_performance(
+            perf,
+            post_mission.success,
+            returned.contains(&i),
+            stats.kills,
+            stats.hacks,
+            post_mission.time_taken,
+            stats.damage_dealt,
+            stats.damage_taken,
+            stealth_mission,
+        );
+        for bonus in &perf.veteran_bonuses[bonuses_before..] {
+            info!("Agent {} earned veteran bonus: {}", i + 1, bonus.name);
+        }
+    }
+
     if post_mission.success {
 
-        info!("mission successful");
+        info!("mission successful ({:?})", post_mission.outcome);
 
         global_data.credits += post_mission.credits_earned;
 
-        let exp_gained = 10 + (post_mission.enemies_killed * 5);
         let recovery_days = if post_mission.time_taken > 240.0 { 2 } else { 1 };
 
-        for (i, _) in agent_query.iter().enumerate().take(3) {
-            global_data.agent_experience[i] += exp_gained;
+        for &i in &returned {
+            global_data.agent_experience[i] += post_mission.performance.agent_xp[i];
             global_data.agent_recovery[i] = current_day + recovery_days;
 
             let required_exp = experience_for_level(global_data.agent_levels[i] + 1);
@@ -186,6 +263,10 @@ pub fn process_mission_results(
         if post_mission.enemies_killed > 0 || post_mission.time_taken >= 180.0 {
             global_data.regions[region_idx].raise_alert(current_day);
         }
+        // Captured agents talk
+        if post_mission.outcome == MissionOutcome::PartialSuccess && !agents_missing.is_empty() {
+            global_data.regions[region_idx].raise_alert(current_day);
+        }
     } else {
         global_data.regions[region_idx].raise_alert(current_day);
         global_data.regions[region_idx].raise_alert(current_day);
@@ -198,6 +279,26 @@ pub fn process_mission_results(
         region.update_alert(current_day);
     }
 
+    for idx in agents_lost.into_iter().filter(|&idx| idx < 3) {
+        if global_data.ironman {
+            info!("Agent {} killed in action - lost permanently", idx + 1);
+            global_data.recruit_replacement_agent(idx, &traits_db);
+        } else {
+            global_data.agent_recovery[idx] = current_day + FALLEN_AGENT_RECOVERY_DAYS;
+        }
+    }
+
+    for idx in agents_missing.into_iter().filter(|&idx| idx < 3) {
+        if global_data.ironman {
+            info!("Agent {} left behind - missing in action", idx + 1);
+            global_data.recruit_replacement_agent(idx, &traits_db);
+        } else {
+            info!("Agent {} captured - released in {} days without their gear", idx + 1, MISSING_AGENT_RECOVERY_DAYS);
+            global_data.agent_recovery[idx] = current_day + MISSING_AGENT_RECOVERY_DAYS;
+            global_data.agent_loadouts[idx] = AgentLoadout::default();
+        }
+    }
+
 
     
     processed.0 = true;
@@ -11,7 +11,7 @@ pub mod save;
 pub mod reload;
 pub mod morale;
 pub mod weapon_swap;
-// pub mod police;
+pub mod police;
 pub mod area_control;
 pub mod vehicles;
 pub mod day_night;
@@ -99,6 +99,7 @@ pub mod tilemap_props;
 pub use tilemap_props::*;
 
 pub use urban_security::*;
+pub use police::*;
 
 pub use minimap::*;
 pub use message_window::*;
@@ -123,3 +124,45 @@ pub mod cover;
 
 pub mod quicksave;
 
+pub mod squads;
+pub use squads::*;
+
+pub mod perception;
+pub use perception::*;
+
+pub mod goap_debug;
+pub use goap_debug::GoapDebugPlugin;
+
+pub mod objectives;
+pub use objectives::*;
+
+pub mod mapgen;
+pub use mapgen::*;
+
+pub mod extraction;
+pub use extraction::*;
+
+pub mod scripting;
+pub use scripting::*;
+
+pub mod scene_editor;
+pub use scene_editor::*;
+
+pub mod grading;
+pub use grading::*;
+
+pub mod replay;
+pub use replay::*;
+
+
+pub mod device_takeover;
+pub use device_takeover::*;
+
+pub mod hack_trace;
+pub use hack_trace::*;
+
+pub mod remote_hacking;
+pub use remote_hacking::*;
+
+pub mod simulation;
+pub use simulation::*;
@@ -1,5 +1,6 @@
 // src/systems/morale.rs
 use bevy::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::*;
 
@@ -112,6 +113,7 @@ pub fn civilian_morale_system(
     agent_query: Query<&Transform, With<Agent>>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
@@ -132,7 +134,8 @@ pub fn civilian_morale_system(
             let flee_direction = if let Some(agent_transform) = agent_query.iter().next() {
                 (civilian_pos - agent_transform.translation.truncate()).normalize_or_zero()
             } else {
-                Vec2::new(rand::random::<f32>() - 0.5, rand::random::<f32>() - 0.5).normalize_or_zero()
+                let rng = rng.stream(RngStream::Civilians);
+                Vec2::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5).normalize_or_zero()
             };
             
             commands.entity(entity).insert(FleeTarget {
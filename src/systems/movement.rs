@@ -1,5 +1,7 @@
 // src/systems/movement.rs - Fixed core movement system (no physics)
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::{CollisionEvent, Velocity};
+use rand::Rng;
 use crate::core::*;
 
 pub fn system(
@@ -130,4 +132,28 @@ pub fn system(
         }
     }
 
-}
\ No newline at end of file
+}
+
+pub fn collision_feedback_system(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut units: Query<&mut Velocity, Or<(With<Agent>, With<Civilian>, With<Enemy>)>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let rng = rng.stream(RngStream::Environment);
+    for collision_event in collision_events.read() {
+        if let CollisionEvent::Started(e1, e2, _) = collision_event {
+            // Check if both entities are units before proceeding
+            if units.get(*e1).is_ok() && units.get(*e2).is_ok() {
+                let separation_force = Vec2::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5) * 50.0;
+
+                // Apply opposite forces to separate the entities
+                if let Ok(mut vel1) = units.get_mut(*e1) {
+                    vel1.linvel += separation_force;
+                }
+                if let Ok(mut vel2) = units.get_mut(*e2) {
+                    vel2.linvel -= separation_force; // Opposite direction
+                }
+            }
+        }
+    }
+}
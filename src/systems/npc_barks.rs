@@ -20,7 +20,8 @@ pub struct BarkEvent {
     pub bark_type: BarkType,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BarkType {
     // Combat
     SpottedEnemy,
@@ -70,9 +71,9 @@ pub fn goap_bark_system(
             }
             
             // Update and get bark type
-            *entry = goal.name.to_string();
+            *entry = goal.name.clone();
             
-            let bark = match goal.name.as_ref() {
+            let bark = match goal.name.as_str() {
                 "eliminate_threat" => BarkType::SpottedEnemy,
                 "investigate_disturbance" => BarkType::Investigating,
                 "coordinate_defense" => BarkType::CallForHelp,
@@ -0,0 +1,405 @@
+// src/systems/objectives.rs - Runtime tracking for the objectives listed in the mission briefing
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::core::*;
+use crate::systems::extraction::{ExtractionState, ExtractionZone, MissionPhase};
+
+const INFILTRATE_RADIUS: f32 = 80.0;
+const PICKUP_RADIUS: f32 = 25.0;
+const ITEM_OFFSET: Vec2 = Vec2::new(20.0, -20.0);
+const SURVIVE_BASE_SECS: f32 = 60.0;
+const SURVIVE_SECS_PER_DIFFICULTY: f32 = 30.0;
+
+// === COMPONENTS ===
+/// Enemy an Eliminate objective wants dead
+#[derive(Component)]
+pub struct ObjectiveTarget(pub usize);
+
+/// Data core an Extract objective needs carried out, lying in the world
+#[derive(Component)]
+pub struct ObjectiveItem(pub usize);
+
+/// Agent currently carrying an Extract objective's data core
+#[derive(Component)]
+pub struct CarryingObjectiveItem(pub usize);
+
+// === RESOURCES ===
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ObjectiveState {
+    Active,
+    Completed,
+    Failed,
+}
+
+/// What has to happen in the world for an objective to count as done
+#[derive(Debug, Clone)]
+pub enum ObjectivePredicate {
+    Unbound,
+    TargetKilled,
+    TerminalAccessed(Entity),
+    ZoneReached { center: Vec2, radius: f32 },
+    TimerSurvived { duration: f32 },
+    /// Carried into any extraction zone
+    ItemExtracted,
+}
+
+#[derive(Clone)]
+pub struct TrackedObjective {
+    pub objective: MissionObjective,
+    pub predicate: ObjectivePredicate,
+    pub state: ObjectiveState,
+    /// 0.0-1.0, shown on the HUD
+    pub progress: f32,
+    pub status: ObjectiveStatus,
+}
+
+/// Objectives for the running mission; kept through PostMission for grading
+#[derive(Resource, Default)]
+pub struct MissionObjectives {
+    pub objectives: Vec<TrackedObjective>,
+    pub bound: bool,
+}
+
+impl MissionObjectives {
+    pub fn from_briefing(objectives: &[MissionObjective]) -> Self {
+        let objectives = if objectives.iter().any(|o| o.required) {
+            objectives.to_vec()
+        } else {
+            // Without a required objective a mission could never succeed
+            let mut fallback = fallback_objectives();
+            fallback.extend(objectives.iter().cloned());
+            fallback
+        };
+
+        Self {
+            objectives: objectives.into_iter().map(|objective| TrackedObjective {
+                objective,
+                predicate: ObjectivePredicate::Unbound,
+                state: ObjectiveState::Active,
+                progress: 0.0,
+                status: ObjectiveStatus::default(),
+            }).collect(),
+            bound: false,
+        }
+    }
+
+    pub fn required_count(&self) -> usize {
+        self.objectives.iter().filter(|o| o.objective.required).count()
+    }
+
+    pub fn required_completed(&self) -> usize {
+        self.objectives.iter().filter(|o| o.objective.required && o.state == ObjectiveState::Completed).count()
+    }
+
+    pub fn optional_completed(&self) -> usize {
+        self.objectives.iter().filter(|o| !o.objective.required && o.state == ObjectiveState::Completed).count()
+    }
+
+    /// Binding drops objectives the scene can't support, so a mission left with none required
+    /// goes straight to extraction
+    pub fn all_required_complete(&self) -> bool {
+        self.bound && self.required_completed() == self.required_count()
+    }
+
+    pub fn any_required_failed(&self) -> bool {
+        self.objectives.iter().any(|o| o.objective.required && o.state == ObjectiveState::Failed)
+    }
+}
+
+/// Scenes launched without a briefing keep the old "access the objective terminal" goal
+pub fn fallback_objectives() -> Vec<MissionObjective> {
+    vec![MissionObjective {
+        name: "Access Objective Terminal".to_string(),
+        description: "Hack the marked terminal".to_string(),
+        objective_type: ObjectiveType::Hack,
+        required: true,
+        difficulty: 1,
+    }]
+}
+
+// === SYSTEMS ===
+/// Ties each objective to scene entities once the mission scene has spawned
+pub fn bind_mission_objectives(
+    mut commands: Commands,
+    mut objectives: ResMut<MissionObjectives>,
+    agents: Query<&Transform, With<Agent>>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Dead>)>,
+    terminals: Query<(Entity, &Transform, &Terminal)>,
+) {
+    if objectives.bound || agents.is_empty() {
+        return;
+    }
+
+    let insertion = agents.iter().map(|t| t.translation.truncate()).sum::<Vec2>() / agents.iter().len() as f32;
+
+    // Objective terminals first, then intel, then anything else
+    let mut candidates: Vec<(Entity, Vec2, u8)> = terminals.iter()
+        .filter(|(_, _, terminal)| !terminal.accessed)
+        .map(|(entity, transform, terminal)| (entity, transform.translation.truncate(), match terminal.terminal_type {
+            TerminalType::Objective => 0,
+            TerminalType::Intel => 1,
+            TerminalType::Equipment => 2,
+        }))
+        .collect();
+    candidates.sort_by_key(|(_, _, rank)| *rank);
+
+    let mut used_terminals: Vec<Entity> = Vec::new();
+    let mut used_enemies: Vec<Entity> = Vec::new();
+
+    for (idx, tracked) in objectives.objectives.iter_mut().enumerate() {
+        let predicate = match tracked.objective.objective_type {
+            ObjectiveType::Infiltrate => candidates.first()
+                .map(|(_, pos, _)| ObjectivePredicate::ZoneReached { center: *pos, radius: INFILTRATE_RADIUS }),
+            ObjectiveType::Hack => candidates.iter()
+                .find(|(entity, _, _)| !used_terminals.contains(entity))
+                .map(|(entity, _, _)| {
+                    used_terminals.push(*entity);
+                    ObjectivePredicate::TerminalAccessed(*entity)
+                }),
+            ObjectiveType::Extract => candidates.iter()
+                .filter(|(entity, _, _)| !used_terminals.contains(entity))
+                .min_by_key(|(_, _, rank)| if *rank == 1 { 0 } else { 1 })
+                .map(|(entity, pos, _)| {
+                    used_terminals.push(*entity);
+                    spawn_objective_item(&mut commands, *pos + ITEM_OFFSET, idx);
+                    ObjectivePredicate::ItemExtracted
+                }),
+            ObjectiveType::Eliminate => enemies.iter()
+                .filter(|(entity, _)| !used_enemies.contains(entity))
+                .max_by(|a, b| {
+                    let da = a.1.translation.truncate().distance(insertion);
+                    let db = b.1.translation.truncate().distance(insertion);
+                    da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map(|(entity, _)| {
+                    used_enemies.push(entity);
+                    commands.entity(entity).insert(ObjectiveTarget(idx));
+                    ObjectivePredicate::TargetKilled
+                }),
+            ObjectiveType::Survive => Some(ObjectivePredicate::TimerSurvived {
+                duration: SURVIVE_BASE_SECS + tracked.objective.difficulty as f32 * SURVIVE_SECS_PER_DIFFICULTY,
+            }),
+        };
+
+        match predicate {
+            Some(predicate) => tracked.predicate = predicate,
+            None => {
+                // Not the squad's fault, so it can't be what fails the mission
+                warn!("Objective '{}' has nothing to bind to in this scene; dropping it", tracked.objective.name);
+                tracked.objective.required = false;
+                tracked.state = ObjectiveState::Failed;
+            }
+        }
+    }
+
+    objectives.bound = true;
+    if objectives.required_count() == 0 {
+        warn!("No required objective could be bound; extraction opens straight away");
+    }
+    info!("Bound {} mission objectives ({} required)", objectives.objectives.len(), objectives.required_count());
+}
+
+pub fn update_mission_objectives(
+    mut commands: Commands,
+    mut objectives: ResMut<MissionObjectives>,
+    mut mission_data: ResMut<MissionData>,
+    agents: Query<(Entity, &Transform, Option<&CarryingObjectiveItem>), (With<Agent>, Without<Dead>)>,
+    fallen_carriers: Query<(Entity, &Transform, &CarryingObjectiveItem), With<Dead>>,
+    targets: Query<&ObjectiveTarget, Without<Dead>>,
+    terminals: Query<&Terminal>,
+    items: Query<(Entity, &Transform, &ObjectiveItem), Without<MarkedForDespawn>>,
+    zones: Query<(&Transform, &ExtractionZone)>,
+) {
+    if !objectives.bound {
+        return;
+    }
+
+    // A dead carrier drops the data core where they fell
+    for (entity, transform, carrying) in fallen_carriers.iter() {
+        commands.entity(entity).remove::<CarryingObjectiveItem>();
+        spawn_objective_item(&mut commands, transform.translation.truncate(), carrying.0);
+        info!("Objective data dropped");
+    }
+
+    // Picking up a data core is automatic when walking over it
+    let mut picked_up: Vec<usize> = Vec::new();
+    for (item_entity, item_transform, item) in items.iter() {
+        let item_pos = item_transform.translation.truncate();
+        let carrier = agents.iter().find(|(_, transform, carrying)| {
+            carrying.is_none() && transform.translation.truncate().distance(item_pos) <= PICKUP_RADIUS
+        });
+        if let Some((agent_entity, _, _)) = carrier {
+            commands.entity(item_entity).insert(MarkedForDespawn);
+            commands.entity(agent_entity).insert(CarryingObjectiveItem(item.0));
+            picked_up.push(item.0);
+            info!("Objective data picked up");
+        }
+    }
+
+    let timer = mission_data.timer;
+    let alert_level = mission_data.alert_level;
+
+    for (idx, tracked) in objectives.objectives.iter_mut().enumerate() {
+        if tracked.state != ObjectiveState::Active {
+            continue;
+        }
+
+        let done = match &tracked.predicate {
+            ObjectivePredicate::Unbound => false,
+            ObjectivePredicate::TargetKilled => {
+                !targets.iter().any(|target| target.0 == idx)
+            }
+            ObjectivePredicate::TerminalAccessed(entity) => match terminals.get(*entity) {
+                Ok(terminal) => terminal.accessed,
+                Err(_) => {
+                    tracked.state = ObjectiveState::Failed;
+                    false
+                }
+            },
+            ObjectivePredicate::ZoneReached { center, radius } => {
+                let closest = agents.iter()
+                    .map(|(_, transform, _)| transform.translation.truncate().distance(*center))
+                    .fold(f32::MAX, f32::min);
+                tracked.progress = tracked.progress.max((1.0 - (closest - radius) / (radius * 4.0)).clamp(0.0, 1.0));
+                closest <= *radius
+            }
+            ObjectivePredicate::TimerSurvived { duration } => {
+                tracked.progress = (timer / duration).min(1.0);
+                timer >= *duration && !agents.is_empty()
+            }
+            ObjectivePredicate::ItemExtracted => {
+                let carrier = agents.iter().find(|(_, _, carrying)| carrying.is_some_and(|c| c.0 == idx));
+                match carrier {
+                    Some((agent_entity, transform, _)) => {
+                        tracked.progress = 0.5;
+                        let pos = transform.translation.truncate();
+                        let delivered = zones.iter().any(|(zone_transform, zone)| {
+                            zone_transform.translation.truncate().distance(pos) <= zone.radius
+                        });
+                        if delivered {
+                            commands.entity(agent_entity).remove::<CarryingObjectiveItem>();
+                        }
+                        delivered
+                    }
+                    None => {
+                        tracked.progress = if picked_up.contains(&idx) { 0.5 } else { 0.0 };
+                        false
+                    }
+                }
+            }
+        };
+
+        if done {
+            tracked.state = ObjectiveState::Completed;
+            tracked.progress = 1.0;
+            tracked.status = ObjectiveStatus {
+                completed: true,
+                method: Some(completion_method(tracked.objective.objective_type, alert_level)),
+                time_taken: timer,
+            };
+            info!("Objective complete: {} ({:?})", tracked.objective.name, tracked.status.method);
+        } else if tracked.state == ObjectiveState::Failed {
+            warn!("Objective failed: {}", tracked.objective.name);
+        }
+    }
+
+    // Keep the legacy counters in step for anything still reading them
+    mission_data.objectives_completed = objectives.required_completed() as u32;
+    mission_data.total_objectives = objectives.required_count() as u32;
+}
+
+pub fn objective_gizmos_system(
+    mut gizmos: Gizmos,
+    objectives: Res<MissionObjectives>,
+    targets: Query<&Transform, (With<ObjectiveTarget>, Without<Dead>)>,
+    items: Query<&Transform, With<ObjectiveItem>>,
+    carriers: Query<&Transform, With<CarryingObjectiveItem>>,
+    terminals: Query<&Transform, With<Terminal>>,
+) {
+    let color = Color::srgba(1.0, 0.85, 0.2, 0.8);
+
+    for tracked in objectives.objectives.iter().filter(|o| o.state == ObjectiveState::Active) {
+        match &tracked.predicate {
+            ObjectivePredicate::ZoneReached { center, radius } => {
+                gizmos.circle_2d(*center, *radius, color);
+            }
+            ObjectivePredicate::TerminalAccessed(entity) => {
+                if let Ok(transform) = terminals.get(*entity) {
+                    gizmos.circle_2d(transform.translation.truncate(), 20.0, color);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for transform in targets.iter().chain(items.iter()).chain(carriers.iter()) {
+        let pos = transform.translation.truncate();
+        gizmos.line_2d(pos + Vec2::new(0.0, 26.0), pos + Vec2::new(-6.0, 34.0), color);
+        gizmos.line_2d(pos + Vec2::new(0.0, 26.0), pos + Vec2::new(6.0, 34.0), color);
+    }
+}
+
+pub fn objectives_hud_system(
+    mut contexts: EguiContexts,
+    objectives: Res<MissionObjectives>,
+    extraction: Res<ExtractionState>,
+    mission_data: Res<MissionData>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else { return; };
+
+    egui::Window::new("Objectives")
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 10.0))
+        .collapsible(true)
+        .resizable(false)
+        .auto_sized()
+        .show(ctx, |ui| {
+            if extraction.phase == MissionPhase::Extraction {
+                ui.colored_label(egui::Color32::from_rgb(80, 255, 120), "EXTRACT - reach an extraction zone");
+                ui.label(format!("{} out | {:.0}s left",
+                    extraction.extracted.len(), (mission_data.time_limit - mission_data.timer).max(0.0)));
+                ui.separator();
+            }
+
+            for tracked in &objectives.objectives {
+                let (marker, color) = match tracked.state {
+                    ObjectiveState::Completed => ("✔", egui::Color32::LIGHT_GREEN),
+                    ObjectiveState::Failed => ("✖", egui::Color32::LIGHT_RED),
+                    ObjectiveState::Active if tracked.objective.required => ("•", egui::Color32::WHITE),
+                    ObjectiveState::Active => ("•", egui::Color32::GRAY),
+                };
+
+                ui.horizontal(|ui| {
+                    ui.colored_label(color, marker);
+                    ui.colored_label(color, &tracked.objective.name);
+                    if !tracked.objective.required {
+                        ui.weak("(optional)");
+                    }
+                });
+
+                if tracked.state == ObjectiveState::Active && tracked.progress > 0.0 {
+                    ui.add(egui::ProgressBar::new(tracked.progress).desired_width(180.0));
+                }
+            }
+        });
+}
+
+// === HELPERS ===
+pub fn spawn_objective_item(commands: &mut Commands, pos: Vec2, objective_idx: usize) -> Entity {
+    commands.spawn((
+        Sprite {
+            color: Color::srgb(1.0, 0.85, 0.2),
+            custom_size: Some(Vec2::splat(10.0)),
+            ..default()
+        },
+        Transform::from_translation(pos.extend(3.0)),
+        ObjectiveItem(objective_idx),
+    )).id()
+}
+
+fn completion_method(objective_type: ObjectiveType, alert_level: AlertLevel) -> CompletionMethod {
+    match objective_type {
+        ObjectiveType::Hack => CompletionMethod::Hacking,
+        _ if alert_level == AlertLevel::Green => CompletionMethod::Stealth,
+        _ => CompletionMethod::Combat,
+    }
+}
@@ -0,0 +1,203 @@
+// src/systems/perception.rs - Per-enemy stimulus memory with decaying confidence and graded suspicion
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use crate::core::*;
+use crate::systems::ai::{AIMode, AIState};
+use crate::systems::death::Corpse;
+
+pub const SUSPICIOUS_THRESHOLD: f32 = 0.3;
+pub const ALERTED_THRESHOLD: f32 = 1.0;
+
+const MIN_CONFIDENCE: f32 = 0.05;
+const SUSPICION_DECAY: f32 = 0.05;
+const STIMULUS_MERGE_RADIUS: f32 = 40.0;
+const BASE_SEARCH_RADIUS: f32 = 40.0;
+const MAX_SEARCH_RADIUS: f32 = 160.0;
+
+// === STIMULI ===
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StimulusKind {
+    Sight,
+    Sound,
+    Alert,
+    Corpse,
+}
+
+impl StimulusKind {
+    /// Confidence lost per second
+    fn decay_rate(&self) -> f32 {
+        match self {
+            StimulusKind::Sight => 0.08,
+            StimulusKind::Sound => 0.15,
+            StimulusKind::Alert => 0.1,
+            StimulusKind::Corpse => 0.02,
+        }
+    }
+
+    /// Immediate suspicion added when the stimulus is first registered
+    fn suspicion_bump(&self) -> f32 {
+        match self {
+            StimulusKind::Sight => 0.0, // Sight accumulates over time instead
+            StimulusKind::Sound => 0.35,
+            StimulusKind::Alert => 0.5,
+            StimulusKind::Corpse => 0.7,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stimulus {
+    pub kind: StimulusKind,
+    pub position: Vec2,
+    pub source: Option<Entity>,
+    pub confidence: f32,
+    pub time: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuspicionLevel {
+    Calm,
+    Suspicious,
+    Alerted,
+}
+
+// === COMPONENT ===
+#[derive(Component, Debug, Clone, Default)]
+pub struct PerceptionMemory {
+    pub stimuli: Vec<Stimulus>,
+    /// 0.0 = calm, 1.0 = fully alerted
+    pub suspicion: f32,
+    seen_corpses: HashSet<Entity>,
+}
+
+impl PerceptionMemory {
+    pub fn level(&self) -> SuspicionLevel {
+        if self.suspicion >= ALERTED_THRESHOLD {
+            SuspicionLevel::Alerted
+        } else if self.suspicion >= SUSPICIOUS_THRESHOLD {
+            SuspicionLevel::Suspicious
+        } else {
+            SuspicionLevel::Calm
+        }
+    }
+
+    pub fn is_alerted(&self) -> bool {
+        self.level() == SuspicionLevel::Alerted
+    }
+
+    /// Stores a stimulus, refreshing an existing one from the same source or place
+    pub fn record(&mut self, kind: StimulusKind, position: Vec2, source: Option<Entity>, confidence: f32, now: f32) {
+        let existing = self.stimuli.iter_mut().find(|s| {
+            s.kind == kind && match (s.source, source) {
+                (Some(a), Some(b)) => a == b,
+                _ => s.position.distance(position) <= STIMULUS_MERGE_RADIUS,
+            }
+        });
+
+        match existing {
+            Some(stimulus) => {
+                stimulus.position = position;
+                stimulus.confidence = stimulus.confidence.max(confidence);
+                stimulus.time = now;
+            }
+            None => {
+                self.stimuli.push(Stimulus { kind, position, source, confidence, time: now });
+                self.suspicion = (self.suspicion + kind.suspicion_bump() * confidence).min(ALERTED_THRESHOLD);
+            }
+        }
+    }
+
+    /// Sight builds suspicion gradually; close, central targets are confirmed faster
+    pub fn observe(&mut self, source: Entity, position: Vec2, distance_ratio: f32, delta: f32, now: f32) {
+        let gain = 1.5 * (1.0 - 0.7 * distance_ratio.clamp(0.0, 1.0));
+        self.suspicion = (self.suspicion + gain * delta).min(ALERTED_THRESHOLD);
+        self.record(StimulusKind::Sight, position, Some(source), 1.0, now);
+    }
+
+    /// Returns true the first time this corpse is noticed
+    pub fn notice_corpse(&mut self, corpse: Entity, position: Vec2, now: f32) -> bool {
+        if !self.seen_corpses.insert(corpse) {
+            return false;
+        }
+        self.record(StimulusKind::Corpse, position, Some(corpse), 1.0, now);
+        true
+    }
+
+    pub fn strongest(&self) -> Option<&Stimulus> {
+        self.stimuli.iter()
+            .max_by(|a, b| a.confidence.partial_cmp(&b.confidence).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// Low-confidence memories cover more ground when searched
+    pub fn search_radius(&self) -> f32 {
+        self.strongest().map_or(BASE_SEARCH_RADIUS, |s| {
+            BASE_SEARCH_RADIUS + (MAX_SEARCH_RADIUS - BASE_SEARCH_RADIUS) * (1.0 - s.confidence)
+        })
+    }
+
+    pub fn decay(&mut self, delta: f32, observing: bool) {
+        for stimulus in &mut self.stimuli {
+            stimulus.confidence -= stimulus.kind.decay_rate() * delta;
+        }
+        self.stimuli.retain(|s| s.confidence > MIN_CONFIDENCE);
+
+        if !observing {
+            // Stay on edge while any memory is still fresh
+            let floor = self.strongest().map_or(0.0, |s| s.confidence * SUSPICIOUS_THRESHOLD);
+            self.suspicion = (self.suspicion - SUSPICION_DECAY * delta).max(floor.min(self.suspicion));
+        }
+    }
+}
+
+// === SYSTEMS ===
+pub fn attach_perception_memory(
+    mut commands: Commands,
+    enemies: Query<Entity, (With<Enemy>, With<Vision>, Without<PerceptionMemory>, Without<Dead>)>,
+) {
+    for entity in enemies.iter() {
+        commands.entity(entity).insert(PerceptionMemory::default());
+    }
+}
+
+/// Corpses inside an enemy's vision cone raise suspicion once each
+pub fn corpse_perception_system(
+    mut enemies: Query<(&Transform, &Vision, &mut PerceptionMemory), (With<Enemy>, Without<Dead>)>,
+    corpses: Query<(Entity, &Transform), With<Corpse>>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+
+    for (transform, vision, mut memory) in enemies.iter_mut() {
+        let pos = transform.translation.truncate();
+        for (corpse, corpse_transform) in corpses.iter() {
+            let corpse_pos = corpse_transform.translation.truncate();
+            let to_corpse = corpse_pos - pos;
+            let distance = to_corpse.length();
+            if distance > vision.range || distance < 1.0 {
+                continue;
+            }
+            if vision.direction.dot(to_corpse / distance) < (vision.angle / 2.0).cos() {
+                continue;
+            }
+            if memory.notice_corpse(corpse, corpse_pos, now) {
+                info!("Enemy found a body at ({:.0}, {:.0})", corpse_pos.x, corpse_pos.y);
+            }
+        }
+    }
+}
+
+pub fn perception_decay_system(
+    mut memories: Query<(&mut PerceptionMemory, &AIState), Without<Dead>>,
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+) {
+    if game_mode.paused { return; }
+
+    let now = time.elapsed_secs();
+    for (mut memory, ai_state) in memories.iter_mut() {
+        let observing = memory.stimuli.iter().any(|s| s.kind == StimulusKind::Sight && now - s.time < 0.1)
+            || matches!(ai_state.mode, AIMode::Combat { .. });
+        memory.decay(time.delta_secs(), observing);
+    }
+}
@@ -0,0 +1,454 @@
+// src/systems/police.rs - Incident heat, escalation, dispatch and de-escalation of police responses
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::core::*;
+use crate::systems::ai::AIState;
+use crate::systems::perception::{PerceptionMemory, StimulusKind};
+use crate::systems::spawners::*;
+use crate::systems::traffic::*;
+use crate::systems::urban_security::*;
+
+pub const POLICE_CONFIG_PATH: &str = "data/config/police_config.ron";
+
+const DISPATCH_DISTANCE: f32 = 450.0;
+const CRUISER_SPEED: f32 = 180.0;
+const ARRIVAL_RADIUS: f32 = 70.0;
+const ROADBLOCK_RADIUS: f32 = 40.0;
+const DEESCALATION_HEAT: f32 = 10.0;
+const DEESCALATION_DELAY: f32 = 30.0;
+const WITHDRAW_DISTANCE: f32 = 600.0;
+
+// === CONFIGURATION ===
+#[derive(Resource, Deserialize, Serialize, Clone)]
+pub struct PoliceConfig {
+    pub heat_decay_rate: f32,
+    pub escalation_check_delay: f32,
+    pub escalation_cooldown: f32,
+    pub mass_hysteria_threshold: usize,
+    pub incident_heat_values: HashMap<String, f32>,
+    pub escalation_levels: HashMap<String, LevelConfig>,
+    #[serde(default)]
+    pub patrol_patterns: HashMap<String, Vec<(f32, f32)>>,
+    #[serde(default)]
+    pub level_patrol_patterns: HashMap<String, String>,
+}
+
+impl PoliceConfig {
+    pub fn level(&self, level: EscalationLevel) -> Option<&LevelConfig> {
+        self.escalation_levels.get(level.as_str())
+    }
+
+    pub fn heat_for(&self, incident: IncidentType) -> f32 {
+        self.incident_heat_values.get(incident.as_str()).copied().unwrap_or(5.0)
+    }
+
+    /// Patrol route for a unit of `level` anchored at `origin`, from the RON patterns when present
+    pub fn patrol_for(&self, level: EscalationLevel, origin: Vec2) -> Vec<Vec2> {
+        self.level_patrol_patterns.get(level.as_str())
+            .and_then(|pattern| self.patrol_patterns.get(pattern))
+            .filter(|points| !points.is_empty())
+            .map(|points| points.iter().map(|(x, y)| origin + Vec2::new(*x, *y)).collect())
+            .unwrap_or_else(|| generate_patrol_pattern(origin, level))
+    }
+}
+
+impl Default for PoliceConfig {
+    fn default() -> Self {
+        let mut incident_heat_values = HashMap::new();
+        incident_heat_values.insert("Gunshot".to_string(), 2.0);
+        incident_heat_values.insert("CivilianKilled".to_string(), 15.0);
+        incident_heat_values.insert("PoliceKilled".to_string(), 25.0);
+        incident_heat_values.insert("Explosion".to_string(), 20.0);
+        incident_heat_values.insert("MassHysteria".to_string(), 10.0);
+        incident_heat_values.insert("NetworkIntrusion".to_string(), 12.0);
+
+        let mut escalation_levels = HashMap::new();
+        escalation_levels.insert("None".to_string(), LevelConfig {
+            count: 0, response_time: 0.0, health: 0.0, weapon: "none".to_string(),
+            speed: 0.0, vision: 0.0, color: (0.0, 0.0, 0.0, 0.0), heat_threshold: 20.0, spawn_interval: 0.0,
+        });
+        escalation_levels.insert("Patrol".to_string(), LevelConfig {
+            count: 2, response_time: 15.0, health: 60.0, weapon: "pistol".to_string(),
+            speed: 80.0, vision: 150.0, color: (0.3, 0.3, 0.8, 1.0), heat_threshold: 999999.0, spawn_interval: 12.0,
+        });
+
+        Self {
+            heat_decay_rate: 1.0,
+            escalation_check_delay: 5.0,
+            escalation_cooldown: 10.0,
+            mass_hysteria_threshold: 8,
+            incident_heat_values,
+            escalation_levels,
+            patrol_patterns: HashMap::new(),
+            level_patrol_patterns: HashMap::new(),
+        }
+    }
+}
+
+pub fn load_police_config() -> PoliceConfig {
+    match std::fs::read_to_string(POLICE_CONFIG_PATH) {
+        Ok(config_str) => ron::from_str(&config_str).unwrap_or_else(|e| {
+            error!("Failed to parse {}: {}", POLICE_CONFIG_PATH, e);
+            PoliceConfig::default()
+        }),
+        Err(e) => {
+            warn!("Could not read {}: {}, using defaults", POLICE_CONFIG_PATH, e);
+            PoliceConfig::default()
+        }
+    }
+}
+
+pub fn setup_police_system(mut commands: Commands) {
+    // Load configuration from file
+    let config = load_police_config();
+
+    // Insert as resources
+    commands.insert_resource(config);
+    commands.insert_resource(PoliceResponse::default());
+    commands.insert_resource(PoliceEscalation::default());
+}
+
+// === EVENTS ===
+/// Raised by anything the police should hear about (explosions, bodies, alarms)
+#[derive(Event)]
+pub struct PoliceIncidentEvent {
+    pub position: Vec2,
+    pub incident: IncidentType,
+}
+
+// === RESOURCES ===
+#[derive(Clone)]
+pub struct PoliceUnit {
+    pub entity: Entity,
+    pub unit_type: EscalationLevel,
+    pub spawn_time: f32,
+}
+
+#[derive(Clone)]
+pub struct PendingDispatch {
+    pub level: EscalationLevel,
+    pub target: Vec2,
+    pub eta: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct PoliceEscalation {
+    pub level: EscalationLevel,
+    pub escalation_timer: f32,
+    pub cooldown: f32,
+    pub reinforcement_timer: f32,
+    pub calm_timer: f32,
+    pub incident_count: u32,
+    pub hysteria_reported: bool,
+    pub pending: Vec<PendingDispatch>,
+    pub active_units: Vec<PoliceUnit>,
+    pub roadblocks: Vec<Vec2>,
+}
+
+impl PoliceEscalation {
+    fn queue_dispatch(&mut self, level: EscalationLevel, target: Vec2, config: &PoliceConfig) {
+        let Some(level_config) = config.level(level) else { return; };
+        if level_config.count == 0 {
+            return;
+        }
+        self.pending.push(PendingDispatch { level, target, eta: level_config.response_time });
+        self.reinforcement_timer = level_config.spawn_interval;
+        info!("Police dispatch queued: {:?} arriving in {:.0}s", level, level_config.response_time);
+    }
+}
+
+// === COMPONENTS ===
+/// Police car en route to an incident; its crew deploys on arrival
+#[derive(Component)]
+pub struct PoliceCruiser {
+    pub level: EscalationLevel,
+    pub target: Vec2,
+}
+
+#[derive(Component)]
+pub struct PoliceWithdrawing {
+    pub exit: Vec2,
+}
+
+// === SYSTEMS ===
+/// Converts combat, noise, panic and reported incidents into heat
+pub fn police_incident_system(
+    mut response: ResMut<PoliceResponse>,
+    mut escalation: ResMut<PoliceEscalation>,
+    mut incident_events: EventReader<PoliceIncidentEvent>,
+    mut combat_events: EventReader<CombatEvent>,
+    mut audio_events: EventReader<AudioEvent>,
+    dead_civilians: Query<&Transform, (With<Civilian>, With<Dead>)>,
+    dead_police: Query<&Transform, (With<Police>, With<Dead>)>,
+    civilians: Query<&Morale, (With<Civilian>, Without<Dead>)>,
+    config: Res<PoliceConfig>,
+) {
+    let mut incidents: Vec<(Vec2, IncidentType)> = incident_events.read()
+        .map(|event| (event.position, event.incident))
+        .collect();
+
+    for event in combat_events.read() {
+        if !event.hit { continue; }
+
+        if let Ok(transform) = dead_civilians.get(event.target) {
+            response.civilian_casualties += 1;
+            incidents.push((transform.translation.truncate(), IncidentType::CivilianKilled));
+        } else if let Ok(transform) = dead_police.get(event.target) {
+            incidents.push((transform.translation.truncate(), IncidentType::PoliceKilled));
+        }
+    }
+
+    // Gunshots carry no position; attribute them to the last known incident
+    if let Some(pos) = response.last_incident_pos {
+        for event in audio_events.read() {
+            if matches!(event.sound, AudioType::Gunshot) {
+                incidents.push((pos, IncidentType::Gunshot));
+            }
+        }
+    }
+
+    // Mass hysteria is reported once each time the panicked crowd crosses the threshold
+    let panicked = civilians.iter().filter(|m| m.is_panicked()).count();
+    let hysteria = panicked >= config.mass_hysteria_threshold;
+    if hysteria && !escalation.hysteria_reported {
+        if let Some(pos) = response.last_incident_pos {
+            incidents.push((pos, IncidentType::MassHysteria));
+        }
+    }
+    escalation.hysteria_reported = hysteria;
+
+    for (pos, incident) in incidents {
+        response.add_incident(pos, config.heat_for(incident));
+        escalation.incident_count += 1;
+        escalation.escalation_timer = config.escalation_check_delay;
+        escalation.calm_timer = 0.0;
+    }
+}
+
+/// Heat decay, escalation, reinforcements and stand-down
+pub fn police_escalation_system(
+    mut commands: Commands,
+    mut response: ResMut<PoliceResponse>,
+    mut escalation: ResMut<PoliceEscalation>,
+    mut traffic_system: ResMut<TrafficSystem>,
+    units: Query<&Transform, (With<Police>, Without<Dead>)>,
+    config: Res<PoliceConfig>,
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+) {
+    if game_mode.paused { return; }
+
+    let dt = time.delta_secs();
+    response.heat_level = (response.heat_level - dt * config.heat_decay_rate).max(0.0);
+    escalation.escalation_timer -= dt;
+    escalation.cooldown -= dt;
+    escalation.reinforcement_timer -= dt;
+
+    let target = response.last_incident_pos.unwrap_or(Vec2::ZERO);
+    let current = escalation.level;
+
+    // === ESCALATION ===
+    let threshold = config.level(current).map_or(f32::MAX, |l| l.heat_threshold);
+    if escalation.escalation_timer <= 0.0 && escalation.cooldown <= 0.0 && response.heat_level >= threshold && current.next() != current {
+        escalation.level = current.next();
+        escalation.cooldown = config.escalation_cooldown;
+        escalation.calm_timer = 0.0;
+        info!("Police escalated to {:?} (heat {:.0})", escalation.level, response.heat_level);
+
+        let level = escalation.level;
+        escalation.queue_dispatch(level, target, &config);
+        return;
+    }
+
+    // === REINFORCEMENTS ===
+    if current != EscalationLevel::None && escalation.pending.is_empty() && escalation.reinforcement_timer <= 0.0 {
+        let wanted = config.level(current).map_or(0, |l| l.count) as usize;
+        let on_scene = escalation.active_units.iter().filter(|u| u.unit_type == current).count();
+        if on_scene < wanted {
+            escalation.queue_dispatch(current, target, &config);
+        } else {
+            escalation.reinforcement_timer = config.level(current).map_or(30.0, |l| l.spawn_interval);
+        }
+    }
+
+    // === DE-ESCALATION ===
+    if current == EscalationLevel::None {
+        return;
+    }
+
+    if response.heat_level < DEESCALATION_HEAT {
+        escalation.calm_timer += dt;
+    } else {
+        escalation.calm_timer = 0.0;
+    }
+
+    if escalation.calm_timer >= DEESCALATION_DELAY {
+        escalation.level = current.prev();
+        escalation.calm_timer = 0.0;
+        escalation.pending.retain(|d| d.level <= current.prev());
+        info!("Police de-escalated to {:?}", escalation.level);
+
+        if escalation.level == EscalationLevel::None {
+            stand_down(&mut commands, &mut escalation, &mut traffic_system, &units, target);
+        }
+    }
+}
+
+/// Spawns a cruiser at the nearest road entry once a dispatch's response time elapses
+pub fn police_dispatch_system(
+    mut commands: Commands,
+    mut escalation: ResMut<PoliceEscalation>,
+    traffic_system: Res<TrafficSystem>,
+    sprites: Res<GameSprites>,
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+) {
+    if game_mode.paused { return; }
+
+    let dt = time.delta_secs();
+    let mut arrived = Vec::new();
+    escalation.pending.retain_mut(|dispatch| {
+        dispatch.eta -= dt;
+        if dispatch.eta <= 0.0 {
+            arrived.push(dispatch.clone());
+            false
+        } else {
+            true
+        }
+    });
+
+    for dispatch in arrived {
+        let spawn_pos = entry_point(&traffic_system.road_network.spawn_points, dispatch.target);
+        let cruiser = spawn_traffic_vehicle(&mut commands, spawn_pos, TrafficVehicleType::PoliceCar, &sprites);
+
+        // Steered by police_cruiser_system rather than the traffic flow field
+        commands.entity(cruiser)
+            .remove::<TrafficFlow>()
+            .insert(PoliceCruiser { level: dispatch.level, target: dispatch.target });
+    }
+}
+
+/// Drives cruisers to the incident, deploys their crew and sets up roadblocks
+pub fn police_cruiser_system(
+    mut commands: Commands,
+    mut escalation: ResMut<PoliceEscalation>,
+    mut traffic_system: ResMut<TrafficSystem>,
+    mut cruisers: Query<(Entity, &Transform, &PoliceCruiser, &mut Velocity, Option<&mut EmergencyVehicle>)>,
+    sprites: Res<GameSprites>,
+    config: Res<PoliceConfig>,
+    time: Res<Time>,
+) {
+    for (entity, transform, cruiser, mut velocity, emergency) in cruisers.iter_mut() {
+        let pos = transform.translation.truncate();
+        let to_target = cruiser.target - pos;
+
+        if let Some(mut emergency) = emergency {
+            emergency.siren_active = true;
+            emergency.response_target = Some(cruiser.target);
+        }
+
+        if to_target.length() > ARRIVAL_RADIUS {
+            velocity.linvel = to_target.normalize_or_zero() * CRUISER_SPEED;
+            continue;
+        }
+
+        velocity.linvel = Vec2::ZERO;
+        commands.entity(entity).remove::<PoliceCruiser>();
+
+        let Some(level_config) = config.level(cruiser.level) else { continue; };
+        let side = Vec2::new(-to_target.y, to_target.x).normalize_or_zero();
+        for i in 0..level_config.count {
+            let offset = side * ((i as f32 - level_config.count as f32 / 2.0) * 25.0) - to_target.normalize_or_zero() * 20.0;
+            let unit = spawn_police_unit(&mut commands, pos + offset, cruiser.level, &sprites, &config);
+
+            // Responders arrive already knowing where the trouble is
+            let mut memory = PerceptionMemory::default();
+            memory.record(StimulusKind::Alert, cruiser.target, None, 1.0, time.elapsed_secs());
+            commands.entity(unit).insert(memory);
+
+            escalation.active_units.push(PoliceUnit {
+                entity: unit,
+                unit_type: cruiser.level,
+                spawn_time: time.elapsed_secs(),
+            });
+        }
+
+        if cruiser.level >= EscalationLevel::Armed {
+            mark_road_blocked(&mut traffic_system, pos, ROADBLOCK_RADIUS);
+            escalation.roadblocks.push(pos);
+        }
+
+        info!("{:?} police unit deployed {} officers", cruiser.level, level_config.count);
+    }
+}
+
+/// Drops dead officers from the roster and removes withdrawn ones once they leave the area
+pub fn police_cleanup_system(
+    mut commands: Commands,
+    mut escalation: ResMut<PoliceEscalation>,
+    dead_police: Query<Entity, (With<Police>, With<Dead>)>,
+    withdrawing: Query<(Entity, &Transform, &PoliceWithdrawing), Without<Dead>>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    for entity in dead_police.iter() {
+        escalation.active_units.retain(|unit| unit.entity != entity);
+    }
+
+    for (entity, transform, withdraw) in withdrawing.iter() {
+        if transform.translation.truncate().distance(withdraw.exit) < 30.0 {
+            commands.entity(entity).insert(MarkedForDespawn);
+        } else {
+            action_events.write(ActionEvent { entity, action: Action::MoveTo(withdraw.exit) });
+        }
+    }
+}
+
+pub fn reset_police_state(
+    mut response: ResMut<PoliceResponse>,
+    mut escalation: ResMut<PoliceEscalation>,
+    mut traffic_system: ResMut<TrafficSystem>,
+) {
+    *response = PoliceResponse::default();
+    *escalation = PoliceEscalation::default();
+    clear_road_blocks(&mut traffic_system);
+}
+
+// === HELPERS ===
+fn entry_point(spawn_points: &[Vec2], target: Vec2) -> Vec2 {
+    // Closest road entry that is still out of sight of the incident
+    spawn_points.iter()
+        .copied()
+        .min_by(|a, b| {
+            let score = |p: &Vec2| (p.distance(target) - DISPATCH_DISTANCE).abs();
+            score(a).partial_cmp(&score(b)).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(target + Vec2::new(DISPATCH_DISTANCE, 0.0))
+}
+
+fn stand_down(
+    commands: &mut Commands,
+    escalation: &mut PoliceEscalation,
+    traffic_system: &mut ResMut<TrafficSystem>,
+    units: &Query<&Transform, (With<Police>, Without<Dead>)>,
+    incident_pos: Vec2,
+) {
+    for unit in escalation.active_units.drain(..) {
+        let Ok(transform) = units.get(unit.entity) else { continue; };
+        let pos = transform.translation.truncate();
+        let away = (pos - incident_pos).normalize_or_zero();
+        let exit = pos + if away == Vec2::ZERO { Vec2::X } else { away } * WITHDRAW_DISTANCE;
+
+        commands.entity(unit.entity)
+            .remove::<GoapAgent>()
+            .insert((AIState::default(), PoliceWithdrawing { exit }));
+    }
+
+    if !escalation.roadblocks.is_empty() {
+        escalation.roadblocks.clear();
+        clear_road_blocks(traffic_system);
+    }
+
+    info!("Police standing down");
+}
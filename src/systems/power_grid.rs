@@ -1,7 +1,11 @@
 // src/systems/power_grid.rs - Complete power grid implementation
 use bevy::prelude::*;
+use bevy_light_2d::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
 use crate::core::*;
-use crate::systems::spawners::*;
+use crate::systems::device_takeover::{Hijacked, Overloaded, Spotted, SPOTTED_SECS};
+use crate::systems::projectiles::spawn_projectile;
 
 // === DEVICE COMPONENTS ===
 #[derive(Component)]
@@ -62,43 +66,265 @@ pub struct Drone {
 }
 
 // === POWER GRID MANAGEMENT ===
-pub fn power_grid_management_system(
-    power_stations: Query<(Entity, &PowerStation, &DeviceState)>,
+const SOLVE_INTERVAL: f32 = 0.5;
+/// Each network's stations feed it through one substation
+const SUBSTATION_CAPACITY: f32 = 150.0;
+const FEEDER_CAPACITY: f32 = 120.0;
+/// Tie lines between neighbouring substations, what a district falls back on when its own stations go
+const TIE_CAPACITY: f32 = 45.0;
+const TIES_PER_SUBSTATION: usize = 2;
+/// Share of its draw a district needs to count as fully powered, and below which it's effectively dark
+const FULL_SUPPLY: f32 = 0.95;
+const BROWNOUT_FLOOR: f32 = 0.4;
+/// Districts with devices this secure get a backup generator
+const BACKUP_SECURITY_LEVEL: u8 = 4;
+const BACKUP_FUEL_SECS: f32 = 45.0;
+/// A station run flat out this long after the grid has lost generation trips and takes a while to restart
+const TRIP_AFTER_SECS: f32 = 6.0;
+const TRIP_RECOVERY_SECS: f32 = 20.0;
+
+/// What a device draws from its district
+fn device_load(device_type: DeviceType) -> f32 {
+    match device_type {
+        DeviceType::Turret => 12.0,
+        DeviceType::SecuritySystem => 10.0,
+        DeviceType::Elevator => 8.0,
+        DeviceType::Drone | DeviceType::Billboard => 6.0,
+        DeviceType::Camera | DeviceType::SecurityCamera => 4.0,
+        DeviceType::Door | DeviceType::Terminal => 3.0,
+        DeviceType::StreetLight | DeviceType::TrafficLight | DeviceType::AlarmPanel | DeviceType::ATM => 2.0,
+        DeviceType::ElectricPanel => 1.0,
+        DeviceType::Vehicle | DeviceType::PowerStation => 0.0,
+    }
+}
+
+/// Lays the grid out from the stations and networked devices in the mission; blown stations
+/// are left out. Trip timers and backup fuel carry over from the previous layout so a rebuild
+/// can't reset them.
+fn build_topology(
+    power_grid: &PowerGrid,
+    stations: &Query<(Entity, &Transform, &mut PowerStation, &DeviceState), Without<Overloaded>>,
+    devices: &Query<(&Transform, &Hackable), Without<PowerStation>>,
+) -> GridTopology {
+    let previous = &power_grid.topology;
+    let mut topology = GridTopology::default();
+
+    let mut network_ids: Vec<&String> = power_grid.networks.keys().collect();
+    network_ids.sort();
+
+    // Stations, and a substation for every network that has any
+    let mut substations = Vec::new();
+    for &network_id in &network_ids {
+        let mut fed_by: Vec<(Entity, Vec2, f32)> = stations.iter()
+            .filter(|(_, _, station, _)| &station.network_id == network_id)
+            .map(|(entity, transform, station, _)| (entity, transform.translation.truncate(), station.max_capacity as f32))
+            .collect();
+        if fed_by.is_empty() {
+            continue;
+        }
+        fed_by.sort_by_key(|(entity, ..)| *entity);
+
+        let center = fed_by.iter().map(|(_, pos, _)| *pos).sum::<Vec2>() / fed_by.len() as f32;
+        let substation = topology.add_node(GridNode::new(network_id.clone(), GridTier::Substation, center, SUBSTATION_CAPACITY));
+        substations.push(substation);
+
+        for (entity, position, capacity) in fed_by {
+            let mut node = GridNode::new(network_id.clone(), GridTier::Station, position, capacity);
+            node.entity = Some(entity);
+            if let Some(old) = previous.nodes.iter().find(|old| old.entity == Some(entity)) {
+                node.online = old.online;
+                node.strain = old.strain;
+                node.trip_timer = old.trip_timer;
+            }
+            let station = topology.add_node(node);
+            topology.link(station, substation, capacity);
+        }
+    }
+
+    // Tie lines to the nearest substations, both ways
+    let nearest = |topology: &GridTopology, from: Vec2, skip: Option<usize>| {
+        let mut by_distance: Vec<usize> = substations.iter().copied().filter(|&other| Some(other) != skip).collect();
+        by_distance.sort_by(|&a, &b| {
+            topology.nodes[a].position.distance(from).total_cmp(&topology.nodes[b].position.distance(from))
+        });
+        by_distance
+    };
+    let mut tied = HashSet::new();
+    for &substation in &substations {
+        let position = topology.nodes[substation].position;
+        for other in nearest(&topology, position, Some(substation)).into_iter().take(TIES_PER_SUBSTATION) {
+            if tied.insert((substation.min(other), substation.max(other))) {
+                topology.link(substation, other, TIE_CAPACITY);
+                topology.link(other, substation, TIE_CAPACITY);
+            }
+        }
+    }
+
+    // A district for every network with something drawing power
+    for &network_id in &network_ids {
+        let network = &power_grid.networks[network_id];
+        let members: Vec<(Vec2, &Hackable)> = network.connected_devices.iter()
+            .filter_map(|&entity| devices.get(entity).ok())
+            .map(|(transform, hackable)| (transform.translation.truncate(), hackable))
+            .collect();
+        let demand: f32 = members.iter().map(|(_, hackable)| device_load(hackable.device_type)).sum();
+        if demand <= 0.0 {
+            continue;
+        }
+
+        let center = members.iter().map(|(pos, _)| *pos).sum::<Vec2>() / members.len() as f32;
+        let mut node = GridNode::new(network_id.clone(), GridTier::District, center, demand);
+        node.supply = network.supply;
+        if members.iter().any(|(_, hackable)| hackable.security_level >= BACKUP_SECURITY_LEVEL) {
+            node.backup_fuel = Some(previous.district(network_id).and_then(|old| old.backup_fuel).unwrap_or(BACKUP_FUEL_SECS));
+        }
+        let district = topology.add_node(node);
+
+        // Fed by its own substation, or the closest one if the network has no stations of its own
+        let own = substations.iter().copied().find(|&substation| &topology.nodes[substation].id == network_id);
+        if let Some(feeder) = own.or_else(|| nearest(&topology, center, None).first().copied()) {
+            topology.link(feeder, district, FEEDER_CAPACITY);
+        }
+    }
+
+    topology
+}
+
+/// Re-routes power around whatever's failed, works out each district's supply and sends a
+/// PowerGridEvent for every district whose supply changed
+pub fn power_flow_system(
+    mut stations: Query<(Entity, &Transform, &mut PowerStation, &DeviceState), Without<Overloaded>>,
+    blown: Query<(), (With<PowerStation>, With<Overloaded>)>,
+    devices: Query<(&Transform, &Hackable), Without<PowerStation>>,
     mut power_grid: ResMut<PowerGrid>,
     mut power_events: EventWriter<PowerGridEvent>,
+    mut since_solve: Local<f32>,
+    time: Res<Time>,
 ) {
-    for (entity, station, device_state) in power_stations.iter() {
-        if let Some(network) = power_grid.networks.get_mut(&station.network_id) {
-            let was_powered = network.powered;
-
-            // Network is powered if at least one power station is operational
-            network.powered = power_stations.iter()
-                .filter(|(_, s, _)| s.network_id == station.network_id)
-                .any(|(_, _, state)| state.operational && state.powered);
-
-            // Send event if power state changed
-            if was_powered != network.powered {
-                power_events.write(PowerGridEvent {
-                    network_id: station.network_id.clone(),
-                    powered: network.powered,
-                    source: entity,
-                });
+    let station_count = power_grid.topology.nodes.iter().filter(|node| node.tier == GridTier::Station).count();
+    if power_grid.topology_dirty || station_count != stations.iter().count() {
+        power_grid.topology = build_topology(&power_grid, &stations, &devices);
+        power_grid.topology_dirty = false;
+    }
+
+    *since_solve += time.delta_secs();
+    if *since_solve < SOLVE_INTERVAL {
+        return;
+    }
+    let elapsed = std::mem::take(&mut *since_solve);
+
+    // No stations, no grid: devices run off their own supply. Once they've all blown, though,
+    // the districts still solve to a blackout.
+    if stations.is_empty() && blown.is_empty() {
+        return;
+    }
+
+    let topology = &mut power_grid.topology;
+
+    // Restart tripped stations whose timer has run out
+    for node in topology.nodes.iter_mut().filter(|node| !node.online) {
+        node.trip_timer -= elapsed;
+        if node.trip_timer <= 0.0 {
+            node.online = true;
+            info!("Power station on {} back online", node.id);
+        }
+    }
+
+    let generating = |node: &GridNode| {
+        node.online && node.entity
+            .and_then(|entity| stations.get(entity).ok())
+            .is_some_and(|(_, _, _, state)| state.operational && state.powered)
+    };
+    let available: Vec<f32> = topology.nodes.iter()
+        .map(|node| match node.tier {
+            GridTier::Station if generating(node) => node.capacity,
+            GridTier::Station => 0.0,
+            _ => node.capacity,
+        })
+        .collect();
+    let generation_lost = topology.nodes.iter().any(|node| node.tier == GridTier::Station && !generating(node));
+    topology.solve(|i, _| available[i]);
+
+    let shortfall = topology.nodes.iter()
+        .any(|node| node.tier == GridTier::District && node.flow < node.capacity * FULL_SUPPLY);
+
+    for node in topology.nodes.iter_mut() {
+        match node.tier {
+            GridTier::Station => {
+                if let Some((_, _, mut station, _)) = node.entity.and_then(|entity| stations.get_mut(entity).ok()) {
+                    station.current_load = node.flow.round() as u32;
+                }
+
+                // Stations picking up the slack for a failed one can give out in turn
+                let saturated = node.online && node.capacity > 0.0 && node.flow >= node.capacity * 0.98;
+                if saturated && shortfall && generation_lost {
+                    node.strain += elapsed;
+                } else {
+                    node.strain = (node.strain - elapsed).max(0.0);
+                }
+                if node.strain >= TRIP_AFTER_SECS {
+                    node.online = false;
+                    node.strain = 0.0;
+                    node.trip_timer = TRIP_RECOVERY_SECS;
+                    warn!("Power station on {} tripped under load", node.id);
+                }
+            }
+            GridTier::District => {
+                let ratio = node.flow / node.capacity;
+                let on_backup = node.backup_fuel.is_some_and(|fuel| fuel > 0.0);
+                let supply = if ratio >= FULL_SUPPLY {
+                    SupplyState::Powered
+                } else if ratio >= BROWNOUT_FLOOR {
+                    SupplyState::Brownout
+                } else if on_backup {
+                    SupplyState::Backup
+                } else {
+                    SupplyState::Blackout
+                };
+
+                if supply == SupplyState::Backup {
+                    if let Some(fuel) = node.backup_fuel.as_mut() {
+                        *fuel = (*fuel - elapsed).max(0.0);
+                    }
+                }
+
+                if supply != node.supply {
+                    info!("District {}: {:?} -> {:?} ({:.0}/{:.0})", node.id, node.supply, supply, node.flow, node.capacity);
+                    node.supply = supply;
+                    power_events.write(PowerGridEvent {
+                        network_id: node.id.clone(),
+                        powered: supply.is_powered(),
+                        supply,
+                    });
+                }
             }
+            GridTier::Substation => {}
         }
     }
 }
 
+/// Stale networks from the last mission would otherwise end up in this one's topology
+pub fn reset_power_grid(mut power_grid: ResMut<PowerGrid>) {
+    *power_grid = PowerGrid::default();
+}
+
 // === DEVICE BEHAVIOR SYSTEMS ===
 pub fn street_light_system(
-    mut street_lights: Query<(&mut Sprite, &DeviceState), With<StreetLight>>,
+    mut street_lights: Query<(&mut Sprite, &DeviceState, Option<&Hackable>, Option<&mut PointLight2d>), With<StreetLight>>,
+    power_grid: Res<PowerGrid>,
 ) {
-    for (mut sprite, device_state) in street_lights.iter_mut() {
-        if device_state.powered && device_state.operational {
-            sprite.color = Color::srgb(0.9, 0.9, 0.7); // Bright
-            // enable light2d
+    for (mut sprite, device_state, hackable, light) in street_lights.iter_mut() {
+        let supply = power_grid.supply_for(hackable.and_then(|h| h.network_id.as_ref()));
+        let (color, intensity) = if !device_state.powered || !device_state.operational {
+            (Color::srgb(0.3, 0.3, 0.3), 0.0) // Dark
+        } else if supply == SupplyState::Brownout {
+            (Color::srgb(0.55, 0.55, 0.4), 1.2) // Dimmed
         } else {
-            sprite.color = Color::srgb(0.3, 0.3, 0.3); // Dark
-            // disable light2s
+            (Color::srgb(0.9, 0.9, 0.7), 3.0) // Bright
+        };
+        sprite.color = color;
+        if let Some(mut light) = light {
+            light.intensity = intensity;
         }
     }
 }
@@ -147,13 +373,17 @@ pub fn traffic_light_system(
 }
 
 pub fn security_camera_system(
-    mut cameras: Query<(&mut SecurityCamera, &mut Vision, &Transform, &DeviceState)>,
+    mut cameras: Query<(&mut SecurityCamera, &mut Vision, &Transform, &DeviceState, Option<&Hackable>)>,
     agent_query: Query<&Transform, (With<Agent>, Without<SecurityCamera>)>,
     mut alert_events: EventWriter<AlertEvent>,
+    power_grid: Res<PowerGrid>,
     time: Res<Time>,
 ) {
-    for (mut camera, mut vision, camera_transform, device_state) in cameras.iter_mut() {
-        camera.active = device_state.powered && device_state.operational;
+    for (mut camera, mut vision, camera_transform, device_state, hackable) in cameras.iter_mut() {
+        // Browned-out cameras keep cutting out, leaving gaps to slip past in
+        let supply = power_grid.supply_for(hackable.and_then(|h| h.network_id.as_ref()));
+        let flickering = supply == SupplyState::Brownout && (time.elapsed_secs() * 0.5).fract() < 0.4;
+        camera.active = device_state.powered && device_state.operational && !flickering;
 
         if !camera.active {
             continue;
@@ -194,13 +424,16 @@ pub fn security_camera_system(
 }
 
 pub fn automated_turret_system(
-    mut turrets: Query<(Entity, &mut AutomatedTurret, &Transform, &DeviceState)>,
-    agent_query: Query<(Entity, &Transform), With<Agent>>,
+    mut commands: Commands,
+    mut turrets: Query<(Entity, &mut AutomatedTurret, &Transform, &DeviceState, Option<&Hackable>, Has<Hijacked>)>,
+    target_query: Query<(Entity, &Transform, Has<Agent>), (Or<(With<Agent>, With<Enemy>)>, Without<Dead>)>,
     mut combat_events: EventWriter<CombatEvent>,
     mut audio_events: EventWriter<AudioEvent>,
+    power_grid: Res<PowerGrid>,
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
 ) {
-    for (turret_entity, mut turret, turret_transform, device_state) in turrets.iter_mut() {
+    for (turret_entity, mut turret, turret_transform, device_state, hackable, hijacked) in turrets.iter_mut() {
         if !device_state.powered || !device_state.operational {
             turret.target = None;
             continue;
@@ -208,46 +441,124 @@ pub fn automated_turret_system(
 
         turret.fire_timer -= time.delta_secs();
         let turret_pos = turret_transform.translation.truncate();
+        // A hijacked turret turns on the people it was guarding
+        let hostile = |is_agent: bool| is_agent != hijacked;
 
         // Find target
         if turret.target.is_none() {
-            turret.target = agent_query.iter()
-                .filter(|(_, agent_transform)| {
-                    turret_pos.distance(agent_transform.translation.truncate()) <= turret.range
+            turret.target = target_query.iter()
+                .filter(|(_, agent_transform, is_agent)| {
+                    hostile(*is_agent) && turret_pos.distance(agent_transform.translation.truncate()) <= turret.range
                 })
-                .min_by(|(_, a), (_, b)| {
+                .min_by(|(_, a, _), (_, b, _)| {
                     let dist_a = turret_pos.distance(a.translation.truncate());
                     let dist_b = turret_pos.distance(b.translation.truncate());
                     dist_a.partial_cmp(&dist_b).unwrap_or(std::cmp::Ordering::Equal)
                 })
-                .map(|(entity, _)| entity);
+                .map(|(entity, _, _)| entity);
         }
 
         // Fire at target
         if let Some(target) = turret.target {
-            if let Ok((_, target_transform)) = agent_query.get(target) {
-                let distance = turret_pos.distance(target_transform.translation.truncate());
-
-                if distance <= turret.range && turret.fire_timer <= 0.0 {
-                    // Fire!
-                    combat_events.write(CombatEvent {
-                        attacker: turret_entity,
-                        target,
-                        damage: turret.damage,
-                        hit: rand::random::<f32>() < 0.8, // 80% accuracy
-                    });
+            match target_query.get(target) {
+                Ok((_, target_transform, is_agent)) if hostile(is_agent) => {
+                    let target_pos = target_transform.translation.truncate();
+                    let distance = turret_pos.distance(target_pos);
+
+                    if distance <= turret.range && turret.fire_timer <= 0.0 {
+                        // Fire!
+                        let hit = rng.stream(RngStream::Combat).gen::<f32>() < 0.8; // 80% accuracy
+                        if hijacked {
+                            if hit {
+                                spawn_projectile(&mut commands, turret_entity, target, turret_pos, target_pos, turret.damage, WeaponType::Rifle);
+                            }
+                        } else {
+                            combat_events.write(CombatEvent {
+                                attacker: turret_entity,
+                                target,
+                                damage: turret.damage,
+                                hit,
+                            });
+                        }
+
+                        audio_events.write(AudioEvent {
+                            sound: AudioType::Gunshot,
+                            volume: 0.6,
+                        });
+
+                        // Half the rate of fire on a browned-out grid
+                        let supply = power_grid.supply_for(hackable.and_then(|h| h.network_id.as_ref()));
+                        let slowdown = if supply == SupplyState::Brownout { 2.0 } else { 1.0 };
+                        turret.fire_timer = slowdown / turret.fire_rate;
+                    } else if distance > turret.range {
+                        turret.target = None; // Lost target
+                    }
+                }
+                _ => turret.target = None, // Target gone, or no longer an enemy of this turret
+            }
+        }
+    }
+}
 
-                    audio_events.write(AudioEvent {
-                        sound: AudioType::Gunshot,
-                        volume: 0.6,
-                    });
+/// Drones fly their patrol loop and call in agents like cameras do; a hijacked drone flies the
+/// player's route instead and marks the enemies it sees
+pub fn drone_system(
+    mut commands: Commands,
+    mut drones: Query<(Entity, &mut Drone, &mut Transform, &DeviceState, Option<&mut Hijacked>)>,
+    agent_query: Query<&Transform, (With<Agent>, Without<Drone>)>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<Dead>, Without<Drone>)>,
+    mut alert_events: EventWriter<AlertEvent>,
+    time: Res<Time>,
+) {
+    for (drone_entity, mut drone, mut transform, device_state, hijacked) in drones.iter_mut() {
+        if !device_state.powered || !device_state.operational {
+            continue;
+        }
 
-                    turret.fire_timer = 1.0 / turret.fire_rate;
-                } else if distance > turret.range {
-                    turret.target = None; // Lost target
+        let pos = transform.translation.truncate();
+        let step = drone.speed * time.delta_secs();
+
+        match hijacked {
+            Some(mut hijacked) => {
+                if let Some(&waypoint) = hijacked.route.front() {
+                    if pos.distance(waypoint) <= step {
+                        transform.translation = waypoint.extend(transform.translation.z);
+                        hijacked.route.pop_front();
+                    } else {
+                        transform.translation += ((waypoint - pos).normalize() * step).extend(0.0);
+                    }
+                }
+
+                for (enemy, enemy_transform) in enemy_query.iter() {
+                    if pos.distance(enemy_transform.translation.truncate()) <= drone.detection_range {
+                        commands.entity(enemy).insert(Spotted { remaining: SPOTTED_SECS });
+                    }
+                }
+            }
+            None => {
+                if !drone.patrol_route.is_empty() {
+                    let index = drone.current_waypoint % drone.patrol_route.len();
+                    let waypoint = drone.patrol_route[index];
+                    if pos.distance(waypoint) <= step {
+                        transform.translation = waypoint.extend(transform.translation.z);
+                        drone.current_waypoint = (index + 1) % drone.patrol_route.len();
+                    } else {
+                        transform.translation += ((waypoint - pos).normalize() * step).extend(0.0);
+                    }
+                }
+
+                for agent_transform in agent_query.iter() {
+                    let agent_pos = agent_transform.translation.truncate();
+                    if pos.distance(agent_pos) <= drone.detection_range {
+                        alert_events.write(AlertEvent {
+                            alerter: drone_entity,
+                            position: agent_pos,
+                            alert_level: 2,
+                            source: AlertSource::SpottedAgent,
+                            alert_type: AlertType::EnemySpotted,
+                        });
+                    }
                 }
-            } else {
-                turret.target = None; // Target no longer exists
             }
         }
     }
@@ -257,7 +568,7 @@ pub fn security_door_system(
     mut doors: Query<(&mut SecurityDoor, &DeviceState, &mut bevy_rapier2d::prelude::Collider)>,
 ) {
     for (mut door, device_state, mut collider) in doors.iter_mut() {
-        // Doors unlock when hacked or powered down
+        // Doors unlock when hacked or powered down; a backup generator keeps them locked
         if !device_state.operational || !device_state.powered {
             door.locked = false;
         }
@@ -271,71 +582,6 @@ pub fn security_door_system(
     }
 }
 
-// === INTEGRATION HELPERS ===
-pub fn setup_district_power_grid(
-    commands: &mut Commands,
-    mut power_grid: ResMut<PowerGrid>,
-    center: Vec2,
-) {
-    let network_id = "district_main".to_string();
-
-    // Main power station
-    spawn_power_station(
-        commands,
-        center + Vec2::new(-200.0, -100.0),
-        network_id.clone(),
-        &mut power_grid,
-    );
-
-    // Street lights along main road
-    for i in 0..8 {
-        let x = center.x - 200.0 + (i as f32 * 50.0);
-        spawn_street_light(
-            commands,
-            Vec2::new(x, center.y),
-            network_id.clone(),
-            &mut power_grid,
-        );
-    }
-
-    // Traffic lights at intersections
-    spawn_traffic_light(
-        commands,
-        center + Vec2::new(-50.0, 0.0),
-        network_id.clone(),
-        &mut power_grid,
-    );
-    spawn_traffic_light(
-        commands,
-        center + Vec2::new(100.0, 50.0),
-        network_id.clone(),
-        &mut power_grid,
-    );
-
-    // Security infrastructure
-    let mut power_grid_option = Some(power_grid);
-
-    spawn_security_camera(
-        commands,
-        center + Vec2::new(0.0, 100.0),
-        Some(network_id.clone()),
-        &mut power_grid_option,
-    );
-
-    spawn_automated_turret(
-        commands,
-        center + Vec2::new(150.0, -50.0),
-        Some(network_id.clone()),
-        &mut power_grid_option,
-    );
-
-    spawn_security_door(
-        commands,
-        center + Vec2::new(200.0, 0.0),
-        Some(network_id),
-        &mut power_grid_option,
-    );
-}
 
 // === POWER GRID DEBUG ===
 pub fn power_grid_debug_system(
@@ -354,6 +600,34 @@ pub fn power_grid_debug_system(
 
     if !*show_power_debug { return; }
 
+    // Grid topology: links shade from green to red as they fill up
+    let topology = &power_grid.topology;
+    for link in &topology.links {
+        let (from, to) = (&topology.nodes[link.from], &topology.nodes[link.to]);
+        let load = if link.capacity > 0.0 { (link.flow / link.capacity).clamp(0.0, 1.0) } else { 0.0 };
+        let color = if link.flow > 0.0 { Color::srgb(0.2 + load * 0.8, 1.0 - load * 0.7, 0.2) } else { Color::srgba(0.5, 0.5, 0.5, 0.4) };
+        gizmos.line_2d(from.position, to.position, color);
+    }
+    for node in &topology.nodes {
+        let color = match node.tier {
+            GridTier::Station if !node.online => Color::srgb(0.8, 0.2, 0.2),
+            GridTier::Station => Color::srgb(0.9, 0.9, 0.2),
+            GridTier::Substation => Color::srgb(0.4, 0.7, 1.0),
+            GridTier::District => match node.supply {
+                SupplyState::Powered => Color::srgb(0.2, 0.8, 0.2),
+                SupplyState::Brownout => Color::srgb(0.9, 0.6, 0.1),
+                SupplyState::Backup => Color::srgb(0.6, 0.4, 0.9),
+                SupplyState::Blackout => Color::srgb(0.8, 0.2, 0.2),
+            },
+        };
+        let radius = match node.tier {
+            GridTier::Station => 14.0,
+            GridTier::Substation => 10.0,
+            GridTier::District => 18.0,
+        };
+        gizmos.circle_2d(node.position, radius, color);
+    }
+
     // Draw power connections
     for (transform, hackable, device_state) in hackable_query.iter() {
         if let Some(network_id) = &hackable.network_id {
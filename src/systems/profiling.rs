@@ -24,10 +24,11 @@ impl Default for ProfilingConfig {
     }
 }
 
-#[derive(Clone, Resource, Default, PartialEq)]
+#[derive(Clone, Resource, Default, PartialEq, serde::Serialize)]
 pub struct PerformanceMetrics {
     pub fps: f32,
     pub frame_time_ms: f32,
+    #[serde(skip)]
     pub frame_time_history: VecDeque<f32>,
     pub total_entities: usize,
     pub agent_count: usize,
@@ -60,6 +61,25 @@ impl Plugin for ProfilingPlugin {
 
 // === SYSTEMS ===
 
+// Helper function to profile systems
+pub fn profile_system<T>(name: &'static str) -> impl Fn(In<T>, ResMut<PerformanceMetrics>) -> T {
+    move |input: In<T>, mut metrics: ResMut<PerformanceMetrics>| {
+        let start = std::time::Instant::now();
+        let result = input.0;
+        
+        let elapsed = start.elapsed().as_secs_f32() * 1000.0;
+        match name {
+            "movement" => metrics.physics_ms = elapsed,
+            "depth_sorting" => metrics.depth_sort_ms = elapsed,
+            "goap_ai" | "legacy_ai" => metrics.ai_ms += elapsed,
+            "pathfinding" => metrics.pathfinding_ms = elapsed,
+            _ => {}
+        }
+        
+        result
+    }
+}
+
 fn collect_metrics(
     mut metrics: ResMut<PerformanceMetrics>,
     diagnostics: Res<DiagnosticsStore>,
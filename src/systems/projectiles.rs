@@ -198,7 +198,7 @@ pub fn unified_projectile_system(
     targets: Query<&Transform, (Without<Projectile>, Or<(With<Enemy>, With<Vehicle>, With<Agent>)>)>,
     mut combat_events: EventWriter<CombatEvent>,
     mut damage_text_events: EventWriter<DamageTextEvent>,
-    mut target_health: Query<&mut Health>,
+    mut target_health: Query<(&mut Health, Option<&Armor>)>,
     time: Res<Time>,
 ) {
     let dt = time.delta_secs();
@@ -289,7 +289,7 @@ fn handle_standard_projectile(
     targets: &Query<&Transform, (Without<Projectile>, Or<(With<Enemy>, With<Vehicle>, With<Agent>)>)>,
     combat_events: &mut EventWriter<CombatEvent>,
     damage_text_events: &mut EventWriter<DamageTextEvent>,
-    target_health: &mut Query<&mut Health>,
+    target_health: &mut Query<(&mut Health, Option<&Armor>)>,
     dt: f32,
 ) {
     if let Ok(target_t) = targets.get(projectile.target) {
@@ -331,7 +331,7 @@ fn handle_grenade_projectile(
     targets: &Query<&Transform, (Without<Projectile>, Or<(With<Enemy>, With<Vehicle>, With<Agent>)>)>,
     combat_events: &mut EventWriter<CombatEvent>,
     damage_text_events: &mut EventWriter<DamageTextEvent>,
-    target_health: &mut Query<&mut Health>,
+    target_health: &mut Query<(&mut Health, Option<&Armor>)>,
     dt: f32,
 ) {
     *fuse_timer -= dt;
@@ -339,7 +339,8 @@ fn handle_grenade_projectile(
     if *fuse_timer <= 0.0 {
         // Explode
         let pos = transform.translation.truncate();
-        spawn_explosion(commands, pos, 100.0, projectile.damage, ExplosionType::Grenade);
+        let explosion = spawn_explosion(commands, pos, 100.0, projectile.damage, ExplosionType::Grenade);
+        commands.entity(explosion).insert(DamageSource(projectile.attacker));
         commands.entity(entity).insert(MarkedForDespawn);
     } else {
         // Physics movement
@@ -365,7 +366,7 @@ fn handle_rocket_projectile(
     targets: &Query<&Transform, (Without<Projectile>, Or<(With<Enemy>, With<Vehicle>, With<Agent>)>)>,
     combat_events: &mut EventWriter<CombatEvent>,
     damage_text_events: &mut EventWriter<DamageTextEvent>,
-    target_health: &mut Query<&mut Health>,
+    target_health: &mut Query<(&mut Health, Option<&Armor>)>,
     dt: f32,
 ) {
     // Trail particles
@@ -384,7 +385,8 @@ fn handle_rocket_projectile(
         
         if current_pos.distance(target_pos) <= move_distance + 15.0 {
             // Hit with explosion
-            spawn_explosion(commands, target_pos, 120.0, projectile.damage, ExplosionType::Grenade);
+            let explosion = spawn_explosion(commands, target_pos, 120.0, projectile.damage, ExplosionType::Grenade);
+            commands.entity(explosion).insert(DamageSource(projectile.attacker));
             commands.entity(entity).insert(MarkedForDespawn);
         } else {
             transform.translation += direction.extend(0.0) * move_distance;
@@ -405,20 +407,20 @@ fn handle_beam_projectile(
     end_pos: &Vec2,
     combat_events: &mut EventWriter<CombatEvent>,
     damage_text_events: &mut EventWriter<DamageTextEvent>,
-    target_health: &mut Query<&mut Health>,
+    target_health: &mut Query<(&mut Health, Option<&Armor>)>,
 ) {
     // Apply damage once
     if !*applied_damage {
-        if let Ok(mut health) = target_health.get_mut(projectile.target) {
-            health.0 = (health.0 - projectile.damage).max(0.0);
+        if let Ok((mut health, armor)) = target_health.get_mut(projectile.target) {
+            let damage = Armor::apply_damage(&mut health, armor, projectile.damage);
             damage_text_events.write(DamageTextEvent {
                 position: *end_pos,
-                damage: projectile.damage,
+                damage,
             });
             combat_events.write(CombatEvent {
                 attacker: projectile.attacker,
                 target: projectile.target,
-                damage: projectile.damage,
+                damage,
                 hit: true,
             });
         }
@@ -436,22 +438,22 @@ fn handle_beam_projectile(
 fn apply_damage(
     combat_events: &mut EventWriter<CombatEvent>,
     damage_text_events: &mut EventWriter<DamageTextEvent>,
-    target_health: &mut Query<&mut Health>,
+    target_health: &mut Query<(&mut Health, Option<&Armor>)>,
     projectile: &Projectile,
     position: Vec2,
 ) {
-    if let Ok(mut health) = target_health.get_mut(projectile.target) {
-        health.0 = (health.0 - projectile.damage).max(0.0);
+    if let Ok((mut health, armor)) = target_health.get_mut(projectile.target) {
+        let damage = Armor::apply_damage(&mut health, armor, projectile.damage);
         
         damage_text_events.write(DamageTextEvent {
             position,
-            damage: projectile.damage,
+            damage,
         });
         
         combat_events.write(CombatEvent {
             attacker: projectile.attacker,
             target: projectile.target,
-            damage: projectile.damage,
+            damage,
             hit: true,
         });
     }
@@ -460,7 +462,7 @@ fn apply_damage(
 fn apply_area_damage(
     combat_events: &mut EventWriter<CombatEvent>,
     damage_text_events: &mut EventWriter<DamageTextEvent>,
-    target_health: &mut Query<&mut Health>,
+    target_health: &mut Query<(&mut Health, Option<&Armor>)>,
     targets: &Query<&Transform, (Without<Projectile>, Or<(With<Enemy>, With<Vehicle>, With<Agent>)>)>,
     explosion_pos: Vec2,
     base_damage: f32,
@@ -475,8 +477,8 @@ fn apply_area_damage(
             
             // Create a fake entity ID from the index (this is a hack for the example)
             // In real code, you'd need to properly track entity IDs
-            if let Ok(mut health) = target_health.get_mut(Entity::from_raw(entity as u32)) {
-                health.0 = (health.0 - actual_damage).max(0.0);
+            if let Ok((mut health, armor)) = target_health.get_mut(Entity::from_raw(entity as u32)) {
+                let actual_damage = Armor::apply_damage(&mut health, armor, actual_damage);
                 
                 damage_text_events.write(DamageTextEvent {
                     position: transform.translation.truncate(),
@@ -1,78 +1,670 @@
-// src/systems/quicksave.rs - Minimal mission quicksave
+// src/systems/quicksave.rs - Mission quicksave: snapshots and restores the live mission world
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use crate::core::*;
+use crate::core::factions::Faction;
+use crate::systems::access_control::{Door, Gate};
+use crate::systems::ai::{AIMode, AIState};
+use crate::systems::death::{Corpse, CorpseType};
+use crate::systems::extraction::{ExtractionState, MissionPhase};
+use crate::systems::scripting::MissionScript;
+use crate::systems::objectives::{spawn_objective_item, CarryingObjectiveItem, MissionObjectives, ObjectiveItem, ObjectiveState, ObjectiveTarget};
+use crate::systems::perception::{PerceptionMemory, Stimulus, StimulusKind};
+use crate::systems::police::{PendingDispatch, PoliceConfig, PoliceEscalation, PoliceUnit};
+use crate::systems::projectiles::{spawn_projectile, Projectile};
+use crate::systems::replay::finish_replay_recording;
+use crate::systems::scenes::{spawn_agent_isometric, spawn_enemy_isometric_with, spawn_urban_civilian_isometric};
+use crate::systems::selection::AgentIndex;
+use crate::systems::spawners::spawn_police_unit;
+use crate::systems::squads::SquadBlackboards;
+use crate::systems::urban_security::EscalationLevel;
 
 const QUICKSAVE_FILE: &str = "quicksave.json";
+const SNAPSHOT_VERSION: u32 = 3;
+/// Static entities (devices, doors, terminals) are matched back by spawn position
+const MATCH_TOLERANCE: f32 = 2.0;
 
+// === SNAPSHOT DATA ===
 #[derive(Serialize, Deserialize)]
-struct QuickSave {
+struct MissionSnapshot {
+    version: u32,
+    scene_name: String,
+    city_id: Option<String>,
+    mission: MissionCounters,
+    units: Vec<UnitSnapshot>,
+    corpses: Vec<CorpseSnapshot>,
+    projectiles: Vec<ProjectileSnapshot>,
+    devices: Vec<DeviceSnapshot>,
+    doors: Vec<DoorSnapshot>,
+    terminals: Vec<TerminalSnapshot>,
+    power_networks: HashMap<String, bool>,
+    /// Networks locked down by a completed hack trace
+    #[serde(default)]
+    hardened_networks: Vec<String>,
+    police: PoliceSnapshot,
+    objectives: Vec<ObjectiveSnapshot>,
+    objective_items: Vec<(usize, [f32; 2])>,
+    /// Fired and latched conditions per scene trigger
+    #[serde(default)]
+    triggers: Vec<(bool, Vec<bool>)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MissionCounters {
     timer: f32,
-    objectives: u32,
-    kills: u32,
-    terminals: u32,
+    alert_level: AlertLevel,
+    objectives_completed: u32,
+    total_objectives: u32,
+    enemies_killed: u32,
+    terminals_accessed: u32,
+    time_limit: f32,
+    #[serde(default)]
+    agents_lost: Vec<usize>,
+    #[serde(default)]
+    phase: MissionPhase,
+    #[serde(default)]
+    extraction_started_at: f32,
+    #[serde(default)]
+    agents_extracted: Vec<usize>,
+    #[serde(default)]
+    extracted_credits: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+enum UnitKind {
+    Agent { index: usize, level: u8, experience: u32 },
+    Enemy { archetype: EnemyType, faction: Faction },
+    Police { response_level: u8 },
+    Civilian,
+}
+
+/// `id` is snapshot-local and only used to re-link targets after respawning
+#[derive(Serialize, Deserialize)]
+struct UnitSnapshot {
+    id: u32,
+    kind: UnitKind,
+    position: [f32; 2],
+    health: f32,
+    morale: Option<Morale>,
+    weapon_state: Option<WeaponState>,
+    inventory: Option<Inventory>,
+    ai: Option<AiSnapshot>,
+    objective_target: Option<usize>,
+    carrying_item: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum AiModeSnapshot {
+    Patrol,
+    Investigate { location: [f32; 2] },
+    Combat { target: u32 },
+    Search { area: [f32; 2] },
+    Panic,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AiSnapshot {
+    mode: AiModeSnapshot,
+    last_known_target: Option<[f32; 2]>,
+    investigation_timer: f32,
+    alert_cooldown: f32,
+    use_goap: bool,
+    current_target: Option<u32>,
+    /// Seconds since the current target was last seen
+    #[serde(default)]
+    target_last_seen: f32,
+    patrol: Vec<[f32; 2]>,
+    patrol_index: usize,
+    goap_profile: Option<String>,
+    world_state: WorldState,
+    suspicion: f32,
+    stimuli: Vec<StimulusSnapshot>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StimulusSnapshot {
+    kind: StimulusKind,
+    position: [f32; 2],
+    source: Option<u32>,
+    confidence: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CorpseSnapshot {
+    kind: CorpseType,
+    position: [f32; 3],
+    size: Option<[f32; 2]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProjectileSnapshot {
+    position: [f32; 2],
+    attacker: u32,
+    target: u32,
+    damage: f32,
+    weapon_type: WeaponType,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DeviceSnapshot {
+    position: [f32; 2],
+    device_type: DeviceType,
+    is_hacked: bool,
+    disabled_duration: Option<f32>,
+    hack_timer: f32,
+    powered: bool,
+    operational: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DoorSnapshot {
+    position: [f32; 2],
+    is_gate: bool,
+    is_open: bool,
+    open_timer: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TerminalSnapshot {
+    position: [f32; 2],
+    accessed: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+struct ObjectiveSnapshot {
+    state: ObjectiveState,
+    progress: f32,
+    status: ObjectiveStatus,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PoliceSnapshot {
+    heat_level: f32,
+    civilian_casualties: u32,
+    last_incident_pos: Option<[f32; 2]>,
+    escalation_level: u8,
+    escalation_cooldown: f32,
+    calm_timer: f32,
+    incident_count: u32,
+    pending: Vec<(u8, [f32; 2], f32)>,
+}
+
+// === SYSTEMS ===
+/// F5: snapshot the running mission to disk
 pub fn quicksave_system(
     input: Res<ButtonInput<KeyCode>>,
     game_state: Res<State<GameState>>,
     game_mode: Res<GameMode>,
-    mut mission_data: ResMut<MissionData>,
-    mut commands: Commands,
-    entities: Query<Entity, (Without<Camera>, Without<Window>)>,
-    sprites: Res<GameSprites>,
+    mission_data: Res<MissionData>,
+    active_scene: Option<Res<ActiveMissionScene>>,
+    units: Query<(
+        Entity, &Transform, &Health,
+        Option<&Agent>, Option<&AgentIndex>, Option<&Police>, Option<&Civilian>,
+        Option<&EnemyArchetype>, Option<&Faction>, Option<&Morale>,
+        Option<&WeaponState>, Option<&Inventory>,
+        Option<&ObjectiveTarget>, Option<&CarryingObjectiveItem>,
+    ), (Or<(With<Agent>, With<Enemy>, With<Civilian>)>, Without<Dead>)>,
+    ai_query: Query<(&AIState, &GoapAgent, Option<&Patrol>, Option<&PerceptionMemory>)>,
+    corpses: Query<(&Transform, &Sprite, &Corpse)>,
+    projectiles: Query<(&Transform, &Projectile)>,
+    hackables: Query<(&Transform, &Hackable, Option<&DeviceState>)>,
+    (doors, gates, terminals): (Query<(&Transform, &Door)>, Query<(&Transform, &Gate)>, Query<(&Transform, &Terminal)>),
+    power_grid: Res<PowerGrid>,
+    (police_response, escalation): (Res<PoliceResponse>, Res<PoliceEscalation>),
     global_data: Res<GlobalData>,
+    (objectives, objective_items, extraction, script): (Res<MissionObjectives>, Query<(&Transform, &ObjectiveItem)>, Res<ExtractionState>, Res<MissionScript>),
 ) {
-    debug!("quicksave_system");
-    if *game_state.get() != GameState::Mission || game_mode.paused {
+    if *game_state.get() != GameState::Mission || game_mode.paused || !input.just_pressed(KeyCode::F5) {
         return;
     }
 
-    // F5: Quick save
-    if input.just_pressed(KeyCode::F5) {
-        let save = QuickSave {
-            timer: mission_data.timer,
-            objectives: mission_data.objectives_completed,
-            kills: mission_data.enemies_killed,
-            terminals: mission_data.terminals_accessed,
+    if global_data.ironman {
+        info!("Quicksave is disabled in Ironman mode");
+        return;
+    }
+
+    let Some(active_scene) = active_scene else {
+        warn!("Quicksave unavailable: no active mission scene");
+        return;
+    };
+
+    // Snapshot-local ids so cross-references survive entity respawning
+    let ids: HashMap<Entity, u32> = units.iter().enumerate().map(|(i, unit)| (unit.0, i as u32)).collect();
+
+    let units = units.iter().map(|(entity, transform, health, agent, agent_index, police, civilian, archetype, faction, morale, weapon_state, inventory, objective_target, carrying_item)| {
+        let kind = match (agent, police, civilian) {
+            (Some(agent), _, _) => UnitKind::Agent {
+                index: agent_index.map_or(0, |i| i.0),
+                level: agent.level,
+                experience: agent.experience,
+            },
+            (_, Some(police), _) => UnitKind::Police { response_level: police.response_level },
+            (_, _, Some(_)) => UnitKind::Civilian,
+            _ => UnitKind::Enemy {
+                archetype: archetype.map_or(EnemyType::Guard, |a| a.0),
+                faction: faction.copied().unwrap_or(Faction::Corporate),
+            },
         };
 
-        if let Ok(json) = serde_json::to_string(&save) {
-            if fs::write(QUICKSAVE_FILE, json).is_ok() {
-                info!("Mission quicksaved");
-            }
+        UnitSnapshot {
+            id: ids[&entity],
+            kind,
+            position: transform.translation.truncate().into(),
+            health: health.0,
+            morale: morale.cloned(),
+            weapon_state: weapon_state.cloned(),
+            inventory: inventory.cloned(),
+            ai: ai_query.get(entity).ok().map(|(ai_state, goap_agent, patrol, memory)| snapshot_ai(ai_state, goap_agent, patrol, memory, &ids)),
+            objective_target: objective_target.map(|t| t.0),
+            carrying_item: carrying_item.map(|c| c.0),
+        }
+    }).collect();
+
+    let snapshot = MissionSnapshot {
+        version: SNAPSHOT_VERSION,
+        scene_name: active_scene.scene_name.clone(),
+        city_id: active_scene.city_id.clone(),
+        mission: MissionCounters {
+            timer: mission_data.timer,
+            alert_level: mission_data.alert_level,
+            objectives_completed: mission_data.objectives_completed,
+            total_objectives: mission_data.total_objectives,
+            enemies_killed: mission_data.enemies_killed,
+            terminals_accessed: mission_data.terminals_accessed,
+            time_limit: mission_data.time_limit,
+            agents_lost: mission_data.agents_lost.clone(),
+            phase: extraction.phase,
+            extraction_started_at: extraction.started_at,
+            agents_extracted: extraction.extracted.clone(),
+            extracted_credits: extraction.credits,
+        },
+        units,
+        corpses: corpses.iter()
+            .filter(|(_, _, corpse)| !matches!(corpse.entity_type, CorpseType::Vehicle))
+            .map(|(transform, sprite, corpse)| CorpseSnapshot {
+                kind: corpse.entity_type,
+                position: transform.translation.into(),
+                size: sprite.custom_size.map(Into::into),
+            })
+            .collect(),
+        projectiles: projectiles.iter()
+            .filter_map(|(transform, projectile)| Some(ProjectileSnapshot {
+                position: transform.translation.truncate().into(),
+                attacker: *ids.get(&projectile.attacker)?,
+                target: *ids.get(&projectile.target)?,
+                damage: projectile.damage,
+                weapon_type: projectile.weapon_type,
+            }))
+            .collect(),
+        devices: hackables.iter().map(|(transform, hackable, device_state)| DeviceSnapshot {
+            position: transform.translation.truncate().into(),
+            device_type: hackable.device_type,
+            is_hacked: hackable.is_hacked,
+            disabled_duration: hackable.disabled_duration,
+            hack_timer: device_state.map_or(0.0, |d| d.hack_timer),
+            powered: device_state.map_or(true, |d| d.powered),
+            operational: device_state.map_or(true, |d| d.operational),
+        }).collect(),
+        doors: doors.iter()
+            .map(|(t, door)| DoorSnapshot { position: t.translation.truncate().into(), is_gate: false, is_open: door.is_open, open_timer: door.open_timer })
+            .chain(gates.iter().map(|(t, gate)| DoorSnapshot { position: t.translation.truncate().into(), is_gate: true, is_open: gate.is_open, open_timer: gate.open_timer }))
+            .collect(),
+        terminals: terminals.iter()
+            .map(|(t, terminal)| TerminalSnapshot { position: t.translation.truncate().into(), accessed: terminal.accessed })
+            .collect(),
+        power_networks: power_grid.networks.iter().map(|(id, network)| (id.clone(), network.powered)).collect(),
+        hardened_networks: power_grid.networks.iter().filter(|(_, network)| network.hardened).map(|(id, _)| id.clone()).collect(),
+        police: PoliceSnapshot {
+            heat_level: police_response.heat_level,
+            civilian_casualties: police_response.civilian_casualties,
+            last_incident_pos: police_response.last_incident_pos.map(Into::into),
+            escalation_level: escalation.level as u8,
+            escalation_cooldown: escalation.cooldown,
+            calm_timer: escalation.calm_timer,
+            incident_count: escalation.incident_count,
+            pending: escalation.pending.iter().map(|d| (d.level as u8, d.target.into(), d.eta)).collect(),
+        },
+        objectives: objectives.objectives.iter().map(|tracked| ObjectiveSnapshot {
+            state: tracked.state,
+            progress: tracked.progress,
+            status: tracked.status.clone(),
+        }).collect(),
+        objective_items: objective_items.iter()
+            .map(|(transform, item)| (item.0, transform.translation.truncate().into()))
+            .collect(),
+        triggers: script.progress(),
+    };
+
+    match serde_json::to_string(&snapshot) {
+        Ok(json) => match fs::write(QUICKSAVE_FILE, json) {
+            Ok(_) => info!("Mission quicksaved: {} units in {}", snapshot.units.len(), snapshot.scene_name),
+            Err(e) => error!("Failed to write quicksave: {}", e),
+        },
+        Err(e) => error!("Failed to serialize quicksave: {}", e),
+    }
+}
+
+/// F8: replace the mission's dynamic entities with the snapshot and patch static ones in place
+pub fn quickload_system(
+    input: Res<ButtonInput<KeyCode>>,
+    game_state: Res<State<GameState>>,
+    game_mode: Res<GameMode>,
+    mut commands: Commands,
+    mut mission_data: ResMut<MissionData>,
+    active_scene: Option<Res<ActiveMissionScene>>,
+    (global_data, sprites, police_config, mut rng): (Res<GlobalData>, Res<GameSprites>, Res<PoliceConfig>, ResMut<GameRng>),
+    existing: Query<Entity, (Or<(With<Agent>, With<Enemy>, With<Civilian>, With<Corpse>, With<Projectile>, With<ObjectiveItem>)>, Without<Vehicle>)>,
+    mut hackables: Query<(&Transform, &mut Hackable, Option<&mut DeviceState>)>,
+    (mut doors, mut gates, mut terminals): (Query<(&Transform, &mut Door)>, Query<(&Transform, &mut Gate)>, Query<(&Transform, &mut Terminal)>),
+    mut power_grid: ResMut<PowerGrid>,
+    (mut police_response, mut escalation, mut squads): (ResMut<PoliceResponse>, ResMut<PoliceEscalation>, ResMut<SquadBlackboards>),
+    time: Res<Time>,
+    (mut objectives, mut extraction, mut script): (ResMut<MissionObjectives>, ResMut<ExtractionState>, ResMut<MissionScript>),
+) {
+    if *game_state.get() != GameState::Mission || game_mode.paused || !input.just_pressed(KeyCode::F8) {
+        return;
+    }
+
+    if global_data.ironman {
+        info!("Quickload is disabled in Ironman mode");
+        return;
+    }
+
+    let snapshot = match fs::read_to_string(QUICKSAVE_FILE).map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<MissionSnapshot>(&content).map_err(|e| e.to_string()))
+    {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            warn!("No usable quicksave: {}", e);
+            return;
         }
+    };
+
+    if snapshot.version != SNAPSHOT_VERSION {
+        warn!("Quicksave version {} is not supported (expected {})", snapshot.version, SNAPSHOT_VERSION);
+        return;
+    }
+
+    let matches_scene = active_scene.as_ref().map_or(false, |active| {
+        active.scene_name == snapshot.scene_name && active.city_id == snapshot.city_id
+    });
+    if !matches_scene {
+        warn!("Quicksave belongs to {} ({:?}), not the running mission", snapshot.scene_name, snapshot.city_id);
+        return;
+    }
+
+    for entity in existing.iter() {
+        commands.entity(entity).insert(MarkedForDespawn);
     }
 
-    // F8: Quick load
-    if input.just_pressed(KeyCode::F8) {
-        if let Ok(content) = fs::read_to_string(QUICKSAVE_FILE) {
-            if let Ok(save) = serde_json::from_str::<QuickSave>(&content) {
-                // Clear current mission
-                for entity in entities.iter() {
-                    commands.entity(entity).insert(MarkedForDespawn);
-                }
-
-                // Restart with saved progress
-                match crate::systems::scenes::load_scene("mission1") {
-                    Some(scene) => {
-                        crate::systems::scenes::spawn_from_scene(&mut commands, &scene, &global_data, &sprites);
-                    },
-                    None => {
-                        error!("Failed to load scene for quickload. Using fallback.");
-                        // spawn_fallback_mission(&mut commands, &*global_data, &sprites);
-                    }
-                }
-
-                // Restore progress
-                mission_data.timer = save.timer;
-                mission_data.objectives_completed = save.objectives;
-                mission_data.enemies_killed = save.kills;
-                mission_data.terminals_accessed = save.terminals;
-
-                info!("Mission quickloaded");
+    // A restored world can't be reached by replaying commands, so the recording stops here
+    commands.queue(finish_replay_recording);
+
+    let now = time.elapsed_secs();
+    squads.clear();
+    escalation.active_units.clear();
+
+    // === UNITS ===
+    let rng = rng.stream(RngStream::Spawning);
+    let mut spawned: HashMap<u32, Entity> = HashMap::new();
+    for unit in &snapshot.units {
+        let pos = Vec2::from(unit.position);
+        let entity = match &unit.kind {
+            UnitKind::Agent { index, level, experience } => {
+                let entity = spawn_agent_isometric(&mut commands, pos, *level, *index, &global_data, &sprites);
+                commands.entity(entity).insert(Agent { experience: *experience, level: *level });
+                entity
+            }
+            UnitKind::Enemy { archetype, faction } => {
+                let weapon = equipped_weapon(unit).unwrap_or_else(|| archetype.pick_weapon(faction, rng));
+                spawn_enemy_isometric_with(&mut commands, pos, Vec::new(), *archetype, *faction, weapon, &global_data, &sprites)
+            }
+            UnitKind::Police { response_level } => {
+                let level = EscalationLevel::from_level(*response_level);
+                let entity = spawn_police_unit(&mut commands, pos, level, &sprites, &police_config);
+                escalation.active_units.push(PoliceUnit { entity, unit_type: level, spawn_time: now });
+                entity
             }
+            UnitKind::Civilian => spawn_urban_civilian_isometric(&mut commands, pos, &sprites, rng),
+        };
+        spawned.insert(unit.id, entity);
+    }
+
+    for unit in &snapshot.units {
+        let entity = spawned[&unit.id];
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(Health(unit.health));
+
+        if let Some(weapon_state) = &unit.weapon_state {
+            entity_commands.insert(weapon_state.clone());
+        }
+        if let Some(inventory) = &unit.inventory {
+            entity_commands.insert(inventory.clone());
+        }
+        if let Some(ai) = &unit.ai {
+            let (ai_state, patrol, goap_agent, memory) = restore_ai(ai, &spawned, now);
+            entity_commands.insert((ai_state, patrol, goap_agent, memory));
+        }
+        if let Some(morale) = &unit.morale {
+            entity_commands.insert(morale.clone());
+        }
+        if let Some(objective_idx) = unit.objective_target {
+            entity_commands.insert(ObjectiveTarget(objective_idx));
+        }
+        if let Some(objective_idx) = unit.carrying_item {
+            entity_commands.insert(CarryingObjectiveItem(objective_idx));
+        }
+    }
+
+    // === OBJECTIVES ===
+    for (objective_idx, position) in &snapshot.objective_items {
+        spawn_objective_item(&mut commands, Vec2::from(*position), *objective_idx);
+    }
+    if snapshot.objectives.len() == objectives.objectives.len() {
+        for (tracked, saved) in objectives.objectives.iter_mut().zip(&snapshot.objectives) {
+            tracked.state = saved.state;
+            tracked.progress = saved.progress;
+            tracked.status = saved.status.clone();
+        }
+    } else {
+        warn!("Quicksave objective list doesn't match the running mission; objective progress kept");
+    }
+    if !script.restore_progress(&snapshot.triggers) {
+        warn!("Quicksave trigger list doesn't match the running mission; trigger progress kept");
+    }
+
+    // === CORPSES AND PROJECTILES ===
+    for corpse in &snapshot.corpses {
+        commands.spawn((
+            Sprite {
+                color: Color::srgb(0.3, 0.1, 0.1),
+                custom_size: corpse.size.map(Vec2::from),
+                ..default()
+            },
+            Transform::from_translation(Vec3::from(corpse.position)),
+            Corpse { entity_type: corpse.kind, decay_timer: None },
+            Dead,
+        ));
+    }
+
+    let unit_positions: HashMap<u32, Vec2> = snapshot.units.iter().map(|u| (u.id, Vec2::from(u.position))).collect();
+    for projectile in &snapshot.projectiles {
+        let (Some(&attacker), Some(&target)) = (spawned.get(&projectile.attacker), spawned.get(&projectile.target)) else { continue; };
+        let Some(&target_pos) = unit_positions.get(&projectile.target) else { continue; };
+        spawn_projectile(&mut commands, attacker, target, Vec2::from(projectile.position), target_pos, projectile.damage, projectile.weapon_type);
+    }
+
+    // === STATIC ENTITIES ===
+    for (transform, mut hackable, device_state) in hackables.iter_mut() {
+        let pos = transform.translation.truncate();
+        let Some(saved) = snapshot.devices.iter()
+            .find(|d| d.device_type == hackable.device_type && near(d.position, pos)) else { continue; };
+
+        hackable.is_hacked = saved.is_hacked;
+        hackable.disabled_duration = saved.disabled_duration;
+        if let Some(mut device_state) = device_state {
+            device_state.hack_timer = saved.hack_timer;
+            device_state.powered = saved.powered;
+            device_state.operational = saved.operational;
+        }
+    }
+
+    for (transform, mut door) in doors.iter_mut() {
+        if let Some(saved) = snapshot.doors.iter().find(|d| !d.is_gate && near(d.position, transform.translation.truncate())) {
+            door.is_open = saved.is_open;
+            door.open_timer = saved.open_timer;
         }
     }
-}
\ No newline at end of file
+
+    for (transform, mut gate) in gates.iter_mut() {
+        if let Some(saved) = snapshot.doors.iter().find(|d| d.is_gate && near(d.position, transform.translation.truncate())) {
+            gate.is_open = saved.is_open;
+            gate.open_timer = saved.open_timer;
+        }
+    }
+
+    for (transform, mut terminal) in terminals.iter_mut() {
+        if let Some(saved) = snapshot.terminals.iter().find(|t| near(t.position, transform.translation.truncate())) {
+            terminal.accessed = saved.accessed;
+        }
+    }
+
+    for (id, powered) in &snapshot.power_networks {
+        if let Some(network) = power_grid.networks.get_mut(id) {
+            network.powered = *powered;
+            network.hardened = snapshot.hardened_networks.contains(id);
+        }
+    }
+    // Re-solved from the restored stations on the next tick
+    power_grid.topology_dirty = true;
+
+    // === RESOURCES ===
+    let police = &snapshot.police;
+    police_response.heat_level = police.heat_level;
+    police_response.civilian_casualties = police.civilian_casualties;
+    police_response.last_incident_pos = police.last_incident_pos.map(Vec2::from);
+    escalation.level = EscalationLevel::from_level(police.escalation_level);
+    escalation.cooldown = police.escalation_cooldown;
+    escalation.calm_timer = police.calm_timer;
+    escalation.incident_count = police.incident_count;
+    escalation.pending = police.pending.iter()
+        .map(|(level, target, eta)| PendingDispatch { level: EscalationLevel::from_level(*level), target: Vec2::from(*target), eta: *eta })
+        .collect();
+
+    let mission = &snapshot.mission;
+    mission_data.timer = mission.timer;
+    mission_data.alert_level = mission.alert_level;
+    mission_data.objectives_completed = mission.objectives_completed;
+    mission_data.total_objectives = mission.total_objectives;
+    mission_data.enemies_killed = mission.enemies_killed;
+    mission_data.terminals_accessed = mission.terminals_accessed;
+    mission_data.time_limit = mission.time_limit;
+    mission_data.agents_lost = mission.agents_lost.clone();
+    *extraction = ExtractionState {
+        phase: mission.phase,
+        started_at: mission.extraction_started_at,
+        extracted: mission.agents_extracted.clone(),
+        credits: mission.extracted_credits,
+        abandon_requested: false,
+    };
+
+    info!("Mission quickloaded: {} units, {} corpses, {} devices",
+          snapshot.units.len(), snapshot.corpses.len(), snapshot.devices.len());
+}
+
+// === HELPERS ===
+fn near(saved: [f32; 2], pos: Vec2) -> bool {
+    Vec2::from(saved).distance(pos) <= MATCH_TOLERANCE
+}
+
+fn equipped_weapon(unit: &UnitSnapshot) -> Option<WeaponType> {
+    unit.inventory.as_ref()?.equipped_weapon.as_ref().map(|w| w.base_weapon)
+}
+
+fn snapshot_ai(
+    ai_state: &AIState,
+    goap_agent: &GoapAgent,
+    patrol: Option<&Patrol>,
+    memory: Option<&PerceptionMemory>,
+    ids: &HashMap<Entity, u32>,
+) -> AiSnapshot {
+    let mode = match &ai_state.mode {
+        AIMode::Patrol => AiModeSnapshot::Patrol,
+        AIMode::Investigate { location } => AiModeSnapshot::Investigate { location: (*location).into() },
+        AIMode::Combat { target } => match ids.get(target) {
+            Some(id) => AiModeSnapshot::Combat { target: *id },
+            None => AiModeSnapshot::Patrol,
+        },
+        AIMode::Search { area } => AiModeSnapshot::Search { area: (*area).into() },
+        AIMode::Panic => AiModeSnapshot::Panic,
+    };
+
+    AiSnapshot {
+        mode,
+        last_known_target: ai_state.last_known_target.map(Into::into),
+        investigation_timer: ai_state.investigation_timer,
+        alert_cooldown: ai_state.alert_cooldown,
+        use_goap: ai_state.use_goap,
+        current_target: ai_state.current_target.and_then(|e| ids.get(&e).copied()),
+        target_last_seen: ai_state.target_last_seen,
+        patrol: patrol.map_or(Vec::new(), |p| p.points.iter().map(|&point| point.into()).collect()),
+        patrol_index: patrol.map_or(0, |p| p.current_index),
+        goap_profile: goap_agent.profile.clone(),
+        world_state: goap_agent.world_state.clone(),
+        suspicion: memory.map_or(0.0, |m| m.suspicion),
+        stimuli: memory.map_or(Vec::new(), |m| m.stimuli.iter().map(|s| StimulusSnapshot {
+            kind: s.kind,
+            position: s.position.into(),
+            source: s.source.and_then(|e| ids.get(&e).copied()),
+            confidence: s.confidence,
+        }).collect()),
+    }
+}
+
+/// `now` stamps the restored stimuli, which perception ages from the time they were recorded
+fn restore_ai(ai: &AiSnapshot, spawned: &HashMap<u32, Entity>, now: f32) -> (AIState, Patrol, GoapAgent, PerceptionMemory) {
+    let mode = match &ai.mode {
+        AiModeSnapshot::Patrol => AIMode::Patrol,
+        AiModeSnapshot::Investigate { location } => AIMode::Investigate { location: Vec2::from(*location) },
+        AiModeSnapshot::Combat { target } => match spawned.get(target) {
+            Some(entity) => AIMode::Combat { target: *entity },
+            None => AIMode::Patrol,
+        },
+        AiModeSnapshot::Search { area } => AIMode::Search { area: Vec2::from(*area) },
+        AiModeSnapshot::Panic => AIMode::Panic,
+    };
+
+    let ai_state = AIState {
+        mode,
+        last_known_target: ai.last_known_target.map(Vec2::from),
+        investigation_timer: ai.investigation_timer,
+        alert_cooldown: ai.alert_cooldown,
+        use_goap: ai.use_goap,
+        current_target: ai.current_target.and_then(|id| spawned.get(&id).copied()),
+        target_last_seen: ai.target_last_seen,
+    };
+
+    let mut patrol = Patrol::new(ai.patrol.iter().map(|&p| Vec2::from(p)).collect());
+    patrol.current_index = ai.patrol_index.min(patrol.points.len().saturating_sub(1));
+
+    // Libraries are rebuilt from the profile when the component is added; only beliefs carry over
+    let mut goap_agent = match &ai.goap_profile {
+        Some(profile) => GoapAgent::default().with_profile(profile.clone()),
+        None => GoapAgent::default(),
+    };
+    goap_agent.world_state = ai.world_state.clone();
+
+    let mut memory = PerceptionMemory::default();
+    memory.suspicion = ai.suspicion;
+    memory.stimuli = ai.stimuli.iter().map(|s| Stimulus {
+        kind: s.kind,
+        position: Vec2::from(s.position),
+        source: s.source.and_then(|id| spawned.get(&id).copied()),
+        confidence: s.confidence,
+        time: now,
+    }).collect();
+
+    (ai_state, patrol, goap_agent, memory)
+}
@@ -0,0 +1,134 @@
+// src/systems/remote_hacking.rs - Hacking across a network from a device that's already been compromised
+//
+// Devices on a network talk to the ones near them. Once one of them is hacked, an agent carrying a
+// network scanner or satellite uplink can pivot from it to anything further along the same
+// network, paying extra hack time for every hop on the way.
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use crate::core::*;
+use crate::systems::world_scan::WorldScanState;
+
+/// How far apart two devices on a network can be and still reach each other directly
+pub const NETWORK_LINK_RANGE: f32 = 260.0;
+/// Routes longer than this are too noisy to hold open
+pub const MAX_HOPS: u32 = 6;
+/// Hack time added per hop between the compromised device and the target
+pub const HOP_HACK_SECS: f32 = 2.0;
+
+// === RESOURCES ===
+/// Every device a remote hack can currently reach, rebuilt each tick from what's been compromised
+#[derive(Resource, Default)]
+pub struct RemoteAccess {
+    pub routes: HashMap<Entity, RemoteRoute>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoteRoute {
+    pub network_id: String,
+    pub hops: u32,
+    /// The device the route comes through on its last hop
+    pub via: Entity,
+}
+
+/// Remote hacks need something to route them through the network
+pub fn can_hack_remotely(inventory: &Inventory) -> bool {
+    inventory.equipped_tools.iter().any(|tool| matches!(tool, ToolType::NetworkScanner | ToolType::SatelliteUplink))
+}
+
+// === SYSTEMS ===
+/// Walks out from every compromised device on each network, one link at a time
+pub fn remote_access_system(
+    mut remote_access: ResMut<RemoteAccess>,
+    power_grid: Res<PowerGrid>,
+    devices: Query<(&Transform, &Hackable)>,
+) {
+    remote_access.routes.clear();
+
+    // By id, as build_topology does: hash order differs between runs and replays must not
+    let mut network_ids: Vec<&String> = power_grid.networks.keys().collect();
+    network_ids.sort();
+
+    for network_id in network_ids {
+        let network = &power_grid.networks[network_id];
+        let mut nodes: Vec<(Entity, Vec2, bool)> = network.connected_devices.iter()
+            .filter_map(|&entity| devices.get(entity).ok().map(|(transform, hackable)| {
+                (entity, transform.translation.truncate(), hackable.is_hacked)
+            }))
+            .collect();
+        nodes.sort_by_key(|(entity, ..)| *entity);
+
+        let mut hops: Vec<Option<u32>> = nodes.iter().map(|(_, _, hacked)| hacked.then_some(0)).collect();
+        let mut queue: VecDeque<usize> = (0..nodes.len()).filter(|&i| nodes[i].2).collect();
+
+        while let Some(at) = queue.pop_front() {
+            let distance = hops[at].unwrap_or(0) + 1;
+            if distance > MAX_HOPS {
+                continue;
+            }
+            let (via, from, _) = nodes[at];
+            for (next, &(entity, position, _)) in nodes.iter().enumerate() {
+                if hops[next].is_some() || from.distance(position) > NETWORK_LINK_RANGE {
+                    continue;
+                }
+                hops[next] = Some(distance);
+                queue.push_back(next);
+                remote_access.routes.insert(entity, RemoteRoute {
+                    network_id: network_id.clone(),
+                    hops: distance,
+                    via,
+                });
+            }
+        }
+    }
+}
+
+/// Starts hacks ordered from the network view, provided the agent has the kit and a route
+pub fn remote_hack_system(
+    mut action_events: EventReader<ActionEvent>,
+    mut hack_events: EventWriter<HackAttemptEvent>,
+    agents: Query<&Inventory, With<Agent>>,
+    remote_access: Res<RemoteAccess>,
+) {
+    for event in action_events.read() {
+        let Action::RemoteHack(target) = event.action else { continue; };
+        let Ok(inventory) = agents.get(event.entity) else { continue; };
+
+        if !can_hack_remotely(inventory) {
+            info!("Remote hack failed: needs a network scanner or satellite uplink");
+            continue;
+        }
+        let Some(route) = remote_access.routes.get(&target) else {
+            info!("Remote hack failed: no compromised device on the network reaches that far");
+            continue;
+        };
+
+        hack_events.write(HackAttemptEvent {
+            agent: event.entity,
+            target,
+            tool_used: HackTool::BasicHacker,
+            hops: route.hops,
+        });
+        info!("Remote hack on {} routed over {} hops", route.network_id, route.hops);
+    }
+}
+
+// === VISUALS ===
+/// With scan overlays up, traces each reachable device back along its route
+pub fn remote_access_gizmos(
+    mut gizmos: Gizmos,
+    remote_access: Res<RemoteAccess>,
+    scan_state: Res<WorldScanState>,
+    transforms: Query<&Transform>,
+) {
+    if !scan_state.show_overlays {
+        return;
+    }
+
+    for (&target, route) in &remote_access.routes {
+        let (Ok(target_transform), Ok(via_transform)) = (transforms.get(target), transforms.get(route.via)) else { continue; };
+        let target_pos = target_transform.translation.truncate();
+        let fade = 1.0 - route.hops as f32 / (MAX_HOPS + 1) as f32;
+        gizmos.line_2d(via_transform.translation.truncate(), target_pos, Color::srgba(0.2, 0.9, 0.6, 0.3 + 0.5 * fade));
+        gizmos.circle_2d(target_pos, 10.0, Color::srgba(0.2, 0.9, 0.6, 0.8));
+    }
+}
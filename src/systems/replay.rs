@@ -0,0 +1,871 @@
+// src/systems/replay.rs - Records player commands per simulation step and plays missions back from them
+//
+// A replay is the campaign snapshot, scene and mission seed plus every command the player gave,
+// keyed on the fixed step it took effect in. The simulation is deterministic for a given seed,
+// so feeding the same commands in at the same steps reproduces the mission; periodic world
+// checksums show where a playback stopped matching the recording.
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::core::*;
+use crate::systems::ice::{is_jacked_in, CyberEffect, CyberspaceOutcomeEvent};
+use crate::systems::isometric_camera::IsometricCamera;
+use crate::systems::mission::restart_system_optimized;
+use crate::systems::save::{self, SaveData, SaveSession};
+use crate::systems::scene_editor::EditorPlaytest;
+
+pub const REPLAY_DIR: &str = "replays";
+const REPLAY_VERSION: u32 = 2;
+const MAX_REPLAYS: usize = 20;
+/// Steps between recorded world checksums
+const CHECKSUM_INTERVAL: u32 = 60;
+
+const PLAYBACK_SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+const NORMAL_SPEED: usize = 2;
+const SEEK_SPEED: f32 = 16.0;
+const SEEK_JUMP_SECS: f64 = 10.0;
+/// Bevy's default cap on a frame's virtual time; lifted while seeking so the speed-up isn't clamped
+const DEFAULT_MAX_DELTA: Duration = Duration::from_millis(250);
+const SEEK_MAX_DELTA: Duration = Duration::from_secs(1);
+const FREE_CAMERA_SPEED: f32 = 600.0;
+
+// === IDENTITY ===
+/// `Entity` ids depend on everything spawned before the mission; this is handed out
+/// in a fixed order from inside the simulation, so it matches between recording and playback
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ReplayId(pub u32);
+
+/// Fixed steps since the mission started; replay entries are keyed on it
+#[derive(Resource, Default)]
+pub struct ReplayClock {
+    pub step: u32,
+    /// Ids keep counting across missions so leftovers from an earlier one never share an id
+    next_id: u32,
+    first_id: u32,
+}
+
+// === FILE FORMAT ===
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub version: u32,
+    pub scene_name: String,
+    pub city_id: Option<String>,
+    pub region_id: usize,
+    pub playtest: bool,
+    pub mission_seed: u64,
+    pub sim_hz: f64,
+    pub final_step: u32,
+    /// First `ReplayId` handed out in the mission
+    pub first_id: u32,
+    /// Campaign as it stood at launch, in save format so save migrations apply
+    pub campaign: serde_json::Value,
+    pub entries: Vec<ReplayEntry>,
+    /// (step, checksum) every `CHECKSUM_INTERVAL` steps
+    pub checksums: Vec<(u32, u64)>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    /// Fixed step the command takes effect in
+    pub step: u32,
+    #[serde(flatten)]
+    pub command: ReplayCommand,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayCommand {
+    Action { entity: u32, action: ReplayAction },
+    Select { agents: Vec<u32> },
+    Pause { paused: bool },
+    /// Button press, kept for reading the timeline; its effects are already in the other commands
+    Input { action: PlayerAction },
+    /// Hack the simulation started from an interaction, kept for reading the timeline
+    Hack { agent: u32, target: u32 },
+    /// What a cyberspace run changed; the run itself isn't replayed, so it lands on the step the agent came back on
+    CyberspaceOutcome { agent: u32, effects: Vec<ReplayCyberEffect>, feedback_damage: f32, traced: bool },
+}
+
+/// The player-issued subset of `Action`, with entities as `ReplayId`s
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayAction {
+    MoveTo { position: [f32; 2] },
+    Attack { target: u32 },
+    InteractWith { target: u32 },
+    RemoteHack { target: u32 },
+    Reload,
+    UseMedKit,
+    ThrowGrenade { target_pos: [f32; 2] },
+    NeurovectorControl { target: u32 },
+    CommandDevice { target: [f32; 2], queue: bool },
+}
+
+impl ReplayAction {
+    fn encode(action: &Action, id_of: impl Fn(Entity) -> Option<u32>) -> Option<Self> {
+        Some(match action {
+            Action::MoveTo(position) => Self::MoveTo { position: position.to_array() },
+            Action::Attack(target) => Self::Attack { target: id_of(*target)? },
+            Action::InteractWith(target) => Self::InteractWith { target: id_of(*target)? },
+            Action::RemoteHack(target) => Self::RemoteHack { target: id_of(*target)? },
+            Action::Reload => Self::Reload,
+            Action::UseMedKit => Self::UseMedKit,
+            Action::ThrowGrenade { target_pos } => Self::ThrowGrenade { target_pos: target_pos.to_array() },
+            Action::NeurovectorControl { target } => Self::NeurovectorControl { target: id_of(*target)? },
+            Action::CommandDevice { target, queue } => Self::CommandDevice { target: target.to_array(), queue: *queue },
+            _ => return None,
+        })
+    }
+
+    fn decode(&self, entity_of: impl Fn(u32) -> Option<Entity>) -> Option<Action> {
+        Some(match *self {
+            Self::MoveTo { position } => Action::MoveTo(Vec2::from(position)),
+            Self::Attack { target } => Action::Attack(entity_of(target)?),
+            Self::InteractWith { target } => Action::InteractWith(entity_of(target)?),
+            Self::RemoteHack { target } => Action::RemoteHack(entity_of(target)?),
+            Self::Reload => Action::Reload,
+            Self::UseMedKit => Action::UseMedKit,
+            Self::ThrowGrenade { target_pos } => Action::ThrowGrenade { target_pos: Vec2::from(target_pos) },
+            Self::NeurovectorControl { target } => Action::NeurovectorControl { target: entity_of(target)? },
+            Self::CommandDevice { target, queue } => Action::CommandDevice { target: Vec2::from(target), queue },
+        })
+    }
+}
+
+/// `CyberEffect` with entities as `ReplayId`s
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayCyberEffect {
+    Disable { device: u32 },
+    Download { terminal: u32 },
+    Drain { bank_id: String, amount: u32 },
+}
+
+impl ReplayCyberEffect {
+    fn encode(effect: &CyberEffect, id_of: impl Fn(Entity) -> Option<u32>) -> Option<Self> {
+        Some(match effect {
+            CyberEffect::Disable { device } => Self::Disable { device: id_of(*device)? },
+            CyberEffect::Download { terminal } => Self::Download { terminal: id_of(*terminal)? },
+            CyberEffect::Drain { bank_id, amount } => Self::Drain { bank_id: bank_id.clone(), amount: *amount },
+        })
+    }
+
+    fn decode(&self, entity_of: impl Fn(u32) -> Option<Entity>) -> Option<CyberEffect> {
+        Some(match self {
+            Self::Disable { device } => CyberEffect::Disable { device: entity_of(*device)? },
+            Self::Download { terminal } => CyberEffect::Download { terminal: entity_of(*terminal)? },
+            Self::Drain { bank_id, amount } => CyberEffect::Drain { bank_id: bank_id.clone(), amount: *amount },
+        })
+    }
+}
+
+impl ReplayCommand {
+    fn describe(&self) -> String {
+        match self {
+            ReplayCommand::Action { entity, action } => match action {
+                ReplayAction::MoveTo { position } => format!("#{} move to ({:.0}, {:.0})", entity, position[0], position[1]),
+                ReplayAction::Attack { target } => format!("#{} attack #{}", entity, target),
+                ReplayAction::InteractWith { .. } => format!("#{} interact", entity),
+                ReplayAction::RemoteHack { target } => format!("#{} remote hack #{}", entity, target),
+                ReplayAction::Reload => format!("#{} reload", entity),
+                ReplayAction::UseMedKit => format!("#{} medkit", entity),
+                ReplayAction::ThrowGrenade { target_pos } => format!("#{} grenade at ({:.0}, {:.0})", entity, target_pos[0], target_pos[1]),
+                ReplayAction::NeurovectorControl { target } => format!("#{} neurovector #{}", entity, target),
+                ReplayAction::CommandDevice { target, .. } => format!("#{} device to ({:.0}, {:.0})", entity, target[0], target[1]),
+            },
+            ReplayCommand::Select { agents } => format!("select {:?}", agents),
+            ReplayCommand::Pause { paused: true } => "pause".to_string(),
+            ReplayCommand::Pause { paused: false } => "resume".to_string(),
+            ReplayCommand::Input { action } => format!("{:?}", action),
+            ReplayCommand::Hack { agent, target } => format!("#{} hacks #{}", agent, target),
+            ReplayCommand::CyberspaceOutcome { agent, effects, traced, .. } => {
+                format!("#{} jacks out ({} effects{})", agent, effects.len(), if *traced { ", traced" } else { "" })
+            }
+        }
+    }
+}
+
+pub fn load_replay(path: &Path) -> Result<ReplayFile, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file: ReplayFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    if file.version != REPLAY_VERSION {
+        return Err(format!("replay version {} is not supported (expected {})", file.version, REPLAY_VERSION));
+    }
+    Ok(file)
+}
+
+/// Replays are named by start time, so the last one by name is the newest
+pub fn latest_replay_path() -> Option<PathBuf> {
+    list_replays().pop()
+}
+
+fn list_replays() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(REPLAY_DIR) else { return Vec::new(); };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn write_replay(path: &Path, file: &ReplayFile) {
+    if let Err(e) = fs::create_dir_all(REPLAY_DIR) {
+        warn!("Failed to create {}: {}", REPLAY_DIR, e);
+        return;
+    }
+
+    match serde_json::to_string(file) {
+        Ok(json) => match fs::write(path, json) {
+            Ok(_) => info!("Replay saved to {:?} ({} commands, {} steps)", path, file.entries.len(), file.final_step),
+            Err(e) => warn!("Failed to save replay: {}", e),
+        },
+        Err(e) => warn!("Failed to serialize replay: {}", e),
+    }
+
+    let replays = list_replays();
+    for old in replays.iter().take(replays.len().saturating_sub(MAX_REPLAYS)) {
+        let _ = fs::remove_file(old);
+    }
+}
+
+fn step_clock(step: u32, hz: f64) -> String {
+    let secs = (step as f64 / hz) as u32;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Order-independent summary of where everything is and how hurt it is
+fn world_checksum<'a>(units: impl Iterator<Item = (&'a ReplayId, &'a Transform, Option<&'a Health>)>) -> u64 {
+    let mut units: Vec<(u32, Vec3, f32)> = units
+        .map(|(id, transform, health)| (id.0, transform.translation, health.map_or(-1.0, |h| h.0)))
+        .collect();
+    units.sort_by_key(|(id, _, _)| *id);
+
+    units.iter().fold(0, |hash, (id, position, health)| derive_seed(hash, &[
+        &id.to_le_bytes(),
+        &position.x.to_bits().to_le_bytes(),
+        &position.y.to_bits().to_le_bytes(),
+        &health.to_bits().to_le_bytes(),
+    ]))
+}
+
+// === RESOURCES ===
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    file: ReplayFile,
+    path: PathBuf,
+    /// Newest `ActionEvent` the simulation has seen; anything after it was written by input
+    sim_watermark: Option<usize>,
+    last_selection: Vec<Entity>,
+    last_paused: bool,
+}
+
+impl ReplayRecorder {
+    fn push(&mut self, step: u32, command: ReplayCommand) {
+        self.file.entries.push(ReplayEntry { step, command });
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub file: ReplayFile,
+    cursor: usize,
+    checksum_cursor: usize,
+    pub diverged_at: Option<u32>,
+    pub seek_target: Option<u32>,
+    pause_after_seek: bool,
+    speed_index: usize,
+}
+
+impl ReplayPlayback {
+    fn new(file: ReplayFile, seek_to: Option<u32>) -> Self {
+        Self {
+            file,
+            cursor: 0,
+            checksum_cursor: 0,
+            diverged_at: None,
+            seek_target: seek_to,
+            pause_after_seek: seek_to.is_some(),
+            speed_index: NORMAL_SPEED,
+        }
+    }
+
+    /// Commands due by `step`, consumed
+    fn take_due(&mut self, step: u32) -> Vec<ReplayCommand> {
+        let start = self.cursor;
+        while self.file.entries.get(self.cursor).is_some_and(|entry| entry.step <= step) {
+            self.cursor += 1;
+        }
+        self.file.entries[start..self.cursor].iter().map(|entry| entry.command.clone()).collect()
+    }
+}
+
+/// Replay waiting for the main menu to launch it; `seek_to` fast-forwards after a rewind
+#[derive(Resource)]
+pub struct PendingReplay {
+    pub file: ReplayFile,
+    pub seek_to: Option<u32>,
+}
+
+/// Campaign the replay displaced, put back when playback ends
+#[derive(Resource)]
+struct ReplayCampaignBackup(GlobalData);
+
+pub fn is_replaying(playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.is_some()
+}
+
+fn is_recording(recorder: Option<Res<ReplayRecorder>>) -> bool {
+    recorder.is_some()
+}
+
+/// `subversive --replay replays/<file>.json` goes straight from the menu into playback
+fn pending_replay_from_args() -> Option<PendingReplay> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg != "--replay" {
+            continue;
+        }
+        let path = args.next()?;
+        return match load_replay(Path::new(&path)) {
+            Ok(file) => Some(PendingReplay { file, seek_to: None }),
+            Err(e) => {
+                error!("Can't play replay {}: {}", path, e);
+                None
+            }
+        };
+    }
+    None
+}
+
+// === PLUGIN ===
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(pending) = pending_replay_from_args() {
+            app.insert_resource(pending);
+        }
+
+        app
+            .init_resource::<ReplayClock>()
+            .add_systems(OnEnter(GameState::Mission), reset_replay_clock.run_if(not(is_jacked_in)))
+            .add_systems(FixedFirst, (
+                advance_replay_clock,
+                assign_replay_ids,
+                begin_recording.run_if(not(is_recording)).run_if(not(is_replaying)),
+                apply_replay_commands.run_if(is_replaying),
+            ).chain().run_if(in_state(GameState::Mission)))
+            .add_systems(FixedLast, (
+                record_simulation_step.run_if(is_recording),
+                verify_replay_checksums.run_if(is_replaying),
+            ).run_if(in_state(GameState::Mission)))
+            .add_systems(PostUpdate, (
+                record_player_input,
+                save_replay_snapshot_system,
+            ).run_if(in_state(GameState::Mission)).run_if(is_recording))
+            .add_systems(Update, (
+                replay_controls_system,
+                replay_camera_system,
+            ).run_if(in_state(GameState::Mission)).run_if(is_replaying))
+            .add_systems(OnExit(GameState::Mission), finish_replay_recording.run_if(not(is_jacked_in)))
+
+            .add_systems(OnEnter(GameState::MainMenu), end_replay)
+            .add_systems(Update, start_pending_replay
+                .run_if(in_state(GameState::MainMenu))
+                .run_if(resource_exists::<PendingReplay>))
+            // Replays launch from the menu, so the mission has to start from a clean slate
+            .add_systems(OnExit(GameState::MainMenu), restart_system_optimized);
+    }
+}
+
+// === SIMULATION ===
+fn reset_replay_clock(mut clock: ResMut<ReplayClock>, playback: Option<Res<ReplayPlayback>>) {
+    clock.step = 0;
+    if let Some(playback) = playback {
+        clock.next_id = playback.file.first_id;
+    }
+    clock.first_id = clock.next_id;
+}
+
+fn advance_replay_clock(mut clock: ResMut<ReplayClock>) {
+    clock.step += 1;
+}
+
+/// Newcomers are numbered by kind and position, not query order, which depends on archetype history
+fn assign_replay_ids(
+    mut commands: Commands,
+    mut clock: ResMut<ReplayClock>,
+    newcomers: Query<
+        (Entity, &Transform, Has<Agent>, Has<Enemy>, Has<Civilian>, Has<Police>, Has<Vehicle>),
+        (
+            Or<(With<Agent>, With<Enemy>, With<Civilian>, With<Police>, With<Vehicle>, With<Terminal>, With<Hackable>)>,
+            Without<ReplayId>,
+            Without<MarkedForDespawn>,
+        ),
+    >,
+) {
+    let mut newcomers: Vec<(u8, Vec3, Entity)> = newcomers.iter()
+        .map(|(entity, transform, agent, enemy, civilian, police, vehicle)| {
+            let kind = [agent, enemy, civilian, police, vehicle].iter().position(|&is| is).unwrap_or(5) as u8;
+            (kind, transform.translation, entity)
+        })
+        .collect();
+
+    newcomers.sort_by(|(kind_a, pos_a, entity_a), (kind_b, pos_b, entity_b)| {
+        kind_a.cmp(kind_b)
+            .then(pos_a.x.total_cmp(&pos_b.x))
+            .then(pos_a.y.total_cmp(&pos_b.y))
+            .then(entity_a.cmp(entity_b))
+    });
+
+    for (_, _, entity) in newcomers {
+        commands.entity(entity).insert(ReplayId(clock.next_id));
+        clock.next_id += 1;
+    }
+}
+
+fn begin_recording(
+    mut commands: Commands,
+    rng: Res<GameRng>,
+    clock: Res<ReplayClock>,
+    scene: Option<Res<ActiveMissionScene>>,
+    launch_data: Option<Res<MissionLaunchData>>,
+    playtest: Option<Res<EditorPlaytest>>,
+    global_data: Res<GlobalData>,
+    (territory_manager, progression_tracker, session): (Res<TerritoryManager>, Res<CampaignProgressionTracker>, Res<SaveSession>),
+) {
+    let Some(scene) = scene else { return; };
+
+    let campaign = SaveData::capture(&global_data, &territory_manager, &progression_tracker, &session);
+    let campaign = match serde_json::to_value(campaign) {
+        Ok(campaign) => campaign,
+        Err(e) => {
+            warn!("Mission won't be recorded: {}", e);
+            return;
+        }
+    };
+
+    let started = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let path = Path::new(REPLAY_DIR).join(format!("replay_{}_{}.json", started, scene.scene_name));
+
+    commands.insert_resource(ReplayRecorder {
+        file: ReplayFile {
+            version: REPLAY_VERSION,
+            scene_name: scene.scene_name.clone(),
+            city_id: scene.city_id.clone(),
+            region_id: launch_data.map_or(global_data.selected_region, |data| data.region_id),
+            playtest: playtest.is_some(),
+            mission_seed: rng.seed(),
+            sim_hz: SIMULATION_HZ,
+            final_step: 0,
+            first_id: clock.first_id,
+            campaign,
+            entries: Vec::new(),
+            checksums: Vec::new(),
+        },
+        path,
+        sim_watermark: None,
+        last_selection: Vec::new(),
+        last_paused: false,
+    });
+}
+
+fn apply_replay_commands(
+    mut commands: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    clock: Res<ReplayClock>,
+    ids: Query<(Entity, &ReplayId), Without<MarkedForDespawn>>,
+    selected: Query<Entity, With<Selected>>,
+    mut action_events: EventWriter<ActionEvent>,
+    mut cyberspace_outcomes: EventWriter<CyberspaceOutcomeEvent>,
+    mut selection: ResMut<SelectionState>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    let due = playback.take_due(clock.step);
+    if due.is_empty() {
+        return;
+    }
+
+    let entities: HashMap<u32, Entity> = ids.iter().map(|(entity, id)| (id.0, entity)).collect();
+    let entity_of = |id: u32| entities.get(&id).copied();
+
+    for command in due {
+        match command {
+            ReplayCommand::Action { entity, action } => {
+                match (entity_of(entity), action.decode(entity_of)) {
+                    (Some(entity), Some(action)) => { action_events.write(ActionEvent { entity, action }); }
+                    _ => warn!("Replay step {}: {:?} refers to a missing entity", clock.step, action),
+                }
+            }
+            ReplayCommand::Select { agents } => {
+                for entity in selected.iter() {
+                    commands.entity(entity).remove::<Selected>();
+                }
+                selection.selected = agents.into_iter().filter_map(entity_of).collect();
+                for &entity in &selection.selected {
+                    commands.entity(entity).insert(Selected);
+                }
+            }
+            ReplayCommand::Pause { paused } => game_mode.paused = paused,
+            ReplayCommand::CyberspaceOutcome { agent, effects, feedback_damage, traced } => {
+                let Some(agent) = entity_of(agent) else {
+                    warn!("Replay step {}: cyberspace outcome for a missing agent", clock.step);
+                    continue;
+                };
+                let effects = effects.iter()
+                    .filter_map(|effect| {
+                        let decoded = effect.decode(entity_of);
+                        if decoded.is_none() {
+                            warn!("Replay step {}: {:?} refers to a missing entity", clock.step, effect);
+                        }
+                        decoded
+                    })
+                    .collect();
+                cyberspace_outcomes.write(CyberspaceOutcomeEvent { agent, effects, feedback_damage, traced });
+            }
+            ReplayCommand::Input { .. } | ReplayCommand::Hack { .. } => {}
+        }
+    }
+}
+
+fn record_simulation_step(
+    mut recorder: ResMut<ReplayRecorder>,
+    clock: Res<ReplayClock>,
+    mut action_events: EventReader<ActionEvent>,
+    mut hack_events: EventReader<HackAttemptEvent>,
+    mut cyberspace_outcomes: EventReader<CyberspaceOutcomeEvent>,
+    ids: Query<&ReplayId>,
+    units: Query<(&ReplayId, &Transform, Option<&Health>), Without<MarkedForDespawn>>,
+) {
+    if let Some((_, event_id)) = action_events.read_with_id().last() {
+        recorder.sim_watermark = Some(event_id.id);
+    }
+
+    for event in hack_events.read() {
+        if let (Ok(agent), Ok(target)) = (ids.get(event.agent), ids.get(event.target)) {
+            recorder.push(clock.step, ReplayCommand::Hack { agent: agent.0, target: target.0 });
+        }
+    }
+
+    // Read in the step it's applied, so playback hands it to the simulation at the same point
+    let id_of = |entity: Entity| ids.get(entity).ok().map(|id| id.0);
+    for outcome in cyberspace_outcomes.read() {
+        let Some(agent) = id_of(outcome.agent) else { continue; };
+        let effects = outcome.effects.iter()
+            .filter_map(|effect| ReplayCyberEffect::encode(effect, id_of))
+            .collect();
+        recorder.push(clock.step, ReplayCommand::CyberspaceOutcome {
+            agent,
+            effects,
+            feedback_damage: outcome.feedback_damage,
+            traced: outcome.traced,
+        });
+    }
+
+    if clock.step % CHECKSUM_INTERVAL == 0 {
+        let checksum = world_checksum(units.iter());
+        recorder.file.checksums.push((clock.step, checksum));
+    }
+}
+
+fn verify_replay_checksums(
+    mut playback: ResMut<ReplayPlayback>,
+    clock: Res<ReplayClock>,
+    units: Query<(&ReplayId, &Transform, Option<&Health>), Without<MarkedForDespawn>>,
+) {
+    while let Some(&(step, expected)) = playback.file.checksums.get(playback.checksum_cursor) {
+        if step > clock.step {
+            break;
+        }
+        playback.checksum_cursor += 1;
+
+        if step == clock.step && playback.diverged_at.is_none() && world_checksum(units.iter()) != expected {
+            warn!("Replay diverged from the recording at {} (step {})", step_clock(step, playback.file.sim_hz), step);
+            playback.diverged_at = Some(step);
+        }
+    }
+}
+
+// === RECORDING ===
+fn record_player_input(
+    mut recorder: ResMut<ReplayRecorder>,
+    clock: Res<ReplayClock>,
+    mut action_events: EventReader<ActionEvent>,
+    selection: Res<SelectionState>,
+    game_mode: Res<GameMode>,
+    input: Query<&ActionState<PlayerAction>>,
+    ids: Query<&ReplayId>,
+) {
+    // The simulation first sees this frame's input in its next step
+    let step = clock.step + 1;
+    let id_of = |entity: Entity| ids.get(entity).ok().map(|id| id.0);
+
+    for (event, event_id) in action_events.read_with_id() {
+        if recorder.sim_watermark.is_some_and(|watermark| event_id.id <= watermark) {
+            continue;
+        }
+        let (Some(entity), Some(action)) = (id_of(event.entity), ReplayAction::encode(&event.action, id_of)) else {
+            continue;
+        };
+        recorder.push(step, ReplayCommand::Action { entity, action });
+    }
+
+    if selection.selected != recorder.last_selection {
+        recorder.last_selection = selection.selected.clone();
+        let agents = selection.selected.iter().filter_map(|&entity| id_of(entity)).collect();
+        recorder.push(step, ReplayCommand::Select { agents });
+    }
+
+    if game_mode.paused != recorder.last_paused {
+        recorder.last_paused = game_mode.paused;
+        recorder.push(step, ReplayCommand::Pause { paused: game_mode.paused });
+    }
+
+    if let Ok(action_state) = input.single() {
+        for action in action_state.get_just_pressed() {
+            recorder.push(step, ReplayCommand::Input { action });
+        }
+    }
+}
+
+/// F6 writes what has been recorded so far, for reporting a problem without ending the mission
+fn save_replay_snapshot_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    clock: Res<ReplayClock>,
+) {
+    if keyboard.just_pressed(KeyCode::F6) {
+        recorder.file.final_step = clock.step;
+        write_replay(&recorder.path, &recorder.file);
+    }
+}
+
+/// Saves and stops the recording; also queued by anything that makes the rest unreplayable
+pub fn finish_replay_recording(world: &mut World) {
+    let Some(mut recorder) = world.remove_resource::<ReplayRecorder>() else { return; };
+    recorder.file.final_step = world.resource::<ReplayClock>().step;
+    write_replay(&recorder.path, &recorder.file);
+}
+
+// === PLAYBACK ===
+fn start_pending_replay(
+    mut commands: Commands,
+    pending: Res<PendingReplay>,
+    mut global_data: ResMut<GlobalData>,
+    backup: Option<Res<ReplayCampaignBackup>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    leftovers: Query<Entity, (With<ReplayId>, Without<MarkedForDespawn>)>,
+) {
+    commands.remove_resource::<PendingReplay>();
+    let file = &pending.file;
+
+    let campaign = match save::migrate(file.campaign.clone()) {
+        Ok(campaign) => campaign,
+        Err(e) => {
+            error!("Replay campaign is unreadable: {}", e);
+            return;
+        }
+    };
+
+    if backup.is_none() {
+        commands.insert_resource(ReplayCampaignBackup(global_data.clone()));
+    }
+    *global_data = GlobalData::from(campaign);
+    global_data.selected_region = file.region_id;
+
+    match &file.city_id {
+        Some(city_id) => commands.insert_resource(MissionLaunchData { city_id: city_id.clone(), region_id: file.region_id }),
+        None => commands.remove_resource::<MissionLaunchData>(),
+    }
+    if file.playtest {
+        commands.insert_resource(EditorPlaytest { scene_name: file.scene_name.clone() });
+    } else {
+        commands.remove_resource::<EditorPlaytest>();
+    }
+
+    // Units left from an earlier run would collide with the new ids
+    for entity in leftovers.iter() {
+        commands.entity(entity).insert(MarkedForDespawn);
+    }
+
+    info!("Playing replay of {} (seed {})", file.scene_name, file.mission_seed);
+    commands.insert_resource(ReplayPlayback::new(file.clone(), pending.seek_to));
+    commands.insert_resource(ShouldRestart);
+    next_state.set(GameState::Mission);
+}
+
+fn end_replay(
+    mut commands: Commands,
+    pending: Option<Res<PendingReplay>>,
+    backup: Option<Res<ReplayCampaignBackup>>,
+    mut global_data: ResMut<GlobalData>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    commands.remove_resource::<ReplayPlayback>();
+    time.unpause();
+    time.set_relative_speed(1.0);
+    time.set_max_delta(DEFAULT_MAX_DELTA);
+
+    // A rewind passes through the menu and keeps the replay's campaign
+    if pending.is_some() {
+        return;
+    }
+
+    if let Some(backup) = backup {
+        *global_data = backup.0.clone();
+        commands.remove_resource::<ReplayCampaignBackup>();
+        commands.remove_resource::<EditorPlaytest>();
+    }
+}
+
+/// Space pause, [ ] speed, , . skip 10s, Home restart, Esc leave; the slider scrubs
+fn replay_controls_system(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut playback: ResMut<ReplayPlayback>,
+    clock: Res<ReplayClock>,
+    mut time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let hz = playback.file.sim_hz;
+    let final_step = playback.file.final_step;
+    let jump = (SEEK_JUMP_SECS * hz) as u32;
+
+    let mut toggle_pause = keyboard.just_pressed(KeyCode::Space);
+    let mut speed_change: i32 = 0;
+    let mut seek = None;
+
+    if keyboard.just_pressed(KeyCode::BracketLeft) { speed_change -= 1; }
+    if keyboard.just_pressed(KeyCode::BracketRight) { speed_change += 1; }
+    if keyboard.just_pressed(KeyCode::Comma) { seek = Some(clock.step.saturating_sub(jump)); }
+    if keyboard.just_pressed(KeyCode::Period) { seek = Some(clock.step + jump); }
+    if keyboard.just_pressed(KeyCode::Home) { seek = Some(0); }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+        return;
+    }
+
+    if let Ok(ctx) = contexts.ctx_mut() {
+        egui::Window::new("Replay")
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 10.0))
+            .collapsible(false)
+            .resizable(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong(&playback.file.scene_name);
+                    ui.label(format!("{} / {}", step_clock(clock.step, hz), step_clock(final_step, hz)));
+                    if playback.seek_target.is_some() {
+                        ui.weak("seeking...");
+                    } else if time.is_paused() {
+                        ui.weak("paused");
+                    } else {
+                        ui.weak(format!("x{}", PLAYBACK_SPEEDS[playback.speed_index]));
+                    }
+                });
+
+                let mut scrub = clock.step.min(final_step);
+                let response = ui.add(egui::Slider::new(&mut scrub, 0..=final_step.max(1)).show_value(false));
+                if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                    seek = Some(scrub);
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("⏮").clicked() { seek = Some(0); }
+                    if ui.button("-10s").clicked() { seek = Some(clock.step.saturating_sub(jump)); }
+                    if ui.button(if time.is_paused() { "▶" } else { "⏸" }).clicked() { toggle_pause = true; }
+                    if ui.button("+10s").clicked() { seek = Some(clock.step + jump); }
+                    if ui.button("Slower").clicked() { speed_change -= 1; }
+                    if ui.button("Faster").clicked() { speed_change += 1; }
+                });
+
+                if let Some(step) = playback.diverged_at {
+                    ui.colored_label(egui::Color32::LIGHT_RED,
+                        format!("Diverged from the recording at {}", step_clock(step, hz)));
+                }
+
+                ui.separator();
+                let recent: Vec<&ReplayEntry> = playback.file.entries[..playback.cursor].iter().rev()
+                    .filter(|entry| !matches!(entry.command, ReplayCommand::Input { .. }))
+                    .take(5)
+                    .collect();
+                for entry in recent.into_iter().rev() {
+                    ui.weak(format!("{}  {}", step_clock(entry.step, hz), entry.command.describe()));
+                }
+                ui.weak("Space pause | [ ] speed | , . skip 10s | Home restart | Esc leave");
+            });
+    }
+
+    playback.speed_index = (playback.speed_index as i32 + speed_change).clamp(0, PLAYBACK_SPEEDS.len() as i32 - 1) as usize;
+
+    if let Some(target) = seek.map(|step| step.min(final_step)) {
+        if target < clock.step {
+            // The simulation only runs forwards; rewinding restarts it and fast-forwards
+            commands.insert_resource(PendingReplay { file: playback.file.clone(), seek_to: (target > 0).then_some(target) });
+            next_state.set(GameState::MainMenu);
+            return;
+        }
+        if target > clock.step {
+            playback.seek_target = Some(target);
+            playback.pause_after_seek = time.is_paused();
+        }
+    }
+
+    if let Some(target) = playback.seek_target {
+        if clock.step >= target {
+            playback.seek_target = None;
+            time.set_max_delta(DEFAULT_MAX_DELTA);
+            if playback.pause_after_seek {
+                time.pause();
+            }
+        } else {
+            time.unpause();
+            time.set_max_delta(SEEK_MAX_DELTA);
+            time.set_relative_speed(SEEK_SPEED);
+            return;
+        }
+    }
+
+    if toggle_pause {
+        if time.is_paused() { time.unpause(); } else { time.pause(); }
+    }
+    // Nothing was recorded past the last step
+    if clock.step >= final_step {
+        time.pause();
+    }
+    time.set_relative_speed(PLAYBACK_SPEEDS[playback.speed_index]);
+}
+
+/// Camera that ignores selection and pausing, and moves in real time whatever the playback speed
+fn replay_camera_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut camera_query: Query<(&mut Transform, &mut IsometricCamera), With<Camera2d>>,
+    time: Res<Time<Real>>,
+) {
+    let Ok((mut camera_transform, mut iso_camera)) = camera_query.single_mut() else { return; };
+    iso_camera.follow_target = None;
+
+    let mut movement = Vec2::ZERO;
+    if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) { movement.y += 1.0; }
+    if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) { movement.y -= 1.0; }
+    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) { movement.x -= 1.0; }
+    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) { movement.x += 1.0; }
+
+    let speed = FREE_CAMERA_SPEED / iso_camera.zoom;
+    camera_transform.translation += (movement.normalize_or_zero() * speed * time.delta_secs()).extend(0.0);
+
+    for wheel_event in mouse_wheel.read() {
+        iso_camera.zoom = (iso_camera.zoom + wheel_event.y * 0.1).clamp(iso_camera.min_zoom, iso_camera.max_zoom);
+    }
+    camera_transform.scale = Vec3::splat(1.0 / iso_camera.zoom);
+}
@@ -1,5 +1,6 @@
 // src/systems/roads.rs - Efficient road network and flow field pathfinding
 use bevy::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::traffic::*;
 use crate::systems::death::*;
@@ -406,15 +407,17 @@ pub fn road_clearing_system(
     mut road_grid: ResMut<RoadGrid>,
     mut clear_timer: Local<f32>,
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
 ) {
     *clear_timer -= time.delta_secs();
     
     if *clear_timer <= 0.0 {
         // Gradually clear blocked roads (emergency services clearing debris)
         let mut dirty = false;
+        let rng = rng.stream(RngStream::Traffic);
         
         for tile in &mut road_grid.tiles {
-            if tile.blocked && rand::random::<f32>() < 0.01 { // 1% chance per tile
+            if tile.blocked && rng.gen::<f32>() < 0.01 { // 1% chance per tile
                 tile.blocked = false;
                 dirty = true;
             }
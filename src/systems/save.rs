@@ -1,13 +1,35 @@
+// src/systems/save.rs - Versioned campaign saves with migrations, named slots and atomic writes
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
+use std::io::Write;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 use crate::core::*;
 
-const SAVE_FILE: &str = "subversive_save.json";
+const LEGACY_SAVE_FILE: &str = "subversive_save.json";
+const SAVE_DIR: &str = "saves";
+pub const MAX_SAVE_SLOTS: usize = 5;
+const BACKUP_COUNT: usize = 2;
+
+/// Current schema; bump it and append to `MIGRATIONS` whenever `SaveData` changes shape
+pub const SAVE_VERSION: u32 = 6;
+
+/// (from_version, upgrade) pairs applied in order until the data reaches `SAVE_VERSION`
+const MIGRATIONS: &[(u32, fn(&mut Value))] = &[
+    (1, migrate_v1_to_v2),
+    (2, migrate_v2_to_v3),
+    (3, migrate_v3_to_v4),
+    (4, migrate_v4_to_v5),
+    (5, migrate_v5_to_v6),
+];
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SaveData {
+    pub version: u32,
+    pub metadata: SaveMetadata,
     pub credits: u32,
     pub current_day: u32,
     pub agent_levels: [u8; 3],
@@ -20,9 +42,26 @@ pub struct SaveData {
     pub recruited_scientists: Vec<Scientist>,
     pub research_facilities_discovered: HashSet<String>,
     pub alert_level: u8,
-     // 0.2.17
-    pub territory_manager: Option<TerritoryManager>,
-    pub progression_tracker: Option<CampaignProgressionTracker>,
+    pub territory_manager: TerritoryManager,
+    pub progression_tracker: CampaignProgressionTracker,
+    pub ironman: bool,
+    pub agent_traits: [Vec<AgentTrait>; 3],
+    pub agent_performance: [AgentPerformance; 3],
+    pub campaign_seed: u64,
+}
+
+/// Shown on the load screen without deserializing the campaign
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SaveMetadata {
+    pub slot_name: String,
+    pub day: u32,
+    pub credits: u32,
+    pub city: String,
+    pub playtime_secs: f64,
+    pub summary: String,
+    /// Unix timestamp of the write
+    pub saved_at: u64,
+    pub ironman: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -33,9 +72,50 @@ pub struct SaveRegion {
     pub alert_decay_timer: u32,
 }
 
-impl From<&GlobalData> for SaveData {
-    fn from(data: &GlobalData) -> Self {
+/// Slot the campaign writes to, plus accumulated playtime for that campaign
+#[derive(Resource, Default)]
+pub struct SaveSession {
+    pub active_slot: usize,
+    pub playtime_secs: f64,
+}
+
+pub struct SaveSlotInfo {
+    pub slot: usize,
+    pub metadata: Option<SaveMetadata>,
+    pub corrupt: bool,
+}
+
+/// Slot listing read once when a menu opens; Continue, Load Game and New Game all answer from it
+#[derive(Resource, Default)]
+pub struct SaveSlotCache {
+    pub slots: Vec<SaveSlotInfo>,
+}
+
+impl SaveSlotCache {
+    pub fn refresh(&mut self) {
+        self.slots = list_save_slots();
+    }
+}
+
+impl SaveData {
+    pub fn capture(
+        data: &GlobalData,
+        territory_manager: &TerritoryManager,
+        progression_tracker: &CampaignProgressionTracker,
+        session: &SaveSession,
+    ) -> Self {
         Self {
+            version: SAVE_VERSION,
+            metadata: SaveMetadata {
+                slot_name: format!("{} {}", if data.ironman { "Ironman" } else { "Slot" }, session.active_slot + 1),
+                day: data.current_day,
+                credits: data.credits,
+                city: data.cities_progress.current_city.clone(),
+                playtime_secs: session.playtime_secs,
+                summary: campaign_summary(data, territory_manager),
+                saved_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+                ironman: data.ironman,
+            },
             credits: data.credits,
             current_day: data.current_day,
             agent_levels: data.agent_levels,
@@ -58,11 +138,20 @@ impl From<&GlobalData> for SaveData {
             recruited_scientists: data.recruited_scientists.clone(),
             research_facilities_discovered: data.research_facilities_discovered.clone(),
             alert_level: data.alert_level,
-            // 0.2.17
-            territory_manager: None,
-            progression_tracker: None,
+            territory_manager: territory_manager.clone(),
+            progression_tracker: progression_tracker.clone(),
+            ironman: data.ironman,
+            agent_traits: data.agent_traits.clone(),
+            agent_performance: data.agent_performance.clone(),
+            campaign_seed: data.campaign_seed,
         }
     }
+
+    pub fn into_campaign(self) -> (GlobalData, TerritoryManager, CampaignProgressionTracker) {
+        let territory_manager = self.territory_manager.clone();
+        let progression_tracker = self.progression_tracker.clone();
+        (GlobalData::from(self), territory_manager, progression_tracker)
+    }
 }
 
 impl From<SaveData> for GlobalData {
@@ -91,51 +180,319 @@ impl From<SaveData> for GlobalData {
             recruited_scientists: save.recruited_scientists,
             research_facilities_discovered: save.research_facilities_discovered,
             alert_level: save.alert_level,
+            ironman: save.ironman,
+            agent_traits: save.agent_traits,
+            agent_performance: save.agent_performance,
+            campaign_seed: save.campaign_seed,
         };
 
         global_data
     }
 }
 
+fn campaign_summary(data: &GlobalData, territory_manager: &TerritoryManager) -> String {
+    let levels: Vec<String> = data.agent_levels.iter().map(|l| format!("L{}", l)).collect();
+    format!("{} districts held, {:.0}% liberated, agents {}",
+        territory_manager.controlled_districts.len(),
+        territory_manager.global_liberation_progress * 100.0,
+        levels.join("/"))
+}
+
+// === MIGRATIONS ===
+/// Files without a `version` field predate versioning; 0.2.17 added the territory fields
+fn detect_version(value: &Value) -> u32 {
+    match value.get("version").and_then(Value::as_u64) {
+        Some(version) => version as u32,
+        None if value.get("territory_manager").is_some() => 2,
+        None => 1,
+    }
+}
+
+fn migrate_v1_to_v2(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else { return; };
+    obj.entry("territory_manager").or_insert(Value::Null);
+    obj.entry("progression_tracker").or_insert(Value::Null);
+}
+
+fn migrate_v2_to_v3(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else { return; };
+
+    // Older saves wrote `None` here; start those campaigns with fresh territory state
+    if obj.get("territory_manager").map_or(true, Value::is_null) {
+        obj.insert("territory_manager".into(), serde_json::to_value(TerritoryManager::default()).unwrap_or(Value::Null));
+    }
+    if obj.get("progression_tracker").map_or(true, Value::is_null) {
+        obj.insert("progression_tracker".into(), serde_json::to_value(CampaignProgressionTracker::default()).unwrap_or(Value::Null));
+    }
+
+    let metadata = SaveMetadata {
+        slot_name: "Imported save".to_string(),
+        day: obj.get("current_day").and_then(Value::as_u64).unwrap_or(1) as u32,
+        credits: obj.get("credits").and_then(Value::as_u64).unwrap_or(0) as u32,
+        city: obj.get("cities_progress")
+            .and_then(|c| c.get("current_city"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        playtime_secs: 0.0,
+        summary: "Migrated from an earlier version".to_string(),
+        saved_at: 0,
+        ironman: false,
+    };
+    obj.insert("metadata".into(), serde_json::to_value(metadata).unwrap_or(Value::Null));
+}
+
+fn migrate_v3_to_v4(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else { return; };
+    obj.entry("ironman").or_insert(Value::Bool(false));
+    obj.entry("agent_traits").or_insert(serde_json::json!([[], [], []]));
+    if let Some(metadata) = obj.get_mut("metadata").and_then(Value::as_object_mut) {
+        metadata.entry("ironman").or_insert(Value::Bool(false));
+    }
+}
+
+fn migrate_v4_to_v5(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else { return; };
+    let fresh: [AgentPerformance; 3] = Default::default();
+    obj.entry("agent_performance").or_insert(serde_json::to_value(fresh).unwrap_or(Value::Null));
+}
+
+/// Older campaigns never had a seed; it comes from the save itself, so loading the same
+/// file twice (or listing it, then loading it) always yields the same campaign
+fn migrate_v5_to_v6(value: &mut Value) {
+    let seed = derive_seed(0, &[b"campaign", value.to_string().as_bytes()]);
+    let Some(obj) = value.as_object_mut() else { return; };
+    obj.entry("campaign_seed").or_insert(Value::from(seed));
+}
+
+/// Brings raw save JSON up to the current layout; replays embed a campaign snapshot too
+pub fn migrate(value: Value) -> Result<SaveData, String> {
+    upgrade(value).and_then(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+}
+
+fn upgrade(mut value: Value) -> Result<Value, String> {
+    let mut version = detect_version(&value);
+    if version > SAVE_VERSION {
+        return Err(format!("save version {} is newer than supported version {}", version, SAVE_VERSION));
+    }
+
+    while version < SAVE_VERSION {
+        let (_, upgrade) = MIGRATIONS.iter()
+            .find(|(from, _)| *from == version)
+            .ok_or_else(|| format!("no migration from save version {}", version))?;
+        upgrade(&mut value);
+        version += 1;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("version".into(), Value::from(version));
+        }
+        info!("Migrated save data to version {}", version);
+    }
+
+    Ok(value)
+}
+
+fn parse_save(content: &str) -> Result<SaveData, String> {
+    serde_json::from_str::<Value>(content)
+        .map_err(|e| e.to_string())
+        .and_then(migrate)
+}
+
+/// Just the metadata block, so listing slots never builds whole campaigns
+fn parse_metadata(content: &str) -> Result<SaveMetadata, String> {
+    let mut value = serde_json::from_str::<Value>(content)
+        .map_err(|e| e.to_string())
+        .and_then(upgrade)?;
+    let metadata = value.get_mut("metadata").map(Value::take).ok_or("save has no metadata")?;
+    serde_json::from_value(metadata).map_err(|e| e.to_string())
+}
+
+// === SLOT STORAGE ===
+fn slot_path(slot: usize) -> PathBuf {
+    Path::new(SAVE_DIR).join(format!("slot_{}.json", slot + 1))
+}
+
+fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    path.with_extension(format!("json.bak{}", generation))
+}
+
+/// Writes to a temp file, rotates backups, then renames over the live file
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    for generation in (1..BACKUP_COUNT).rev() {
+        let from = backup_path(path, generation);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, generation + 1))?;
+        }
+    }
+    if path.exists() {
+        fs::copy(path, backup_path(path, 1))?;
+    }
+
+    fs::rename(&tmp, path)
+}
+
+/// Moves a pre-slot `subversive_save.json` into the first slot once
+fn import_legacy_save() {
+    let legacy = Path::new(LEGACY_SAVE_FILE);
+    if !legacy.exists() || slot_path(0).exists() {
+        return;
+    }
+
+    match fs::read_to_string(legacy).map_err(|e| e.to_string()).and_then(|c| parse_save(&c)) {
+        Ok(save_data) => {
+            let written = serde_json::to_string_pretty(&save_data)
+                .map_err(|e| e.to_string())
+                .and_then(|json| write_atomic(&slot_path(0), &json).map_err(|e| e.to_string()));
+            match written {
+                Ok(_) => {
+                    let _ = fs::rename(legacy, legacy.with_extension("json.migrated"));
+                    info!("Imported legacy save into slot 1");
+                }
+                Err(e) => warn!("Failed to import legacy save: {}", e),
+            }
+        }
+        Err(e) => warn!("Legacy save is unreadable: {}", e),
+    }
+}
+
+/// Reads a slot, falling back to its backups when the live file is damaged
+pub fn load_save_slot(slot: usize) -> Option<SaveData> {
+    read_slot(slot, parse_save)
+}
+
+fn read_slot<T>(slot: usize, parse: fn(&str) -> Result<T, String>) -> Option<T> {
+    import_legacy_save();
+
+    let path = slot_path(slot);
+    let candidates = std::iter::once(path.clone()).chain((1..=BACKUP_COUNT).map(|g| backup_path(&path, g)));
+
+    for candidate in candidates {
+        let Ok(content) = fs::read_to_string(&candidate) else { continue; };
+        match parse(&content) {
+            Ok(parsed) => {
+                if candidate != path {
+                    warn!("Slot {} was damaged; loaded backup {:?}", slot + 1, candidate);
+                }
+                return Some(parsed);
+            }
+            Err(e) => warn!("Failed to read {:?}: {}", candidate, e),
+        }
+    }
+    None
+}
+
+/// Reads every slot from disk; menus go through `SaveSlotCache` rather than calling this per frame
+pub fn list_save_slots() -> Vec<SaveSlotInfo> {
+    import_legacy_save();
+
+    (0..MAX_SAVE_SLOTS).map(|slot| {
+        if !slot_path(slot).exists() {
+            return SaveSlotInfo { slot, metadata: None, corrupt: false };
+        }
+        match read_slot(slot, parse_metadata) {
+            Some(metadata) => SaveSlotInfo { slot, metadata: Some(metadata), corrupt: false },
+            None => SaveSlotInfo { slot, metadata: None, corrupt: true },
+        }
+    }).collect()
+}
+
+pub fn latest_save_slot(slots: &[SaveSlotInfo]) -> Option<usize> {
+    slots.iter()
+        .filter_map(|info| info.metadata.as_ref().map(|m| (info.slot, m.saved_at)))
+        .max_by_key(|(_, saved_at)| *saved_at)
+        .map(|(slot, _)| slot)
+}
+
+/// First empty slot, or the least recently written one when all are taken
+pub fn slot_for_new_game(slots: &[SaveSlotInfo]) -> usize {
+    slots.iter()
+        .find(|info| info.metadata.is_none() && !info.corrupt)
+        .or_else(|| slots.iter().min_by_key(|info| info.metadata.as_ref().map_or(0, |m| m.saved_at)))
+        .map_or(0, |info| info.slot)
+}
+
+pub fn delete_save_slot(slot: usize) {
+    let path = slot_path(slot);
+    for file in std::iter::once(path.clone()).chain((1..=BACKUP_COUNT).map(|g| backup_path(&path, g))) {
+        if file.exists() && fs::remove_file(&file).is_err() {
+            warn!("Failed to delete {:?}", file);
+        }
+    }
+    info!("Deleted save slot {}", slot + 1);
+}
+
 pub fn save_game_complete(
     global_data: &GlobalData,
     research_progress: &ResearchProgress,
     territory_manager: &TerritoryManager,
     progression_tracker: &CampaignProgressionTracker,
+    session: &SaveSession,
 ) {
     let mut updated_global_data = global_data.clone();
     updated_global_data.research_progress = research_progress.clone();
 
-    let mut save_data = SaveData::from(&updated_global_data);
+    let save_data = SaveData::capture(&updated_global_data, territory_manager, progression_tracker, session);
 
-    // ADD TERRITORY DATA:
-    save_data.territory_manager = Some(territory_manager.clone());
-    save_data.progression_tracker = Some(progression_tracker.clone());
-
-    if let Ok(json) = serde_json::to_string_pretty(&save_data) {
-        if fs::write(SAVE_FILE, json).is_ok() {
-            info!("Game saved successfully");
-        } else {
-            warn!("Failed to save game");
-        }
+    match serde_json::to_string_pretty(&save_data) {
+        Ok(json) => match write_atomic(&slot_path(session.active_slot), &json) {
+            Ok(_) => info!("Game saved to slot {}", session.active_slot + 1),
+            Err(e) => warn!("Failed to save game: {}", e),
+        },
+        Err(e) => warn!("Failed to serialize save: {}", e),
     }
 }
 
+/// Most recently written slot, used at startup and by Continue
 pub fn load_game() -> Option<(GlobalData, TerritoryManager, CampaignProgressionTracker)> {
-    fs::read_to_string(SAVE_FILE)
-        .ok()
-        .and_then(|content| serde_json::from_str::<SaveData>(&content).ok())
-        .map(|save_data| {
-            let global_data = GlobalData::from(save_data.clone());
-            let territory_manager = save_data.territory_manager.unwrap_or_default();
-            let progression_tracker = save_data.progression_tracker.unwrap_or_default();
-            (global_data, territory_manager, progression_tracker)
-        })
+    latest_save_slot(&list_save_slots())
+        .and_then(load_save_slot)
+        .map(SaveData::into_campaign)
+}
+
+/// Installs a loaded slot as the running campaign
+pub fn apply_save(
+    slot: usize,
+    save_data: SaveData,
+    global_data: &mut GlobalData,
+    research_progress: &mut ResearchProgress,
+    territory_manager: &mut TerritoryManager,
+    progression_tracker: &mut CampaignProgressionTracker,
+    session: &mut SaveSession,
+) {
+    session.active_slot = slot;
+    session.playtime_secs = save_data.metadata.playtime_secs;
+
+    let (data, territory, progression) = save_data.into_campaign();
+    *research_progress = data.research_progress.clone();
+    *global_data = data;
+    *territory_manager = territory;
+    *progression_tracker = progression;
 }
 
 // Used by Main Menu for Continue logic
-pub fn save_game_exists() -> bool {
-    std::path::Path::new(SAVE_FILE).exists()
+pub fn save_game_exists(slots: &[SaveSlotInfo]) -> bool {
+    slots.iter().any(|info| info.metadata.is_some())
+}
+
+// === SYSTEMS ===
+pub fn playtime_system(
+    time: Res<Time>,
+    game_state: Res<State<GameState>>,
+    mut session: ResMut<SaveSession>,
+) {
+    if is_campaign_state(game_state.get()) {
+        session.playtime_secs += time.delta_secs_f64();
+    }
 }
 
 pub fn save_input_system(
@@ -144,10 +501,15 @@ pub fn save_input_system(
     research_progress: Res<ResearchProgress>,
     territory_manager: Res<TerritoryManager>,
     progression_tracker: Res<CampaignProgressionTracker>,
+    session: Res<SaveSession>,
     game_state: Res<State<GameState>>,
 ) {
     if input.just_pressed(KeyCode::F5) && *game_state.get() == GameState::GlobalMap {
-        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker);
+        if global_data.ironman {
+            info!("Ironman campaigns save automatically");
+            return;
+        }
+        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker, &session);
     }
 }
 
@@ -156,10 +518,11 @@ pub fn auto_save_system(
     research_progress: Res<ResearchProgress>,
     territory_manager: Res<TerritoryManager>,
     progression_tracker: Res<CampaignProgressionTracker>,
+    session: Res<SaveSession>,
     mut last_day: Local<u32>,
 ) {
     if global_data.current_day != *last_day && global_data.current_day > 1 {
-        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker);
+        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker, &session);
         *last_day = global_data.current_day;
     }
 }
@@ -170,12 +533,34 @@ pub fn post_mission_save_system(
     research_progress: Res<ResearchProgress>,
     territory_manager: Res<TerritoryManager>,
     progression_tracker: Res<CampaignProgressionTracker>,
-    cities_progress: Res<CitiesProgress>,
+    session: Res<SaveSession>,
     post_mission: Res<PostMissionResults>,
 ) {
     if processed.0 && post_mission.success {
-        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker);
+        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker, &session);
         info!("Auto-saved after successful mission completion");
         processed.0 = false;
     }
-}
\ No newline at end of file
+}
+
+/// Ironman keeps a single slot current by writing it on every campaign state change
+pub fn ironman_autosave_system(
+    mut transitions: EventReader<StateTransitionEvent<GameState>>,
+    global_data: Res<GlobalData>,
+    research_progress: Res<ResearchProgress>,
+    territory_manager: Res<TerritoryManager>,
+    progression_tracker: Res<CampaignProgressionTracker>,
+    session: Res<SaveSession>,
+) {
+    let campaign_transition = transitions.read().fold(false, |any, transition| {
+        any || [&transition.exited, &transition.entered].into_iter().flatten().any(is_campaign_state)
+    });
+
+    if campaign_transition && global_data.ironman {
+        save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker, &session);
+    }
+}
+
+fn is_campaign_state(state: &GameState) -> bool {
+    matches!(state, GameState::GlobalMap | GameState::Mission | GameState::PostMission | GameState::Cyberspace)
+}
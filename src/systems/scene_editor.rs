@@ -0,0 +1,829 @@
+// src/systems/scene_editor.rs - In-game editor for authoring scenes/*.json on the isometric grid
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::core::*;
+use crate::systems::extraction::ExtractionZone;
+use crate::systems::isometric_camera::{get_isometric_mouse_position, isometric_camera_movement, IsometricCamera};
+use crate::systems::mission::restart_system_optimized;
+use crate::systems::scenes::*;
+use crate::systems::tilemap::IsometricSettings;
+use crate::systems::ui::cleanup_mission_ui;
+
+const PICK_RADIUS: f32 = 20.0;
+const DEFAULT_ZONE_RADIUS: f32 = 100.0;
+const DEFAULT_ZONE_CAPACITY: usize = 20;
+const DEFAULT_FOOT_TRAFFIC: f32 = 0.5;
+
+const TERMINAL_TYPES: [&str; 3] = ["objective", "equipment", "intel"];
+const VEHICLE_TYPES: [&str; 7] = ["civilian_car", "police_car", "apc", "vtol", "tank", "truck", "fuel_truck"];
+const ENEMY_ARCHETYPES: [EnemyType; 4] = [EnemyType::Guard, EnemyType::Patrol, EnemyType::Elite, EnemyType::Cyborg];
+
+const GRID_COLOR: Color = Color::srgba(0.4, 0.5, 0.6, 0.25);
+const HOVER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+const PATROL_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const ROUTE_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+
+// === TOOLS ===
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum EditorTool {
+    #[default]
+    Select,
+    Agent,
+    Enemy,
+    /// Appends to the selected enemy's patrol
+    PatrolPoint,
+    Civilian,
+    Terminal,
+    Vehicle,
+    WorkZone,
+    ShoppingZone,
+    ResidentialZone,
+    /// Extends the selected route, or starts a new one
+    TransitRoute,
+    Erase,
+}
+
+impl EditorTool {
+    const ALL: [EditorTool; 12] = [
+        EditorTool::Select, EditorTool::Erase,
+        EditorTool::Agent, EditorTool::Enemy, EditorTool::PatrolPoint, EditorTool::Civilian,
+        EditorTool::Terminal, EditorTool::Vehicle,
+        EditorTool::WorkZone, EditorTool::ShoppingZone, EditorTool::ResidentialZone, EditorTool::TransitRoute,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            EditorTool::Select => "Select",
+            EditorTool::Agent => "Agent",
+            EditorTool::Enemy => "Enemy",
+            EditorTool::PatrolPoint => "Patrol",
+            EditorTool::Civilian => "Civilian",
+            EditorTool::Terminal => "Terminal",
+            EditorTool::Vehicle => "Vehicle",
+            EditorTool::WorkZone => "Work zone",
+            EditorTool::ShoppingZone => "Shopping zone",
+            EditorTool::ResidentialZone => "Residential zone",
+            EditorTool::TransitRoute => "Transit route",
+            EditorTool::Erase => "Erase",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ZoneKind {
+    Work,
+    Shopping,
+    Residential,
+}
+
+impl ZoneKind {
+    const ALL: [ZoneKind; 3] = [ZoneKind::Work, ZoneKind::Shopping, ZoneKind::Residential];
+
+    fn color(self) -> Color {
+        match self {
+            ZoneKind::Work => Color::srgb(0.3, 0.5, 1.0),
+            ZoneKind::Shopping => Color::srgb(1.0, 0.3, 0.9),
+            ZoneKind::Residential => Color::srgb(0.2, 0.9, 0.8),
+        }
+    }
+}
+
+/// One editable point of the scene, by index into its list
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditorHandle {
+    Agent(usize),
+    Enemy(usize),
+    PatrolPoint(usize, usize),
+    Civilian(usize),
+    Terminal(usize),
+    Vehicle(usize),
+    Zone(ZoneKind, usize),
+    RoutePoint(usize, usize),
+}
+
+impl EditorHandle {
+    /// Units go through `adjust_position_for_isometric` when the mission spawns them; zones and routes are used as written
+    fn snapped_on_spawn(self) -> bool {
+        !matches!(self, EditorHandle::Zone(..) | EditorHandle::RoutePoint(..))
+    }
+}
+
+// === RESOURCES ===
+#[derive(Resource)]
+pub struct SceneEditorState {
+    pub scene_name: String,
+    pub scene: SceneData,
+    pub tool: EditorTool,
+    pub selected: Option<EditorHandle>,
+    pub dragging: bool,
+    pub dirty: bool,
+    pub status: String,
+    pub available_scenes: Vec<String>,
+    /// Given to newly placed enemies; None leaves it to the briefing's resistance profile
+    pub enemy_archetype: Option<EnemyType>,
+    pub terminal_type: String,
+    pub vehicle_type: String,
+}
+
+impl Default for SceneEditorState {
+    fn default() -> Self {
+        Self {
+            scene_name: "untitled".to_string(),
+            scene: SceneData::default(),
+            tool: EditorTool::Select,
+            selected: None,
+            dragging: false,
+            dirty: false,
+            status: String::new(),
+            available_scenes: Vec::new(),
+            enemy_archetype: None,
+            terminal_type: TERMINAL_TYPES[0].to_string(),
+            vehicle_type: VEHICLE_TYPES[0].to_string(),
+        }
+    }
+}
+
+/// Present while a mission is running as a playtest of the edited scene; the campaign is left alone
+#[derive(Resource)]
+pub struct EditorPlaytest {
+    pub scene_name: String,
+}
+
+impl SceneEditorState {
+    /// Every editable point with its position as written in the scene
+    fn handles(&self) -> Vec<(EditorHandle, [f32; 2])> {
+        let scene = &self.scene;
+        let mut handles = Vec::new();
+
+        handles.extend(scene.agents.iter().enumerate().map(|(i, a)| (EditorHandle::Agent(i), a.position)));
+        for (i, enemy) in scene.enemies.iter().enumerate() {
+            handles.push((EditorHandle::Enemy(i), enemy.position));
+            handles.extend(enemy.patrol_points.iter().enumerate().map(|(p, &point)| (EditorHandle::PatrolPoint(i, p), point)));
+        }
+        handles.extend(scene.civilians.iter().enumerate().map(|(i, c)| (EditorHandle::Civilian(i), c.position)));
+        handles.extend(scene.terminals.iter().enumerate().map(|(i, t)| (EditorHandle::Terminal(i), t.position)));
+        handles.extend(scene.vehicles.iter().enumerate().map(|(i, v)| (EditorHandle::Vehicle(i), v.position)));
+
+        if let Some(areas) = &scene.urban_areas {
+            for kind in ZoneKind::ALL {
+                handles.extend(zones_of(areas, kind).iter().enumerate().map(|(i, z)| (EditorHandle::Zone(kind, i), z.center)));
+            }
+            for (r, route) in areas.transit_routes.iter().enumerate() {
+                handles.extend(route.points.iter().enumerate().map(|(p, &point)| (EditorHandle::RoutePoint(r, p), point)));
+            }
+        }
+
+        handles
+    }
+
+    fn position_mut(&mut self, handle: EditorHandle) -> Option<&mut [f32; 2]> {
+        let scene = &mut self.scene;
+        match handle {
+            EditorHandle::Agent(i) => scene.agents.get_mut(i).map(|a| &mut a.position),
+            EditorHandle::Enemy(i) => scene.enemies.get_mut(i).map(|e| &mut e.position),
+            EditorHandle::PatrolPoint(i, p) => scene.enemies.get_mut(i).and_then(|e| e.patrol_points.get_mut(p)),
+            EditorHandle::Civilian(i) => scene.civilians.get_mut(i).map(|c| &mut c.position),
+            EditorHandle::Terminal(i) => scene.terminals.get_mut(i).map(|t| &mut t.position),
+            EditorHandle::Vehicle(i) => scene.vehicles.get_mut(i).map(|v| &mut v.position),
+            EditorHandle::Zone(kind, i) => scene.urban_areas.as_mut()
+                .and_then(|areas| zones_of_mut(areas, kind).get_mut(i))
+                .map(|z| &mut z.center),
+            EditorHandle::RoutePoint(r, p) => scene.urban_areas.as_mut()
+                .and_then(|areas| areas.transit_routes.get_mut(r))
+                .and_then(|route| route.points.get_mut(p)),
+        }
+    }
+
+    fn pick(&self, cursor: Vec2, tilemap_settings: &Option<Res<IsometricSettings>>) -> Option<EditorHandle> {
+        self.handles().into_iter()
+            .map(|(handle, position)| (handle, displayed_position(handle, position, tilemap_settings).distance(cursor)))
+            .filter(|(_, distance)| *distance <= PICK_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(handle, _)| handle)
+    }
+
+    fn place(&mut self, position: [f32; 2]) {
+        let placed = match self.tool {
+            EditorTool::Agent => {
+                self.scene.agents.push(AgentSpawn { position, level: 1 });
+                Some(EditorHandle::Agent(self.scene.agents.len() - 1))
+            }
+            EditorTool::Enemy => {
+                self.scene.enemies.push(EnemySpawn { position, patrol_points: Vec::new(), archetype: self.enemy_archetype });
+                Some(EditorHandle::Enemy(self.scene.enemies.len() - 1))
+            }
+            EditorTool::PatrolPoint => match self.selected {
+                Some(EditorHandle::Enemy(i) | EditorHandle::PatrolPoint(i, _)) => {
+                    self.scene.enemies.get_mut(i).map(|enemy| {
+                        enemy.patrol_points.push(position);
+                        EditorHandle::PatrolPoint(i, enemy.patrol_points.len() - 1)
+                    })
+                }
+                _ => {
+                    self.status = "Select an enemy to draw its patrol".to_string();
+                    None
+                }
+            },
+            EditorTool::Civilian => {
+                self.scene.civilians.push(CivilianSpawn { position });
+                Some(EditorHandle::Civilian(self.scene.civilians.len() - 1))
+            }
+            EditorTool::Terminal => {
+                self.scene.terminals.push(TerminalSpawn { position, terminal_type: self.terminal_type.clone() });
+                Some(EditorHandle::Terminal(self.scene.terminals.len() - 1))
+            }
+            EditorTool::Vehicle => {
+                self.scene.vehicles.push(VehicleSpawn { position, vehicle_type: self.vehicle_type.clone() });
+                Some(EditorHandle::Vehicle(self.scene.vehicles.len() - 1))
+            }
+            EditorTool::WorkZone | EditorTool::ShoppingZone | EditorTool::ResidentialZone => {
+                let kind = match self.tool {
+                    EditorTool::WorkZone => ZoneKind::Work,
+                    EditorTool::ShoppingZone => ZoneKind::Shopping,
+                    _ => ZoneKind::Residential,
+                };
+                let zones = zones_of_mut(self.urban_areas(), kind);
+                zones.push(UrbanZoneData { center: position, radius: DEFAULT_ZONE_RADIUS, capacity: DEFAULT_ZONE_CAPACITY });
+                Some(EditorHandle::Zone(kind, zones.len() - 1))
+            }
+            EditorTool::TransitRoute => {
+                let extending = match self.selected {
+                    Some(EditorHandle::RoutePoint(r, _)) => Some(r),
+                    _ => None,
+                };
+                let routes = &mut self.urban_areas().transit_routes;
+                let r = match extending.filter(|&r| r < routes.len()) {
+                    Some(r) => r,
+                    None => {
+                        routes.push(TransitRouteData { points: Vec::new(), foot_traffic_density: DEFAULT_FOOT_TRAFFIC });
+                        routes.len() - 1
+                    }
+                };
+                routes[r].points.push(position);
+                Some(EditorHandle::RoutePoint(r, routes[r].points.len() - 1))
+            }
+            EditorTool::Select | EditorTool::Erase => None,
+        };
+
+        if placed.is_some() {
+            self.selected = placed;
+            self.dirty = true;
+        }
+    }
+
+    fn remove(&mut self, handle: EditorHandle) {
+        let scene = &mut self.scene;
+        match handle {
+            EditorHandle::Agent(i) => remove_at(&mut scene.agents, i),
+            EditorHandle::Enemy(i) => remove_at(&mut scene.enemies, i),
+            EditorHandle::PatrolPoint(i, p) => {
+                if let Some(enemy) = scene.enemies.get_mut(i) {
+                    remove_at(&mut enemy.patrol_points, p);
+                }
+            }
+            EditorHandle::Civilian(i) => remove_at(&mut scene.civilians, i),
+            EditorHandle::Terminal(i) => remove_at(&mut scene.terminals, i),
+            EditorHandle::Vehicle(i) => remove_at(&mut scene.vehicles, i),
+            EditorHandle::Zone(kind, i) => {
+                if let Some(areas) = scene.urban_areas.as_mut() {
+                    remove_at(zones_of_mut(areas, kind), i);
+                }
+            }
+            EditorHandle::RoutePoint(r, p) => {
+                if let Some(routes) = scene.urban_areas.as_mut().map(|areas| &mut areas.transit_routes) {
+                    if let Some(route) = routes.get_mut(r) {
+                        remove_at(&mut route.points, p);
+                        if route.points.is_empty() {
+                            routes.remove(r);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.selected = None;
+        self.dragging = false;
+        self.dirty = true;
+    }
+
+    fn urban_areas(&mut self) -> &mut UrbanAreasData {
+        self.scene.urban_areas.get_or_insert_with(|| UrbanAreasData {
+            work_zones: Vec::new(),
+            shopping_zones: Vec::new(),
+            residential_zones: Vec::new(),
+            transit_routes: Vec::new(),
+        })
+    }
+
+    fn summary(&self) -> String {
+        let scene = &self.scene;
+        let (zones, routes) = scene.urban_areas.as_ref().map_or((0, 0), |areas| (
+            ZoneKind::ALL.iter().map(|&kind| zones_of(areas, kind).len()).sum::<usize>(),
+            areas.transit_routes.len(),
+        ));
+        format!("{} agents, {} enemies, {} civilians, {} terminals, {} vehicles, {} zones, {} routes",
+            scene.agents.len(), scene.enemies.len(), scene.civilians.len(),
+            scene.terminals.len(), scene.vehicles.len(), zones, routes)
+    }
+}
+
+// === PLUGIN ===
+pub struct SceneEditorPlugin;
+
+impl Plugin for SceneEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SceneEditorState>()
+            .add_systems(OnEnter(GameState::SceneEditor), (
+                enter_scene_editor,
+                cleanup_mission_ui,
+            ))
+            .add_systems(Update, (
+                scene_editor_ui,
+                scene_editor_input,
+                scene_editor_gizmos,
+                isometric_camera_movement,
+            ).chain().run_if(in_state(GameState::SceneEditor)))
+            .add_systems(Update, playtest_return_system.run_if(in_state(GameState::Mission)))
+            // Playtests launch from here, so the mission has to start from a clean slate
+            .add_systems(OnExit(GameState::SceneEditor), restart_system_optimized);
+    }
+}
+
+// === SYSTEMS ===
+fn enter_scene_editor(
+    mut commands: Commands,
+    mut editor: ResMut<SceneEditorState>,
+    mission_entities: Query<Entity, (
+        Or<(With<Agent>, With<Enemy>, With<Civilian>, With<Police>, With<Terminal>, With<Vehicle>, With<Hackable>, With<ExtractionZone>)>,
+        Without<MarkedForDespawn>,
+    )>,
+) {
+    commands.remove_resource::<EditorPlaytest>();
+
+    // Whatever the last mission or playtest left behind would clutter the grid
+    for entity in mission_entities.iter() {
+        commands.entity(entity).insert(MarkedForDespawn);
+    }
+
+    editor.available_scenes = list_scene_files();
+    editor.dragging = false;
+}
+
+enum EditorCommand {
+    New,
+    Load(String),
+    Save,
+    Playtest,
+    Exit,
+}
+
+fn scene_editor_ui(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut editor: ResMut<SceneEditorState>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut scene_cache: ResMut<SceneCache>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else { return; };
+    let editor = &mut *editor;
+    let mut command = None;
+
+    egui::SidePanel::left("scene_editor_panel")
+        .default_width(280.0)
+        .show(ctx, |ui| {
+            ui.heading("Scene Editor");
+
+            ui.horizontal(|ui| {
+                ui.label("Scene:");
+                ui.text_edit_singleline(&mut editor.scene_name);
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button("New").clicked() { command = Some(EditorCommand::New); }
+                if ui.button(if editor.dirty { "Save*" } else { "Save" }).clicked() { command = Some(EditorCommand::Save); }
+                if ui.button("Playtest").clicked() { command = Some(EditorCommand::Playtest); }
+                if ui.button("Exit").clicked() { command = Some(EditorCommand::Exit); }
+            });
+
+            egui::ComboBox::from_label("Load")
+                .selected_text("scenes/*.json")
+                .show_ui(ui, |ui| {
+                    for name in &editor.available_scenes {
+                        if ui.selectable_label(*name == editor.scene_name, name).clicked() {
+                            command = Some(EditorCommand::Load(name.clone()));
+                        }
+                    }
+                });
+
+            ui.separator();
+            ui.label("Palette");
+            ui.horizontal_wrapped(|ui| {
+                for tool in EditorTool::ALL {
+                    ui.selectable_value(&mut editor.tool, tool, tool.label());
+                }
+            });
+
+            ui.separator();
+            ui.label("New entities");
+            ui.horizontal(|ui| {
+                ui.label("Enemy");
+                archetype_combo(ui, "new_enemy_archetype", &mut editor.enemy_archetype);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Terminal");
+                type_combo(ui, "new_terminal_type", &mut editor.terminal_type, &TERMINAL_TYPES);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Vehicle");
+                type_combo(ui, "new_vehicle_type", &mut editor.vehicle_type, &VEHICLE_TYPES);
+            });
+
+            ui.separator();
+            selection_properties(ui, editor);
+
+            ui.separator();
+            ui.weak(editor.summary());
+            ui.weak("LMB: place / select & drag | RMB: deselect | Del: delete");
+            ui.weak("WASD: pan | Wheel: zoom | F11 in playtest: back here");
+
+            if !editor.status.is_empty() {
+                ui.separator();
+                ui.label(&editor.status);
+            }
+        });
+
+    match command {
+        Some(EditorCommand::New) => {
+            *editor = SceneEditorState {
+                available_scenes: std::mem::take(&mut editor.available_scenes),
+                status: "New scene".to_string(),
+                ..default()
+            };
+        }
+        Some(EditorCommand::Load(name)) => match load_scene(&name) {
+            Some(scene) => {
+                editor.scene = scene;
+                editor.status = format!("Loaded scenes/{}.json", name);
+                editor.scene_name = name;
+                editor.selected = None;
+                editor.dirty = false;
+            }
+            None => editor.status = format!("Couldn't read scenes/{}.json", name),
+        },
+        Some(EditorCommand::Save) => {
+            save_edited_scene(editor, &mut scene_cache);
+        }
+        Some(EditorCommand::Playtest) => {
+            if editor.scene.agents.is_empty() {
+                editor.status = "Place at least one agent before playtesting".to_string();
+            } else if save_edited_scene(editor, &mut scene_cache) {
+                commands.insert_resource(EditorPlaytest { scene_name: editor.scene_name.clone() });
+                commands.insert_resource(ShouldRestart);
+                next_state.set(GameState::Mission);
+            }
+        }
+        Some(EditorCommand::Exit) => next_state.set(GameState::MainMenu),
+        None => {}
+    }
+}
+
+fn scene_editor_input(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform, &IsometricCamera)>,
+    mut contexts: EguiContexts,
+    mut editor: ResMut<SceneEditorState>,
+    tilemap_settings: Option<Res<IsometricSettings>>,
+) {
+    let (over_ui, typing) = contexts.ctx_mut()
+        .map_or((false, false), |ctx| (ctx.is_pointer_over_area(), ctx.wants_keyboard_input()));
+
+    if !typing && (keyboard.just_pressed(KeyCode::Delete) || keyboard.just_pressed(KeyCode::Backspace)) {
+        if let Some(handle) = editor.selected {
+            editor.remove(handle);
+        }
+    }
+
+    if mouse.just_released(MouseButton::Left) {
+        editor.dragging = false;
+    }
+    if mouse.just_pressed(MouseButton::Right) && !over_ui {
+        editor.selected = None;
+    }
+
+    let Some(cursor) = get_isometric_mouse_position(&windows, &cameras) else { return; };
+    let snapped = snap_to_tile(cursor, &tilemap_settings);
+
+    if editor.dragging && mouse.pressed(MouseButton::Left) {
+        let Some(handle) = editor.selected else { return; };
+        let moved = match editor.position_mut(handle) {
+            Some(position) if displayed_position(handle, *position, &tilemap_settings) != snapped => {
+                *position = snapped.to_array();
+                true
+            }
+            _ => false,
+        };
+        editor.dirty |= moved;
+        return;
+    }
+
+    if over_ui || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    match editor.tool {
+        EditorTool::Select => {
+            editor.selected = editor.pick(cursor, &tilemap_settings);
+            editor.dragging = editor.selected.is_some();
+        }
+        EditorTool::Erase => {
+            if let Some(handle) = editor.pick(cursor, &tilemap_settings) {
+                editor.remove(handle);
+            }
+        }
+        _ => editor.place(snapped.to_array()),
+    }
+}
+
+fn scene_editor_gizmos(
+    mut gizmos: Gizmos,
+    editor: Res<SceneEditorState>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform, &IsometricCamera)>,
+    tilemap_settings: Option<Res<IsometricSettings>>,
+) {
+    if let Some(settings) = &tilemap_settings {
+        draw_tile_grid(&mut gizmos, settings);
+        if let Some(cursor) = get_isometric_mouse_position(&windows, &cameras) {
+            draw_tile_outline(&mut gizmos, settings, snap_to_tile(cursor, &tilemap_settings), HOVER_COLOR);
+        }
+    }
+
+    let scene = &editor.scene;
+    let at = |handle: EditorHandle, position: [f32; 2]| displayed_position(handle, position, &tilemap_settings);
+
+    // Not editable here, but the squad has to be able to reach them
+    for zone in &scene.extraction_zones {
+        let pos = adjust_position_for_isometric(Vec2::from(zone.position), &tilemap_settings);
+        gizmos.circle_2d(pos, zone.radius, Color::srgba(0.2, 1.0, 0.4, 0.4));
+    }
+
+    if let Some(areas) = &scene.urban_areas {
+        for kind in ZoneKind::ALL {
+            for zone in zones_of(areas, kind) {
+                gizmos.circle_2d(Vec2::from(zone.center), zone.radius, kind.color().with_alpha(0.5));
+            }
+        }
+        for route in &areas.transit_routes {
+            gizmos.linestrip_2d(route.points.iter().map(|&p| Vec2::from(p)), ROUTE_COLOR);
+        }
+    }
+
+    for (i, enemy) in scene.enemies.iter().enumerate() {
+        if enemy.patrol_points.is_empty() {
+            continue;
+        }
+        let patrol = std::iter::once(at(EditorHandle::Enemy(i), enemy.position))
+            .chain(enemy.patrol_points.iter().enumerate().map(|(p, &point)| at(EditorHandle::PatrolPoint(i, p), point)));
+        gizmos.linestrip_2d(patrol, PATROL_COLOR);
+    }
+
+    for (handle, position) in editor.handles() {
+        let pos = at(handle, position);
+        match handle {
+            EditorHandle::Agent(_) => { gizmos.circle_2d(pos, 12.0, Color::srgb(0.2, 1.0, 0.3)); }
+            EditorHandle::Enemy(_) => { gizmos.circle_2d(pos, 12.0, Color::srgb(1.0, 0.2, 0.2)); }
+            EditorHandle::PatrolPoint(..) => { gizmos.circle_2d(pos, 5.0, PATROL_COLOR); }
+            EditorHandle::Civilian(_) => { gizmos.circle_2d(pos, 8.0, Color::srgb(0.6, 0.8, 1.0)); }
+            EditorHandle::Terminal(_) => { gizmos.rect_2d(pos, Vec2::splat(16.0), Color::srgb(1.0, 0.9, 0.2)); }
+            EditorHandle::Vehicle(_) => { gizmos.rect_2d(pos, Vec2::new(32.0, 18.0), Color::srgb(0.7, 0.7, 0.7)); }
+            EditorHandle::Zone(kind, _) => { gizmos.circle_2d(pos, 6.0, kind.color()); }
+            EditorHandle::RoutePoint(..) => { gizmos.circle_2d(pos, 4.0, ROUTE_COLOR); }
+        }
+
+        if editor.selected == Some(handle) {
+            gizmos.circle_2d(pos, 18.0, Color::WHITE);
+        }
+    }
+}
+
+/// F11 during a playtest goes straight back to the editor (F12 is the profiling overlay)
+fn playtest_return_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    playtest: Option<Res<EditorPlaytest>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if playtest.is_some() && keyboard.just_pressed(KeyCode::F11) {
+        next_state.set(GameState::SceneEditor);
+    }
+}
+
+// === UI HELPERS ===
+fn selection_properties(ui: &mut egui::Ui, editor: &mut SceneEditorState) {
+    let Some(handle) = editor.selected else {
+        ui.weak("Nothing selected");
+        return;
+    };
+
+    let mut changed = false;
+    let mut patrol_cleared = false;
+    let scene = &mut editor.scene;
+
+    match handle {
+        EditorHandle::Agent(i) => {
+            if let Some(agent) = scene.agents.get_mut(i) {
+                ui.label(format!("Agent {}", i + 1));
+                changed |= ui.add(egui::Slider::new(&mut agent.level, 1..=10).text("Level")).changed();
+                if i < 3 {
+                    ui.weak("Squad slots use the campaign's agent levels");
+                }
+            }
+        }
+        EditorHandle::Enemy(i) | EditorHandle::PatrolPoint(i, _) => {
+            if let Some(enemy) = scene.enemies.get_mut(i) {
+                ui.label(format!("Enemy {} ({} patrol points)", i + 1, enemy.patrol_points.len()));
+                changed |= archetype_combo(ui, "selected_enemy_archetype", &mut enemy.archetype);
+                if !enemy.patrol_points.is_empty() && ui.button("Clear patrol").clicked() {
+                    enemy.patrol_points.clear();
+                    patrol_cleared = true;
+                }
+            }
+        }
+        EditorHandle::Civilian(i) => {
+            ui.label(format!("Civilian {}", i + 1));
+        }
+        EditorHandle::Terminal(i) => {
+            if let Some(terminal) = scene.terminals.get_mut(i) {
+                ui.label(format!("Terminal {}", i + 1));
+                changed |= type_combo(ui, "selected_terminal_type", &mut terminal.terminal_type, &TERMINAL_TYPES);
+            }
+        }
+        EditorHandle::Vehicle(i) => {
+            if let Some(vehicle) = scene.vehicles.get_mut(i) {
+                ui.label(format!("Vehicle {}", i + 1));
+                changed |= type_combo(ui, "selected_vehicle_type", &mut vehicle.vehicle_type, &VEHICLE_TYPES);
+            }
+        }
+        EditorHandle::Zone(kind, i) => {
+            if let Some(zone) = scene.urban_areas.as_mut().and_then(|areas| zones_of_mut(areas, kind).get_mut(i)) {
+                ui.label(format!("{:?} zone {}", kind, i + 1));
+                changed |= ui.add(egui::Slider::new(&mut zone.radius, 20.0..=600.0).text("Radius")).changed();
+                changed |= ui.add(egui::Slider::new(&mut zone.capacity, 1..=200).text("Capacity")).changed();
+            }
+        }
+        EditorHandle::RoutePoint(r, p) => {
+            if let Some(route) = scene.urban_areas.as_mut().and_then(|areas| areas.transit_routes.get_mut(r)) {
+                ui.label(format!("Route {}, point {} of {}", r + 1, p + 1, route.points.len()));
+                changed |= ui.add(egui::Slider::new(&mut route.foot_traffic_density, 0.0..=1.0).text("Foot traffic")).changed();
+            }
+        }
+    }
+
+    if let Some(position) = editor.position_mut(handle).copied() {
+        ui.weak(format!("Position: ({:.0}, {:.0})", position[0], position[1]));
+    }
+
+    if ui.button("Delete").clicked() {
+        editor.remove(handle);
+        return;
+    }
+
+    if patrol_cleared {
+        if let EditorHandle::PatrolPoint(i, _) = handle {
+            editor.selected = Some(EditorHandle::Enemy(i));
+        }
+    }
+    editor.dirty |= changed || patrol_cleared;
+}
+
+fn archetype_combo(ui: &mut egui::Ui, id: &str, archetype: &mut Option<EnemyType>) -> bool {
+    let label = |archetype: Option<EnemyType>| archetype.map_or("From briefing".to_string(), |a| format!("{:?}", a));
+    let mut changed = false;
+
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(label(*archetype))
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(archetype, None, label(None)).changed();
+            for option in ENEMY_ARCHETYPES {
+                changed |= ui.selectable_value(archetype, Some(option), label(Some(option))).changed();
+            }
+        });
+
+    changed
+}
+
+fn type_combo(ui: &mut egui::Ui, id: &str, value: &mut String, options: &[&str]) -> bool {
+    let mut changed = false;
+
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(value.as_str())
+        .show_ui(ui, |ui| {
+            for &option in options {
+                if ui.selectable_label(value.as_str() == option, option).clicked() {
+                    *value = option.to_string();
+                    changed = true;
+                }
+            }
+        });
+
+    changed
+}
+
+// === HELPERS ===
+fn save_edited_scene(editor: &mut SceneEditorState, scene_cache: &mut SceneCache) -> bool {
+    match save_scene(&editor.scene_name, &editor.scene) {
+        Ok(()) => {
+            // Saves within the same second can slip past the cache's mtime check
+            scene_cache.reload_scene(&editor.scene_name);
+            editor.dirty = false;
+            editor.status = format!("Saved scenes/{}.json", editor.scene_name);
+            if !editor.available_scenes.contains(&editor.scene_name) {
+                editor.available_scenes.push(editor.scene_name.clone());
+                editor.available_scenes.sort();
+            }
+            true
+        }
+        Err(e) => {
+            warn!("Failed to save scene '{}': {}", editor.scene_name, e);
+            editor.status = format!("Save failed: {}", e);
+            false
+        }
+    }
+}
+
+fn list_scene_files() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("scenes") else { return Vec::new(); };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Center of the tile under the cursor; already a fixed point of `adjust_position_for_isometric`
+fn snap_to_tile(world_pos: Vec2, tilemap_settings: &Option<Res<IsometricSettings>>) -> Vec2 {
+    match tilemap_settings {
+        Some(settings) => settings.tile_to_world(settings.nearest_tile(world_pos)),
+        None => world_pos.round(),
+    }
+}
+
+/// Where the mission will actually put the point
+fn displayed_position(handle: EditorHandle, position: [f32; 2], tilemap_settings: &Option<Res<IsometricSettings>>) -> Vec2 {
+    if handle.snapped_on_spawn() {
+        adjust_position_for_isometric(Vec2::from(position), tilemap_settings)
+    } else {
+        Vec2::from(position)
+    }
+}
+
+fn draw_tile_grid(gizmos: &mut Gizmos, settings: &IsometricSettings) {
+    let corner = |x: f32, y: f32| Vec2::new(
+        (x - y) * settings.tile_width * 0.5,
+        (x + y) * settings.tile_height * 0.5,
+    );
+    let (width, height) = (settings.map_width as f32, settings.map_height as f32);
+
+    // Tile centers sit on whole tile coordinates, so the edges run along the half steps
+    for x in 0..=settings.map_width {
+        let x = x as f32 - 0.5;
+        gizmos.line_2d(corner(x, -0.5), corner(x, height - 0.5), GRID_COLOR);
+    }
+    for y in 0..=settings.map_height {
+        let y = y as f32 - 0.5;
+        gizmos.line_2d(corner(-0.5, y), corner(width - 0.5, y), GRID_COLOR);
+    }
+}
+
+fn draw_tile_outline(gizmos: &mut Gizmos, settings: &IsometricSettings, center: Vec2, color: Color) {
+    let half_w = Vec2::new(settings.tile_width * 0.5, 0.0);
+    let half_h = Vec2::new(0.0, settings.tile_height * 0.5);
+    gizmos.linestrip_2d([center - half_w, center + half_h, center + half_w, center - half_h, center - half_w], color);
+}
+
+fn zones_of(areas: &UrbanAreasData, kind: ZoneKind) -> &Vec<UrbanZoneData> {
+    match kind {
+        ZoneKind::Work => &areas.work_zones,
+        ZoneKind::Shopping => &areas.shopping_zones,
+        ZoneKind::Residential => &areas.residential_zones,
+    }
+}
+
+fn zones_of_mut(areas: &mut UrbanAreasData, kind: ZoneKind) -> &mut Vec<UrbanZoneData> {
+    match kind {
+        ZoneKind::Work => &mut areas.work_zones,
+        ZoneKind::Shopping => &mut areas.shopping_zones,
+        ZoneKind::Residential => &mut areas.residential_zones,
+    }
+}
+
+fn remove_at<T>(items: &mut Vec<T>, index: usize) {
+    if index < items.len() {
+        items.remove(index);
+    }
+}
@@ -1,11 +1,13 @@
 // src/systems/scenes.rs - Quick wins: removed debug/fallback code and consolidated helpers
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::core::*;
 use crate::systems::ai::*;
 use crate::systems::spawners::{spawn_agent};
+use crate::systems::explosions::{spawn_explodable, ExplodableType};
 use crate::core::factions::Faction;
 use crate::systems::*;
 use crate::systems::selection::*;
@@ -24,6 +26,14 @@ pub struct SceneData {
     pub vehicles: Vec<VehicleSpawn>,
     pub urban_areas: Option<UrbanAreasData>,
     pub police: Option<Vec<PoliceSpawn>>,
+    #[serde(default)]
+    pub infrastructure: Vec<InfrastructureSpawn>,
+    /// Where agents leave the map once objectives are done; defaults to the insertion point
+    #[serde(default)]
+    pub extraction_zones: Vec<ExtractionZoneSpawn>,
+    /// Scripted beats, see `systems::scripting`
+    #[serde(default)]
+    pub triggers: Vec<SceneTrigger>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -62,6 +72,9 @@ pub struct CivilianSpawn {
 pub struct EnemySpawn {
     pub position: [f32; 2],
     pub patrol_points: Vec<[f32; 2]>,
+    /// Fixed archetype; when absent one is drawn from the mission briefing's resistance profile
+    #[serde(default)]
+    pub archetype: Option<EnemyType>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -83,12 +96,123 @@ pub struct PoliceSpawn {
     pub unit_type: String,
 }
 
+/// Device or prop placed by the scene, e.g. `{"type": "street_light", "position": [0, 0], "network_id": "district_main"}`
+///
+/// Power networks are declared by the power stations feeding them; anything else with a
+/// `network_id` is wired into that network and goes dark with it.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InfrastructureSpawn {
+    PowerStation { position: [f32; 2], network_id: String },
+    StreetLight { position: [f32; 2], network_id: String },
+    TrafficLight { position: [f32; 2], network_id: String },
+    SecurityCamera {
+        position: [f32; 2],
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    AutomatedTurret {
+        position: [f32; 2],
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    Drone {
+        position: [f32; 2],
+        /// Patrol loop; a drone without one hovers where it spawned
+        #[serde(default)]
+        route: Vec<[f32; 2]>,
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    /// Planting a virus here spreads it to the rest of the network
+    SecuritySystem { position: [f32; 2], network_id: String },
+    Gate {
+        position: [f32; 2],
+        #[serde(default)]
+        requires_vehicle: bool,
+        /// Card level needed to open it; open gates have none
+        #[serde(default)]
+        access_level: Option<u8>,
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    Door {
+        position: [f32; 2],
+        #[serde(default)]
+        requires_person: bool,
+        #[serde(default)]
+        access_level: Option<u8>,
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    Atm {
+        position: [f32; 2],
+        bank_id: String,
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    Billboard {
+        position: [f32; 2],
+        #[serde(default)]
+        network_id: Option<String>,
+    },
+    AccessCard { position: [f32; 2], level: u8, card_type: CardType },
+    Explodable { position: [f32; 2], object: ExplodableType },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExtractionZoneSpawn {
+    pub position: [f32; 2],
+    #[serde(default = "default_extraction_radius")]
+    pub radius: f32,
+}
+
+fn default_extraction_radius() -> f32 {
+    DEFAULT_EXTRACTION_RADIUS
+}
+
+impl InfrastructureSpawn {
+    pub fn network_id(&self) -> Option<&str> {
+        match self {
+            Self::PowerStation { network_id, .. }
+            | Self::StreetLight { network_id, .. }
+            | Self::TrafficLight { network_id, .. }
+            | Self::SecuritySystem { network_id, .. } => Some(network_id),
+            Self::SecurityCamera { network_id, .. }
+            | Self::AutomatedTurret { network_id, .. }
+            | Self::Drone { network_id, .. }
+            | Self::Gate { network_id, .. }
+            | Self::Door { network_id, .. }
+            | Self::Atm { network_id, .. }
+            | Self::Billboard { network_id, .. } => network_id.as_deref(),
+            Self::AccessCard { .. } | Self::Explodable { .. } => None,
+        }
+    }
+}
+
+impl SceneData {
+    /// Networks that devices hang off but no power station feeds
+    pub fn unfed_networks(&self) -> Vec<&str> {
+        let fed: Vec<&str> = self.infrastructure.iter()
+            .filter(|device| matches!(device, InfrastructureSpawn::PowerStation { .. }))
+            .filter_map(|device| device.network_id())
+            .collect();
+        let mut unfed: Vec<&str> = self.infrastructure.iter()
+            .filter_map(|device| device.network_id())
+            .filter(|id| !fed.contains(id))
+            .collect();
+        unfed.sort_unstable();
+        unfed.dedup();
+        unfed
+    }
+}
+
 // === CORE FUNCTIONS ===
 pub fn load_scene_cached(scene_cache: &mut SceneCache, name: &str) -> Option<SceneData> {
     scene_cache.get_scene(name).cloned()
 }
 
-pub fn spawn_from_scene(commands: &mut Commands, scene: &SceneData, global_data: &GlobalData, sprites: &GameSprites) {
+pub fn spawn_from_scene(commands: &mut Commands, scene: &SceneData, global_data: &GlobalData, sprites: &GameSprites, rng: &mut impl Rng) {
     setup_urban_areas(commands, scene, global_data.selected_region);
 
     for (i, agent) in scene.agents.iter().enumerate() {
@@ -97,12 +221,13 @@ pub fn spawn_from_scene(commands: &mut Commands, scene: &SceneData, global_data:
     }
 
     for civilian in &scene.civilians {
-        spawn_original_urban_civilian(commands, Vec2::from(civilian.position), sprites);
+        spawn_original_urban_civilian(commands, Vec2::from(civilian.position), sprites, rng);
     }
 
     for enemy in &scene.enemies {
         let patrol = enemy.patrol_points.iter().map(|&p| Vec2::from(p)).collect();
-        spawn_enemy(commands, Vec2::from(enemy.position), patrol, global_data, sprites);
+        let archetype = enemy.archetype.clone().unwrap_or(EnemyType::Guard);
+        spawn_enemy(commands, Vec2::from(enemy.position), patrol, archetype, global_data, sprites, rng);
     }
 
     for terminal in &scene.terminals {
@@ -147,6 +272,8 @@ pub fn spawn_from_scene_isometric(
     global_data: &GlobalData,
     sprites: &GameSprites,
     tilemap_settings: &Option<Res<IsometricSettings>>,
+    resistance: Option<&ResistanceProfile>,
+    rng: &mut impl Rng,
 ) {
     // Setup urban areas first
     setup_urban_areas_isometric(commands, scene, global_data.selected_region);
@@ -163,7 +290,7 @@ pub fn spawn_from_scene_isometric(
     for civilian in &scene.civilians {
         let world_pos = Vec2::from(civilian.position);
         let adjusted_pos = adjust_position_for_isometric(world_pos, tilemap_settings);
-        spawn_urban_civilian_isometric(commands, adjusted_pos, sprites);
+        spawn_urban_civilian_isometric(commands, adjusted_pos, sprites, rng);
     }
 
     for enemy in &scene.enemies {
@@ -172,7 +299,8 @@ pub fn spawn_from_scene_isometric(
         let patrol = enemy.patrol_points.iter()
             .map(|&p| adjust_position_for_isometric(Vec2::from(p), tilemap_settings))
             .collect();
-        spawn_enemy_isometric(commands, adjusted_pos, patrol, global_data, sprites);
+        let archetype = enemy.archetype.clone().unwrap_or_else(|| pick_enemy_archetype(resistance, rng));
+        spawn_enemy_isometric(commands, adjusted_pos, patrol, archetype, global_data, sprites, rng);
     }
 
     for terminal in &scene.terminals {
@@ -187,10 +315,104 @@ pub fn spawn_from_scene_isometric(
         let v_type = parse_vehicle_type(&vehicle.vehicle_type);
         spawn_vehicle_isometric(commands, adjusted_pos, v_type, sprites);
     }
+
+    if scene.extraction_zones.is_empty() {
+        // Older scenes leave the way they came in
+        let insertion: Vec<Vec2> = scene.agents.iter()
+            .map(|agent| adjust_position_for_isometric(Vec2::from(agent.position), tilemap_settings))
+            .collect();
+        if !insertion.is_empty() {
+            let center = insertion.iter().sum::<Vec2>() / insertion.len() as f32;
+            spawn_extraction_zone(commands, center, DEFAULT_EXTRACTION_RADIUS);
+        }
+    }
+    for zone in &scene.extraction_zones {
+        let adjusted_pos = adjust_position_for_isometric(Vec2::from(zone.position), tilemap_settings);
+        spawn_extraction_zone(commands, adjusted_pos, zone.radius);
+    }
+}
+
+/// Spawns the devices a scene declares, whether it came from a file or from the map generator
+pub fn spawn_scene_infrastructure(
+    commands: &mut Commands,
+    scene: &SceneData,
+    power_grid: &mut Option<ResMut<PowerGrid>>,
+    tilemap_settings: &Option<Res<IsometricSettings>>,
+) {
+    let adjust = |position: &[f32; 2]| adjust_position_for_isometric(Vec2::from(*position), tilemap_settings);
+
+    for device in &scene.infrastructure {
+        match device {
+            InfrastructureSpawn::PowerStation { position, network_id } => {
+                if let Some(grid) = power_grid.as_mut() {
+                    spawn_power_station(commands, adjust(position), network_id.clone(), grid);
+                }
+            }
+            InfrastructureSpawn::StreetLight { position, network_id } => {
+                if let Some(grid) = power_grid.as_mut() {
+                    spawn_street_light(commands, adjust(position), network_id.clone(), grid);
+                }
+            }
+            InfrastructureSpawn::TrafficLight { position, network_id } => {
+                if let Some(grid) = power_grid.as_mut() {
+                    spawn_traffic_light(commands, adjust(position), network_id.clone(), grid);
+                }
+            }
+            InfrastructureSpawn::SecurityCamera { position, network_id } => {
+                spawn_security_camera(commands, adjust(position), network_id.clone(), power_grid);
+            }
+            InfrastructureSpawn::AutomatedTurret { position, network_id } => {
+                spawn_automated_turret(commands, adjust(position), network_id.clone(), power_grid);
+            }
+            InfrastructureSpawn::Drone { position, route, network_id } => {
+                let route = route.iter().map(adjust).collect();
+                spawn_security_drone(commands, adjust(position), route, network_id.clone(), power_grid);
+            }
+            InfrastructureSpawn::SecuritySystem { position, network_id } => {
+                spawn_security_system(commands, adjust(position), network_id.clone(), power_grid);
+            }
+            InfrastructureSpawn::Gate { position, requires_vehicle, access_level, network_id } => {
+                let entity = spawn_gate(commands, adjust(position), *requires_vehicle, *access_level);
+                wire_access_point(commands, entity, network_id, power_grid);
+            }
+            InfrastructureSpawn::Door { position, requires_person, access_level, network_id } => {
+                let entity = spawn_door(commands, adjust(position), *requires_person, *access_level);
+                wire_access_point(commands, entity, network_id, power_grid);
+            }
+            InfrastructureSpawn::Atm { position, bank_id, network_id } => {
+                spawn_atm(commands, adjust(position), bank_id.clone(), network_id.clone(), power_grid);
+            }
+            InfrastructureSpawn::Billboard { position, network_id } => {
+                spawn_billboard(commands, adjust(position), network_id.clone(), power_grid);
+            }
+            InfrastructureSpawn::AccessCard { position, level, card_type } => {
+                spawn_access_card(commands, adjust(position), *level, *card_type);
+            }
+            InfrastructureSpawn::Explodable { position, object } => {
+                spawn_explodable(commands, adjust(position), *object);
+            }
+        }
+    }
+
+    for network_id in scene.unfed_networks() {
+        warn!("Scene power network '{}' has no power station; its devices can't be cut off", network_id);
+    }
+}
+
+/// Gates and doors on a network lose their lock along with the power
+fn wire_access_point(
+    commands: &mut Commands,
+    entity: Entity,
+    network_id: &Option<String>,
+    power_grid: &mut Option<ResMut<PowerGrid>>,
+) {
+    if let (Some(id), Some(grid)) = (network_id, power_grid.as_mut()) {
+        make_hackable_networked(commands, entity, DeviceType::Door, id.clone(), grid);
+    }
 }
 
 // === POSITION ADJUSTMENT FOR ISOMETRIC ===
-fn adjust_position_for_isometric(
+pub fn adjust_position_for_isometric(
     world_pos: Vec2,
     tilemap_settings: &Option<Res<IsometricSettings>>,
 ) -> Vec2 {
@@ -205,14 +427,14 @@ fn adjust_position_for_isometric(
 }
 
 // === ISOMETRIC ENTITY SPAWNERS ===
-fn spawn_agent_isometric(
+pub fn spawn_agent_isometric(
     commands: &mut Commands,
     pos: Vec2,
     level: u8,
     idx: usize,
     global_data: &GlobalData,
     sprites: &GameSprites,
-) {
+) -> Entity {
     let (sprite, _) = create_agent_sprite(sprites);
     let loadout = global_data.get_agent_loadout(idx);
     let mut inventory = create_inventory_from_loadout(&loadout);
@@ -245,27 +467,33 @@ fn spawn_agent_isometric(
             active: false,
         },
         IsometricDepth(10.0), // For proper z-sorting
-    ));
+    )).insert(AgentUpgrades {
+        traits: global_data.agent_traits[idx.min(2)].clone(),
+        performance: global_data.agent_performance[idx.min(2)].clone(),
+        ..default()
+    }).id()
 }
 
-fn spawn_urban_civilian_isometric(commands: &mut Commands, pos: Vec2, sprites: &GameSprites) {
+pub fn spawn_urban_civilian_isometric(commands: &mut Commands, pos: Vec2, sprites: &GameSprites, rng: &mut impl Rng) -> Entity {
     let (sprite, _) = create_civilian_sprite(sprites);
-    let crowd_influence = 0.2 + rand::random::<f32>() * 0.6;
-    let panic_threshold = 15.0 + rand::random::<f32>() * 50.0;
-    let daily_state = random_daily_state();
+    let crowd_influence = 0.2 + rng.gen::<f32>() * 0.6;
+    let panic_threshold = 15.0 + rng.gen::<f32>() * 50.0;
+    let daily_state = random_daily_state(rng);
+    let speed = 80.0 + rng.gen::<f32>() * 40.0;
+    let state_timer = rng.gen::<f32>() * 15.0;
 
     commands.spawn((
         sprite,
         Transform::from_translation(pos.extend(5.0)), // Lower Z than agents
         Civilian,
         Faction::Civilian,
-        create_base_unit_bundle(50.0, 80.0 + rand::random::<f32>() * 40.0),
+        create_base_unit_bundle(50.0, speed),
         Morale::new(80.0, panic_threshold),
         Controllable,
         NeurovectorTarget,
         UrbanCivilian {
             daily_state,
-            state_timer: rand::random::<f32>() * 15.0,
+            state_timer,
             next_destination: None,
             crowd_influence,
             panic_threshold,
@@ -275,21 +503,44 @@ fn spawn_urban_civilian_isometric(commands: &mut Commands, pos: Vec2, sprites: &
         create_physics_bundle(7.5, CIVILIAN_GROUP),
         Scannable,
         IsometricDepth(5.0),
-    ));
+    )).id()
 }
 
-fn spawn_enemy_isometric(
+pub fn spawn_enemy_isometric(
     commands: &mut Commands,
     pos: Vec2,
     patrol: Vec<Vec2>,
+    archetype: EnemyType,
     global_data: &GlobalData,
     sprites: &GameSprites,
+    rng: &mut impl Rng,
 ) {
-    let (sprite, _) = create_enemy_sprite(sprites);
+    let faction = random_enemy_faction(rng);
+    let weapon = archetype.pick_weapon(&faction, rng);
+    spawn_enemy_isometric_with(commands, pos, patrol, archetype, faction, weapon, global_data, sprites);
+}
+
+/// Enemy spawn with an explicit faction and weapon (used when restoring snapshots)
+pub fn spawn_enemy_isometric_with(
+    commands: &mut Commands,
+    pos: Vec2,
+    patrol: Vec<Vec2>,
+    archetype: EnemyType,
+    faction: Faction,
+    weapon: WeaponType,
+    global_data: &GlobalData,
+    sprites: &GameSprites,
+) -> Entity {
+    let (mut sprite, _) = create_enemy_sprite(sprites);
     let difficulty = global_data.regions[global_data.selected_region].mission_difficulty_modifier();
+    let stats = archetype.stats();
+    sprite.color = tint_color(sprite.color, stats.tint);
+    sprite.custom_size = sprite.custom_size.map(|size| size * stats.scale);
 
-    let faction = random_enemy_faction();
-    let weapon = select_weapon_for_faction(&faction);
+    let goap_agent = match stats.goap_profile {
+        Some(profile) => GoapAgent::default().with_profile(profile),
+        None => GoapAgent::default(),
+    };
 
     let mut inventory = Inventory::default();
     inventory.equipped_weapon = Some(WeaponConfig::new(weapon.clone()));
@@ -302,18 +553,19 @@ fn spawn_enemy_isometric(
         Transform::from_translation(pos.extend(8.0)), // Mid-level Z
         Enemy,
         faction,
-        create_base_unit_bundle(100.0 * difficulty, 100.0),
-        Morale::new(100.0 * difficulty, 25.0),
-        Vision::new(120.0 * difficulty, 60.0),
+        (EnemyArchetype(archetype), Armor(stats.armor)),
+        create_base_unit_bundle(stats.health * difficulty, stats.speed),
+        Morale::new(stats.morale * difficulty, stats.panic_threshold),
+        Vision::new(stats.vision_range * difficulty, stats.vision_fov),
         Patrol::new(patrol),
         AIState::default(),
-        GoapAgent::default(),
+        goap_agent,
         weapon_state,
         inventory,
-        create_physics_bundle(9.0, ENEMY_GROUP),
+        create_physics_bundle(9.0 * stats.scale, ENEMY_GROUP),
         Scannable,
         IsometricDepth(8.0),
-    ));
+    )).id()
 }
 
 fn spawn_terminal_isometric(commands: &mut Commands, pos: Vec2, terminal_type: &str, sprites: &GameSprites) {
@@ -394,6 +646,7 @@ pub fn spawn_fallback_isometric_mission(
     global_data: &GlobalData,
     sprites: &GameSprites,
     tilemap_settings: &Option<Res<IsometricSettings>>,
+    rng: &mut impl Rng,
 ) {
 
     let positions = [Vec2::new(-200.0, 0.0), Vec2::new(-170.0, 0.0), Vec2::new(-140.0, 0.0)];
@@ -401,11 +654,13 @@ pub fn spawn_fallback_isometric_mission(
         let adjusted_pos = adjust_position_for_isometric(pos, tilemap_settings);
         spawn_agent_isometric(commands, adjusted_pos, global_data.agent_levels[i], i, global_data, sprites);
     }
+    let extraction_pos = adjust_position_for_isometric(positions[1], tilemap_settings);
+    spawn_extraction_zone(commands, extraction_pos, DEFAULT_EXTRACTION_RADIUS);
 
     let civilian_positions = [Vec2::new(100.0, 100.0), Vec2::new(150.0, 80.0), Vec2::new(80.0, 150.0)];
     for &pos in &civilian_positions {
         let adjusted_pos = adjust_position_for_isometric(pos, tilemap_settings);
-        spawn_urban_civilian_isometric(commands, adjusted_pos, sprites);
+        spawn_urban_civilian_isometric(commands, adjusted_pos, sprites, rng);
     }
 
     let terminal_pos = adjust_position_for_isometric(Vec2::new(200.0, 0.0), tilemap_settings);
@@ -455,6 +710,20 @@ pub fn load_scene(name: &str) -> Option<SceneData> {
     serde_json::from_str(&content).ok()
 }
 
+/// Writes through a temp file so a failed save never leaves a truncated scene behind
+pub fn save_scene(name: &str, scene: &SceneData) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(format!("'{}' is not a valid scene name", name));
+    }
+
+    let json = serde_json::to_string_pretty(scene).map_err(|e| e.to_string())?;
+    let path = format!("scenes/{}.json", name);
+    let tmp_path = format!("{}.tmp", path);
+    std::fs::write(&tmp_path, json)
+        .and_then(|_| std::fs::rename(&tmp_path, &path))
+        .map_err(|e| e.to_string())
+}
+
 
 // 0.2.5.3 - added Pathfinding Obstacle component
 pub fn spawn_cover_points(commands: &mut Commands) {
@@ -491,8 +760,8 @@ pub fn spawn_cover_points(commands: &mut Commands) {
 
 
 
-pub fn random_daily_state() -> DailyState {
-    match rand::random::<f32>() {
+pub fn random_daily_state(rng: &mut impl Rng) -> DailyState {
+    match rng.gen::<f32>() {
         x if x < 0.3 => DailyState::Working,
         x if x < 0.5 => DailyState::Shopping,
         x if x < 0.7 => DailyState::GoingHome,
@@ -500,19 +769,24 @@ pub fn random_daily_state() -> DailyState {
     }
 }
 
-pub fn random_enemy_faction() -> Faction {
-    match rand::random::<f32>() {
+pub fn tint_color(base: Color, tint: Color) -> Color {
+    let (b, t) = (base.to_srgba(), tint.to_srgba());
+    Color::srgba(b.red * t.red, b.green * t.green, b.blue * t.blue, b.alpha)
+}
+
+pub fn random_enemy_faction(rng: &mut impl Rng) -> Faction {
+    match rng.gen::<f32>() {
         x if x < 0.4 => Faction::Corporate,
         x if x < 0.8 => Faction::Syndicate,
         _ => Faction::Police,
     }
 }
 
-pub fn select_weapon_for_faction(faction: &Faction) -> WeaponType {
+pub fn select_weapon_for_faction(faction: &Faction, rng: &mut impl Rng) -> WeaponType {
 
     match faction {
-        Faction::Corporate => if rand::random::<f32>() < 0.7 { WeaponType::Rifle } else { WeaponType::Pistol },
-        Faction::Syndicate => match rand::random::<f32>() {
+        Faction::Corporate => if rng.gen::<f32>() < 0.7 { WeaponType::Rifle } else { WeaponType::Pistol },
+        Faction::Syndicate => match rng.gen::<f32>() {
             x if x < 0.5 => WeaponType::Pistol,
             // x if x < 0.8 => WeaponType::Flamethrower,
             _ => WeaponType::Rifle,
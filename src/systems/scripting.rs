@@ -0,0 +1,327 @@
+// src/systems/scripting.rs - Scene-authored triggers that fire scripted mission beats
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::core::*;
+use crate::systems::access_control::{Door, DoorStateChange, Gate, GateStateChange};
+use crate::systems::message_window::MessageLog;
+use crate::systems::npc_barks::{BarkEvent, BarkType};
+use crate::systems::scenes::{adjust_position_for_isometric, spawn_enemy_isometric};
+use crate::systems::spawners::spawn_time_bomb;
+use crate::systems::tilemap::IsometricSettings;
+
+/// How far a scripted position may be from the gate, door, device or NPC it means
+const TARGET_SNAP_RADIUS: f32 = 48.0;
+const REINFORCEMENT_SPREAD: f32 = 20.0;
+
+// === SCENE DATA ===
+/// `{"id": "lockdown", "conditions": [{"type": "alert_level", "level": "Orange"}], "actions": [{"type": "open_gate", "position": [0, 0]}]}`
+///
+/// Fires once, when all conditions hold at the same time; no conditions fires at mission start.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneTrigger {
+    pub id: String,
+    #[serde(default)]
+    pub conditions: Vec<TriggerCondition>,
+    pub actions: Vec<TriggerAction>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerCondition {
+    /// Any living agent inside the circle
+    ZoneEntered { position: [f32; 2], radius: f32 },
+    /// The device nearest the position was hacked at some point
+    DeviceHacked { position: [f32; 2] },
+    AlertLevel { level: AlertLevel },
+    TimerElapsed { seconds: f32 },
+    /// The enemy, civilian or officer standing nearest the position at mission start died
+    NpcKilled { position: [f32; 2] },
+    EnemiesKilled { count: u32 },
+    TriggerFired { id: String },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerAction {
+    SpawnReinforcements {
+        position: [f32; 2],
+        #[serde(default = "default_reinforcement_count")]
+        count: u32,
+        #[serde(default)]
+        patrol_points: Vec<[f32; 2]>,
+        /// Drawn from the briefing's resistance profile when absent
+        #[serde(default)]
+        archetype: Option<EnemyType>,
+    },
+    /// Opens and unlocks the gate, so its motion sensor keeps letting people through
+    OpenGate { position: [f32; 2] },
+    OpenDoor { position: [f32; 2] },
+    StartTimeBomb {
+        position: [f32; 2],
+        timer: f32,
+        #[serde(default = "default_bomb_damage")]
+        damage: f32,
+        #[serde(default = "default_bomb_radius")]
+        radius: f32,
+    },
+    Message { text: String },
+    /// The NPC nearest the position shouts a bark
+    Bark { position: [f32; 2], bark: BarkType },
+    DiscoverLore { id: String },
+}
+
+fn default_reinforcement_count() -> u32 { 1 }
+fn default_bomb_damage() -> f32 { 150.0 }
+fn default_bomb_radius() -> f32 { 100.0 }
+
+// === RESOURCES ===
+pub struct ScriptedTrigger {
+    pub trigger: SceneTrigger,
+    pub fired: bool,
+    /// Per condition; hacks and kills stay met once they've happened
+    pub latched: Vec<bool>,
+    /// Per condition; the NPC an NpcKilled condition is watching
+    npc_targets: Vec<Option<Entity>>,
+}
+
+/// Triggers for the running mission, built from the scene when it loads
+#[derive(Resource, Default)]
+pub struct MissionScript {
+    pub triggers: Vec<ScriptedTrigger>,
+    pub bound: bool,
+    /// Actions of triggers that fired this frame, run by `run_trigger_actions`
+    pending: Vec<TriggerAction>,
+    resistance: Option<ResistanceProfile>,
+}
+
+impl MissionScript {
+    pub fn from_scene(triggers: &[SceneTrigger], resistance: Option<ResistanceProfile>) -> Self {
+        Self {
+            triggers: triggers.iter().map(|trigger| ScriptedTrigger {
+                trigger: trigger.clone(),
+                fired: false,
+                latched: vec![false; trigger.conditions.len()],
+                npc_targets: vec![None; trigger.conditions.len()],
+            }).collect(),
+            bound: false,
+            pending: Vec::new(),
+            resistance,
+        }
+    }
+
+    /// Fired and latched flags per trigger, for quicksaves
+    pub fn progress(&self) -> Vec<(bool, Vec<bool>)> {
+        self.triggers.iter().map(|t| (t.fired, t.latched.clone())).collect()
+    }
+
+    /// Returns false when the saved progress belongs to a different script
+    pub fn restore_progress(&mut self, progress: &[(bool, Vec<bool>)]) -> bool {
+        let matches = progress.len() == self.triggers.len()
+            && self.triggers.iter().zip(progress).all(|(t, (_, latched))| t.latched.len() == latched.len());
+        if !matches {
+            return false;
+        }
+
+        for (scripted, (fired, latched)) in self.triggers.iter_mut().zip(progress) {
+            scripted.fired = *fired;
+            scripted.latched = latched.clone();
+        }
+        self.pending.clear();
+        // Watched NPCs were respawned as new entities
+        self.bound = false;
+        true
+    }
+}
+
+// === SYSTEMS ===
+/// Resolves NpcKilled conditions to the NPCs the scene placed there
+pub fn bind_scene_triggers(
+    mut script: ResMut<MissionScript>,
+    agents: Query<(), With<Agent>>,
+    npcs: Query<(Entity, &Transform), (Or<(With<Enemy>, With<Civilian>, With<Police>)>, Without<Dead>)>,
+    tilemap_settings: Option<Res<IsometricSettings>>,
+) {
+    if script.bound || agents.is_empty() {
+        return;
+    }
+
+    for scripted in script.triggers.iter_mut().filter(|t| !t.fired) {
+        for (i, condition) in scripted.trigger.conditions.iter().enumerate() {
+            let TriggerCondition::NpcKilled { position } = condition else { continue };
+            if scripted.latched[i] {
+                continue;
+            }
+
+            let pos = adjust_position_for_isometric(Vec2::from(*position), &tilemap_settings);
+            scripted.npc_targets[i] = nearest(pos, npcs.iter().map(|(e, t)| (e, t.translation.truncate())));
+            if scripted.npc_targets[i].is_none() {
+                warn!("Trigger '{}' watches for a kill at {:?} but no NPC stands there", scripted.trigger.id, position);
+            }
+        }
+    }
+
+    script.bound = true;
+    info!("Bound {} scene triggers", script.triggers.len());
+}
+
+pub fn scene_trigger_system(
+    mut script: ResMut<MissionScript>,
+    mut hack_events: EventReader<HackCompletedEvent>,
+    transforms: Query<&Transform>,
+    agents: Query<&Transform, (With<Agent>, Without<Dead>)>,
+    npc_dead: Query<Has<Dead>>,
+    mission_data: Res<MissionData>,
+    game_mode: Res<GameMode>,
+    tilemap_settings: Option<Res<IsometricSettings>>,
+) {
+    let hacked: Vec<Vec2> = hack_events.read()
+        .filter_map(|event| transforms.get(event.target).ok())
+        .map(|t| t.translation.truncate())
+        .collect();
+
+    if game_mode.paused || !script.bound {
+        return;
+    }
+
+    let agent_positions: Vec<Vec2> = agents.iter().map(|t| t.translation.truncate()).collect();
+    let fired_ids: Vec<String> = script.triggers.iter()
+        .filter(|t| t.fired)
+        .map(|t| t.trigger.id.clone())
+        .collect();
+    let adjust = |position: &[f32; 2]| adjust_position_for_isometric(Vec2::from(*position), &tilemap_settings);
+
+    let script = &mut *script;
+    for scripted in script.triggers.iter_mut().filter(|t| !t.fired) {
+        for (i, condition) in scripted.trigger.conditions.iter().enumerate() {
+            if scripted.latched[i] {
+                continue;
+            }
+            scripted.latched[i] = match condition {
+                TriggerCondition::DeviceHacked { position } => {
+                    let pos = adjust(position);
+                    hacked.iter().any(|h| h.distance(pos) <= TARGET_SNAP_RADIUS)
+                }
+                // A despawned NPC is as dead as it gets
+                TriggerCondition::NpcKilled { .. } => scripted.npc_targets[i]
+                    .is_some_and(|npc| npc_dead.get(npc).unwrap_or(true)),
+                _ => false,
+            };
+        }
+
+        let met = scripted.trigger.conditions.iter().enumerate().all(|(i, condition)| {
+            scripted.latched[i] || match condition {
+                TriggerCondition::ZoneEntered { position, radius } => {
+                    let pos = adjust(position);
+                    agent_positions.iter().any(|a| a.distance(pos) <= *radius)
+                }
+                TriggerCondition::AlertLevel { level } => mission_data.alert_level as u8 >= *level as u8,
+                TriggerCondition::TimerElapsed { seconds } => mission_data.timer >= *seconds,
+                TriggerCondition::EnemiesKilled { count } => mission_data.enemies_killed >= *count,
+                TriggerCondition::TriggerFired { id } => fired_ids.contains(id),
+                TriggerCondition::DeviceHacked { .. } | TriggerCondition::NpcKilled { .. } => false,
+            }
+        });
+
+        if met {
+            scripted.fired = true;
+            script.pending.extend(scripted.trigger.actions.iter().cloned());
+            info!("Scene trigger '{}' fired", scripted.trigger.id);
+        }
+    }
+}
+
+pub fn run_trigger_actions(
+    mut commands: Commands,
+    mut script: ResMut<MissionScript>,
+    mut gates: Query<(Entity, &mut Gate, &Transform), Without<Door>>,
+    mut doors: Query<(Entity, &mut Door, &Transform), Without<Gate>>,
+    mut gate_events: EventWriter<GateStateChange>,
+    mut door_events: EventWriter<DoorStateChange>,
+    mut bark_events: EventWriter<BarkEvent>,
+    npcs: Query<(Entity, &Transform), (Or<(With<Enemy>, With<Civilian>, With<Police>)>, Without<Dead>)>,
+    mut message_log: ResMut<MessageLog>,
+    mut lore_db: ResMut<LoreDatabase>,
+    global_data: Res<GlobalData>,
+    sprites: Res<GameSprites>,
+    tilemap_settings: Option<Res<IsometricSettings>>,
+    mut rng: ResMut<GameRng>,
+) {
+    if script.pending.is_empty() {
+        return;
+    }
+
+    let adjust = |position: &[f32; 2]| adjust_position_for_isometric(Vec2::from(*position), &tilemap_settings);
+
+    for action in std::mem::take(&mut script.pending) {
+        match action {
+            TriggerAction::SpawnReinforcements { position, count, patrol_points, archetype } => {
+                let center = adjust(&position);
+                let patrol: Vec<Vec2> = patrol_points.iter().map(adjust).collect();
+                for i in 0..count {
+                    let offset = if count > 1 {
+                        Vec2::from_angle(i as f32 * std::f32::consts::TAU / count as f32) * REINFORCEMENT_SPREAD
+                    } else {
+                        Vec2::ZERO
+                    };
+                    let rng = rng.stream(RngStream::Spawning);
+                    let archetype = archetype.unwrap_or_else(|| pick_enemy_archetype(script.resistance.as_ref(), rng));
+                    spawn_enemy_isometric(&mut commands, center + offset, patrol.clone(), archetype, &global_data, &sprites, rng);
+                }
+                info!("Scripted reinforcements: {} at {:?}", count, position);
+            }
+            TriggerAction::OpenGate { position } => {
+                let pos = adjust(&position);
+                let target = nearest(pos, gates.iter().map(|(e, _, t)| (e, t.translation.truncate())));
+                match target.and_then(|entity| gates.get_mut(entity).ok()) {
+                    Some((entity, mut gate, _)) => {
+                        gate.access_level = None;
+                        gate.is_open = true;
+                        gate.open_timer = 0.0;
+                        gate_events.write(GateStateChange { gate: entity, opened: true });
+                    }
+                    None => warn!("Scripted open_gate at {:?} found no gate", position),
+                }
+            }
+            TriggerAction::OpenDoor { position } => {
+                let pos = adjust(&position);
+                let target = nearest(pos, doors.iter().map(|(e, _, t)| (e, t.translation.truncate())));
+                match target.and_then(|entity| doors.get_mut(entity).ok()) {
+                    Some((entity, mut door, _)) => {
+                        door.access_level = None;
+                        door.is_open = true;
+                        door.open_timer = 0.0;
+                        door_events.write(DoorStateChange { door: entity, opened: true });
+                    }
+                    None => warn!("Scripted open_door at {:?} found no door", position),
+                }
+            }
+            TriggerAction::StartTimeBomb { position, timer, damage, radius } => {
+                spawn_time_bomb(&mut commands, adjust(&position), timer, damage, radius);
+            }
+            TriggerAction::Message { text } => {
+                message_log.add(text);
+            }
+            TriggerAction::Bark { position, bark } => {
+                let pos = adjust(&position);
+                match nearest(pos, npcs.iter().map(|(e, t)| (e, t.translation.truncate()))) {
+                    Some(entity) => { bark_events.write(BarkEvent { entity, bark_type: bark }); }
+                    None => warn!("Scripted bark at {:?} found nobody to say it", position),
+                }
+            }
+            TriggerAction::DiscoverLore { id } => {
+                if lore_db.discover_entry(&id) {
+                    spawn_lore_notification(&mut commands, &id, &lore_db);
+                }
+            }
+        }
+    }
+}
+
+// === HELPERS ===
+fn nearest(pos: Vec2, candidates: impl Iterator<Item = (Entity, Vec2)>) -> Option<Entity> {
+    candidates
+        .map(|(entity, p)| (entity, p.distance(pos)))
+        .filter(|(_, distance)| *distance <= TARGET_SNAP_RADIUS)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(entity, _)| entity)
+}
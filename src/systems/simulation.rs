@@ -0,0 +1,307 @@
+// src/systems/simulation.rs - Everything the mission simulation registers, shared by the game and the headless runner
+//
+// Only state, events and FixedUpdate steps live here. Input, camera, UI and the Update-side
+// presentation stay with the game binary, so both binaries step the same systems in the same order.
+use bevy::prelude::*;
+use bevy::ecs::schedule::ExecutorKind;
+use bevy_rapier2d::prelude::CollisionEvent;
+use crate::core::*;
+use crate::systems::*;
+use crate::systems::enhanced_pathfinding::EnhancedPathfindingGrid;
+use crate::systems::profiling::{profile_system, PerformanceMetrics};
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // One thread keeps ambiguous systems (and their RNG draws) in the same order every step
+            .edit_schedule(FixedUpdate, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            })
+
+            .init_resource::<GameMode>()
+            .init_resource::<SelectionState>()
+            .init_resource::<MissionData>()
+            .init_resource::<MissionObjectives>()
+            .init_resource::<ExtractionState>()
+            .init_resource::<MissionPerformanceTracker>()
+            .init_resource::<MissionScript>()
+            .init_resource::<PostMissionResults>()
+            .init_resource::<GameRng>()
+            .init_resource::<PerformanceMetrics>()
+            .init_resource::<DecalSettings>()
+            .init_resource::<InteractiveDecalSettings>()
+            .init_resource::<CombatTextSettings>()
+            .init_resource::<GoapConfigCache>()
+            .init_resource::<SquadBlackboards>()
+            .init_resource::<MessageLog>()
+            .init_resource::<PowerGrid>()
+            .init_resource::<RemoteAccess>()
+            .init_resource::<TrafficSystem>()
+            .init_resource::<PoliceResponse>()
+            .init_resource::<DayNightCycle>()
+            .init_resource::<UrbanSecurity>()
+            .init_resource::<PathfindingGrid>()
+            .init_resource::<EnhancedPathfindingGrid>()
+            .init_resource::<BankingNetwork>()
+            .init_resource::<IsometricSettings>()
+
+            .add_event::<ActionEvent>()
+            .add_event::<CombatEvent>()
+            .add_event::<AudioEvent>()
+            .add_event::<AlertEvent>()
+            .add_event::<PoliceIncidentEvent>()
+            .add_event::<GrenadeEvent>()
+            .add_event::<BarkEvent>()
+            .add_event::<LoreAccessEvent>()
+            .add_event::<HackAttemptEvent>()
+            .add_event::<HackCompletedEvent>()
+            .add_event::<PowerGridEvent>()
+            .add_event::<DamageTextEvent>()
+            .add_event::<AccessEvent>()
+            .add_event::<GateStateChange>()
+            .add_event::<DoorStateChange>()
+            .add_event::<TileDamageEvent>()
+            // Rapier registers this too; without physics it simply never fires
+            .add_event::<CollisionEvent>()
+
+            .add_systems(Startup, (
+                urban_security::setup_urban_security_system,
+                police::setup_police_system,
+                traffic::setup_traffic_system,
+                hacking_financial::setup_banking_network,
+                pathfinding::setup_pathfinding_grid,
+                enhanced_pathfinding::setup_enhanced_pathfinding_grid,
+            ))
+
+            .add_systems(FixedUpdate, (
+                movement::system.pipe(profile_system("movement")),
+            ).run_if(in_state(GameState::Mission)))
+
+            // Core AI Systems
+            .add_systems(FixedUpdate, (
+                goap_config::apply_goap_profiles_system,
+                squads::squad_assignment_system,
+                squads::squad_blackboard_system,
+                perception::attach_perception_memory,
+                perception::corpse_perception_system,
+                perception::perception_decay_system,
+                goap::goap_ai_system.pipe(profile_system("goap_ai")),
+
+                ai::goap_sound_detection_system,
+                ai::alert_system,
+                ai::legacy_enemy_ai_system.pipe(profile_system("legacy_ai")),
+                ai::sound_detection_system,
+
+                morale::morale_system,
+                morale::civilian_morale_system,
+                morale::flee_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // Combat and interaction systems
+            .add_systems(FixedUpdate, (
+                weapon_swap::weapon_drop_system,
+                weapon_swap::weapon_pickup_system,
+                weapon_swap::weapon_behavior_system,
+
+                interaction::system,
+                movement::collision_feedback_system,
+
+                combat::process_attack_events,
+                combat::enemy_combat_system,
+
+                death::death_system,
+                death::explodable_death_system,
+                combat::auto_reload_system,
+                combat::cleanup_miss_targets,
+            ).run_if(in_state(GameState::Mission)))
+
+            .add_systems(FixedUpdate, (
+                enhanced_pathfinding::add_enhanced_pathfinding_to_agents,
+
+                // === CORE TILE SYSTEMS ===
+                tile_properties::assign_tile_properties_system,
+                tile_properties::tile_destruction_system,
+                // tile_interaction_system, // DAMN THING CRASHES
+                tile_properties::tile_cover_system,
+
+                // === ENHANCED PATHFINDING ===
+                enhanced_pathfinding::update_enhanced_pathfinding_system,
+
+                enhanced_pathfinding::enhanced_movement_system.pipe(profile_system("pathfinding")),
+
+                // === VISION ===
+                enhanced_pathfinding::enhanced_vision_system.pipe(profile_system("vision")),
+            ).run_if(in_state(GameState::Mission)))
+
+            .add_systems(FixedUpdate, (
+                projectiles::unified_projectile_system,
+
+                // Add decals for projectile impacts
+                // projectile_impact_decals,
+                explosion_decal_integration::enhanced_projectile_impact_decals,
+            ).chain().run_if(in_state(GameState::Mission)))
+
+            // Mission management systems
+            .add_systems(FixedUpdate, (
+                cover::cover_management_system,
+                cover::cover_exit_system,
+
+                reload::reload_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // Area control and formations
+            .add_systems(FixedUpdate, (
+                // CRASH RISK
+                area_control::weapon_area_control_system,
+
+                area_control::area_effect_system,
+                area_control::suppression_movement_system,
+
+                formations::formation_movement_system,
+                enhanced_neurovector::neurovector_control_system,
+                enhanced_neurovector::controlled_civilian_behavior_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // 0.2.9
+            .add_systems(FixedUpdate, (
+                // Traffic core systems
+                traffic::traffic_spawn_system,
+                traffic::traffic_movement_system,
+                traffic::traffic_collision_system,
+                traffic::traffic_cleanup_system,
+
+                // Civilian Handling
+                traffic::civilian_traffic_interaction_system,
+                traffic::traffic_light_vehicle_system,
+
+                // Emergency and military systems
+                traffic::emergency_response_system,
+                traffic::military_convoy_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // 0.2.10
+            .add_systems(FixedUpdate, (
+                // Access control systems
+                access_control::motion_sensor_system,
+                access_control::access_control_system,
+                access_control::access_point_power_system,
+
+                hacking_financial::atm_hacking_system,
+                hacking_financial::billboard_influence_system,
+                hacking_financial::terminal_account_data_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // Environmental systems
+            .add_systems(FixedUpdate, (
+                vehicles::vehicle_explosion_system,
+                //vehicles::explosion_damage_system,
+                vehicles::vehicle_cover_system,
+                vehicles::vehicle_spawn_system,
+
+                day_night::day_night_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // Police escalation
+            .add_systems(FixedUpdate, (
+                // CORE
+                urban_security::unified_urban_security_system,
+                police::police_incident_system,
+                police::police_escalation_system,
+                police::police_dispatch_system,
+                police::police_cruiser_system,
+                police::police_cleanup_system,
+                explosions::explosion_road_blocking_system,
+
+                weapons::enemy_weapon_update_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            .add_systems(FixedUpdate, (
+                // explosions::explosion_damage_system,
+                explosion_decal_integration::enhanced_explosion_damage_system,
+                explosions::time_bomb_system,
+                explosions::pending_explosion_system,
+                explosions::status_effect_system,
+                // explosions::handle_grenade_events,
+                explosion_decal_integration::enhanced_handle_grenade_events,
+                // explosions::handle_vehicle_explosions,
+                explosion_decal_integration::enhanced_handle_vehicle_explosions,
+            ).run_if(in_state(GameState::Mission)))
+
+            .add_systems(FixedUpdate, (
+                // Movement and interaction systems
+                interactive_decals::interactive_decal_movement_system,
+                interactive_decals::electrical_hazard_system,
+                interactive_decals::stuck_entities_system,
+
+                // Fire systems
+                interactive_decals::fire_ignition_system,
+                interactive_decals::fire_burn_system,
+
+                // 0.2.12
+                spawners::spawn_scientists_in_mission,
+            ).run_if(in_state(GameState::Mission)))
+
+            .add_systems(FixedUpdate, (
+                // Power grid updates
+                power_grid::power_flow_system,
+                power_grid_system,
+            ).chain().run_if(in_state(GameState::Mission)))
+
+            // Hacking and infrastructure
+            .add_systems(FixedUpdate, (
+                (remote_hacking::remote_access_system, remote_hacking::remote_hack_system).chain()
+                    .before(hacking_feedback::enhanced_hacking_system),
+                hacking_feedback::enhanced_hacking_system,
+                hacking_feedback::hack_interruption_system,
+                hack_trace::hack_trace_system.after(hacking_feedback::enhanced_hacking_system),
+                hack_recovery_system,
+
+                power_grid::street_light_system,
+                power_grid::traffic_light_system,
+                power_grid::security_camera_system,
+                power_grid::automated_turret_system,
+                power_grid::drone_system,
+                power_grid::security_door_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // What hacks do beyond switching devices off
+            .add_systems(FixedUpdate, (
+                device_takeover::hack_takeover_system,
+                device_takeover::device_command_system,
+                device_takeover::release_hijacked_devices,
+                device_takeover::network_virus_system,
+                device_takeover::power_surge_system,
+                device_takeover::spotted_decay_system,
+            ).run_if(in_state(GameState::Mission)))
+
+            // Mission flow
+            .add_systems(FixedUpdate, (
+                mission::timer_system,
+                (
+                    objectives::bind_mission_objectives,
+                    objectives::update_mission_objectives,
+                    mission::check_completion,
+                    extraction::agent_extraction_system,
+                ).chain(),
+                extraction::extraction_pressure_system,
+                (
+                    scripting::bind_scene_triggers,
+                    scripting::scene_trigger_system,
+                    scripting::run_trigger_actions,
+                ).chain(),
+                (
+                    grading::track_combat_performance,
+                    grading::track_casualties,
+                    grading::track_neurovector_usage,
+                    grading::track_hacks,
+                    grading::track_detection,
+                ).chain(),
+                mapgen::apply_generated_layout,
+            ).run_if(in_state(GameState::Mission)))
+
+            // Entities marked during a step are gone before the next one, however many steps a frame runs
+            .add_systems(FixedLast, despawn::despawn_marked_entities.run_if(in_state(GameState::Mission)));
+    }
+}
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use bevy_light_2d::prelude::*;
 use bevy::color::palettes::css::{YELLOW, GREEN};
+use rand::Rng;
 
 use crate::core::*;
 use crate::core::factions::Faction;
@@ -11,6 +12,7 @@ use crate::systems::traffic::*;
 use crate::systems::scanner::Scannable;
 use crate::systems::ai::*;
 use crate::systems::urban_security::*;
+use crate::systems::police::*;
 use crate::systems::selection::*;
 use crate::systems::power_grid::*;
 use crate::systems::hacking_financial::*;
@@ -190,13 +192,21 @@ pub fn spawn_enemy(
     commands: &mut Commands, 
     pos: Vec2, 
     patrol: Vec<Vec2>, 
+    archetype: EnemyType,
     global_data: &GlobalData, 
-    sprites: &GameSprites
+    sprites: &GameSprites,
+    rng: &mut impl Rng,
 ) {
     let (sprite, _) = create_enemy_sprite(sprites);
     let difficulty = global_data.regions[global_data.selected_region].mission_difficulty_modifier();
-    let faction = random_enemy_faction();
-    let weapon = select_weapon_for_faction(&faction);
+    let stats = archetype.stats();
+    let faction = random_enemy_faction(rng);
+    let weapon = archetype.pick_weapon(&faction, rng);
+    let size = sprite.custom_size.unwrap_or(Vec2::splat(24.0)) * stats.scale;
+    let goap_agent = match stats.goap_profile {
+        Some(profile) => GoapAgent::default().with_profile(profile),
+        None => GoapAgent::default(),
+    };
     
     let mut inventory = Inventory::default();
     inventory.equipped_weapon = Some(WeaponConfig::new(weapon.clone()));
@@ -205,18 +215,19 @@ pub fn spawn_enemy(
     weapon_state.complete_reload();
     
     commands.spawn((
-        sprite_bundle(sprite.color, sprite.custom_size.unwrap_or(Vec2::splat(24.0)), pos, 1.0),
+        sprite_bundle(tint_color(sprite.color, stats.tint), size, pos, 1.0),
         Enemy,
         faction,
-        base_unit_components(100.0 * difficulty, 100.0),
-        Morale::new(100.0 * difficulty, 25.0),
-        Vision::new(120.0 * difficulty, DEFAULT_VISION_FOV),
+        (EnemyArchetype(archetype), Armor(stats.armor)),
+        base_unit_components(stats.health * difficulty, stats.speed),
+        Morale::new(stats.morale * difficulty, stats.panic_threshold),
+        Vision::new(stats.vision_range * difficulty, stats.vision_fov),
         Patrol::new(patrol),
         AIState::default(),
-        GoapAgent::default(),
+        goap_agent,
         weapon_state,
         inventory,
-        unit_physics(ENEMY_RADIUS, ENEMY_GROUP),
+        unit_physics(ENEMY_RADIUS * stats.scale, ENEMY_GROUP),
         Scannable,
     ));
 }
@@ -229,7 +240,7 @@ pub fn spawn_police_unit_simple(
     unit_type: EscalationLevel,
     sprites: &GameSprites,
 ) -> Entity {
-    let config = load_urban_config();
+    let config = load_police_config();
     spawn_police_unit(commands, position, unit_type, sprites, &config)
 }
 
@@ -238,13 +249,13 @@ pub fn spawn_police_unit(
     position: Vec2,
     unit_type: EscalationLevel,
     sprites: &GameSprites,
-    config: &UrbanConfig,
+    config: &PoliceConfig,
 ) -> Entity {
     let level_config = unit_type.get_config(config);
     let (mut sprite, _) = create_police_sprite(sprites);
     sprite.color = Color::srgba(level_config.color.0, level_config.color.1, level_config.color.2, level_config.color.3);
     
-    let patrol = generate_patrol_pattern(position, unit_type, config);
+    let patrol = config.patrol_for(unit_type, position);
     let weapon_type = parse_weapon_type(&level_config.weapon);
     
     let mut inventory = Inventory::default();
@@ -272,7 +283,7 @@ pub fn spawn_police_unit(
 }
 
 fn parse_weapon_type(weapon: &str) -> WeaponType {
-    match weapon {
+    match weapon.to_lowercase().as_str() {
         "pistol" => WeaponType::Pistol,
         "rifle" => WeaponType::Rifle,
         "minigun" => WeaponType::Minigun,
@@ -307,15 +318,15 @@ fn spawn_civilian_base<'a>(
     ))
 }
 
-pub fn spawn_civilian(commands: &mut Commands, position: Vec2, sprites: &GameSprites) {
-    let crowd_influence = 0.3 + rand::random::<f32>() * 0.4;
-    let panic_threshold = 20.0 + rand::random::<f32>() * 40.0;
-    let daily_state = random_daily_state();
+pub fn spawn_civilian(commands: &mut Commands, position: Vec2, sprites: &GameSprites, rng: &mut impl Rng) {
+    let crowd_influence = 0.3 + rng.gen::<f32>() * 0.4;
+    let panic_threshold = 20.0 + rng.gen::<f32>() * 40.0;
+    let daily_state = random_daily_state(rng);
     
     spawn_civilian_base(commands, position, sprites, 50.0, 80.0, 40.0, 140.0)
         .insert(UrbanCivilian {
             daily_state,
-            state_timer: rand::random::<f32>() * 10.0,
+            state_timer: rng.gen::<f32>() * 10.0,
             next_destination: None,
             crowd_influence,
             panic_threshold,
@@ -344,16 +355,19 @@ pub fn spawn_urban_civilian(
     position: Vec2,
     sprites: &GameSprites,
     urban_areas: &UrbanSecurity,
+    rng: &mut impl Rng,
 ) {
-    let crowd_influence = 0.3 + rand::random::<f32>() * 0.4;
-    let panic_threshold = 20.0 + rand::random::<f32>() * 40.0;
-    let daily_state = random_daily_state();
+    let crowd_influence = 0.3 + rng.gen::<f32>() * 0.4;
+    let panic_threshold = 20.0 + rng.gen::<f32>() * 40.0;
+    let daily_state = random_daily_state(rng);
+    let state_timer = rng.gen::<f32>() * 10.0;
+    let next_destination = pick_destination_for_state(daily_state, urban_areas, rng);
     
     spawn_civilian_base(commands, position, sprites, 50.0, 80.0, panic_threshold, 80.0)
         .insert(UrbanCivilian {
             daily_state,
-            state_timer: rand::random::<f32>() * 10.0,
-            next_destination: pick_destination_for_state(daily_state, urban_areas),
+            state_timer,
+            next_destination,
             crowd_influence,
             panic_threshold,
             movement_urgency: 0.0,
@@ -362,15 +376,16 @@ pub fn spawn_urban_civilian(
 }
 
 
-pub fn spawn_original_urban_civilian(commands: &mut Commands, pos: Vec2, sprites: &GameSprites) {
+pub fn spawn_original_urban_civilian(commands: &mut Commands, pos: Vec2, sprites: &GameSprites, rng: &mut impl Rng) {
     info!("spawn_urban_civilian");
-    let crowd_influence = 0.2 + rand::random::<f32>() * 0.6;
-    let panic_threshold = 15.0 + rand::random::<f32>() * 50.0;
+    let crowd_influence = 0.2 + rng.gen::<f32>() * 0.6;
+    let panic_threshold = 15.0 + rng.gen::<f32>() * 50.0;
+    let speed = 80.0 + rng.gen::<f32>() * 40.0;
 
-    spawn_civilian_base(commands, pos, sprites, 50.0, 80.0, panic_threshold, 80.0 + rand::random::<f32>() * 40.0)
+    spawn_civilian_base(commands, pos, sprites, 50.0, 80.0, panic_threshold, speed)
         .insert(UrbanCivilian {
-            daily_state: random_daily_state(),
-            state_timer: rand::random::<f32>() * 15.0,
+            daily_state: random_daily_state(rng),
+            state_timer: rng.gen::<f32>() * 15.0,
             next_destination: None,
             crowd_influence,
             panic_threshold,
@@ -488,7 +503,7 @@ pub fn spawn_traffic_vehicle(
     position: Vec2,
     vehicle_type: TrafficVehicleType,
     sprites: &GameSprites,
-) {
+) -> Entity {
     let spec = VehicleSpec::for_traffic(&vehicle_type);
     
     let base_vehicle = match vehicle_type {
@@ -545,6 +560,8 @@ pub fn spawn_traffic_vehicle(
         }
         _ => {}
     }
+
+    entity_cmd.id()
 }
 
 // === TERMINAL & DEVICE SPAWNERS ===
@@ -653,11 +670,8 @@ fn spawn_power_device<T: Component>(
     let entity = entity_cmd.id();
     make_hackable_networked(commands, entity, device_type, network_id.clone(), power_grid);
 
-    let cloned_network_id = network_id.clone();
     if device_type == DeviceType::PowerStation {
-        let network = power_grid.networks.entry(network_id)
-            .or_insert_with(|| PowerNetwork::new(cloned_network_id));
-        network.power_sources.insert(entity);
+        power_grid.network_mut(&network_id).power_sources.insert(entity);
     }
     
     entity
@@ -780,6 +794,42 @@ pub fn spawn_security_door(
     entity
 }
 
+pub fn spawn_security_drone(
+    commands: &mut Commands,
+    position: Vec2,
+    patrol_route: Vec<Vec2>,
+    network_id: Option<String>,
+    power_grid: &mut Option<ResMut<PowerGrid>>,
+) -> Entity {
+    let entity = commands.spawn((
+        sprite_bundle(Color::srgb(0.5, 0.5, 0.6), Vec2::new(14.0, 14.0), position, 2.0),
+        Drone {
+            patrol_route,
+            current_waypoint: 0,
+            speed: 60.0,
+            detection_range: 140.0,
+        },
+        Vision::new(140.0, 360.0),
+    )).id();
+
+    hackable_device(commands, entity, DeviceType::Drone, network_id, power_grid, 3, 5.0, Some(HackTool::AdvancedHacker));
+    entity
+}
+
+pub fn spawn_security_system(
+    commands: &mut Commands,
+    position: Vec2,
+    network_id: String,
+    power_grid: &mut Option<ResMut<PowerGrid>>,
+) -> Entity {
+    let entity = commands.spawn(
+        sprite_bundle(Color::srgb(0.3, 0.4, 0.7), Vec2::new(24.0, 18.0), position, 1.0),
+    ).id();
+
+    hackable_device(commands, entity, DeviceType::SecuritySystem, Some(network_id), power_grid, 4, 10.0, Some(HackTool::AdvancedHacker));
+    entity
+}
+
 // === EXPLOSIVE SPAWNERS ===
 
 pub fn spawn_time_bomb(
@@ -802,20 +852,22 @@ pub fn spawn_scientists_in_mission(
     sprites: Res<GameSprites>,
     global_data: Res<GlobalData>,
     scientist_query: Query<Entity, With<Scientist>>,
+    mut rng: ResMut<GameRng>,
 ) {
     if scientist_query.iter().count() >= 3 {
         return;
     }
     
-    let spawn_count = if rand::random::<f32>() < 0.6 { 1 } else { 2 };
+    let rng = rng.stream(RngStream::Spawning);
+    let spawn_count = if rng.gen::<f32>() < 0.6 { 1 } else { 2 };
     
     for _ in 0..spawn_count {
         let position = Vec2::new(
-            (rand::random::<f32>() - 0.5) * 400.0,
-            (rand::random::<f32>() - 0.5) * 400.0,
+            (rng.gen::<f32>() - 0.5) * 400.0,
+            (rng.gen::<f32>() - 0.5) * 400.0,
         );
         
-        let specialization = match rand::random::<f32>() {
+        let specialization = match rng.gen::<f32>() {
             x if x < 0.25 => ResearchCategory::Weapons,
             x if x < 0.5 => ResearchCategory::Equipment,
             x if x < 0.75 => ResearchCategory::Cybernetics,
@@ -933,8 +985,8 @@ pub fn spawn_research_content_in_scene(
 
 // === HELPER FUNCTIONS ===
 
-fn random_daily_state() -> DailyState {
-    match rand::random::<f32>() {
+fn random_daily_state(rng: &mut impl Rng) -> DailyState {
+    match rng.gen::<f32>() {
         x if x < 0.4 => DailyState::GoingToWork,
         x if x < 0.6 => DailyState::Shopping,
         x if x < 0.8 => DailyState::GoingHome,
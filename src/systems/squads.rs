@@ -0,0 +1,270 @@
+// src/systems/squads.rs - Squad-level tactical blackboard shared between GOAP agents
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use crate::core::*;
+use crate::core::factions::Faction;
+
+const SQUAD_JOIN_RADIUS: f32 = 250.0;
+const MAX_SQUAD_SIZE: usize = 4;
+const SIGHTING_MEMORY: f32 = 12.0;
+const SIGHTING_FRESH: f32 = 3.0;
+const ENGAGEMENT_WINDOW: f32 = 0.5;
+
+// === COMPONENTS ===
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SquadRole {
+    Rifleman,
+    Suppressor,
+    Flanker,
+    Medic,
+}
+
+impl SquadRole {
+    /// Role roster in fill order; the fourth member onward are riflemen
+    fn for_slot(slot: usize) -> Self {
+        match slot {
+            0 => SquadRole::Suppressor,
+            1 => SquadRole::Flanker,
+            2 => SquadRole::Medic,
+            _ => SquadRole::Rifleman,
+        }
+    }
+
+    pub fn cost_multiplier(&self, action_name: &str) -> f32 {
+        match (self, action_name) {
+            (SquadRole::Suppressor, "suppressing_fire" | "minigun_suppression") => 0.5,
+            (SquadRole::Suppressor, "flank_target") => 3.0,
+            (SquadRole::Flanker, "flank_target") => 0.5,
+            (SquadRole::Flanker, "suppressing_fire") => 2.0,
+            (SquadRole::Medic, "use_medkit" | "take_cover") => 0.5,
+            (SquadRole::Medic, "flank_target" | "move_to_target") => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SquadMember {
+    pub squad_id: u32,
+    pub role: SquadRole,
+}
+
+// === BLACKBOARD ===
+#[derive(Debug, Clone)]
+pub struct KnownTarget {
+    pub position: Vec2,
+    pub last_seen: f32,
+    pub reported_by: Entity,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SquadGoal {
+    Patrol,
+    Investigate { location: Vec2 },
+    Assault { target_pos: Vec2 },
+    Withdraw,
+}
+
+/// Ordered maps throughout: ties between equally fresh sightings or equally near squads must
+/// resolve the same way on every run, or replays drift
+#[derive(Debug, Clone)]
+pub struct SquadBlackboard {
+    pub faction: Faction,
+    pub members: BTreeMap<Entity, SquadRole>,
+    pub known_targets: BTreeMap<Entity, KnownTarget>,
+    /// Cover point -> member holding it
+    pub cover_reservations: BTreeMap<Entity, Entity>,
+    /// Member -> last time it had eyes on a target
+    pub engaged: BTreeMap<Entity, f32>,
+    pub goal: SquadGoal,
+    pub initial_size: usize,
+    /// Centroid of living members, used when new enemies look for a squad to join
+    pub anchor: Vec2,
+}
+
+impl SquadBlackboard {
+    fn new(faction: Faction, anchor: Vec2) -> Self {
+        Self {
+            faction,
+            members: BTreeMap::new(),
+            known_targets: BTreeMap::new(),
+            cover_reservations: BTreeMap::new(),
+            engaged: BTreeMap::new(),
+            goal: SquadGoal::Patrol,
+            initial_size: 0,
+            anchor,
+        }
+    }
+
+    fn next_role(&self) -> SquadRole {
+        // Fill the first missing specialist slot before adding riflemen
+        (0..MAX_SQUAD_SIZE).map(SquadRole::for_slot)
+            .find(|role| *role != SquadRole::Rifleman && !self.members.values().any(|r| r == role))
+            .unwrap_or(SquadRole::Rifleman)
+    }
+
+    pub fn report_sighting(&mut self, target: Entity, position: Vec2, reporter: Entity, now: f32) {
+        self.known_targets.insert(target, KnownTarget { position, last_seen: now, reported_by: reporter });
+        self.engaged.insert(reporter, now);
+    }
+
+    pub fn freshest_target(&self, now: f32) -> Option<&KnownTarget> {
+        self.known_targets.values()
+            .filter(|t| now - t.last_seen <= SIGHTING_MEMORY)
+            .max_by(|a, b| a.last_seen.partial_cmp(&b.last_seen).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    pub fn fresh_target_count(&self, now: f32) -> usize {
+        self.known_targets.values().filter(|t| now - t.last_seen <= SIGHTING_FRESH).count()
+    }
+
+    pub fn role_engaged(&self, role: SquadRole, now: f32) -> bool {
+        self.engaged.iter()
+            .any(|(e, seen)| now - seen <= ENGAGEMENT_WINDOW && self.members.get(e) == Some(&role))
+    }
+
+    pub fn is_cover_reserved_by_other(&self, cover: Entity, member: Entity) -> bool {
+        self.cover_reservations.get(&cover).map_or(false, |holder| *holder != member)
+    }
+
+    pub fn reserve_cover(&mut self, cover: Entity, member: Entity) {
+        self.cover_reservations.retain(|_, holder| *holder != member);
+        self.cover_reservations.insert(cover, member);
+    }
+
+    fn remove_member(&mut self, member: Entity) {
+        self.members.remove(&member);
+        self.engaged.remove(&member);
+        self.cover_reservations.retain(|_, holder| *holder != member);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SquadBlackboards {
+    pub squads: BTreeMap<u32, SquadBlackboard>,
+    next_id: u32,
+}
+
+impl SquadBlackboards {
+    pub fn get(&self, squad_id: u32) -> Option<&SquadBlackboard> {
+        self.squads.get(&squad_id)
+    }
+
+    pub fn get_mut(&mut self, squad_id: u32) -> Option<&mut SquadBlackboard> {
+        self.squads.get_mut(&squad_id)
+    }
+
+    pub fn clear(&mut self) {
+        self.squads.clear();
+        self.next_id = 0;
+    }
+}
+
+/// Complementary goal for a member given the squad's current intent
+pub fn squad_goal_for(role: SquadRole, squad_goal: &SquadGoal) -> Option<Goal> {
+    let goal = |name: &str, priority: f32, desired_state: WorldState| Some(Goal { name: name.to_string(), priority, desired_state });
+
+    match (squad_goal, role) {
+        (SquadGoal::Assault { .. }, SquadRole::Flanker) =>
+            goal("squad_flank", 10.5, WorldState::from([(WorldKey::FlankingPosition, true)])),
+        (SquadGoal::Assault { .. }, SquadRole::Suppressor) =>
+            goal("squad_suppress", 10.5, WorldState::from([(WorldKey::EnemySuppressed, true)])),
+        (SquadGoal::Assault { .. }, SquadRole::Medic) =>
+            goal("squad_hold_back", 9.5, WorldState::from([(WorldKey::InCover, true)])),
+        (SquadGoal::Withdraw, _) =>
+            goal("squad_withdraw", 11.5, WorldState::from([(WorldKey::AtSafeDistance, true)])),
+        _ => None,
+    }
+}
+
+// === SYSTEMS ===
+/// Groups newly spawned enemies into nearby same-faction squads and hands out roles.
+/// Medics are issued the medkit their role plans around.
+pub fn squad_assignment_system(
+    mut commands: Commands,
+    mut squads: ResMut<SquadBlackboards>,
+    mut unassigned: Query<(Entity, &Transform, &Faction, Option<&mut Inventory>), (With<Enemy>, With<GoapAgent>, Without<SquadMember>, Without<Dead>)>,
+) {
+    for (entity, transform, faction, inventory) in unassigned.iter_mut() {
+        let pos = transform.translation.truncate();
+
+        let existing = squads.squads.iter()
+            .filter(|(_, board)| board.faction == *faction && board.members.len() < MAX_SQUAD_SIZE)
+            .filter(|(_, board)| board.anchor.distance(pos) <= SQUAD_JOIN_RADIUS)
+            .min_by(|(_, a), (_, b)| a.anchor.distance(pos).partial_cmp(&b.anchor.distance(pos)).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(id, _)| *id);
+
+        let squad_id = existing.unwrap_or_else(|| {
+            let id = squads.next_id;
+            squads.next_id += 1;
+            squads.squads.insert(id, SquadBlackboard::new(*faction, pos));
+            id
+        });
+
+        let board = squads.squads.get_mut(&squad_id).unwrap();
+        let role = board.next_role();
+        board.members.insert(entity, role);
+        board.initial_size = board.initial_size.max(board.members.len());
+
+        if role == SquadRole::Medic {
+            if let Some(mut inventory) = inventory {
+                inventory.add_tool(ToolType::MedKit);
+            }
+        }
+
+        commands.entity(entity).insert(SquadMember { squad_id, role });
+    }
+}
+
+/// Prunes dead members, decays shared knowledge, picks the squad goal and pushes it to members
+pub fn squad_blackboard_system(
+    mut squads: ResMut<SquadBlackboards>,
+    mut living: Query<(Entity, &Transform, &SquadMember, &Health, &mut GoapAgent), (With<Enemy>, Without<Dead>)>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+
+    for board in squads.squads.values_mut() {
+        let dead: Vec<Entity> = board.members.keys().copied().filter(|e| !living.contains(*e)).collect();
+        for member in dead {
+            board.remove_member(member);
+        }
+
+        board.known_targets.retain(|_, t| now - t.last_seen <= SIGHTING_MEMORY);
+        board.engaged.retain(|_, seen| now - *seen <= ENGAGEMENT_WINDOW);
+
+        let (positions, healths): (Vec<Vec2>, Vec<f32>) = board.members.keys()
+            .filter_map(|e| living.get(*e).ok().map(|(_, t, _, h, _)| (t.translation.truncate(), h.0)))
+            .unzip();
+        if !positions.is_empty() {
+            board.anchor = positions.iter().sum::<Vec2>() / positions.len() as f32;
+        }
+        let average_health = healths.iter().sum::<f32>() / healths.len().max(1) as f32;
+        let losses_heavy = board.members.len() * 2 < board.initial_size;
+
+        board.goal = if !board.members.is_empty() && (losses_heavy || average_health < 30.0) && board.fresh_target_count(now) > 0 {
+            SquadGoal::Withdraw
+        } else if let Some(target) = board.known_targets.values().find(|t| now - t.last_seen <= SIGHTING_FRESH) {
+            SquadGoal::Assault { target_pos: target.position }
+        } else if let Some(target) = board.freshest_target(now) {
+            SquadGoal::Investigate { location: target.position }
+        } else {
+            SquadGoal::Patrol
+        };
+    }
+
+    squads.squads.retain(|_, board| !board.members.is_empty());
+
+    for (_, _, member, _, mut goap_agent) in living.iter_mut() {
+        let new_goal = squads.get(member.squad_id).and_then(|board| squad_goal_for(member.role, &board.goal));
+        let changed = goap_agent.squad_goal.as_ref().map(|g| &g.name) != new_goal.as_ref().map(|g| &g.name);
+        if changed {
+            goap_agent.squad_goal = new_goal;
+            goap_agent.abort_plan();
+        }
+    }
+}
+
+pub fn cleanup_squads(mut squads: ResMut<SquadBlackboards>) {
+    squads.clear();
+}
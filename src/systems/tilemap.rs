@@ -52,6 +52,14 @@ impl IsometricSettings {
         IVec2::new(tile_x.floor() as i32, tile_y.floor() as i32)
     }
 
+    /// Tile whose center is closest to the world position; `world_to_tile` of that center gives it back
+    pub fn nearest_tile(&self, world_pos: Vec2) -> IVec2 {
+        let tile_x = ((world_pos.x / (self.tile_width * 0.5)) + (world_pos.y / (self.tile_height * 0.5))) * 0.5;
+        let tile_y = ((world_pos.y / (self.tile_height * 0.5)) - (world_pos.x / (self.tile_width * 0.5))) * 0.5;
+
+        IVec2::new(tile_x.round() as i32, tile_y.round() as i32)
+    }
+
     /// Convert tile coordinates to world coordinates (center of tile)
     pub fn tile_to_world(&self, tile_pos: IVec2) -> Vec2 {
         let x = (tile_pos.x - tile_pos.y) as f32 * (self.tile_width * 0.5);
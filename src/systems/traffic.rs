@@ -1,6 +1,7 @@
 // src/systems/traffic.rs - Efficient traffic simulation for cyberpunk urban environment
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::*;
 use crate::systems::spawners::*;
@@ -273,6 +274,7 @@ pub fn traffic_spawn_system(
     time: Res<Time>,
     game_mode: Res<GameMode>,
     sprites: Res<GameSprites>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
@@ -280,16 +282,17 @@ pub fn traffic_spawn_system(
     let current_count = vehicle_query.iter().count();
 
     if traffic_system.spawn_timer <= 0.0 && current_count < traffic_system.max_vehicles {
-        if let Some(&spawn_pos) = traffic_system.road_network.spawn_points.get(rand::random::<usize>() % traffic_system.road_network.spawn_points.len()) {
-            let vehicle_type = choose_vehicle_type();
+        let rng = rng.stream(RngStream::Traffic);
+        if let Some(&spawn_pos) = traffic_system.road_network.spawn_points.get(rng.gen_range(0..traffic_system.road_network.spawn_points.len().max(1))) {
+            let vehicle_type = choose_vehicle_type(rng);
             spawn_traffic_vehicle(&mut commands, spawn_pos, vehicle_type, &sprites);
-            traffic_system.spawn_timer = 3.0 + rand::random::<f32>() * 4.0;
+            traffic_system.spawn_timer = 3.0 + rng.gen::<f32>() * 4.0;
         }
     }
 }
 
-fn choose_vehicle_type() -> TrafficVehicleType {
-    match rand::random::<f32>() {
+fn choose_vehicle_type(rng: &mut impl Rng) -> TrafficVehicleType {
+    match rng.gen::<f32>() {
         x if x < 0.7 => TrafficVehicleType::CivilianCar,
         x if x < 0.8 => TrafficVehicleType::Bus,
         x if x < 0.9 => TrafficVehicleType::Truck,
@@ -430,14 +433,16 @@ pub fn emergency_response_system(
     mut traffic_system: ResMut<TrafficSystem>,
     mut alert_events: EventReader<AlertEvent>,
     sprites: Res<GameSprites>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream(RngStream::Traffic);
     for alert in alert_events.read() {
         if alert.alert_level >= 3 { // High alert
             traffic_system.emergency_response_timer = 5.0; // Delay before response
 
             // Spawn emergency vehicles
-            if let Some(&spawn_pos) = traffic_system.road_network.spawn_points.get(rand::random::<usize>() % traffic_system.road_network.spawn_points.len()) {
-                match rand::random::<f32>() {
+            if let Some(&spawn_pos) = traffic_system.road_network.spawn_points.get(rng.gen_range(0..traffic_system.road_network.spawn_points.len().max(1))) {
+                match rng.gen::<f32>() {
                     x if x < 0.6 => {
                         spawn_emergency_vehicle(&mut commands, spawn_pos, TrafficVehicleType::PoliceCar, alert.position, &sprites);
                     },
@@ -687,6 +692,7 @@ pub fn civilian_traffic_interaction_system(
     traffic_query: Query<(&Transform, &TrafficVehicle, &Velocity), Without<Civilian>>,
     mut alert_events: EventWriter<AlertEvent>,
     mut action_events: EventWriter<ActionEvent>,
+    mut rng: ResMut<GameRng>,
 ) {
     for (civilian_entity, civilian_transform, mut morale) in civilian_query.iter_mut() {
         let civilian_pos = civilian_transform.translation.truncate();
@@ -709,7 +715,7 @@ pub fn civilian_traffic_interaction_system(
                     action: Action::MoveTo(flee_target),
                 });
                 
-            if rand::random::<f32>() < 0.3 {
+            if rng.stream(RngStream::Traffic).gen::<f32>() < 0.3 {
                 alert_events.write(AlertEvent {
                     alerter: civilian_entity,
                     position: civilian_pos,
@@ -773,7 +779,7 @@ pub fn setup_hackable_traffic_lights(
                 hack_time: 3.0,
                 network_id: Some("traffic_network".to_string()),
                 device_type: DeviceType::TrafficLight,
-                disabled_duration: 60.0,
+                disabled_duration: Some(60.0),
                 hack_effects: Vec::new(),
                 is_hacked: true,
                 requires_tool: Some(HackTool::BasicHacker),
@@ -792,15 +798,17 @@ pub fn setup_hackable_traffic_lights(
 // Handle hacked traffic lights:
 pub fn hacked_traffic_light_system(
     mut traffic_lights: Query<(&mut TrafficLight, &DeviceState)>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream(RngStream::Traffic);
     for (mut light, device_state) in traffic_lights.iter_mut() {
         if !device_state.operational {
             // Hacked lights cause chaos - rapid state changes
             light.timer = 0.5; // Very fast cycling
             
             // Random state when hacked
-            if rand::random::<f32>() < 0.1 {
-                light.state = match rand::random::<u8>() % 3 {
+            if rng.gen::<f32>() < 0.1 {
+                light.state = match rng.gen_range(0..3) {
                     0 => TrafficState::Red,
                     1 => TrafficState::Yellow,
                     _ => TrafficState::Green,
@@ -99,6 +99,19 @@ fn show_agent_overview(ui: &mut egui::Ui, global_data: &GlobalData, agent_idx: u
         };
         
         ui.colored_label(recovery_status.1, recovery_status.0);
+
+        let traits = &global_data.agent_traits[agent_idx];
+        if !traits.is_empty() {
+            ui.separator();
+            ui.label("TRAITS:");
+            for agent_trait in traits {
+                let color = agent_trait.rarity.color().to_srgba();
+                ui.colored_label(
+                    egui::Color32::from_rgb((color.red * 255.0) as u8, (color.green * 255.0) as u8, (color.blue * 255.0) as u8),
+                    format!("• {} - {}", agent_trait.name, agent_trait.description),
+                );
+            }
+        }
         
         ui.separator();
         
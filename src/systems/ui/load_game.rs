@@ -0,0 +1,153 @@
+// src/systems/ui/load_game.rs - Save slot browser reached from the main menu
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::core::*;
+use crate::systems::input::MenuInput;
+use crate::systems::save::{self, SaveSession, SaveSlotCache, SaveSlotInfo};
+
+#[derive(Resource, Default)]
+pub struct LoadGameState {
+    pub selected_index: usize,
+    pub confirm_delete: bool,
+}
+
+pub fn setup_load_game_screen(mut state: ResMut<LoadGameState>, slot_cache: Res<SaveSlotCache>) {
+    state.selected_index = slot_cache.slots.iter().position(|info| info.metadata.is_some()).unwrap_or(0);
+    state.confirm_delete = false;
+}
+
+pub fn load_game_system_egui(
+    mut contexts: EguiContexts,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut state: ResMut<LoadGameState>,
+    mut slot_cache: ResMut<SaveSlotCache>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut global_data: ResMut<GlobalData>,
+    mut research_progress: ResMut<ResearchProgress>,
+    mut territory_manager: ResMut<TerritoryManager>,
+    mut progression_tracker: ResMut<CampaignProgressionTracker>,
+    mut session: ResMut<SaveSession>,
+) {
+    let slot_count = slot_cache.slots.len().max(1);
+    let mut load_requested = false;
+    let mut delete_requested = false;
+
+    let input = MenuInput::new(&keyboard, &gamepads);
+    if input.up {
+        state.selected_index = state.selected_index.checked_sub(1).unwrap_or(slot_count - 1);
+        state.confirm_delete = false;
+    } else if input.down {
+        state.selected_index = (state.selected_index + 1) % slot_count;
+        state.confirm_delete = false;
+    } else if input.back {
+        next_state.set(GameState::MainMenu);
+    } else if input.select {
+        load_requested = true;
+    } else if input.option {
+        delete_requested = true;
+    }
+
+    if let Ok(ctx) = contexts.ctx_mut() {
+        egui::CentralPanel::default()
+            .frame(egui::Frame::new().fill(egui::Color32::from_rgb(26, 26, 51)))
+            .show(ctx, |ui| {
+                ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+                    ui.add_space(40.0);
+                    ui.label(egui::RichText::new("LOAD GAME").size(36.0).strong()
+                        .color(egui::Color32::from_rgb(252, 255, 82)));
+                    ui.add_space(30.0);
+
+                    let mut clicked = None;
+                    for (i, info) in slot_cache.slots.iter().enumerate() {
+                        let selected = i == state.selected_index;
+                        let color = if selected { egui::Color32::from_rgb(252, 255, 82) } else { egui::Color32::WHITE };
+
+                        let response = egui::Frame::new()
+                            .stroke(if selected { egui::Stroke::new(2.0, color) } else { egui::Stroke::new(1.0, egui::Color32::from_gray(70)) })
+                            .inner_margin(egui::Margin::same(8))
+                            .show(ui, |ui| {
+                                ui.set_width(480.0);
+                                slot_label(ui, info, color);
+                            }).response.interact(egui::Sense::click());
+
+                        if response.clicked() {
+                            clicked = Some(i);
+                        }
+                        ui.add_space(8.0);
+                    }
+                    if let Some(i) = clicked {
+                        state.selected_index = i;
+                        state.confirm_delete = false;
+                    }
+
+                    ui.add_space(20.0);
+                    ui.horizontal(|ui| {
+                        ui.add_space((ui.available_width() - 330.0).max(0.0) / 2.0);
+                        if ui.add_sized([100.0, 32.0], egui::Button::new("Load")).clicked() {
+                            load_requested = true;
+                        }
+                        let delete_text = if state.confirm_delete { "Confirm" } else { "Delete" };
+                        if ui.add_sized([100.0, 32.0], egui::Button::new(delete_text)).clicked() {
+                            delete_requested = true;
+                        }
+                        if ui.add_sized([100.0, 32.0], egui::Button::new("Back")).clicked() {
+                            next_state.set(GameState::MainMenu);
+                        }
+                    });
+
+                    ui.add_space(30.0);
+                    ui.label(egui::RichText::new("W/S/D-Pad: Navigate | Enter/A: Load | Q/X: Delete | Esc/B: Back")
+                        .size(12.0).color(egui::Color32::from_rgb(128, 128, 128)));
+                });
+            });
+    }
+
+    let Some(slot) = slot_cache.slots.get(state.selected_index).map(|info| info.slot) else { return; };
+
+    if delete_requested {
+        // Deleting takes two presses so a stray key can't wipe a campaign
+        if state.confirm_delete {
+            save::delete_save_slot(slot);
+            slot_cache.refresh();
+            state.confirm_delete = false;
+        } else if slot_cache.slots[state.selected_index].metadata.is_some() || slot_cache.slots[state.selected_index].corrupt {
+            state.confirm_delete = true;
+        }
+    } else if load_requested {
+        match save::load_save_slot(slot) {
+            Some(save_data) => {
+                save::apply_save(slot, save_data, &mut global_data, &mut research_progress,
+                    &mut territory_manager, &mut progression_tracker, &mut session);
+                next_state.set(GameState::GlobalMap);
+            }
+            None => warn!("Slot {} has no loadable save", slot + 1),
+        }
+    }
+}
+
+fn slot_label(ui: &mut egui::Ui, info: &SaveSlotInfo, color: egui::Color32) {
+    match (&info.metadata, info.corrupt) {
+        (Some(meta), _) => {
+            ui.label(egui::RichText::new(format!("{}  -  Day {}", meta.slot_name, meta.day)).size(18.0).color(color));
+            ui.label(egui::RichText::new(format!("{} credits  |  {}  |  {}",
+                meta.credits,
+                if meta.city.is_empty() { "No city" } else { &meta.city },
+                format_playtime(meta.playtime_secs))).size(13.0).color(egui::Color32::LIGHT_GRAY));
+            ui.label(egui::RichText::new(&meta.summary).size(12.0).color(egui::Color32::GRAY));
+        }
+        (None, true) => {
+            ui.label(egui::RichText::new(format!("Slot {}  -  unreadable save", info.slot + 1)).size(18.0)
+                .color(egui::Color32::from_rgb(255, 100, 100)));
+        }
+        (None, false) => {
+            ui.label(egui::RichText::new(format!("Slot {}  -  empty", info.slot + 1)).size(18.0)
+                .color(egui::Color32::from_gray(110)));
+        }
+    }
+}
+
+fn format_playtime(secs: f64) -> String {
+    let minutes = (secs / 60.0) as u64;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
@@ -3,14 +3,18 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 use crate::core::*;
-use crate::systems::save::save_game_exists;
+use crate::systems::replay::{latest_replay_path, load_replay, PendingReplay};
+use crate::systems::save::{self, save_game_exists, SaveSession, SaveSlotCache};
 
 #[derive(PartialEq, Clone, Copy)]
 pub enum MenuOptionType {
     Continue,
+    LoadGame,
     NewGame,
     Settings,
     Credits,
+    SceneEditor,
+    Replay,
     Quit,
 }
 
@@ -33,23 +37,30 @@ impl Default for MainMenuState {
 
 use crate::systems::input::{MenuInput};
 
-pub fn setup_main_menu_egui(mut menu_state: ResMut<MainMenuState>) {
-    menu_state.has_save = save_game_exists();
+pub fn setup_main_menu_egui(mut menu_state: ResMut<MainMenuState>, mut slot_cache: ResMut<SaveSlotCache>) {
+    slot_cache.refresh();
+    menu_state.has_save = save_game_exists(&slot_cache.slots);
     menu_state.selected_index = 0;
     menu_state.options.clear();
 
     if menu_state.has_save {
         menu_state.options.push((MenuOptionType::Continue, "Continue"));
+        menu_state.options.push((MenuOptionType::LoadGame, "Load Game"));
     }
     menu_state.options.extend([
         (MenuOptionType::NewGame, "New Game"),
         (MenuOptionType::Settings, "Settings"),
         (MenuOptionType::Credits, "Credits"),
-        (MenuOptionType::Quit, "Quit Game"),
+        (MenuOptionType::SceneEditor, "Scene Editor"),
     ]);
+    if latest_replay_path().is_some() {
+        menu_state.options.push((MenuOptionType::Replay, "Watch Last Replay"));
+    }
+    menu_state.options.push((MenuOptionType::Quit, "Quit Game"));
 }
 
 pub fn main_menu_system_egui(
+    mut commands: Commands,
     mut contexts: EguiContexts,
     mut next_state: ResMut<NextState<GameState>>,
     mut menu_state: ResMut<MainMenuState>,
@@ -60,6 +71,8 @@ pub fn main_menu_system_egui(
     mut research_progress: ResMut<ResearchProgress>,
     mut territory_manager: ResMut<TerritoryManager>,
     mut progression_tracker: ResMut<CampaignProgressionTracker>,
+    mut session: ResMut<SaveSession>,
+    slot_cache: Res<SaveSlotCache>,
 ) {
     let option_count = menu_state.options.len();
 
@@ -73,7 +86,7 @@ pub fn main_menu_system_egui(
         menu_state.selected_index = option_count - 1;
     } else if input.select {
         if let Some(&(option_type, _)) = menu_state.options.get(menu_state.selected_index) {
-            execute_menu_option(option_type, &mut next_state, &mut app_exit, &mut global_data, &mut research_progress, &mut territory_manager, &mut progression_tracker);
+            execute_menu_option(option_type, &mut commands, &mut next_state, &mut app_exit, &mut global_data, &mut research_progress, &mut territory_manager, &mut progression_tracker, &mut session, &slot_cache);
         }
     }
 
@@ -99,7 +112,7 @@ pub fn main_menu_system_egui(
                             .stroke(if selected { egui::Stroke::new(2.0, color) } else { egui::Stroke::NONE });
 
                         if ui.add_sized([200.0, 40.0], button).clicked() {
-                            execute_menu_option(option_type, &mut next_state, &mut app_exit, &mut global_data, &mut research_progress, &mut territory_manager, &mut progression_tracker);
+                            execute_menu_option(option_type, &mut commands, &mut next_state, &mut app_exit, &mut global_data, &mut research_progress, &mut territory_manager, &mut progression_tracker, &mut session, &slot_cache);
                         }
                         ui.add_space(10.0);
                     }
@@ -115,34 +128,39 @@ pub fn main_menu_system_egui(
 
 fn execute_menu_option(
     option_type: MenuOptionType,
+    commands: &mut Commands,
     next_state: &mut NextState<GameState>,
     app_exit: &mut EventWriter<bevy::app::AppExit>,
     global_data: &mut GlobalData,
     research_progress: &mut ResearchProgress,
     territory_manager: &mut TerritoryManager,
     progression_tracker: &mut CampaignProgressionTracker,
+    session: &mut SaveSession,
+    slot_cache: &SaveSlotCache,
 ) {
     use MenuOptionType::*;
 
     match option_type {
         Continue => {
-            if let Some((data, territory, progression)) = crate::systems::save::load_game() {
-                *global_data = data;
-                *territory_manager = territory;
-                *progression_tracker = progression;
+            let latest = save::latest_save_slot(&slot_cache.slots)
+                .and_then(|slot| save::load_save_slot(slot).map(|data| (slot, data)));
+            if let Some((slot, save_data)) = latest {
+                save::apply_save(slot, save_data, global_data, research_progress, territory_manager, progression_tracker, session);
                 next_state.set(GameState::GlobalMap);
             }
         },
-        NewGame => {
-            *global_data = GlobalData::default();
-            *research_progress = ResearchProgress::default();
-            *territory_manager = TerritoryManager::default();
-            *progression_tracker = CampaignProgressionTracker::default();
-            crate::systems::save::save_game_complete(global_data, research_progress, territory_manager, progression_tracker);
-            next_state.set(GameState::GlobalMap);
-        },
+        LoadGame => next_state.set(GameState::LoadGame),
+        NewGame => next_state.set(GameState::NewGame),
         Settings => next_state.set(GameState::Settings),
         Credits => next_state.set(GameState::Credits),
+        SceneEditor => next_state.set(GameState::SceneEditor),
+        Replay => {
+            match latest_replay_path().map(|path| load_replay(&path)) {
+                Some(Ok(file)) => commands.insert_resource(PendingReplay { file, seek_to: None }),
+                Some(Err(e)) => warn!("Can't play the last replay: {}", e),
+                None => {}
+            }
+        },
         Quit => {
             app_exit.write(bevy::app::AppExit::Success);
         },
@@ -8,6 +8,8 @@ pub mod hub;
 pub mod main_menu;
 pub mod settings;
 pub mod credits;
+pub mod load_game;
+pub mod new_game;
 pub mod fps;
 pub mod pause;
 pub mod post_mission;
@@ -0,0 +1,100 @@
+// src/systems/ui/new_game.rs - Campaign setup reached from the main menu
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::core::*;
+use crate::systems::input::MenuInput;
+use crate::systems::save::{self, SaveSession, SaveSlotCache};
+
+const DIFFICULTY_OPTIONS: [(bool, &str, &str); 2] = [
+    (false, "Standard", "Save anywhere on the world map. Fallen agents recover after a few days."),
+    (true, "Ironman", "One autosave, no loading or quickloading. Fallen agents are gone for good and replaced by fresh recruits."),
+];
+
+#[derive(Resource, Default)]
+pub struct NewGameState {
+    pub selected_index: usize,
+}
+
+pub fn setup_new_game_screen(mut state: ResMut<NewGameState>) {
+    state.selected_index = 0;
+}
+
+pub fn new_game_system_egui(
+    mut contexts: EguiContexts,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut state: ResMut<NewGameState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut global_data: ResMut<GlobalData>,
+    mut research_progress: ResMut<ResearchProgress>,
+    mut territory_manager: ResMut<TerritoryManager>,
+    mut progression_tracker: ResMut<CampaignProgressionTracker>,
+    mut session: ResMut<SaveSession>,
+    slot_cache: Res<SaveSlotCache>,
+    traits_db: Res<TraitsDatabase>,
+) {
+    let mut start_ironman = None;
+
+    let input = MenuInput::new(&keyboard, &gamepads);
+    if input.up {
+        state.selected_index = state.selected_index.checked_sub(1).unwrap_or(DIFFICULTY_OPTIONS.len() - 1);
+    } else if input.down {
+        state.selected_index = (state.selected_index + 1) % DIFFICULTY_OPTIONS.len();
+    } else if input.back {
+        next_state.set(GameState::MainMenu);
+    } else if input.select {
+        start_ironman = Some(DIFFICULTY_OPTIONS[state.selected_index].0);
+    }
+
+    if let Ok(ctx) = contexts.ctx_mut() {
+        egui::CentralPanel::default()
+            .frame(egui::Frame::new().fill(egui::Color32::from_rgb(26, 26, 51)))
+            .show(ctx, |ui| {
+                ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+                    let top_padding = ((ui.available_height() - 400.0) / 2.0).max(20.0);
+                    ui.add_space(top_padding);
+
+                    ui.label(egui::RichText::new("NEW CAMPAIGN").size(36.0).strong()
+                        .color(egui::Color32::from_rgb(252, 255, 82)));
+                    ui.add_space(40.0);
+
+                    for (i, &(ironman, name, description)) in DIFFICULTY_OPTIONS.iter().enumerate() {
+                        let selected = i == state.selected_index;
+                        let color = if selected { egui::Color32::from_rgb(252, 255, 82) } else { egui::Color32::WHITE };
+
+                        let button = egui::Button::new(egui::RichText::new(name).size(24.0).color(color))
+                            .fill(egui::Color32::TRANSPARENT)
+                            .stroke(if selected { egui::Stroke::new(2.0, color) } else { egui::Stroke::NONE });
+
+                        if ui.add_sized([200.0, 40.0], button).clicked() {
+                            state.selected_index = i;
+                            start_ironman = Some(ironman);
+                        }
+                        ui.label(egui::RichText::new(description).size(13.0).color(egui::Color32::LIGHT_GRAY));
+                        ui.add_space(20.0);
+                    }
+
+                    ui.add_space(50.0);
+                    ui.label(egui::RichText::new("W/S/D-Pad: Navigate | Enter/A: Start | Esc/B: Back")
+                        .size(12.0).color(egui::Color32::from_rgb(128, 128, 128)));
+                });
+            });
+    }
+
+    if let Some(ironman) = start_ironman {
+        *global_data = GlobalData { ironman, ..default() };
+        *research_progress = ResearchProgress::default();
+        *territory_manager = TerritoryManager::default();
+        *progression_tracker = CampaignProgressionTracker::default();
+        *session = SaveSession { active_slot: save::slot_for_new_game(&slot_cache.slots), playtime_secs: 0.0 };
+
+        // Starting agents roll their traits the same way replacements do
+        for traits in global_data.agent_traits.iter_mut() {
+            *traits = roll_recruit_traits(&traits_db);
+        }
+
+        save::save_game_complete(&global_data, &research_progress, &territory_manager, &progression_tracker, &session);
+        info!("Started new {} campaign in slot {}", if ironman { "Ironman" } else { "standard" }, session.active_slot + 1);
+        next_state.set(GameState::GlobalMap);
+    }
+}
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 use crate::core::*;
 use crate::systems::input::{MenuInput};
+use crate::systems::extraction::{ExtractionState, MissionPhase};
 
 // Simple pause system using egui modal dialog
 pub fn pause_system(
@@ -13,11 +14,15 @@ pub fn pause_system(
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>, 
     mission_data: Res<MissionData>,
+    mut extraction: ResMut<ExtractionState>,
 ) {
     if !game_mode.paused {
         return;
     }
 
+    // Once someone is out, aborting means leaving the rest behind
+    let abandon = extraction.phase == MissionPhase::Extraction && !extraction.extracted.is_empty();
+
     let input = MenuInput::new(&keyboard, &gamepads);
     
     // Create modal window
@@ -68,19 +73,26 @@ pub fn pause_system(
                 
                 ui.separator();
                 
-                if ui.button("⚠️ Abort Mission (Q)").clicked() || input.option {
-                    // Set mission as failed/aborted
-                    *post_mission = PostMissionResults {
-                        success: false,
-                        time_taken: mission_data.timer,
-                        enemies_killed: mission_data.enemies_killed,
-                        terminals_accessed: mission_data.terminals_accessed,
-                        credits_earned: 0, // No credits for abort
-                        alert_level: mission_data.alert_level,
-                    };
-                    
-                    // Go to post-mission
-                    next_state.set(GameState::PostMission);
+                let abort_label = if abandon { "⚠️ Leave Remaining Agents (Q)" } else { "⚠️ Abort Mission (Q)" };
+                if ui.button(abort_label).clicked() || input.option {
+                    if abandon {
+                        // check_completion wraps up with the stragglers marked missing
+                        extraction.abandon_requested = true;
+                    } else {
+                        // Set mission as failed/aborted
+                        *post_mission = PostMissionResults {
+                            success: false,
+                            time_taken: mission_data.timer,
+                            enemies_killed: mission_data.enemies_killed,
+                            terminals_accessed: mission_data.terminals_accessed,
+                            credits_earned: 0, // No credits for abort
+                            alert_level: mission_data.alert_level,
+                            ..default()
+                        };
+
+                        // Go to post-mission
+                        next_state.set(GameState::PostMission);
+                    }
                 }
                 
                 ui.separator();
@@ -106,7 +118,7 @@ pub fn pause_system(
             ui.vertical_centered(|ui| {
                 ui.colored_label(
                     egui::Color32::from_rgb(200, 100, 100), 
-                    "⚠️ Aborting will count as mission failure"
+                    if abandon { "⚠️ Agents left behind will be captured" } else { "⚠️ Aborting will count as mission failure" }
                 );
                 ui.colored_label(
                     egui::Color32::from_rgb(200, 100, 100), 
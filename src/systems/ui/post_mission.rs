@@ -5,6 +5,9 @@ use bevy_egui::{egui, EguiContexts};
 use egui_plot::{Bar, BarChart, Line, PlotPoints}; /*Plot,*/ 
 use crate::core::*;
 use crate::systems::input::{MenuInput};
+use crate::systems::objectives::{MissionObjectives, ObjectiveState};
+use crate::systems::replay::ReplayPlayback;
+use crate::systems::scene_editor::EditorPlaytest;
 
 #[derive(Resource, Default)]
 pub struct PostMissionUIState {
@@ -22,16 +25,26 @@ pub fn post_mission_ui_system(
     global_data: Res<GlobalData>,
     keyboard: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
+    objectives: Res<MissionObjectives>,
+    playtest: Option<Res<EditorPlaytest>>,
+    replay: Option<Res<ReplayPlayback>>,
 ) {
 
     if !processed.0 {
         return;
     }
 
+    // Playtests go back to the scene editor and replays to the menu instead of the campaign
+    let (return_state, return_label) = match (replay, playtest) {
+        (Some(_), _) => (GameState::MainMenu, "Menu"),
+        (None, Some(_)) => (GameState::SceneEditor, "Editor"),
+        (None, None) => (GameState::GlobalMap, "Map"),
+    };
+
     // Handle input
     if keyboard.just_pressed(KeyCode::KeyR) {
         processed.0 = false;
-        next_state.set(GameState::GlobalMap);
+        next_state.set(return_state);
         return;
     }
     
@@ -59,13 +72,18 @@ pub fn post_mission_ui_system(
                     
                     // Header with success/failure
                     ui.vertical_centered(|ui| {
-                        let (title, title_color) = if post_mission.success {
-                            ("🎯 MISSION SUCCESS", egui::Color32::GREEN)
-                        } else {
-                            ("💥 MISSION FAILED", egui::Color32::RED)
+                        let (title, title_color) = match post_mission.outcome {
+                            MissionOutcome::Success => ("🎯 MISSION SUCCESS", egui::Color32::GREEN),
+                            MissionOutcome::PartialSuccess => ("⚠ PARTIAL SUCCESS", egui::Color32::YELLOW),
+                            MissionOutcome::Failed => ("💥 MISSION FAILED", egui::Color32::RED),
                         };
                         
                         ui.colored_label(title_color, egui::RichText::new(title).heading().strong());
+
+                        if post_mission.agents_extracted > 0 || post_mission.agents_missing > 0 {
+                            ui.label(format!("{} extracted | {} missing in action",
+                                post_mission.agents_extracted, post_mission.agents_missing));
+                        }
                     });
                     
                     ui.separator();
@@ -109,6 +127,30 @@ pub fn post_mission_ui_system(
                         }
                     });
                     
+                    ui.separator();
+
+                    create_grade_display(ui, &post_mission.performance);
+
+                    ui.separator();
+
+                    ui.group(|ui| {
+                        ui.heading("🎯 OBJECTIVES");
+                        for tracked in &objectives.objectives {
+                            let (text, color) = match (tracked.state, tracked.status.method) {
+                                (ObjectiveState::Completed, Some(method)) => (format!("✔ {} - {:?} at {:.0}s", tracked.objective.name, method, tracked.status.time_taken), egui::Color32::LIGHT_GREEN),
+                                (ObjectiveState::Completed, None) => (format!("✔ {}", tracked.objective.name), egui::Color32::LIGHT_GREEN),
+                                (ObjectiveState::Failed, _) => (format!("✖ {}", tracked.objective.name), egui::Color32::LIGHT_RED),
+                                (ObjectiveState::Active, _) => (format!("• {}", tracked.objective.name), egui::Color32::GRAY),
+                            };
+                            ui.horizontal(|ui| {
+                                ui.colored_label(color, text);
+                                if !tracked.objective.required {
+                                    ui.weak("(optional)");
+                                }
+                            });
+                        }
+                    });
+
                     ui.separator();
                     
                     // Credits and rewards
@@ -149,8 +191,9 @@ pub fn post_mission_ui_system(
                         ui.group(|ui| {
                             ui.heading("👥 AGENT PROGRESSION");
                             
-                            let exp_gained = 10 + (post_mission.enemies_killed * 5);
-                            ui.colored_label(egui::Color32::from_rgb(100, 200, 255), format!("Experience Gained: +{}", exp_gained));
+                            let agent_xp = &post_mission.performance.agent_xp;
+                            ui.colored_label(egui::Color32::from_rgb(100, 200, 255), format!("Experience Gained: +{} ({:?} grade x{:.2})",
+                                agent_xp.iter().sum::<u32>(), post_mission.performance.grade, post_mission.performance.grade.xp_multiplier()));
                             
                             ui.separator();
                             
@@ -160,7 +203,7 @@ pub fn post_mission_ui_system(
                             
                             if ui_state.show_agent_performance {
                                 ui.separator();
-                                create_agent_progression_display(ui, &global_data, exp_gained);
+                                create_agent_progression_display(ui, &global_data, agent_xp);
                             }
                         });
                     } else {
@@ -191,9 +234,9 @@ pub fn post_mission_ui_system(
                     // Action buttons
                     ui.vertical_centered(|ui| {
                         ui.horizontal(|ui| {
-                            if ui.button("🗺️ Return to Map (R)").clicked() || keyboard.just_pressed(KeyCode::KeyR) {
+                            if ui.button(format!("🗺️ Return to {} (R)", return_label)).clicked() || keyboard.just_pressed(KeyCode::KeyR) {
                                 processed.0 = false;
-                                next_state.set(GameState::GlobalMap);
+                                next_state.set(return_state.clone());
                             }
                             
                             if ui.button("❌ Quit Game (ESC)").clicked() {
@@ -202,7 +245,7 @@ pub fn post_mission_ui_system(
                         });
                         
                         ui.separator();
-                        ui.weak("R: Return to Map | ESC: Quit Game");
+                        ui.weak(format!("R: Return to {} | ESC: Quit Game", return_label));
                     });
                 });
         });
@@ -244,15 +287,49 @@ fn create_performance_chart(ui: &mut egui::Ui, post_mission: &PostMissionResults
     });
 }
 
-fn create_agent_progression_display(ui: &mut egui::Ui, global_data: &GlobalData, exp_gained: u32) {
+fn create_grade_display(ui: &mut egui::Ui, performance: &MissionPerformance) {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.heading("🏅 GRADE");
+            ui.label(egui::RichText::new(format!("{:?}", performance.grade))
+                .size(32.0)
+                .strong()
+                .color(performance.grade.color()));
+            ui.label(format!("{} / 100", performance.score));
+        });
+
+        ui.indent("grade_breakdown", |ui| {
+            let stealth = if performance.detected {
+                format!("Detected - alert peaked at {:?}", performance.alert_peak)
+            } else {
+                "Undetected".to_string()
+            };
+            ui.label(format!("• Stealth: {} (+{})", stealth, performance.stealth_bonus));
+            ui.label(format!("• Speed: par {:.0}s (+{})", performance.par_time, performance.speed_bonus));
+            ui.label(format!("• Optional objectives: {}/{}", performance.optional_completed, performance.optional_total));
+            ui.label(format!("• Damage taken: {:.0}", performance.damage_taken));
+            if performance.neurovector_uses > 0 {
+                ui.label(format!("• Neurovector uses: {}", performance.neurovector_uses));
+            }
+            if performance.civilian_casualties > 0 {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("• Civilian casualties: {} (-{:.0})",
+                    performance.civilian_casualties, performance.civilian_penalty));
+            }
+        });
+    });
+}
+
+fn create_agent_progression_display(ui: &mut egui::Ui, global_data: &GlobalData, agent_xp: &[u32; 3]) {
     for i in 0..3 {
         ui.horizontal(|ui| {
             ui.label(format!("Agent {}:", i + 1));
             
+            // Experience was already banked by `process_mission_results`
             let current_level = global_data.agent_levels[i];
             let current_exp = global_data.agent_experience[i];
             let next_level_exp = experience_for_level(current_level + 1);
-            let new_exp = current_exp + exp_gained;
+            let level_exp = experience_for_level(current_level);
+            let exp_gained = agent_xp[i];
             
             // Level display
             ui.label(format!("Lv{}", current_level));
@@ -263,8 +340,8 @@ fn create_agent_progression_display(ui: &mut egui::Ui, global_data: &GlobalData,
             ui.add(egui::ProgressBar::new(progress.min(1.0))
                 .text(format!("{}/{}", current_exp, next_level_exp)));
             
-            // Show level up if applicable
-            if new_exp >= next_level_exp {
+            // Show level up if this mission pushed them over
+            if exp_gained > 0 && current_level > 1 && current_exp.saturating_sub(exp_gained) < level_exp {
                 ui.colored_label(egui::Color32::YELLOW, "LEVEL UP!");
             } else {
                 ui.colored_label(egui::Color32::GREEN, format!("+{} XP", exp_gained));
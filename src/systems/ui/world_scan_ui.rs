@@ -2,6 +2,8 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 use crate::core::*;
+use crate::systems::remote_hacking::{can_hack_remotely, RemoteAccess};
+use crate::systems::replay::ReplayPlayback;
 use crate::systems::world_scan::*;
 
 #[derive(Resource, Default)]
@@ -35,6 +37,11 @@ pub fn world_scan_ui_system(
     scanner_query: Query<(Entity, &WorldScanner), With<Agent>>,
     transform_query: Query<&Transform>,
     game_mode: Res<GameMode>,
+    remote_access: Res<RemoteAccess>,
+    inventories: Query<&Inventory, With<Agent>>,
+    hackables: Query<&Hackable>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut action_events: EventWriter<ActionEvent>,
 ) {
     if game_mode.paused { return; }
     
@@ -55,9 +62,18 @@ pub fn world_scan_ui_system(
         draw_intel_database_window(ctx, &mut ui_state, &scan_state, &transform_query);
     }
 
-    // Network topology window
+    // Network topology window; orders are only taken while the mission is live, not replayed
     if ui_state.show_network_window {
-        draw_network_topology_window(ctx, &mut ui_state, &scan_state);
+        let remote_hacker = selection.selected.iter().copied()
+            .find(|&agent| inventories.get(agent).is_ok_and(can_hack_remotely))
+            .filter(|_| playback.is_none());
+        let ordered = draw_network_topology_window(ctx, &mut ui_state, &scan_state, &remote_access, &hackables, remote_hacker);
+        if let (Some(agent), Some(target)) = (remote_hacker, ordered) {
+            action_events.write(ActionEvent {
+                entity: agent,
+                action: Action::RemoteHack(target),
+            });
+        }
     }
 
     // Scan results overlay (temporary popup)
@@ -218,15 +234,23 @@ fn draw_intel_database_window(
         });
 }
 
+/// Returns the device picked for a remote hack, if any
 fn draw_network_topology_window(
     ctx: &egui::Context,
     ui_state: &mut WorldScanUIState,
     scan_state: &WorldScanState,
-) {
+    remote_access: &RemoteAccess,
+    hackables: &Query<&Hackable>,
+    remote_hacker: Option<Entity>,
+) -> Option<Entity> {
+    let mut ordered = None;
+
     egui::Window::new("🔗 Network Topology")
         .default_pos(egui::pos2(710.0, 10.0))
         .default_size(egui::vec2(300.0, 400.0))
         .show(ctx, |ui| {
+            draw_remote_access_section(ui, remote_access, hackables, remote_hacker, &mut ordered);
+
             if scan_state.network_topology.is_empty() {
                 ui.vertical_centered(|ui| {
                     ui.colored_label(egui::Color32::GRAY, "No networks discovered");
@@ -278,6 +302,50 @@ fn draw_network_topology_window(
                     }
                 });
         });
+
+    ordered
+}
+
+/// Devices reachable through ones already compromised, nearest first
+fn draw_remote_access_section(
+    ui: &mut egui::Ui,
+    remote_access: &RemoteAccess,
+    hackables: &Query<&Hackable>,
+    remote_hacker: Option<Entity>,
+    ordered: &mut Option<Entity>,
+) {
+    if remote_access.routes.is_empty() {
+        return;
+    }
+
+    let mut routes: Vec<_> = remote_access.routes.iter().collect();
+    routes.sort_by(|(a, route_a), (b, route_b)| {
+        route_a.network_id.cmp(&route_b.network_id).then(route_a.hops.cmp(&route_b.hops)).then(a.cmp(b))
+    });
+
+    ui.group(|ui| {
+        ui.strong("Remote access");
+        if remote_hacker.is_none() {
+            ui.small("Select an agent with a network scanner or satellite uplink to hack these");
+        }
+
+        for (&target, route) in routes {
+            let Ok(hackable) = hackables.get(target) else { continue; };
+            ui.horizontal(|ui| {
+                ui.painter().rect_filled(
+                    egui::Rect::from_min_size(ui.cursor().min, egui::vec2(8.0, 8.0)),
+                    2.0,
+                    get_network_color(&route.network_id),
+                );
+                ui.allocate_space(egui::vec2(12.0, 8.0));
+                ui.small(format!("{:?} · {} hop{}", hackable.device_type, route.hops, if route.hops == 1 { "" } else { "s" }));
+                if remote_hacker.is_some() && ui.small_button("Hack").clicked() {
+                    *ordered = Some(target);
+                }
+            });
+        }
+    });
+    ui.separator();
 }
 
 fn draw_scan_results_popup(ctx: &egui::Context, scan_record: &ScanRecord) {
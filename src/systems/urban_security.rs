@@ -1,20 +1,14 @@
-// src/systems/urban_security.rs - Unified police, civilian and urban simulation
+// src/systems/urban_security.rs - Civilian and urban simulation (police response lives in police.rs)
 use bevy::prelude::*;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use crate::core::*;
+use crate::systems::police::PoliceConfig;
 use crate::systems::spawners::*;
 
 // === CONFIGURATION ===
 #[derive(Resource, Deserialize, Serialize, Clone)]
 pub struct UrbanConfig {
-    // Police configuration
-    pub heat_decay_rate: f32,
-    pub escalation_check_delay: f32,
-    pub mass_hysteria_threshold: usize,
-    pub incident_heat_values: HashMap<String, f32>,
-    pub escalation_levels: HashMap<String, LevelConfig>,
-    
     // Civilian configuration  
     pub max_civilians: u32,
     pub spawn_interval_min: f32,
@@ -40,8 +34,9 @@ pub struct LevelConfig {
 }
 
 // === ESCALATION SYSTEM ===
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum EscalationLevel {
+    #[default]
     None = 0,
     Patrol = 1, 
     Armed = 2,
@@ -71,7 +66,18 @@ impl EscalationLevel {
         }
     }
 
-    const fn as_str(&self) -> &'static str {
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            0 => Self::None,
+            1 => Self::Patrol,
+            2 => Self::Armed,
+            3 => Self::Tactical,
+            4 => Self::Military,
+            _ => Self::Corporate,
+        }
+    }
+
+    pub const fn as_str(&self) -> &'static str {
         match self {
             Self::None => "None",
             Self::Patrol => "Patrol", 
@@ -82,10 +88,10 @@ impl EscalationLevel {
         }
     }
 
-    pub fn get_config<'a>(&self, config: &'a UrbanConfig) -> &'a LevelConfig {
+    pub fn get_config<'a>(&self, config: &'a PoliceConfig) -> &'a LevelConfig {
         config.escalation_levels.get(self.as_str())
             .expect("Missing escalation level config")
-    }    
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -95,16 +101,18 @@ pub enum IncidentType {
     PoliceKilled,
     Explosion,
     MassHysteria,
+    NetworkIntrusion,
 }
 
 impl IncidentType {
-    const fn as_str(&self) -> &'static str {
+    pub const fn as_str(&self) -> &'static str {
         match self {
             Self::Gunshot => "Gunshot",
             Self::CivilianKilled => "CivilianKilled",
             Self::PoliceKilled => "PoliceKilled",
             Self::Explosion => "Explosion",
             Self::MassHysteria => "MassHysteria",
+            Self::NetworkIntrusion => "NetworkIntrusion",
         }
     }
 }
@@ -112,17 +120,6 @@ impl IncidentType {
 // === UNIFIED RESOURCES ===
 #[derive(Resource)]
 pub struct UrbanSecurity {
-    // Police state
-    pub escalation_level: EscalationLevel,
-    pub heat_level: f32,
-    pub escalation_timer: f32,
-    pub spawn_timer: f32,
-    pub incident_count: u32,
-    pub civilian_casualties: u32,
-    pub last_incident_pos: Option<Vec2>,
-    pub active_units: Vec<PoliceUnit>,
-    pub escalation_cooldown: f32,
-    
     // Civilian spawning
     pub civilian_spawn_timer: f32,
     pub spawn_zones: Vec<SpawnZone>,
@@ -137,15 +134,6 @@ pub struct UrbanSecurity {
 impl Default for UrbanSecurity {
     fn default() -> Self {
         Self {
-            escalation_level: EscalationLevel::None,
-            heat_level: 0.0,
-            escalation_timer: 0.0,
-            spawn_timer: 0.0,
-            incident_count: 0,
-            civilian_casualties: 0,
-            last_incident_pos: None,
-            active_units: Vec::new(),
-            escalation_cooldown: 0.0,
             civilian_spawn_timer: 0.0,
             spawn_zones: vec![
                 SpawnZone { center: Vec2::new(150.0, 150.0), radius: 80.0 },
@@ -197,13 +185,6 @@ pub enum DailyState {
     Following,
 }
 
-#[derive(Clone)]
-pub struct PoliceUnit {
-    pub entity: Entity,
-    pub unit_type: EscalationLevel,
-    pub spawn_time: f32,
-}
-
 #[derive(Clone)]
 pub struct SpawnZone {
     pub center: Vec2,
@@ -228,79 +209,26 @@ pub struct TransitRoute {
 pub fn unified_urban_security_system(
     mut commands: Commands,
     mut urban_security: ResMut<UrbanSecurity>,
-    mut combat_events: EventReader<CombatEvent>,
-    mut audio_events: EventReader<AudioEvent>,
     mut action_events: EventWriter<ActionEvent>,
     mut civilian_query: Query<(Entity, &Transform, Option<&mut UrbanCivilian>, Option<&Morale>), (With<Civilian>, Without<MarkedForDespawn>)>,
-    dead_civilian_query: Query<&Transform, (With<Civilian>, With<Dead>, Without<MarkedForDespawn>)>,
-    dead_police_query: Query<(Entity, &Transform), (With<Police>, With<Dead>, Without<MarkedForDespawn>)>,
     agent_query: Query<&Transform, With<Agent>>,
     sprites: Res<GameSprites>,
     config: Res<UrbanConfig>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
-    let dt = time.delta_secs();
-    
-    // === HEAT DECAY ===
-    urban_security.heat_level = (urban_security.heat_level - dt * config.heat_decay_rate).max(0.0);
-    urban_security.escalation_timer -= dt;
-    urban_security.spawn_timer -= dt;
-    urban_security.escalation_cooldown -= dt;
-    urban_security.civilian_spawn_timer -= dt;
-
-    // === INCIDENT PROCESSING ===
-    for event in combat_events.read() {
-        if !event.hit { continue; }
-        
-        if let Ok(transform) = dead_civilian_query.get(event.target) {
-            urban_security.civilian_casualties += 1;
-            add_incident(&mut urban_security, transform.translation.truncate(), IncidentType::CivilianKilled, &config);
-        } else if let Ok((_, transform)) = dead_police_query.get(event.target) {
-            add_incident(&mut urban_security, transform.translation.truncate(), IncidentType::PoliceKilled, &config);
-        }
-    }
-
-    for event in audio_events.read() {
-        if matches!(event.sound, AudioType::Gunshot) {
-            if let Some(pos) = urban_security.last_incident_pos {
-                add_incident(&mut urban_security, pos, IncidentType::Gunshot, &config);
-            }
-        }
-    }
-
-    // === MASS HYSTERIA CHECK ===
-    let panicked_count = civilian_query.iter()
-        .filter(|(_, _, urban_civ, morale)| {
-            urban_civ.map_or(false, |u| matches!(u.daily_state, DailyState::Panicked)) ||
-            morale.map_or(false, |m| m.is_panicked())
-        })
-        .count();
-
-    if panicked_count >= config.mass_hysteria_threshold && urban_security.incident_count % 10 == 0 {
-        if let Some(pos) = urban_security.last_incident_pos {
-            add_incident(&mut urban_security, pos, IncidentType::MassHysteria, &config);
-        }
-    }
-
-    // === POLICE ESCALATION ===
-    if urban_security.escalation_timer <= 0.0 && should_escalate(&urban_security, &config) {
-        escalate(&mut urban_security, &config);
-    }
-
-    // === POLICE SPAWNING ===
-    if urban_security.spawn_timer <= 0.0 && urban_security.escalation_level != EscalationLevel::None {
-        spawn_police_response(&mut commands, &mut urban_security, &sprites, &config);
-    }
+    let rng = rng.stream(RngStream::Civilians);
+    urban_security.civilian_spawn_timer -= time.delta_secs();
 
     // === CIVILIAN SPAWNING ===
     let current_civilian_count = civilian_query.iter().count();
     if urban_security.civilian_spawn_timer <= 0.0 && current_civilian_count < config.max_civilians as usize {
-        if let Some(spawn_pos) = find_spawn_position(&urban_security.spawn_zones, &urban_security.transit_routes) {
-            spawn_urban_civilian(&mut commands, spawn_pos, &sprites, &urban_security);
-            let interval = config.spawn_interval_min + rand::random::<f32>() * (config.spawn_interval_max - config.spawn_interval_min);
+        if let Some(spawn_pos) = find_spawn_position(&urban_security.spawn_zones, &urban_security.transit_routes, rng) {
+            spawn_urban_civilian(&mut commands, spawn_pos, &sprites, &urban_security, rng);
+            let interval = config.spawn_interval_min + rng.gen::<f32>() * (config.spawn_interval_max - config.spawn_interval_min);
             urban_security.civilian_spawn_timer = interval;
         }
     }
@@ -320,55 +248,42 @@ pub fn unified_urban_security_system(
     // Second pass: apply crowd influence (mutable)
     for (entity, transform, mut urban_civ_option, morale) in civilian_query.iter_mut() {
         if let Some(ref mut urban_civ) = urban_civ_option {
-            apply_crowd_influence(entity, transform, urban_civ, morale, &crowd_nodes, &mut action_events, &urban_security);
+            apply_crowd_influence(entity, transform, urban_civ, morale, &crowd_nodes, &mut action_events, &urban_security, rng);
         }
     }
 
     // === CLEANUP ===
     cleanup_distant_entities(&mut commands, &civilian_query, &agent_query, config.cleanup_distance);
-    
-    // Remove dead police from active units
-    for (dead_entity, _) in dead_police_query.iter() {
-        urban_security.active_units.retain(|unit| unit.entity != dead_entity);
-    }
-
-    // === DE-ESCALATION ===
-    if urban_security.heat_level < 10.0 && urban_security.escalation_timer <= -30.0 {
-        if urban_security.escalation_level != EscalationLevel::None {
-            urban_security.escalation_level = urban_security.escalation_level.prev();
-            urban_security.escalation_timer = 0.0;
-        }
-    }
 }
 
 // === HELPER FUNCTIONS ===
-pub fn pick_destination_for_state(state: DailyState, urban_security: &UrbanSecurity) -> Option<Vec2> {
+pub fn pick_destination_for_state(state: DailyState, urban_security: &UrbanSecurity, rng: &mut impl Rng) -> Option<Vec2> {
     match state {
-        DailyState::GoingToWork => pick_random_zone_center(&urban_security.work_zones),
-        DailyState::Shopping => pick_random_zone_center(&urban_security.shopping_zones),
-        DailyState::GoingHome => pick_random_zone_center(&urban_security.residential_zones),
+        DailyState::GoingToWork => pick_random_zone_center(&urban_security.work_zones, rng),
+        DailyState::Shopping => pick_random_zone_center(&urban_security.shopping_zones, rng),
+        DailyState::GoingHome => pick_random_zone_center(&urban_security.residential_zones, rng),
         _ => None,
     }
 }
 
-fn pick_random_zone_center(zones: &[UrbanZone]) -> Option<Vec2> {
+fn pick_random_zone_center(zones: &[UrbanZone], rng: &mut impl Rng) -> Option<Vec2> {
     if zones.is_empty() { return None; }
-    let zone = &zones[rand::random::<usize>() % zones.len()];
+    let zone = &zones[rng.gen_range(0..zones.len())];
 
-    let angle = rand::random::<f32>() * std::f32::consts::TAU;
-    let distance = rand::random::<f32>() * zone.radius * 0.8;
+    let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+    let distance = rng.gen::<f32>() * zone.radius * 0.8;
     let offset = Vec2::new(angle.cos(), angle.sin()) * distance;
 
     Some(zone.center + offset)
 }
 
-fn transition_daily_state(urban_civ: &mut UrbanCivilian) {
+fn transition_daily_state(urban_civ: &mut UrbanCivilian, rng: &mut impl Rng) {
     urban_civ.daily_state = match urban_civ.daily_state {
         DailyState::GoingToWork => DailyState::Working,
-        DailyState::Working => if rand::random::<f32>() < 0.7 { DailyState::Shopping } else { DailyState::GoingHome },
+        DailyState::Working => if rng.gen::<f32>() < 0.7 { DailyState::Shopping } else { DailyState::GoingHome },
         DailyState::Shopping => DailyState::GoingHome,
         DailyState::GoingHome => DailyState::Idle,
-        DailyState::Idle => match rand::random::<f32>() {
+        DailyState::Idle => match rng.gen::<f32>() {
             x if x < 0.4 => DailyState::GoingToWork,
             x if x < 0.7 => DailyState::Shopping,
             _ => DailyState::Idle,
@@ -391,67 +306,21 @@ fn find_escape_destination(current_pos: Vec2) -> Vec2 {
     current_pos + to_edge * 400.0
 }
 
-fn add_incident(urban_security: &mut UrbanSecurity, pos: Vec2, incident_type: IncidentType, config: &UrbanConfig) {
-    urban_security.last_incident_pos = Some(pos);
-    urban_security.incident_count += 1;
-    
-    let heat_value = config.incident_heat_values.get(incident_type.as_str()).copied().unwrap_or(5.0);
-    urban_security.heat_level += heat_value;
-    urban_security.escalation_timer = config.escalation_check_delay;
-}
-
-fn should_escalate(urban_security: &UrbanSecurity, config: &UrbanConfig) -> bool {
-    urban_security.escalation_cooldown <= 0.0 && {
-        let level_config = config.escalation_levels.get(urban_security.escalation_level.as_str()).unwrap();
-        urban_security.heat_level >= level_config.heat_threshold
-    }
-}
-
-fn escalate(urban_security: &mut UrbanSecurity, config: &UrbanConfig) {
-    urban_security.escalation_level = urban_security.escalation_level.next();
-    let level_config = config.escalation_levels.get(urban_security.escalation_level.as_str()).unwrap();
-    urban_security.spawn_timer = level_config.response_time;
-    urban_security.escalation_cooldown = config.escalation_check_delay;
-}
-
-fn spawn_police_response(
-    commands: &mut Commands,
-    urban_security: &mut UrbanSecurity,
-    sprites: &GameSprites,
-    config: &UrbanConfig,
-) {
-    let level_config = config.escalation_levels.get(urban_security.escalation_level.as_str()).unwrap();
-    let spawn_pos = urban_security.last_incident_pos.unwrap_or(Vec2::new(400.0, 0.0));
-
-    for i in 0..level_config.count {
-        let offset = Vec2::new((i as f32 - level_config.count as f32 / 2.0) * 40.0, (i % 2) as f32 * 30.0);
-        let entity = spawn_police_unit(commands, spawn_pos + Vec2::new(400.0, 0.0) + offset, urban_security.escalation_level, sprites, config);
-        
-        urban_security.active_units.push(PoliceUnit {
-            entity,
-            unit_type: urban_security.escalation_level,
-            spawn_time: 0.0,
-        });
-    }
-
-    urban_security.spawn_timer = level_config.spawn_interval;
-}
-
-fn find_spawn_position(spawn_zones: &[SpawnZone], transit_routes: &[TransitRoute]) -> Option<Vec2> {
+fn find_spawn_position(spawn_zones: &[SpawnZone], transit_routes: &[TransitRoute], rng: &mut impl Rng) -> Option<Vec2> {
     // 60% spawn near transit, 40% near zones
-    if rand::random::<f32>() < 0.6 && !transit_routes.is_empty() {
-        let route = &transit_routes[rand::random::<usize>() % transit_routes.len()];
+    if rng.gen::<f32>() < 0.6 && !transit_routes.is_empty() {
+        let route = &transit_routes[rng.gen_range(0..transit_routes.len())];
         if !route.points.is_empty() {
-            let point = route.points[rand::random::<usize>() % route.points.len()];
-            let offset = Vec2::new((rand::random::<f32>() - 0.5) * 40.0, (rand::random::<f32>() - 0.5) * 40.0);
+            let point = route.points[rng.gen_range(0..route.points.len())];
+            let offset = Vec2::new((rng.gen::<f32>() - 0.5) * 40.0, (rng.gen::<f32>() - 0.5) * 40.0);
             return Some(point + offset);
         }
     }
 
     if !spawn_zones.is_empty() {
-        let zone = &spawn_zones[rand::random::<usize>() % spawn_zones.len()];
-        let angle = rand::random::<f32>() * std::f32::consts::TAU;
-        let distance = rand::random::<f32>() * zone.radius;
+        let zone = &spawn_zones[rng.gen_range(0..spawn_zones.len())];
+        let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+        let distance = rng.gen::<f32>() * zone.radius;
         let offset = Vec2::new(angle.cos(), angle.sin()) * distance;
         return Some(zone.center + offset);
     }
@@ -518,6 +387,7 @@ fn apply_crowd_influence(
     crowd_nodes: &[CrowdNode],
     action_events: &mut EventWriter<ActionEvent>,
     urban_security: &UrbanSecurity,
+    rng: &mut impl Rng,
 ) {
     let pos = transform.translation.truncate();
     let mut panic_influence = 0.0;
@@ -542,9 +412,9 @@ fn apply_crowd_influence(
         urban_civ.state_timer -= 0.016; // Approximate delta time
         
         if urban_civ.state_timer <= 0.0 && !matches!(urban_civ.daily_state, DailyState::Panicked) {
-            transition_daily_state(urban_civ);
-            urban_civ.next_destination = pick_destination_for_state(urban_civ.daily_state, urban_security);
-            urban_civ.state_timer = 15.0 + rand::random::<f32>() * 20.0;
+            transition_daily_state(urban_civ, rng);
+            urban_civ.next_destination = pick_destination_for_state(urban_civ.daily_state, urban_security, rng);
+            urban_civ.state_timer = 15.0 + rng.gen::<f32>() * 20.0;
         }
     }
 
@@ -618,29 +488,7 @@ pub fn load_urban_config() -> UrbanConfig {
 
 impl Default for UrbanConfig {
     fn default() -> Self {
-        let mut incident_heat_values = HashMap::new();
-        incident_heat_values.insert("Gunshot".to_string(), 2.0);
-        incident_heat_values.insert("CivilianKilled".to_string(), 15.0);
-        incident_heat_values.insert("PoliceKilled".to_string(), 25.0);
-        incident_heat_values.insert("Explosion".to_string(), 20.0);
-        incident_heat_values.insert("MassHysteria".to_string(), 10.0);
-
-        let mut escalation_levels = HashMap::new();
-        escalation_levels.insert("None".to_string(), LevelConfig {
-            count: 0, response_time: 0.0, health: 0.0, weapon: "none".to_string(),
-            speed: 0.0, vision: 0.0, color: (0.0, 0.0, 0.0, 0.0), heat_threshold: 0.0, spawn_interval: 0.0,
-        });
-        escalation_levels.insert("Patrol".to_string(), LevelConfig {
-            count: 2, response_time: 15.0, health: 60.0, weapon: "pistol".to_string(),
-            speed: 80.0, vision: 150.0, color: (0.3, 0.3, 0.8, 1.0), heat_threshold: 20.0, spawn_interval: 12.0,
-        });
-
         Self {
-            heat_decay_rate: 1.0,
-            escalation_check_delay: 5.0,
-            mass_hysteria_threshold: 8,
-            incident_heat_values,
-            escalation_levels,
             max_civilians: 12,
             spawn_interval_min: 3.0,
             spawn_interval_max: 8.0,
@@ -651,7 +499,7 @@ impl Default for UrbanConfig {
     }
 }
 
-pub fn generate_patrol_pattern(position: Vec2, unit_type: EscalationLevel, config: &UrbanConfig) -> Vec<Vec2> {
+pub fn generate_patrol_pattern(position: Vec2, unit_type: EscalationLevel) -> Vec<Vec2> {
     // Simple patrol pattern based on unit type
     match unit_type {
         EscalationLevel::None => vec![position],
@@ -1,5 +1,6 @@
 // src/systems/vehicles.rs
 use bevy::prelude::*;
+use rand::Rng;
 use crate::core::*;
 use crate::systems::explosions::*;
 
@@ -62,6 +63,7 @@ pub fn vehicle_spawn_system(
     mut spawn_timer: Local<f32>,
     time: Res<Time>,
     game_mode: Res<GameMode>,
+    mut rng: ResMut<GameRng>,
 ) {
     if game_mode.paused { return; }
 
@@ -76,8 +78,9 @@ pub fn vehicle_spawn_system(
             Vec2::new(100.0, 250.0),
         ];
 
-        let pos = spawn_positions[rand::random::<usize>() % spawn_positions.len()];
-        let vehicle_type = match rand::random::<f32>() {
+        let rng = rng.stream(RngStream::Traffic);
+        let pos = spawn_positions[rng.gen_range(0..spawn_positions.len())];
+        let vehicle_type = match rng.gen::<f32>() {
             x if x < 0.6 => VehicleType::CivilianCar,
             x if x < 0.8 => VehicleType::PoliceCar,
             x if x < 0.95 => VehicleType::APC,
@@ -85,6 +88,6 @@ pub fn vehicle_spawn_system(
         };
 
         spawn_vehicle(&mut commands, pos, vehicle_type, &sprites);
-        *spawn_timer = 15.0 + rand::random::<f32>() * 10.0;
+        *spawn_timer = 15.0 + rng.gen::<f32>() * 10.0;
     }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 // src/systems/weather.rs
 use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use crate::core::*;
 
@@ -79,7 +81,7 @@ impl Default for WeatherParticlePool {
 }
 
 impl WeatherSystem {
-    pub fn determine_weather_for_city(city: &City, day_of_year: u32) -> WeatherState {
+    pub fn determine_weather_for_city(city: &City, day_of_year: u32, rng: &mut impl Rng) -> WeatherState {
         use crate::core::cities::CityTrait;
 
         // Base weather on location (latitude) and season
@@ -125,9 +127,7 @@ impl WeatherSystem {
         let rain_modifier = if has_industrial { 1.3 } else { 1.0 };
         let coastal_modifier = if has_coastal { 1.2 } else { 1.0 };
 
-        // Simple deterministic random based on city ID and day
-        let seed = city.id.len() as u32 + day_of_year;
-        let weather_roll = ((seed * 7919) % 100) as f32 / 100.0;
+        let weather_roll = rng.gen::<f32>();
 
         if weather_roll < snow_chance {
             WeatherState::Snow
@@ -167,14 +167,16 @@ pub fn setup_weather_system(
     let weather = if let Some(launch_data) = launch_data {
         if let Some(city) = cities_db.get_city(&launch_data.city_id) {
             let day_of_year = global_data.current_day % 365;
+            let mut rng = StdRng::seed_from_u64(derive_seed(global_data.campaign_seed,
+                &[b"weather", city.id.as_bytes(), &global_data.current_day.to_le_bytes()]));
             WeatherSystem {
-                current_weather: WeatherSystem::determine_weather_for_city(city, day_of_year),
-                intensity: fastrand::f32() * 0.5 + 0.5, // 0.5 - 1.0
+                current_weather: WeatherSystem::determine_weather_for_city(city, day_of_year, &mut rng),
+                intensity: rng.gen::<f32>() * 0.5 + 0.5, // 0.5 - 1.0
                 wind_direction: Vec2::new(
-                    fastrand::f32() * 0.4 - 0.2,
-                    -0.3 - fastrand::f32() * 0.4
+                    rng.gen::<f32>() * 0.4 - 0.2,
+                    -0.3 - rng.gen::<f32>() * 0.4
                 ),
-                wind_strength: fastrand::f32() * 0.5 + 0.5,
+                wind_strength: rng.gen::<f32>() * 0.5 + 0.5,
             }
         } else {
             WeatherSystem::default()
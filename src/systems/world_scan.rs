@@ -342,7 +342,7 @@ fn scan_infrastructure(
                 .or_default()
                 .push(entity);
 
-            intel_gathered.push(format!("Power Station: {} MW capacity", station.max_capacity));
+            intel_gathered.push(format!("Power Station: {}/{} MW load", station.current_load, station.max_capacity));
             if !device_state.operational {
                 intel_gathered.push("⚠ SYSTEM OFFLINE".to_string());
             }
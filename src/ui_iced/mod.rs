@@ -214,7 +214,7 @@ impl SubversiveUI {
 
     // View functions
     fn view_main_menu(&self, state: &UISharedState) -> Element<Message> {
-        let has_save = crate::systems::save::save_game_exists();
+        let has_save = crate::systems::save::save_game_exists(&crate::systems::save::list_save_slots());
         
         let mut buttons = column![].spacing(10);
         